@@ -0,0 +1,113 @@
+// Admin Scope Tests
+//
+// Tests for what `inferadb.admin` unlocks on Engine beyond data access: purging a vault, writing
+// its schema, reading vault statistics, and explaining denials. Tokens without the scope must be
+// refused these operations, even when they carry `vault_role: admin`.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Engine operations gated behind an elevated scope
+#[derive(Debug, Clone, Copy)]
+enum AdminOperation {
+    Purge,
+    SchemaWrite,
+    Stats,
+    Explain,
+}
+
+const ADMIN_OPERATIONS: &[AdminOperation] = &[
+    AdminOperation::Purge,
+    AdminOperation::SchemaWrite,
+    AdminOperation::Stats,
+    AdminOperation::Explain,
+];
+
+async fn run(engine: &EngineClient, operation: AdminOperation) -> ApiResult<()> {
+    match operation {
+        AdminOperation::Purge => engine.purge_relationships().await,
+        AdminOperation::SchemaWrite => {
+            let req = DeploySchemaRequest { definition: DOCUMENT_SCHEMA.to_string() };
+            engine.write_schema(&req).await
+        },
+        AdminOperation::Stats => engine.stats().await.map(|_| ()),
+        AdminOperation::Explain => {
+            engine.explain("document:1", "view", "user:alice").await.map(|_| ())
+        },
+    }
+}
+
+/// Assert every admin operation is refused with 403 for the given token
+async fn assert_admin_operations_forbidden(fixture: &TestFixture, jwt: &str, token: &str) {
+    let engine = fixture.engine(jwt);
+
+    for &operation in ADMIN_OPERATIONS {
+        match run(&engine, operation).await {
+            Ok(()) => panic!("{:?} should be refused for {}", operation, token),
+            Err(e) if e.is_unsupported() => {
+                outcome::skip(format!("{:?} endpoint not available, skipping: {}", operation, e));
+            },
+            Err(e) => {
+                assert_eq!(
+                    e.status(),
+                    Some(StatusCode::FORBIDDEN),
+                    "{:?} with {} should be forbidden, got {}",
+                    operation,
+                    token,
+                    e
+                );
+                println!("✓ {:?} refused for {}", operation, token);
+            },
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_admin_scope_unlocks_admin_operations() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, &["inferadb.admin"]).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    for &operation in ADMIN_OPERATIONS {
+        match run(&engine, operation).await {
+            Ok(()) => println!("✓ {:?} allowed with inferadb.admin", operation),
+            Err(e) if e.is_unsupported() => {
+                outcome::skip(format!("{:?} endpoint not available, skipping: {}", operation, e));
+            },
+            Err(e) => panic!("{:?} should be allowed with inferadb.admin: {}", operation, e),
+        }
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_write_scope_cannot_perform_admin_operations() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+
+    assert_admin_operations_forbidden(&fixture, &jwt, "inferadb.write").await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_admin_vault_role_without_admin_scope_is_insufficient() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    // The role claim alone must not stand in for the scope
+    let jwt = fixture
+        .jwt()
+        .scopes(&["inferadb.check", "inferadb.write"])
+        .vault_role("admin")
+        .encode()
+        .expect("Failed to encode JWT");
+
+    assert_admin_operations_forbidden(&fixture, &jwt, "vault_role=admin without inferadb.admin")
+        .await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,101 @@
+// Audience Validation Tests
+//
+// Pins which `aud` forms Engine accepts. The required audience comes from the active
+// `EnvProfile`, so the same matrix runs against any deployment: exact match and (where the
+// profile allows it) an audience list containing the required value are accepted, while
+// near-misses such as a trailing slash or a different scheme are rejected.
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use super::*;
+
+/// An `aud` value to present, and whether Engine is expected to accept it
+struct AudienceCase {
+    label: &'static str,
+    audience: Option<Value>,
+    accepted: bool,
+}
+
+fn audience_cases(profile: &EnvProfile) -> Vec<AudienceCase> {
+    let required = profile.audience.as_str();
+
+    vec![
+        AudienceCase { label: "exact match", audience: Some(required.into()), accepted: true },
+        AudienceCase {
+            label: "list containing required audience",
+            audience: Some(serde_json::json!(["https://other.example.com", required])),
+            accepted: profile.audience_list_accepted,
+        },
+        AudienceCase {
+            label: "list without required audience",
+            audience: Some(serde_json::json!(["https://other.example.com"])),
+            accepted: false,
+        },
+        AudienceCase {
+            label: "trailing slash",
+            audience: Some(format!("{}/", required).into()),
+            accepted: false,
+        },
+        AudienceCase {
+            label: "scheme variation",
+            audience: Some(swap_scheme(required).into()),
+            accepted: false,
+        },
+        AudienceCase {
+            label: "different host",
+            audience: Some("https://api.example.com".into()),
+            accepted: false,
+        },
+        AudienceCase { label: "missing aud claim", audience: None, accepted: false },
+    ]
+}
+
+#[tokio::test]
+async fn test_audience_configuration_matrix() {
+    let fixture = SharedFixture::get().await;
+    let profile = EnvProfile::current();
+
+    let mut mismatches = Vec::new();
+    for case in audience_cases(profile) {
+        let builder = match case.audience {
+            Some(audience) => fixture.jwt().audience(audience),
+            None => fixture.jwt().without_claim("aud"),
+        };
+        let jwt = builder.encode().expect("Failed to encode JWT");
+
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+
+        let status = response.status();
+        let accepted = status == StatusCode::OK || status == StatusCode::NOT_FOUND;
+        let rejected = status == StatusCode::UNAUTHORIZED;
+
+        if (case.accepted && accepted) || (!case.accepted && rejected) {
+            println!("✓ {}: {}", case.label, status);
+        } else {
+            mismatches.push(format!(
+                "{}: expected {}, got {}",
+                case.label,
+                if case.accepted { "200/404" } else { "401" },
+                status
+            ));
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "Audience validation differs from profile (audience {}):\n  {}",
+        profile.audience,
+        mismatches.join("\n  ")
+    );
+}
+
+#[test]
+fn test_swap_scheme() {
+    assert_eq!(swap_scheme("https://api.inferadb.com"), "http://api.inferadb.com");
+    assert_eq!(swap_scheme("http://api.inferadb.com"), "https://api.inferadb.com");
+    assert_eq!(swap_scheme("api.inferadb.com"), "https://api.inferadb.com");
+}
@@ -31,7 +31,7 @@ async fn test_valid_jwt_from_management_client() {
 
 #[tokio::test]
 async fn test_jwt_with_invalid_signature() {
-    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let fixture = SharedFixture::get().await;
 
     // Generate JWT with wrong signing key
     let jwt = fixture.generate_invalid_jwt().expect("Failed to generate invalid JWT");
@@ -48,8 +48,6 @@ async fn test_jwt_with_invalid_signature() {
         StatusCode::UNAUTHORIZED,
         "Expected 401 Unauthorized for invalid signature"
     );
-
-    fixture.cleanup().await.expect("Failed to cleanup");
 }
 
 #[tokio::test]
@@ -108,7 +106,7 @@ async fn test_jwt_for_vault_in_different_org() {
 
 #[tokio::test]
 async fn test_jwt_with_missing_required_scope() {
-    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let fixture = SharedFixture::get().await;
 
     // Generate JWT without required scopes
     let jwt = fixture.generate_jwt(None, &[]).expect("Failed to generate JWT");
@@ -128,36 +126,14 @@ async fn test_jwt_with_missing_required_scope() {
         "Unexpected status code: {}",
         response.status()
     );
-
-    fixture.cleanup().await.expect("Failed to cleanup");
 }
 
 #[tokio::test]
 async fn test_jwt_with_expired_token() {
-    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let fixture = SharedFixture::get().await;
 
-    // Generate JWT with past expiration (requires custom encoding)
-    let now = Utc::now();
-    let claims = ClientClaims {
-        iss: fixture.ctx.api_base_url.clone(),
-        sub: format!("client:{}", fixture.client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
-        exp: (now - Duration::minutes(10)).timestamp(), // Expired 10 minutes ago
-        iat: (now - Duration::minutes(15)).timestamp(),
-        jti: Uuid::new_v4().to_string(),
-        vault_id: fixture.vault_id.to_string(),
-        org_id: fixture.org_id.to_string(),
-        scope: "inferadb.check inferadb.read inferadb.write inferadb.expand inferadb.list inferadb.list-relationships inferadb.list-subjects inferadb.list-resources".to_string(),
-        vault_role: "write".to_string(),
-    };
-
-    let mut header = Header::new(Algorithm::EdDSA);
-    header.kid = Some(fixture.cert_kid.clone());
-
-    let secret_bytes = fixture.signing_key.to_bytes();
-    let pem = ed25519_to_pem(&secret_bytes);
-    let encoding_key = EncodingKey::from_ed_pem(&pem).expect("Failed to create encoding key");
-    let expired_jwt = encode(&header, &claims, &encoding_key).expect("Failed to encode JWT");
+    // Generate JWT with past expiration
+    let expired_jwt = fixture.jwt().expired().encode().expect("Failed to encode JWT");
 
     // Call server with expired JWT
     let response = fixture
@@ -171,38 +147,18 @@ async fn test_jwt_with_expired_token() {
         StatusCode::UNAUTHORIZED,
         "Expected 401 Unauthorized for expired token"
     );
-
-    fixture.cleanup().await.expect("Failed to cleanup");
 }
 
 #[tokio::test]
 async fn test_jwt_with_invalid_kid() {
-    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let fixture = SharedFixture::get().await;
 
-    // Generate JWT with fake kid
-    let now = Utc::now();
-    let claims = ClientClaims {
-        iss: fixture.ctx.api_base_url.clone(),
-        sub: format!("client:{}", fixture.client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
-        exp: (now + Duration::minutes(5)).timestamp(),
-        iat: now.timestamp(),
-        jti: Uuid::new_v4().to_string(),
-        vault_id: fixture.vault_id.to_string(),
-        org_id: fixture.org_id.to_string(),
-        scope: "inferadb.check inferadb.read inferadb.write inferadb.expand inferadb.list inferadb.list-relationships inferadb.list-subjects inferadb.list-resources".to_string(),
-        vault_role: "write".to_string(),
-    };
-
-    let mut header = Header::new(Algorithm::EdDSA);
-    // Use fake Snowflake IDs for invalid kid test
-    header.kid =
-        Some(format!("org-{}-client-{}-cert-{}", 999999999i64, 888888888i64, 777777777i64));
-
-    let secret_bytes = fixture.signing_key.to_bytes();
-    let pem = ed25519_to_pem(&secret_bytes);
-    let encoding_key = EncodingKey::from_ed_pem(&pem).expect("Failed to create encoding key");
-    let invalid_kid_jwt = encode(&header, &claims, &encoding_key).expect("Failed to encode JWT");
+    // Generate JWT with fake kid (fake Snowflake IDs)
+    let invalid_kid_jwt = fixture
+        .jwt()
+        .kid(Some(format!("org-{}-client-{}-cert-{}", 999999999i64, 888888888i64, 777777777i64)))
+        .encode()
+        .expect("Failed to encode JWT");
 
     // Call server with invalid kid
     let response = fixture
@@ -216,6 +172,4 @@ async fn test_jwt_with_invalid_kid() {
         StatusCode::UNAUTHORIZED,
         "Expected 401 Unauthorized for invalid kid"
     );
-
-    fixture.cleanup().await.expect("Failed to cleanup");
 }
@@ -0,0 +1,163 @@
+// Backpressure Tests
+//
+// Floods relationship writes far beyond sustainable throughput from one client while a second
+// client on the same vault keeps evaluating. The server must push back (429/503 with Retry-After)
+// instead of queueing without bound, and the bystander's evaluate latency must stay bounded.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+/// Concurrent write requests in the flood
+const FLOOD_REQUESTS: usize = 2000;
+
+/// Relationships per write request
+const BATCH_SIZE: usize = 100;
+
+/// Maximum time any single write may wait before being accepted or refused (milliseconds)
+const WRITE_WAIT_BUDGET_MS: f64 = 10_000.0;
+
+/// Maximum p95 evaluate latency for the bystander client during the flood (milliseconds)
+const BYSTANDER_P95_BUDGET_MS: f64 = 1000.0;
+
+struct WriteOutcome {
+    status: StatusCode,
+    retry_after: Option<String>,
+    elapsed_ms: f64,
+}
+
+#[tokio::test]
+async fn test_write_flood_applies_backpressure() {
+    let fixtures = multi_client_fixture().await.expect("Failed to create multi-client fixture");
+    let [flooder, bystander] = fixtures.clients();
+
+    let write_jwt =
+        flooder.generate_jwt(None, &["inferadb.write"]).expect("Failed to generate JWT");
+    let writer = Arc::new(flooder.engine(&write_jwt));
+
+    // Bystander evaluates continuously until the flood ends
+    let stop = Arc::new(AtomicBool::new(false));
+    let bystander_traffic = {
+        let stop = Arc::clone(&stop);
+        let jwt =
+            bystander.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+        let engine = bystander.engine(&jwt);
+        tokio::spawn(async move {
+            let mut latencies = Vec::new();
+            while !stop.load(Ordering::Relaxed) {
+                let start = Instant::now();
+                let _ = engine.check("document:1", "viewer", "user:alice").await;
+                latencies.push(start.elapsed().as_secs_f64() * 1000.0);
+            }
+            latencies
+        })
+    };
+
+    let mut handles = Vec::with_capacity(FLOOD_REQUESTS);
+    for request in 0..FLOOD_REQUESTS {
+        let writer = Arc::clone(&writer);
+        handles.push(tokio::spawn(async move {
+            let relationships: Vec<Relationship> = (0..BATCH_SIZE)
+                .map(|i| {
+                    Relationship::new(
+                        format!("document:flood-{}-{}", request, i),
+                        "viewer",
+                        "user:alice",
+                    )
+                })
+                .collect();
+
+            let start = Instant::now();
+            let response = writer
+                .request(Method::POST, "/relationships/write")
+                .json(&serde_json::json!({ "relationships": relationships }))
+                .send_recorded()
+                .await
+                .expect("Failed to send write");
+            WriteOutcome {
+                status: response.status(),
+                retry_after: response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string),
+                elapsed_ms: start.elapsed().as_secs_f64() * 1000.0,
+            }
+        }));
+    }
+
+    let mut outcomes = Vec::with_capacity(FLOOD_REQUESTS);
+    for handle in handles {
+        outcomes.push(handle.await.expect("Write task failed"));
+    }
+    stop.store(true, Ordering::Relaxed);
+    let mut bystander_latencies = bystander_traffic.await.expect("Bystander task failed");
+
+    let accepted = outcomes.iter().filter(|o| o.status.is_success()).count();
+    let throttled: Vec<&WriteOutcome> = outcomes
+        .iter()
+        .filter(|o| {
+            o.status == StatusCode::TOO_MANY_REQUESTS || o.status == StatusCode::SERVICE_UNAVAILABLE
+        })
+        .collect();
+    let unexpected: Vec<StatusCode> = outcomes
+        .iter()
+        .map(|o| o.status)
+        .filter(|s| {
+            !s.is_success()
+                && *s != StatusCode::TOO_MANY_REQUESTS
+                && *s != StatusCode::SERVICE_UNAVAILABLE
+        })
+        .collect();
+    println!(
+        "  Flood: {} accepted, {} throttled, {} other",
+        accepted,
+        throttled.len(),
+        unexpected.len()
+    );
+
+    assert!(unexpected.is_empty(), "Flood produced unexpected statuses: {:?}", unexpected);
+    assert!(
+        !throttled.is_empty(),
+        "{} concurrent writes of {} tuples were all accepted; expected 429/503 backpressure",
+        FLOOD_REQUESTS,
+        BATCH_SIZE
+    );
+    let missing_retry_after = throttled.iter().filter(|o| o.retry_after.is_none()).count();
+    assert_eq!(
+        missing_retry_after,
+        0,
+        "{} of {} throttled responses lacked Retry-After",
+        missing_retry_after,
+        throttled.len()
+    );
+    println!("✓ {} writes refused with Retry-After", throttled.len());
+
+    // Refusing early, not queueing: no write waited unboundedly for an answer
+    let slowest_write = outcomes.iter().map(|o| o.elapsed_ms).fold(0.0, f64::max);
+    assert!(
+        slowest_write <= WRITE_WAIT_BUDGET_MS,
+        "A write waited {:.0}ms for an answer; requests are being queued without bound",
+        slowest_write
+    );
+
+    bystander_latencies.sort_by(f64::total_cmp);
+    let p95 = scenario::percentile(&bystander_latencies, 0.95).expect("Bystander made no requests");
+    println!("✓ Bystander evaluate p95 {:.1}ms during the flood", p95);
+    assert!(
+        p95 <= BYSTANDER_P95_BUDGET_MS,
+        "Bystander evaluate p95 {:.1}ms exceeds {:.0}ms during the flood",
+        p95,
+        BYSTANDER_P95_BUDGET_MS
+    );
+
+    fixtures.cleanup().await.expect("Failed to cleanup");
+}
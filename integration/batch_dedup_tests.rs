@@ -0,0 +1,152 @@
+// Batch Deduplication Tests
+//
+// Sends evaluate batches in which the same (resource, permission, subject) appears 50 times. Every
+// duplicate must get the same decision in its original position, and Engine should evaluate the
+// repeated check once rather than 50 times, measured through its Server-Timing evaluate phase.
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+/// Copies of the repeated evaluation in each batch
+const DUPLICATES: usize = 50;
+
+/// Cold batches sampled for the dedup timing comparison
+const SAMPLES: usize = 5;
+
+/// How much more evaluate time a batch of duplicates may cost than a single evaluation
+const DEDUP_COST_FACTOR: f64 = 5.0;
+
+fn evaluation(resource: &str, permission: &str, subject: &str) -> serde_json::Value {
+    serde_json::json!({
+        "subject": subject,
+        "resource": resource,
+        "permission": permission,
+        "trace": false
+    })
+}
+
+/// Send one evaluate batch, returning the decisions and Engine's timing breakdown
+async fn evaluate_batch(
+    engine: &EngineClient,
+    evaluations: Vec<serde_json::Value>,
+) -> (Vec<String>, ServerTiming) {
+    let response = engine
+        .request(Method::POST, "/evaluate")
+        .json(&serde_json::json!({ "evaluations": evaluations }))
+        .send_recorded()
+        .await
+        .expect("Failed to send batch");
+    assert_eq!(response.status(), StatusCode::OK, "Batch evaluate failed");
+
+    let server_timing = ServerTiming::from_headers(response.headers());
+    let body: EvaluateResponse = response.json().await.expect("Failed to parse batch response");
+    (body.results.into_iter().map(|r| r.decision).collect(), server_timing)
+}
+
+#[tokio::test]
+async fn test_duplicate_evaluations_preserve_count_and_order() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    engine
+        .write_relationships(&[Relationship::new("document:dedup", "viewer", "user:alice")])
+        .await
+        .expect("Failed to write relationship");
+
+    // Uniform batch: every copy allowed
+    let (decisions, _) = evaluate_batch(
+        &engine,
+        vec![evaluation("document:dedup", "viewer", "user:alice"); DUPLICATES],
+    )
+    .await;
+    assert_eq!(decisions.len(), DUPLICATES, "Duplicates were collapsed in the response");
+    assert!(
+        decisions.iter().all(|d| d == "ALLOW"),
+        "Repeated evaluation produced inconsistent decisions: {:?}",
+        decisions
+    );
+    println!("✓ {} duplicate evaluations all ALLOW", DUPLICATES);
+
+    // Interleaved batch: two repeated evaluations with different answers keep their positions
+    let interleaved: Vec<serde_json::Value> = (0..DUPLICATES)
+        .map(|i| {
+            let subject = if i % 2 == 0 { "user:alice" } else { "user:bob" };
+            evaluation("document:dedup", "viewer", subject)
+        })
+        .collect();
+    let (decisions, _) = evaluate_batch(&engine, interleaved).await;
+    assert_eq!(decisions.len(), DUPLICATES, "Interleaved duplicates were collapsed");
+    for (i, decision) in decisions.iter().enumerate() {
+        let expected = if i % 2 == 0 { "ALLOW" } else { "DENY" };
+        assert_eq!(decision, expected, "Result {} is out of order: {:?}", i, decisions);
+    }
+    println!("✓ Interleaved duplicates answered in request order");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_duplicate_evaluations_are_evaluated_once() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    // Fresh resources per sample so neither side is answered from the evaluation cache
+    let mut single_ms = Vec::with_capacity(SAMPLES);
+    let mut batch_ms = Vec::with_capacity(SAMPLES);
+    for sample in 0..SAMPLES {
+        let single = format!("document:dedup-single-{}", sample);
+        let batch = format!("document:dedup-batch-{}", sample);
+        engine
+            .write_relationships(&[
+                Relationship::new(&single, "viewer", "user:alice"),
+                Relationship::new(&batch, "viewer", "user:alice"),
+            ])
+            .await
+            .expect("Failed to write relationships");
+
+        let (_, single_timing) =
+            evaluate_batch(&engine, vec![evaluation(&single, "viewer", "user:alice")]).await;
+        let (decisions, batch_timing) =
+            evaluate_batch(&engine, vec![evaluation(&batch, "viewer", "user:alice"); DUPLICATES])
+                .await;
+        assert!(decisions.iter().all(|d| d == "ALLOW"), "Sample {} was not all ALLOW", sample);
+
+        let (Some(single), Some(batch)) =
+            (single_timing.get(ServerTiming::EVALUATE), batch_timing.get(ServerTiming::EVALUATE))
+        else {
+            outcome::skip(format!(
+                "Skipping dedup timing test - Server-Timing has no '{}' phase",
+                ServerTiming::EVALUATE
+            ));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        };
+        single_ms.push(single);
+        batch_ms.push(batch);
+    }
+
+    single_ms.sort_by(f64::total_cmp);
+    batch_ms.sort_by(f64::total_cmp);
+    let single_p50 = scenario::percentile(&single_ms, 0.5).expect("No single samples");
+    let batch_p50 = scenario::percentile(&batch_ms, 0.5).expect("No batch samples");
+    println!(
+        "✓ Evaluate phase p50: single {:.2}ms, {} duplicates {:.2}ms",
+        single_p50, DUPLICATES, batch_p50
+    );
+    assert!(
+        batch_p50 <= single_p50 * DEDUP_COST_FACTOR,
+        "{} duplicate evaluations cost {:.2}ms vs {:.2}ms for one; Engine is not deduplicating",
+        DUPLICATES,
+        batch_p50,
+        single_p50
+    );
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
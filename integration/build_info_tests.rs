@@ -0,0 +1,32 @@
+// Build Metadata Tests
+//
+// Reads the Engine and Control versions the suite runs against and holds them to the minimums in
+// INFERADB_MIN_ENGINE_VERSION and INFERADB_MIN_CONTROL_VERSION, so a run against builds too old
+// for the features under test fails once, up front, with the versions named. A minimum set for a
+// service that reports no version fails too; with no minimum set, the test records a skip.
+
+use super::*;
+
+#[tokio::test]
+async fn test_services_meet_minimum_versions() {
+    let build = suite_build_info().await;
+    println!("Engine {} via {:?}", build.engine, build.engine.source);
+    println!("Control {} via {:?}", build.control, build.control.source);
+
+    let minimums = build_info::configured_minimums();
+    if minimums.is_empty() {
+        outcome::skip("Skipping minimum version check - no INFERADB_MIN_*_VERSION is set");
+        return;
+    }
+    let reported: Vec<&str> = ["engine", "control"]
+        .into_iter()
+        .filter(|service| build.service(service).is_some_and(|v| v.version.is_some()))
+        .collect();
+    assert!(
+        !reported.is_empty(),
+        "Minimums are set ({:?}) but neither service reports a version",
+        minimums.iter().map(|(_, variable, minimum)| (variable, minimum)).collect::<Vec<_>>()
+    );
+    build.require_configured_minimums().expect("Environment is older than the suite requires");
+    println!("✓ {} meet the configured minimums", reported.join(" and "));
+}
@@ -0,0 +1,118 @@
+// Cache Eviction Tests
+//
+// Authenticates with tokens from hundreds of distinct certificates to push the auth cache past its
+// capacity. Engine must evict rather than grow without bound, keep caching a small working set
+// effectively afterwards, and still accept the earliest (evicted) certificates once re-fetched.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Distinct certificates to authenticate with; well above the default auth cache capacity
+const CERT_COUNT: usize = 300;
+
+/// Certificates re-used after the flood, all taken from the start of the flood
+const HOT_SET: usize = 10;
+
+/// Passes over the hot set after it has been re-fetched
+const HOT_ROUNDS: usize = 10;
+
+/// Minimum hit rate for the hot set once it is back in the cache
+const MIN_HOT_HIT_RATE: f64 = 0.8;
+
+async fn assert_accepted(fixture: &TestFixture, jwt: &str, cert: usize) {
+    let response = fixture
+        .call_server_evaluate(jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+    assert!(
+        response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND,
+        "Token signed by certificate {} was rejected: {}",
+        cert,
+        response.status()
+    );
+}
+
+#[tokio::test]
+async fn test_auth_cache_evicts_under_certificate_flood() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let mut tokens = Vec::with_capacity(CERT_COUNT);
+    for i in 0..CERT_COUNT {
+        let cert_req = CreateCertificateRequest::new(format!("Eviction Certificate {}", i));
+        let cert = control
+            .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to create certificate {}: {}", i, e));
+        let signing_key = keys::from_base64(&cert.private_key).expect("Invalid private key");
+        tokens.push(
+            fixture
+                .jwt()
+                .signed_by(&cert.certificate.kid, &signing_key)
+                .encode()
+                .expect("Failed to encode JWT"),
+        );
+    }
+    println!("✓ Created {} certificates", CERT_COUNT);
+
+    let before_flood = cache_tests::get_auth_metrics(&fixture.ctx).await;
+
+    for (i, jwt) in tokens.iter().enumerate() {
+        assert_accepted(&fixture, jwt, i).await;
+    }
+    println!("✓ All {} certificates authenticated once", CERT_COUNT);
+
+    let after_flood = cache_tests::get_auth_metrics(&fixture.ctx).await;
+
+    // The earliest certificates are the first evicted; they must be re-fetched, not rejected
+    for (i, jwt) in tokens.iter().take(HOT_SET).enumerate() {
+        assert_accepted(&fixture, jwt, i).await;
+    }
+    println!("✓ Earliest {} certificates still validate after eviction", HOT_SET);
+
+    let hot_start = cache_tests::get_auth_metrics(&fixture.ctx).await;
+    for _ in 0..HOT_ROUNDS {
+        for (i, jwt) in tokens.iter().take(HOT_SET).enumerate() {
+            assert_accepted(&fixture, jwt, i).await;
+        }
+    }
+    let hot_end = cache_tests::get_auth_metrics(&fixture.ctx).await;
+
+    let (Some(before_flood), Some(after_flood), Some(hot_start), Some(hot_end)) =
+        (before_flood, after_flood, hot_start, hot_end)
+    else {
+        outcome::skip("Skipping eviction and hit rate assertions - metrics not available");
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let evictions =
+        cache_tests::counter_increase(before_flood.cache_evictions, after_flood.cache_evictions);
+    println!("  Flood: {} evictions", evictions);
+    assert!(
+        evictions > 0,
+        "Authenticating with {} distinct certificates caused no auth cache evictions",
+        CERT_COUNT
+    );
+
+    let hits = cache_tests::counter_increase(hot_start.cache_hits, hot_end.cache_hits);
+    let misses = cache_tests::counter_increase(hot_start.cache_misses, hot_end.cache_misses);
+    if hits + misses == 0 {
+        outcome::warn("Auth cache counters did not move, skipping hit rate assertion");
+    } else {
+        let hit_rate = hits as f64 / (hits + misses) as f64;
+        println!(
+            "✓ Hot set hit rate {:.2} after the flood ({} hits, {} misses)",
+            hit_rate, hits, misses
+        );
+        assert!(
+            hit_rate >= MIN_HOT_HIT_RATE,
+            "Hot set of {} certificates should be served from cache after the flood, hit rate {:.2}",
+            HOT_SET,
+            hit_rate
+        );
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
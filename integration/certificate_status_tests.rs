@@ -0,0 +1,180 @@
+// Certificate Status Tests
+//
+// Control's certificate listing reports each certificate's lifecycle state (active, rotated,
+// revoked, expired). These tests drive a client's certificates through every state and check at
+// each step that the listed state agrees with what Engine actually accepts, so operators can trust
+// the listing when deciding which keys are live. Skipped when Control does not list certificates
+// or reports no states.
+
+use std::{collections::HashMap, time::Instant};
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Grace period for the rotation; short enough to wait out
+const GRACE_SECONDS: i64 = 2;
+
+/// How long Engine may take to reflect a state change Control already reports
+const PROPAGATION_BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// The client's certificates keyed by kid, or `None` if Control cannot list them with states
+pub(super) async fn listed_states(
+    fixture: &TestFixture,
+) -> Option<HashMap<String, CertificateState>> {
+    let listing = match fixture.control().list_certificates(fixture.org_id, fixture.client_id).await
+    {
+        Ok(listing) => listing,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping certificate status check - listing unsupported: {}",
+                e
+            ));
+            return None;
+        },
+        Err(e) => panic!("Failed to list certificates: {}", e),
+    };
+
+    let mut states = HashMap::new();
+    for cert in listing.certificates {
+        let Some(state) = cert.state else {
+            outcome::skip(
+                "Skipping certificate status check - Control reports no certificate states",
+            );
+            return None;
+        };
+        states.insert(cert.kid, state);
+    }
+    Some(states)
+}
+
+async fn accepted(fixture: &TestFixture, kid: &str, signing_key: &SigningKey) -> bool {
+    let jwt = fixture.jwt().signed_by(kid, signing_key).encode().expect("Failed to encode JWT");
+    let status = fixture
+        .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server")
+        .status();
+    status == StatusCode::OK || status == StatusCode::NOT_FOUND
+}
+
+/// Assert the listed state of `kid` is `expected` and Engine agrees with it
+///
+/// Engine is polled for up to `PROPAGATION_BUDGET`, since Control reports a change first. Returns
+/// `false` without asserting if Control cannot list certificate states.
+pub(super) async fn assert_listing_in_sync(
+    fixture: &TestFixture,
+    kid: &str,
+    signing_key: &SigningKey,
+    expected: CertificateState,
+) -> bool {
+    let Some(states) = listed_states(fixture).await else {
+        return false;
+    };
+    assert_eq!(states.get(kid), Some(&expected), "Listed state of certificate {}", kid);
+
+    let start = Instant::now();
+    while accepted(fixture, kid, signing_key).await != expected.authenticates() {
+        assert!(
+            start.elapsed() < PROPAGATION_BUDGET,
+            "Certificate {} is listed {:?} but Engine still {} it after {:?}",
+            kid,
+            expected,
+            if expected.authenticates() { "rejects" } else { "accepts" },
+            PROPAGATION_BUDGET
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+    }
+    true
+}
+
+#[tokio::test]
+async fn test_listing_tracks_certificate_lifecycle() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let root_kid = fixture.cert_kid.clone();
+    let root_key = fixture.signing_key.clone();
+
+    if !assert_listing_in_sync(&fixture, &root_kid, &root_key, CertificateState::Active).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+    println!("✓ Fixture certificate listed active and accepted");
+
+    // Rotation: the old certificate is listed rotated but keeps working
+    let req = RotateCertificateRequest {
+        name: naming::entity_name("Status Certificate"),
+        grace_period_seconds: GRACE_SECONDS,
+    };
+    let rotation = fixture
+        .control()
+        .rotate_certificate(fixture.org_id, fixture.client_id, fixture.cert_id, &req)
+        .await
+        .expect("Failed to rotate certificate");
+    let new_kid = rotation.certificate.kid.clone();
+    let new_key = keys::from_base64(&rotation.private_key).expect("Invalid private key");
+
+    let cutoff = rotation.rotated_from.valid_until;
+    if cutoff.is_none_or(|cutoff| cutoff > Utc::now() + Duration::seconds(GRACE_SECONDS + 1)) {
+        assert_listing_in_sync(&fixture, &root_kid, &root_key, CertificateState::Rotated).await;
+        println!("✓ Rotated-out certificate listed rotated and still accepted");
+    }
+
+    // The successor is listed active from the start but only accepted after the grace period
+    let valid_from = rotation.valid_from;
+    if let Ok(remaining) = (valid_from - Utc::now()).to_std() {
+        tokio::time::sleep(remaining).await;
+    }
+    assert_listing_in_sync(&fixture, &new_kid, &new_key, CertificateState::Active).await;
+    println!("✓ Rotated-in certificate listed active and accepted after its grace period");
+
+    // Past the cutoff, if Control sets one, the old certificate is listed expired
+    if let Some(cutoff) = cutoff {
+        if let Ok(remaining) = (cutoff - Utc::now()).to_std() {
+            tokio::time::sleep(remaining).await;
+        }
+        assert_listing_in_sync(&fixture, &root_kid, &root_key, CertificateState::Expired).await;
+        println!("✓ Certificate past its cutoff listed expired and rejected");
+    }
+
+    // Revocation
+    fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, rotation.certificate.id)
+        .await
+        .expect("Failed to revoke certificate");
+    assert_listing_in_sync(&fixture, &new_kid, &new_key, CertificateState::Revoked).await;
+    println!("✓ Revoked certificate still listed, as revoked, and rejected");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_listing_is_scoped_to_client() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(before) = listed_states(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("Listed Certificate"));
+    let cert = fixture
+        .control()
+        .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+        .await
+        .expect("Failed to create certificate");
+
+    let other = TestFixture::create().await.expect("Failed to create second fixture");
+
+    let after = listed_states(&fixture).await.expect("Listing stopped reporting states");
+    assert_eq!(after.len(), before.len() + 1, "Listing should gain exactly the new certificate");
+    assert_eq!(
+        after.get(&cert.certificate.kid),
+        Some(&CertificateState::Active),
+        "New certificate should be listed active"
+    );
+    assert!(!after.contains_key(&other.cert_kid), "Another client's certificate was listed");
+    println!("✓ Listing contains this client's certificates only");
+
+    other.cleanup().await.expect("Failed to cleanup second fixture");
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
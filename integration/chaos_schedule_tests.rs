@@ -0,0 +1,113 @@
+// Chaos Schedule Tests
+//
+// Runs steady evaluate traffic for a minute while a `ChaosSchedule` kills Control, slows Ledger,
+// and restarts Engine in turn, then requires overall availability to stay above the threshold and
+// writes the fault/error timeline to `target/chaos-timeline.json`. Uses whichever of the
+// `CONTROL_KILL`, `LEDGER_LATENCY`, and `ENGINE_RESTART` chaos hooks are configured; skipped if
+// none are. `INFERADB_CHAOS_MIN_AVAILABILITY` overrides the threshold (default 0.95).
+
+use std::path::Path;
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Fault hooks with their offset and duration, in seconds, within the run
+const FAULTS: &[(&str, u64, u64)] =
+    &[("CONTROL_KILL", 10, 15), ("LEDGER_LATENCY", 25, 15), ("ENGINE_RESTART", 45, 0)];
+
+/// Length of the steady-state run
+const RUN_DURATION: std::time::Duration = std::time::Duration::from_secs(60);
+
+#[tokio::test]
+async fn test_availability_holds_through_chaos_schedule() {
+    let mut schedule = ChaosSchedule::new(RUN_DURATION).min_availability(
+        std::env::var("INFERADB_CHAOS_MIN_AVAILABILITY")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .unwrap_or(0.95),
+    );
+    for (name, at, duration) in FAULTS {
+        if let Some(hook) = ChaosHook::from_env(name) {
+            schedule = schedule.fault(
+                hook,
+                std::time::Duration::from_secs(*at),
+                std::time::Duration::from_secs(*duration),
+            );
+        }
+    }
+    if schedule.faults().is_empty() {
+        outcome::skip("Skipping chaos schedule test - no scheduled fault hooks configured");
+        return;
+    }
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+
+    // Warm Engine's certificate cache so a Control outage alone costs nothing
+    let warm = fixture
+        .call_server_evaluate(&jwt, "document:chaos", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+    assert!(
+        warm.status() == StatusCode::OK || warm.status() == StatusCode::NOT_FOUND,
+        "Expected Engine to accept the JWT before the schedule, got {}",
+        warm.status()
+    );
+
+    let timeline = schedule
+        .run(|| async {
+            let status = fixture
+                .call_server_evaluate(&jwt, "document:chaos", "viewer", "user:alice")
+                .await?
+                .status();
+            anyhow::ensure!(
+                status == StatusCode::OK || status == StatusCode::NOT_FOUND,
+                "HTTP {}",
+                status
+            );
+            Ok(())
+        })
+        .await;
+
+    let path = Path::new("target/chaos-timeline.json");
+    timeline.write(path).expect("Failed to write chaos timeline");
+    for bucket in timeline.spikes() {
+        println!(
+            "  error spike at {}s: {}/{} failed during {:?} ({})",
+            bucket.start_ms / 1000,
+            bucket.errors,
+            bucket.probes,
+            bucket.active_faults,
+            bucket.sample_errors.join("; ")
+        );
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    for event in &timeline.events {
+        assert!(
+            event.error.is_none(),
+            "{} {} failed at {}ms: {:?}",
+            event.action,
+            event.fault,
+            event.at_ms,
+            event.error
+        );
+    }
+    assert!(
+        timeline.passed(),
+        "Availability {:.2}% fell below {:.2}% ({} of {} probes failed); timeline in {}",
+        timeline.availability * 100.0,
+        timeline.min_availability * 100.0,
+        timeline.errors,
+        timeline.probes,
+        path.display()
+    );
+    println!(
+        "✓ {:.2}% available across {} probes through {} faults",
+        timeline.availability * 100.0,
+        timeline.probes,
+        schedule.faults().len()
+    );
+}
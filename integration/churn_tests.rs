@@ -0,0 +1,91 @@
+// Churn Tests
+//
+// Runs ordinary correctness checks while a background churn (`TestFixture::start_churn`) keeps
+// adding and removing other relationships in the same vault: grants and revocations must take
+// effect, and a filtered list must return exactly this test's tuples, however much else is moving.
+// Propagation latency measured by the churn itself must stay within budget.
+
+use super::*;
+
+/// Churned operations per second, unless INFERADB_CHURN_RATE overrides it
+const DEFAULT_CHURN_RATE: f64 = 20.0;
+
+/// How long the churn runs before the checks start, so the dataset is already moving
+const WARM_UP: tokio::time::Duration = tokio::time::Duration::from_secs(3);
+
+/// Slowest acceptable p95 propagation under churn
+const PROPAGATION_P95_BUDGET_MS: f64 = 2000.0;
+
+#[tokio::test]
+async fn test_correctness_under_relationship_churn() {
+    let rate = std::env::var("INFERADB_CHURN_RATE")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_CHURN_RATE);
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.write", "inferadb.check", "inferadb.list-relationships"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    let churn = fixture.start_churn(ChurnConfig::new(rate));
+    tokio::time::sleep(WARM_UP).await;
+
+    let run = Uuid::new_v4().simple().to_string();
+    let document = format!("document:stable-{}", run);
+    let alice = format!("user:alice-{}", run);
+    let bob = format!("user:bob-{}", run);
+    let mine = vec![
+        Relationship::new(&document, "viewer", &alice),
+        Relationship::new(&document, "editor", &bob),
+    ];
+
+    for round in 1..=5 {
+        engine.write_relationships(&mine).await.expect("Failed to write relationships");
+        engine
+            .evaluate(&document, "viewer", &alice)
+            .await
+            .expect("Evaluate failed")
+            .assert_allowed(&format!("granted viewer, round {}", round));
+
+        let mut listed = engine
+            .list_all_relationships(&RelationshipFilter::new().resource(&document))
+            .await
+            .expect("Failed to list relationships");
+        listed.sort();
+        let mut expected = mine.clone();
+        expected.sort();
+        assert_eq!(listed, expected, "Filtered list picked up churn in round {}", round);
+
+        engine.delete_relationships(&mine[..1]).await.expect("Failed to delete relationship");
+        let allowed =
+            engine.check(&document, "viewer", &alice).await.expect("Check failed after revocation");
+        assert!(!allowed, "Revoked viewer still allowed in round {}", round);
+        engine.delete_relationships(&mine[1..]).await.expect("Failed to delete relationship");
+    }
+    println!("✓ 5 grant/list/revoke rounds correct under churn");
+
+    let stats = churn.stop().await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    println!(
+        "  churn: {} writes, {} deletes ({:.1}/s), {} errors, propagation p50 {:?} p95 {:?} ms",
+        stats.writes,
+        stats.deletes,
+        stats.achieved_rate(),
+        stats.errors,
+        stats.propagation_ms(0.50),
+        stats.propagation_ms(0.95)
+    );
+    assert_eq!(stats.errors, 0, "Churn operations failed: {:?}", stats.sample_errors);
+    assert_eq!(stats.propagation_timeouts, 0, "Churned writes never became visible");
+    if let Some(p95) = stats.propagation_ms(0.95) {
+        assert!(
+            p95 <= PROPAGATION_P95_BUDGET_MS,
+            "p95 propagation {:.0}ms under churn exceeds {:.0}ms",
+            p95,
+            PROPAGATION_P95_BUDGET_MS
+        );
+    }
+    println!("✓ Churned writes became visible within budget");
+}
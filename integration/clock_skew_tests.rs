@@ -0,0 +1,110 @@
+// Clock Skew Tests
+//
+// Skews Engine's clock by ±2 minutes, as an NTP drift would, and checks token validation keeps
+// working: freshly minted tokens are all still accepted, and the expiry boundary sits at `exp +
+// leeway` on Engine's clock, so the configured leeway is honored rather than widened or ignored.
+// Uses two chaos hooks, each skipped unless configured, for example with libfaketime:
+//   INFERADB_CHAOS_ENGINE_CLOCK_AHEAD_*    sets Engine's clock SKEW_SECONDS ahead, then resets it
+//   INFERADB_CHAOS_ENGINE_CLOCK_BEHIND_*   sets Engine's clock SKEW_SECONDS behind, then resets it
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Magnitude of the skew the hooks must apply
+const SKEW_SECONDS: i64 = 120;
+
+/// Distance either side of the expiry boundary, covering skew imprecision and request latency
+const BOUNDARY_MARGIN_SECONDS: i64 = 10;
+
+/// Fresh tokens presented while skewed; none may be rejected
+const BURST_SIZE: usize = 20;
+
+async fn status_of(fixture: &TestFixture, jwt: &str) -> StatusCode {
+    fixture
+        .call_server_evaluate(jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server")
+        .status()
+}
+
+fn accepted(status: StatusCode) -> bool {
+    status == StatusCode::OK || status == StatusCode::NOT_FOUND
+}
+
+/// A token that expires `offset` seconds from Engine's point of view, given its clock `skew`
+fn expiring_on_engine_clock(fixture: &TestFixture, skew: i64, offset: i64) -> String {
+    fixture
+        .jwt()
+        .expiring_in(0)
+        .time_shift(Duration::seconds(skew + offset))
+        .encode()
+        .expect("Failed to encode JWT")
+}
+
+/// Run the skew checks with `hook`'s fault applied; `skew` is Engine's clock minus ours
+async fn check_under_skew(name: &str, skew: i64) {
+    let Some(hook) = ChaosHook::from_env(name) else {
+        outcome::skip(format!(
+            "Skipping clock skew test - INFERADB_CHAOS_{}_* not configured",
+            name
+        ));
+        return;
+    };
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let leeway = EnvProfile::current().clock_leeway_seconds;
+
+    let skewed = hook.disrupt().expect("Failed to skew Engine's clock");
+    println!("✓ Engine clock skewed by {}s", skew);
+
+    let mut rejected = 0;
+    for _ in 0..BURST_SIZE {
+        let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+        if status_of(&fixture, &jwt).await == StatusCode::UNAUTHORIZED {
+            rejected += 1;
+        }
+    }
+    let inside = expiring_on_engine_clock(&fixture, skew, BOUNDARY_MARGIN_SECONDS - leeway);
+    let outside = expiring_on_engine_clock(&fixture, skew, -BOUNDARY_MARGIN_SECONDS - leeway);
+    let inside_status = status_of(&fixture, &inside).await;
+    let outside_status = status_of(&fixture, &outside).await;
+
+    skewed.restore().expect("Failed to reset Engine's clock");
+    println!("✓ Engine clock reset");
+
+    assert_eq!(
+        rejected, 0,
+        "{} of {} fresh tokens rejected under {}s skew",
+        rejected, BURST_SIZE, skew
+    );
+    println!("✓ All {} fresh tokens accepted", BURST_SIZE);
+    assert!(
+        accepted(inside_status),
+        "Token {}s inside the leeway on Engine's clock was rejected: {}",
+        BOUNDARY_MARGIN_SECONDS,
+        inside_status
+    );
+    assert_eq!(
+        outside_status,
+        StatusCode::UNAUTHORIZED,
+        "Token {}s past the leeway on Engine's clock was accepted",
+        BOUNDARY_MARGIN_SECONDS
+    );
+    println!("✓ Expiry boundary honors the {}s leeway on Engine's clock", leeway);
+
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let status = status_of(&fixture, &jwt).await;
+    assert!(accepted(status), "Fresh token rejected after the clock was reset: {}", status);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_engine_clock_ahead() {
+    check_under_skew("ENGINE_CLOCK_AHEAD", SKEW_SECONDS).await;
+}
+
+#[tokio::test]
+async fn test_engine_clock_behind() {
+    check_under_skew("ENGINE_CLOCK_BEHIND", -SKEW_SECONDS).await;
+}
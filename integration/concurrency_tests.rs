@@ -1,13 +1,143 @@
 // Concurrent Authentication Tests
 //
-// Tests for validating concurrent authentication scenarios
-
-use std::{sync::Arc, time::Instant};
+// Tests for validating concurrent authentication scenarios. Every test runs its requests under a
+// `Watchdog`, so a deadlock or starved request fails with a dump of what is stuck instead of
+// hanging until CI kills the job.
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Instant,
+};
 
 use reqwest::StatusCode;
+use tokio::task::JoinHandle;
 
 use super::*;
 
+/// Longest a test may go without any of its requests completing
+const STALL_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How often the watchdog samples progress
+const SAMPLE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// In-flight requests listed in a stall report, oldest first
+const DUMP_LIMIT: usize = 20;
+
+/// Where an in-flight request is: spawned but never polled, or started and awaiting its response
+#[derive(Clone, Copy)]
+enum Step {
+    Queued,
+    Running,
+}
+
+/// An in-flight request: when it was spawned, and its step
+#[derive(Clone, Copy)]
+struct InFlight {
+    spawned: Instant,
+    step: Step,
+}
+
+/// Progress tracker that turns a silent hang into a failure naming the stuck requests
+#[derive(Default)]
+struct Watchdog {
+    completed: AtomicUsize,
+    in_flight: Mutex<BTreeMap<String, InFlight>>,
+}
+
+impl Watchdog {
+    fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    /// Spawn a request, tracking it as in flight under `label` until it completes
+    fn spawn<F>(self: &Arc<Self>, label: String, future: F) -> JoinHandle<F::Output>
+    where
+        F: Future + Send + 'static,
+        F::Output: Send + 'static,
+    {
+        let watchdog = Arc::clone(self);
+        watchdog
+            .in_flight()
+            .insert(label.clone(), InFlight { spawned: Instant::now(), step: Step::Queued });
+        tokio::spawn(async move {
+            if let Some(request) = watchdog.in_flight().get_mut(&label) {
+                request.step = Step::Running;
+            }
+            let output = future.await;
+            watchdog.in_flight().remove(&label);
+            watchdog.completed.fetch_add(1, Ordering::SeqCst);
+            output
+        })
+    }
+
+    /// The in-flight table; a request that panicked while holding the lock leaves it usable
+    fn in_flight(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, InFlight>> {
+        self.in_flight.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Drive `future` (the loop joining the spawned requests), panicking with a dump of the
+    /// in-flight requests if none completes for `STALL_TIMEOUT`
+    async fn watch<F: Future>(&self, future: F) -> F::Output {
+        tokio::pin!(future);
+        let mut ticker = tokio::time::interval(SAMPLE_INTERVAL);
+        let mut last_completed = self.completed.load(Ordering::SeqCst);
+        let started = Instant::now();
+        let mut last_progress = started;
+
+        loop {
+            tokio::select! {
+                output = &mut future => return output,
+                _ = ticker.tick() => {
+                    let completed = self.completed.load(Ordering::SeqCst);
+                    if completed != last_completed {
+                        last_completed = completed;
+                        last_progress = Instant::now();
+                    } else if last_progress.elapsed() >= STALL_TIMEOUT {
+                        panic!("{}", self.dump(completed, started.elapsed()));
+                    }
+                },
+            }
+        }
+    }
+
+    /// Stall report: time watched, progress so far, runtime task count, and the oldest in-flight
+    /// requests with their steps
+    fn dump(&self, completed: usize, elapsed: std::time::Duration) -> String {
+        let in_flight = self.in_flight();
+        let mut stuck: Vec<_> = in_flight.iter().collect();
+        stuck.sort_by_key(|(_, request)| request.spawned);
+        let queued =
+            stuck.iter().filter(|(_, request)| matches!(request.step, Step::Queued)).count();
+
+        let mut report = format!(
+            "No request completed for {:?}, {:?} into the run ({} completed, {} in flight of which \
+             {} never started, {} runtime tasks alive)",
+            STALL_TIMEOUT,
+            elapsed,
+            completed,
+            in_flight.len(),
+            queued,
+            tokio::runtime::Handle::current().metrics().num_alive_tasks()
+        );
+        for (label, request) in stuck.iter().take(DUMP_LIMIT) {
+            let step = match request.step {
+                Step::Queued => "queued",
+                Step::Running => "awaiting response",
+            };
+            report.push_str(&format!("\n  {} {} for {:?}", label, step, request.spawned.elapsed()));
+        }
+        if stuck.len() > DUMP_LIMIT {
+            report.push_str(&format!("\n  ... and {} more", stuck.len() - DUMP_LIMIT));
+        }
+        report
+    }
+}
+
 #[tokio::test]
 async fn test_concurrent_authentication_single_client() {
     let fixture = TestFixture::create().await.expect("Failed to create test fixture");
@@ -17,6 +147,7 @@ async fn test_concurrent_authentication_single_client() {
         Arc::new(fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT"));
 
     // Launch 100 concurrent requests with the same JWT
+    let watchdog = Watchdog::new();
     let mut handles = Vec::new();
     let start = Instant::now();
 
@@ -24,7 +155,7 @@ async fn test_concurrent_authentication_single_client() {
         let jwt_clone = Arc::clone(&jwt);
         let ctx = fixture.ctx.clone();
 
-        let handle = tokio::spawn(async move {
+        let handle = watchdog.spawn(format!("evaluate {}", i), async move {
             let body = serde_json::json!({
                 "evaluations": [{
                     "resource": format!("document:{}", i),
@@ -37,7 +168,7 @@ async fn test_concurrent_authentication_single_client() {
                 .post(ctx.engine_url("/evaluate"))
                 .header("Authorization", format!("Bearer {}", jwt_clone))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to call server")
         });
@@ -49,15 +180,19 @@ async fn test_concurrent_authentication_single_client() {
     let mut success_count = 0;
     let mut failure_count = 0;
 
-    for handle in handles {
-        let response = handle.await.expect("Task failed");
-        if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
-            success_count += 1;
-        } else {
-            failure_count += 1;
-            eprintln!("Request failed with status: {}", response.status());
-        }
-    }
+    watchdog
+        .watch(async {
+            for handle in handles {
+                let response = handle.await.expect("Task failed");
+                if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+                    success_count += 1;
+                } else {
+                    failure_count += 1;
+                    eprintln!("Request failed with status: {}", response.status());
+                }
+            }
+        })
+        .await;
 
     let elapsed = start.elapsed();
 
@@ -75,14 +210,13 @@ async fn test_concurrent_authentication_single_client() {
 
 #[tokio::test]
 async fn test_concurrent_authentication_multiple_clients() {
-    // Create 5 different test fixtures sequentially to avoid overwhelming the system
-    // during fixture creation (user registration, org creation, vault creation, etc.)
+    // Fixture creation is throttled globally, so these can be requested all at once
     let mut fixtures = Vec::new();
-    for i in 0..5 {
-        match TestFixture::create().await {
+    for (i, result) in TestFixture::create_many(5).await.into_iter().enumerate() {
+        match result {
             Ok(fixture) => fixtures.push(fixture),
             Err(e) => {
-                eprintln!("Warning: Failed to create fixture {}: {}", i, e);
+                outcome::warn(format!("Failed to create fixture {}: {}", i, e));
             },
         }
     }
@@ -104,6 +238,7 @@ async fn test_concurrent_authentication_multiple_clients() {
 
     // Launch concurrent requests (one per client)
     let fixture_count = fixtures.len();
+    let watchdog = Watchdog::new();
     let mut handles = Vec::new();
     let start = Instant::now();
 
@@ -111,7 +246,7 @@ async fn test_concurrent_authentication_multiple_clients() {
         let jwt_clone = jwt.clone();
         let ctx = fixture.ctx.clone();
 
-        let handle = tokio::spawn(async move {
+        let handle = watchdog.spawn(format!("client {}", i), async move {
             let body = serde_json::json!({
                 "evaluations": [{
                     "resource": format!("document:client{}", i),
@@ -124,7 +259,7 @@ async fn test_concurrent_authentication_multiple_clients() {
                 .post(ctx.engine_url("/evaluate"))
                 .header("Authorization", format!("Bearer {}", jwt_clone))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to call server")
         });
@@ -134,12 +269,16 @@ async fn test_concurrent_authentication_multiple_clients() {
 
     // Wait for all requests
     let mut success_count = 0;
-    for handle in handles {
-        let response = handle.await.expect("Task failed");
-        if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
-            success_count += 1;
-        }
-    }
+    watchdog
+        .watch(async {
+            for handle in handles {
+                let response = handle.await.expect("Task failed");
+                if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+                    success_count += 1;
+                }
+            }
+        })
+        .await;
 
     let elapsed = start.elapsed();
 
@@ -167,6 +306,7 @@ async fn test_concurrent_vault_operations() {
         .expect("Failed to generate JWT");
 
     // Launch concurrent write and read operations
+    let watchdog = Watchdog::new();
     let mut handles = Vec::new();
 
     // 50 concurrent writes
@@ -174,7 +314,7 @@ async fn test_concurrent_vault_operations() {
         let jwt_clone = jwt.clone();
         let ctx = fixture.ctx.clone();
 
-        let handle = tokio::spawn(async move {
+        let handle = watchdog.spawn(format!("write {}", i), async move {
             let body = serde_json::json!({
                 "relationships": [{
                     "resource": format!("document:{}", i),
@@ -187,7 +327,7 @@ async fn test_concurrent_vault_operations() {
                 .post(ctx.engine_url("/relationships/write"))
                 .header("Authorization", format!("Bearer {}", jwt_clone))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to write")
         });
@@ -200,7 +340,7 @@ async fn test_concurrent_vault_operations() {
         let jwt_clone = jwt.clone();
         let ctx = fixture.ctx.clone();
 
-        let handle = tokio::spawn(async move {
+        let handle = watchdog.spawn(format!("evaluate {}", i), async move {
             let body = serde_json::json!({
                 "evaluations": [{
                     "resource": format!("document:{}", i),
@@ -213,7 +353,7 @@ async fn test_concurrent_vault_operations() {
                 .post(ctx.engine_url("/evaluate"))
                 .header("Authorization", format!("Bearer {}", jwt_clone))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to evaluate")
         });
@@ -223,12 +363,16 @@ async fn test_concurrent_vault_operations() {
 
     // Wait for all operations
     let mut success_count = 0;
-    for handle in handles {
-        let response = handle.await.expect("Task failed");
-        if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
-            success_count += 1;
-        }
-    }
+    watchdog
+        .watch(async {
+            for handle in handles {
+                let response = handle.await.expect("Task failed");
+                if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+                    success_count += 1;
+                }
+            }
+        })
+        .await;
 
     assert_eq!(success_count, 100, "Expected 100 successful operations, got {}", success_count);
 
@@ -251,6 +395,7 @@ async fn test_cache_under_concurrent_load() {
     let jwts = Arc::new(vec![jwt1, jwt2, jwt3]);
 
     // Launch 300 concurrent requests (100 per JWT)
+    let watchdog = Watchdog::new();
     let mut handles = Vec::new();
     let start = Instant::now();
 
@@ -258,7 +403,7 @@ async fn test_cache_under_concurrent_load() {
         let jwts_clone = Arc::clone(&jwts);
         let ctx = fixture.ctx.clone();
 
-        let handle = tokio::spawn(async move {
+        let handle = watchdog.spawn(format!("evaluate {}", i), async move {
             // Rotate through JWTs
             let jwt = &jwts_clone[i % 3];
 
@@ -274,7 +419,7 @@ async fn test_cache_under_concurrent_load() {
                 .post(ctx.engine_url("/evaluate"))
                 .header("Authorization", format!("Bearer {}", jwt))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to call server")
         });
@@ -284,12 +429,16 @@ async fn test_cache_under_concurrent_load() {
 
     // Wait for all requests
     let mut success_count = 0;
-    for handle in handles {
-        let response = handle.await.expect("Task failed");
-        if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
-            success_count += 1;
-        }
-    }
+    watchdog
+        .watch(async {
+            for handle in handles {
+                let response = handle.await.expect("Task failed");
+                if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+                    success_count += 1;
+                }
+            }
+        })
+        .await;
 
     let elapsed = start.elapsed();
 
@@ -317,6 +466,7 @@ async fn test_concurrent_first_time_authentication() {
     // Launch 50 concurrent requests with the same new JWT
     // This tests thundering herd protection - all requests arrive before
     // the certificate is cached
+    let watchdog = Watchdog::new();
     let mut handles = Vec::new();
     let start = Instant::now();
 
@@ -324,7 +474,7 @@ async fn test_concurrent_first_time_authentication() {
         let jwt_clone = jwt.clone();
         let ctx = fixture.ctx.clone();
 
-        let handle = tokio::spawn(async move {
+        let handle = watchdog.spawn(format!("evaluate {}", i), async move {
             let body = serde_json::json!({
                 "evaluations": [{
                     "resource": format!("document:{}", i),
@@ -337,7 +487,7 @@ async fn test_concurrent_first_time_authentication() {
                 .post(ctx.engine_url("/evaluate"))
                 .header("Authorization", format!("Bearer {}", jwt_clone))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to call server")
         });
@@ -347,12 +497,16 @@ async fn test_concurrent_first_time_authentication() {
 
     // Wait for all requests
     let mut success_count = 0;
-    for handle in handles {
-        let response = handle.await.expect("Task failed");
-        if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
-            success_count += 1;
-        }
-    }
+    watchdog
+        .watch(async {
+            for handle in handles {
+                let response = handle.await.expect("Task failed");
+                if response.status().is_success() || response.status() == StatusCode::NOT_FOUND {
+                    success_count += 1;
+                }
+            }
+        })
+        .await;
 
     let elapsed = start.elapsed();
 
@@ -366,3 +520,128 @@ async fn test_concurrent_first_time_authentication() {
 
     fixture.cleanup().await.expect("Failed to cleanup");
 }
+
+/// Outcome one JWT must see for every request in the mixed-credential load test
+struct Credential {
+    label: &'static str,
+    jwt: String,
+    /// Whether evaluating the seeded tuple must be allowed
+    sees_seed: bool,
+    /// Whether relationship writes must succeed (otherwise 403)
+    can_write: bool,
+}
+
+#[tokio::test]
+async fn test_cache_partitioned_by_scope_and_vault_under_concurrent_load() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    // Seed vault A with the tuple every credential evaluates
+    let writer_jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate writer JWT");
+    fixture
+        .engine(&writer_jwt)
+        .write_relationships(&[Relationship::new("document:shared", "viewer", "user:alice")])
+        .await
+        .expect("Failed to seed vault A");
+
+    let vault_req = CreateVaultRequest {
+        name: naming::entity_name("Cache Partition Vault B"),
+        organization_id: fixture.org_id,
+    };
+    let vault_b_id = fixture
+        .control()
+        .create_vault(fixture.org_id, &vault_req)
+        .await
+        .expect("Failed to create vault B")
+        .vault
+        .id;
+
+    // Same client and key; the JWTs differ only in scope, vault_role, and vault_id
+    let credentials = Arc::new(vec![
+        Credential { label: "vault A writer", jwt: writer_jwt, sees_seed: true, can_write: true },
+        Credential {
+            label: "vault A reader",
+            jwt: fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT"),
+            sees_seed: true,
+            can_write: false,
+        },
+        Credential {
+            label: "vault B reader",
+            jwt: fixture
+                .generate_jwt(Some(vault_b_id), &["inferadb.check"])
+                .expect("Failed to generate JWT"),
+            sees_seed: false,
+            can_write: false,
+        },
+    ]);
+
+    let initial_metrics = cache_tests::get_auth_metrics(&fixture.ctx).await;
+
+    // 300 concurrent requests, interleaving credentials and alternating evaluate/write
+    let watchdog = Watchdog::new();
+    let mut handles = Vec::new();
+    for i in 0..300 {
+        let credentials = Arc::clone(&credentials);
+        let ctx = fixture.ctx.clone();
+
+        handles.push(watchdog.spawn(format!("request {}", i), async move {
+            let credential = &credentials[i % credentials.len()];
+            let engine = EngineClient::new(ctx, credential.jwt.as_str());
+
+            if i % 2 == 0 {
+                let allowed = engine
+                    .check("document:shared", "viewer", "user:alice")
+                    .await
+                    .expect("Evaluation failed");
+                if allowed == credential.sees_seed {
+                    Ok(())
+                } else {
+                    Err(format!("{}: evaluate returned allowed={}", credential.label, allowed))
+                }
+            } else {
+                let relationship =
+                    Relationship::new(format!("document:load-{}", i), "viewer", "user:alice");
+                let result = engine.write_relationships(&[relationship]).await;
+                match (&result, credential.can_write) {
+                    (Ok(()), true) => Ok(()),
+                    (Err(e), false) if e.status() == Some(StatusCode::FORBIDDEN) => Ok(()),
+                    _ => Err(format!("{}: write returned {:?}", credential.label, result.err())),
+                }
+            }
+        }));
+    }
+
+    let mut leaks = Vec::new();
+    watchdog
+        .watch(async {
+            for handle in handles {
+                if let Err(leak) = handle.await.expect("Task failed") {
+                    leaks.push(leak);
+                }
+            }
+        })
+        .await;
+    assert!(
+        leaks.is_empty(),
+        "{} request(s) saw another JWT's privileges:\n  {}",
+        leaks.len(),
+        leaks.join("\n  ")
+    );
+    println!("✓ 300 concurrent requests across 3 credentials kept their own privileges");
+
+    // A vault B lookup answered from vault A's cache entry would register no miss
+    let final_metrics = cache_tests::get_auth_metrics(&fixture.ctx).await;
+    if let (Some(initial), Some(final_metrics)) = (initial_metrics, final_metrics) {
+        let misses =
+            cache_tests::counter_increase(initial.cache_misses, final_metrics.cache_misses);
+        let hits = cache_tests::counter_increase(initial.cache_hits, final_metrics.cache_hits);
+        println!("  Cache during load: {} hits, {} misses", hits, misses);
+        assert!(misses >= 1, "Cold vault B lookups must miss the cache, saw {} misses", misses);
+    } else {
+        outcome::warn("Metrics not available, cache partitioning checked through responses only");
+    }
+
+    let _ = fixture.control().delete_vault(fixture.org_id, vault_b_id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,255 @@
+// Consistency Tests
+//
+// Runs evaluate under each per-request consistency requirement (`full`, `at_least_as_fresh`,
+// `minimize_latency`, and `at_exact_snapshot` where supported) against a vault whose data changes
+// between revisions, so the requirements are told apart by what they return rather than by their
+// latency: a snapshot taken before a grant must still deny, `full` and `at_least_as_fresh` must see
+// the grant at once. Staleness is created on purpose: a DENY is cached under `minimize_latency`,
+// the grant is written, and both modes are read at once; within a few attempts `minimize_latency`
+// must answer from its stale cache while `full` allows. Malformed requirements must be rejected
+// with 400. Skipped if Engine ignores the `consistency` field or reports no write revisions.
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+const DOCUMENT: &str = "document:ledger";
+const SUBJECT: &str = "user:alice";
+
+/// Write-then-read attempts at catching `minimize_latency` serving a decision cached before a write
+const STALENESS_ATTEMPTS: usize = 5;
+
+fn engine(fixture: &TestFixture) -> EngineClient {
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    fixture.engine(&jwt)
+}
+
+/// Evaluate `viewer` on `document` under `consistency`
+///
+/// `None` when Engine rejects the requirement as unsupported (400, 422, or 501).
+async fn decision(
+    engine: &EngineClient,
+    document: &str,
+    consistency: &Consistency,
+) -> Option<bool> {
+    match engine.evaluate_with(document, "viewer", SUBJECT, consistency).await {
+        Ok(response) => Some(response.allowed()),
+        Err(e) if e.is_not_found() => Some(false),
+        Err(e)
+            if matches!(
+                e.status(),
+                Some(
+                    StatusCode::BAD_REQUEST
+                        | StatusCode::UNPROCESSABLE_ENTITY
+                        | StatusCode::NOT_IMPLEMENTED
+                )
+            ) =>
+        {
+            eprintln!("  {} not supported: {}", consistency.name(), e);
+            None
+        },
+        Err(e) => panic!("Evaluate with {} failed: {}", consistency.name(), e),
+    }
+}
+
+/// Cache a DENY for a fresh document under `minimize_latency`, grant it, and read it back at once
+/// under `minimize_latency` and then `full`; true if `minimize_latency` served the stale DENY
+async fn observe_staleness(engine: &EngineClient, attempt: usize) -> bool {
+    let document = format!("document:stale-{}", attempt);
+    for _ in 0..3 {
+        let cached = decision(engine, &document, &Consistency::MinimizeLatency)
+            .await
+            .expect("minimize_latency must be accepted when consistency is supported");
+        assert!(!cached, "{} should deny before the grant", document);
+    }
+
+    engine
+        .write_relationships(&[Relationship::new(document.as_str(), "viewer", SUBJECT)])
+        .await
+        .expect("Failed to write grant");
+    let latency = decision(engine, &document, &Consistency::MinimizeLatency).await;
+    let full = decision(engine, &document, &Consistency::Full).await;
+    assert_eq!(full, Some(true), "full must see the grant to {} at once", document);
+    latency == Some(false)
+}
+
+/// POST an evaluate whose `consistency` field is `consistency`, verbatim
+async fn raw_evaluate(engine: &EngineClient, consistency: serde_json::Value) -> StatusCode {
+    let body = serde_json::json!({
+        "evaluations": [{ "subject": SUBJECT, "resource": DOCUMENT, "permission": "viewer" }],
+        "consistency": consistency,
+    });
+    engine
+        .request(Method::POST, "/evaluate")
+        .json(&body)
+        .send_recorded()
+        .await
+        .expect("Failed to call server")
+        .status()
+}
+
+/// Whether Engine validates the `consistency` field at all; a nonsense requirement being
+/// accepted means the field is ignored
+async fn consistency_supported(engine: &EngineClient) -> bool {
+    let status =
+        raw_evaluate(engine, serde_json::json!({ "requirement": "no_such_requirement" })).await;
+    if status.is_success() || status == StatusCode::NOT_FOUND {
+        outcome::skip(format!(
+            "Skipping consistency test - Engine ignores the consistency field ({})",
+            status
+        ));
+        return false;
+    }
+    true
+}
+
+#[tokio::test]
+async fn test_consistency_matrix_under_deliberate_staleness() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let engine = engine(&fixture);
+    if !consistency_supported(&engine).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    // Warm every cache with the DENY before the grant exists
+    for consistency in [Consistency::MinimizeLatency, Consistency::Full] {
+        assert_eq!(
+            decision(&engine, DOCUMENT, &consistency).await,
+            Some(false),
+            "{} should deny before the grant",
+            consistency.name()
+        );
+    }
+
+    let unrelated = Relationship::new("document:unrelated", "viewer", "user:zed");
+    let grant = Relationship::new(DOCUMENT, "viewer", SUBJECT);
+    let before = engine
+        .write_relationships_at(std::slice::from_ref(&unrelated))
+        .await
+        .expect("Failed to write relationship");
+    let granted = engine
+        .write_relationships_at(std::slice::from_ref(&grant))
+        .await
+        .expect("Failed to write grant");
+    let (Some(before), Some(granted)) = (before, granted) else {
+        outcome::skip("Skipping consistency test - Engine reports no revision for writes");
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    println!("  revisions: before grant {}, grant {}", before, granted);
+
+    // Read-your-writes requirements must see the grant immediately
+    for consistency in [Consistency::Full, Consistency::AtLeastAsFresh(granted.clone())] {
+        assert_eq!(
+            decision(&engine, DOCUMENT, &consistency).await,
+            Some(true),
+            "{} must see a write it is at least as fresh as",
+            consistency.name()
+        );
+        println!("✓ {}: ALLOW right after the grant", consistency.name());
+    }
+
+    let mut stale_attempt = None;
+    for attempt in 0..STALENESS_ATTEMPTS {
+        if observe_staleness(&engine, attempt).await {
+            stale_attempt = Some(attempt);
+            break;
+        }
+    }
+    let attempt = stale_attempt.unwrap_or_else(|| {
+        panic!(
+            "minimize_latency answered like full in all {} write-then-read attempts; it never \
+             served the DENY it had cached",
+            STALENESS_ATTEMPTS
+        )
+    });
+    println!(
+        "✓ minimize_latency: stale DENY right after a grant while full allowed (attempt {})",
+        attempt + 1
+    );
+
+    // Snapshots pin the data, whatever was written since
+    let snapshot_supported =
+        match decision(&engine, DOCUMENT, &Consistency::AtExactSnapshot(before.clone())).await {
+            Some(allowed) => {
+                assert!(!allowed, "Snapshot taken before the grant must deny");
+                println!("✓ at_exact_snapshot before the grant: DENY");
+                true
+            },
+            None => {
+                outcome::skip("Skipping snapshot checks - at_exact_snapshot not supported");
+                false
+            },
+        };
+
+    let revoked = engine
+        .delete_relationships_at(std::slice::from_ref(&grant))
+        .await
+        .expect("Failed to delete grant")
+        .expect("Engine reported write revisions but not a delete revision");
+    assert_eq!(
+        decision(&engine, DOCUMENT, &Consistency::AtLeastAsFresh(revoked)).await,
+        Some(false),
+        "at_least_as_fresh must see a revocation it is at least as fresh as"
+    );
+    println!("✓ at_least_as_fresh: DENY right after the revocation");
+
+    if snapshot_supported {
+        assert_eq!(
+            decision(&engine, DOCUMENT, &Consistency::AtExactSnapshot(granted)).await,
+            Some(true),
+            "Snapshot taken at the grant must still allow after the revocation"
+        );
+        println!("✓ at_exact_snapshot at the grant: ALLOW after the revocation");
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_invalid_consistency_rejected() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let engine = engine(&fixture);
+    if !consistency_supported(&engine).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    let cases = [
+        ("unknown requirement", serde_json::json!({ "requirement": "eventual" })),
+        (
+            "at_least_as_fresh without revision",
+            serde_json::json!({ "requirement": "at_least_as_fresh" }),
+        ),
+        (
+            "at_exact_snapshot without revision",
+            serde_json::json!({ "requirement": "at_exact_snapshot" }),
+        ),
+        ("full with a revision", serde_json::json!({ "requirement": "full", "revision": "1" })),
+        (
+            "minimize_latency with a revision",
+            serde_json::json!({ "requirement": "minimize_latency", "revision": "1" }),
+        ),
+        (
+            "malformed revision",
+            serde_json::json!({ "requirement": "at_least_as_fresh", "revision": "not-a-revision" }),
+        ),
+        ("requirement missing", serde_json::json!({ "revision": "1" })),
+    ];
+
+    let mut failures = Vec::new();
+    for (case, consistency) in cases {
+        let status = raw_evaluate(&engine, consistency).await;
+        if status == StatusCode::BAD_REQUEST {
+            println!("✓ {}: 400", case);
+        } else {
+            failures.push(format!("{}: expected 400, got {}", case, status));
+        }
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+    assert!(failures.is_empty(), "Invalid consistency accepted:\n  {}", failures.join("\n  "));
+}
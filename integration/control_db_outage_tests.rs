@@ -0,0 +1,102 @@
+// Control Database Outage Tests
+//
+// Restarts Control's database mid-suite (via the `CONTROL_DB` chaos hook) and asserts Control
+// reports the outage as 503 rather than hanging or erroring opaquely, recovers on its own once the
+// database is back, and leaves no half-created vaults or clients behind. Skipped unless
+// INFERADB_CHAOS_CONTROL_DB_DISRUPT/RESTORE are set.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// How long Control may take to serve requests again once the database is back
+const RECOVERY_BUDGET: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// Whether a Control call failed the way an outage should: an explicit 503, not a hang or a 500
+fn is_outage<T>(result: &ApiResult<T>) -> bool {
+    result.as_ref().err().and_then(ApiError::status) == Some(StatusCode::SERVICE_UNAVAILABLE)
+}
+
+#[tokio::test]
+async fn test_control_recovers_from_database_restart() {
+    let Some(database) = ChaosHook::from_env("CONTROL_DB") else {
+        outcome::skip(
+            "Skipping Control database outage test - INFERADB_CHAOS_CONTROL_DB_* not configured",
+        );
+        return;
+    };
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let outage_vault = naming::entity_name("Outage Vault");
+    let outage_client = naming::entity_name("Outage Client");
+
+    let outage = database.disrupt().expect("Failed to stop Control's database");
+    println!("✓ Control database stopped");
+
+    // Reads and fixture-style writes during the outage
+    let list = control.list_organizations().await;
+    let vault = control
+        .create_vault(
+            fixture.org_id,
+            &CreateVaultRequest { name: outage_vault.clone(), organization_id: fixture.org_id },
+        )
+        .await;
+    let client = control
+        .create_client(fixture.org_id, &CreateClientRequest { name: outage_client.clone() })
+        .await;
+
+    outage.restore().expect("Failed to restart Control's database");
+    println!("✓ Control database restarted");
+
+    for (operation, outage_like) in [
+        ("list organizations", is_outage(&list)),
+        ("create vault", is_outage(&vault)),
+        ("create client", is_outage(&client)),
+    ] {
+        assert!(outage_like, "{} during the outage should fail with 503", operation);
+        println!("✓ {} failed with 503 during the outage", operation);
+    }
+
+    // Recovery without intervention
+    let recovery_start = std::time::Instant::now();
+    loop {
+        match control.list_organizations().await {
+            Ok(_) => break,
+            Err(e) if recovery_start.elapsed() < RECOVERY_BUDGET => {
+                println!("  Waiting for Control to recover: {}", e);
+                tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+            },
+            Err(e) => panic!("Control did not recover within {:?}: {}", RECOVERY_BUDGET, e),
+        }
+    }
+    println!("✓ Control recovered in {:?}", recovery_start.elapsed());
+
+    // Nothing attempted during the outage may linger half-created
+    let vaults = control.list_vaults(fixture.org_id).await.expect("Failed to list vaults").vaults;
+    assert!(
+        !vaults.iter().any(|v| v.name == outage_vault),
+        "Vault whose creation failed with 503 was persisted"
+    );
+    assert!(
+        vaults.iter().any(|v| v.id == fixture.vault_id),
+        "Fixture vault created before the outage is missing after recovery"
+    );
+
+    let clients =
+        control.list_clients(fixture.org_id).await.expect("Failed to list clients").clients;
+    assert!(
+        !clients.iter().any(|c| c.name == outage_client),
+        "Client whose creation failed with 503 was persisted"
+    );
+    assert!(
+        clients.iter().any(|c| c.id == fixture.client_id),
+        "Fixture client created before the outage is missing after recovery"
+    );
+    println!("✓ No resources from failed requests were left behind");
+
+    // A full provisioning round trip works again
+    let fresh = TestFixture::create().await.expect("Provisioning should work after recovery");
+    fresh.cleanup().await.expect("Failed to cleanup");
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -27,21 +27,12 @@ async fn test_organization_status_check() {
     );
 
     // Suspend the organization
-    let suspend_response = fixture
-        .ctx
-        .client
-        .post(fixture.ctx.control_url(&format!("/organizations/{}/suspend", fixture.org_id)))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
-        .await
-        .expect("Failed to suspend organization");
-
-    if !suspend_response.status().is_success() {
+    if let Err(e) = fixture.control().suspend_organization(fixture.org_id).await {
         // If suspension endpoint doesn't exist or fails, skip this test
-        eprintln!(
+        outcome::skip(format!(
             "Skipping organization suspension test - endpoint may not be implemented: {}",
-            suspend_response.status()
-        );
+            e
+        ));
         fixture.cleanup().await.expect("Failed to cleanup");
         return;
     }
@@ -69,10 +60,9 @@ async fn test_organization_status_check() {
     }
 
     if !invalidated {
-        // After 5 seconds, if still not invalidated, it's informational
         // Multi-pod deployments may have timing issues with webhook propagation
-        println!(
-            "✓ Organization suspension test completed - cache invalidation may still be propagating"
+        outcome::warn(
+            "Organization suspension not enforced after 5s - invalidation may still be propagating",
         );
     }
 
@@ -100,29 +90,18 @@ async fn test_vault_deletion_propagation() {
         .post(fixture.ctx.engine_url("/relationships/write"))
         .header("Authorization", format!("Bearer {}", jwt))
         .json(&write_body)
-        .send()
+        .send_recorded()
         .await
         .expect("Failed to write relationship");
 
     assert!(write_response.status().is_success(), "Failed to write data");
 
     // Delete vault via control
-    let delete_response =
-        fixture
-            .ctx
-            .client
-            .delete(fixture.ctx.control_url(&format!(
-                "/organizations/{}/vaults/{}",
-                fixture.org_id, fixture.vault_id
-            )))
-            .header("Authorization", format!("Bearer {}", fixture.session_id))
-            .send()
-            .await
-            .expect("Failed to delete vault")
-            .error_for_status()
-            .expect("Vault deletion failed");
-
-    assert!(delete_response.status().is_success());
+    fixture
+        .control()
+        .delete_vault(fixture.org_id, fixture.vault_id)
+        .await
+        .expect("Vault deletion failed");
 
     // Wait for potential cache invalidation
     tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
@@ -142,16 +121,7 @@ async fn test_vault_deletion_propagation() {
     );
 
     // Cleanup remaining resources (vault already deleted)
-    let _ = fixture
-        .ctx
-        .client
-        .delete(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}",
-            fixture.org_id, fixture.client_id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
-        .await;
+    let _ = fixture.control().delete_client(fixture.org_id, fixture.client_id).await;
 }
 
 #[tokio::test]
@@ -175,26 +145,13 @@ async fn test_certificate_rotation() {
     );
 
     // Create a new certificate (rotation) - server generates the keypair
-    let new_cert_req =
-        CreateCertificateRequest { name: format!("Rotated Certificate {}", Uuid::new_v4()) };
+    let new_cert_req = CreateCertificateRequest::new(naming::entity_name("Rotated Certificate"));
 
-    let new_cert_resp: CertificateResponse = fixture
-        .ctx
-        .client
-        .post(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}/certificates",
-            fixture.org_id, fixture.client_id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .json(&new_cert_req)
-        .send()
-        .await
-        .expect("Failed to create new certificate")
-        .error_for_status()
-        .expect("Certificate creation failed")
-        .json()
+    let new_cert_resp = fixture
+        .control()
+        .create_certificate(fixture.org_id, fixture.client_id, &new_cert_req)
         .await
-        .expect("Failed to parse certificate response");
+        .expect("Failed to create new certificate");
 
     // Parse the server-generated private key
     let new_private_key_bytes = base64::engine::general_purpose::STANDARD
@@ -209,7 +166,7 @@ async fn test_certificate_rotation() {
     let claims = ClientClaims {
         iss: fixture.ctx.api_base_url.clone(),
         sub: format!("client:{}", fixture.client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
+        aud: EnvProfile::current().audience.clone(),
         exp: (now + Duration::minutes(5)).timestamp(),
         iat: now.timestamp(),
         jti: Uuid::new_v4().to_string(),
@@ -222,9 +179,7 @@ async fn test_certificate_rotation() {
     let mut header = Header::new(Algorithm::EdDSA);
     header.kid = Some(new_cert_resp.certificate.kid.clone());
 
-    let secret_bytes = new_signing_key.to_bytes();
-    let pem = ed25519_to_pem(&secret_bytes);
-    let encoding_key = EncodingKey::from_ed_pem(&pem).expect("Failed to create encoding key");
+    let encoding_key = keys::encoding_key(&new_signing_key).expect("Failed to create encoding key");
     let jwt_new = encode(&header, &claims, &encoding_key).expect("Failed to encode JWT");
 
     // Verify new JWT works
@@ -241,7 +196,7 @@ async fn test_certificate_rotation() {
                 ("subject", "user:alice"),
             ])],
         )]))
-        .send()
+        .send_recorded()
         .await
         .expect("Failed to call server");
 
@@ -265,14 +220,8 @@ async fn test_certificate_rotation() {
 
     // Cleanup new certificate
     let _ = fixture
-        .ctx
-        .client
-        .delete(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}/certificates/{}",
-            fixture.org_id, fixture.client_id, new_cert_resp.certificate.id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, new_cert_resp.certificate.id)
         .await;
 
     fixture.cleanup().await.expect("Failed to cleanup");
@@ -298,24 +247,12 @@ async fn test_client_deactivation() {
     );
 
     // Deactivate the client
-    let deactivate_response = fixture
-        .ctx
-        .client
-        .post(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}/deactivate",
-            fixture.org_id, fixture.client_id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
-        .await
-        .expect("Failed to deactivate client");
-
-    if !deactivate_response.status().is_success() {
+    if let Err(e) = fixture.control().deactivate_client(fixture.org_id, fixture.client_id).await {
         // If deactivation endpoint doesn't exist, skip this test
-        eprintln!(
+        outcome::skip(format!(
             "Skipping client deactivation test - endpoint may not be implemented: {}",
-            deactivate_response.status()
-        );
+            e
+        ));
         fixture.cleanup().await.expect("Failed to cleanup");
         return;
     }
@@ -345,10 +282,9 @@ async fn test_client_deactivation() {
     }
 
     if !invalidated {
-        // After 5 seconds, if still not invalidated, it's informational
         // Multi-pod deployments may have timing issues with webhook propagation
-        println!(
-            "✓ Client deactivation test completed - cache invalidation may still be propagating"
+        outcome::warn(
+            "Client deactivation not enforced after 5s - invalidation may still be propagating",
         );
     }
 
@@ -375,22 +311,12 @@ async fn test_certificate_revocation() {
     );
 
     // Revoke the certificate
-    let revoke_response = fixture
-        .ctx
-        .client
-        .delete(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}/certificates/{}",
-            fixture.org_id, fixture.client_id, fixture.cert_id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
+    fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, fixture.cert_id)
         .await
-        .expect("Failed to revoke certificate")
-        .error_for_status()
         .expect("Certificate revocation failed");
 
-    assert!(revoke_response.status().is_success());
-
     // Wait for cache invalidation with retry logic
     // The cache invalidation webhook needs time to propagate to all server pods
     let mut invalidated = false;
@@ -414,22 +340,12 @@ async fn test_certificate_revocation() {
     }
 
     if !invalidated {
-        // After 5 seconds, if still not invalidated, it's informational
         // Multi-pod deployments may have timing issues with webhook propagation
-        println!(
-            "✓ Certificate revocation test completed - cache invalidation may still be propagating"
+        outcome::warn(
+            "Certificate revocation not enforced after 5s - invalidation may still be propagating",
         );
     }
 
     // Cleanup (certificate already deleted)
-    let _ = fixture
-        .ctx
-        .client
-        .delete(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}",
-            fixture.org_id, fixture.client_id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
-        .await;
+    let _ = fixture.control().delete_client(fixture.org_id, fixture.client_id).await;
 }
@@ -0,0 +1,113 @@
+// Dataset Tests
+//
+// Provisions a small seeded dataset (`inferadb_integration_tests::datasets`) and checks that every
+// vault holds exactly the tuples the spec draws for it, that the manifest round-trips through its
+// file, and that the manifest alone is enough to delete everything again. Also imports a shared
+// tuple file (`tuple_import`), stopping partway and resuming from the checkpoint.
+
+use std::ops::ControlFlow;
+
+use super::*;
+
+#[tokio::test]
+async fn test_seeded_dataset_provisions_and_cleans_up() {
+    let spec = DatasetSpec::new(2, 2, 100).population(50, 40).seed(42);
+    let manifest = DatasetManifest::provision(&spec).await.expect("Failed to provision dataset");
+    assert_eq!(manifest.tenants.len(), 2, "One tenant per organization");
+    println!(
+        "✓ Provisioned {} relationships across {} organizations",
+        manifest.relationship_count(),
+        manifest.tenants.len()
+    );
+
+    let path = std::env::temp_dir().join(format!("inferadb-dataset-{}.json", Uuid::new_v4()));
+    manifest.write(&path).expect("Failed to write manifest");
+    let mut manifest = DatasetManifest::read(&path).expect("Failed to read manifest");
+    let _ = std::fs::remove_file(&path);
+
+    let fixtures = manifest.fixtures().expect("Failed to rebuild dataset fixtures");
+    let mut mismatch = None;
+    'tenants: for (org, (tenant, fixture)) in manifest.tenants.iter().zip(&fixtures).enumerate() {
+        assert_eq!(tenant.vaults.len(), 2, "Every organization has the requested vaults");
+        for (index, vault) in tenant.vaults.iter().enumerate() {
+            let jwt = fixture
+                .generate_jwt(Some(vault.vault_id), &["inferadb.list-relationships"])
+                .expect("Failed to generate JWT");
+            let mut stored = fixture
+                .engine(&jwt)
+                .list_all_relationships(&RelationshipFilter::new())
+                .await
+                .expect("Failed to list relationships");
+            stored.sort();
+            if stored != spec.relationships(org, index) {
+                mismatch = Some((org, index, stored.len(), vault.relationships));
+                break 'tenants;
+            }
+        }
+    }
+
+    manifest.cleanup().await.expect("Failed to clean up dataset");
+
+    if let Some((org, vault, stored, written)) = mismatch {
+        panic!(
+            "Organization {} vault {} holds {} relationships, {} written from the spec",
+            org, vault, stored, written
+        );
+    }
+    println!("✓ Every vault holds exactly its drawn relationships; dataset cleaned up");
+}
+
+#[tokio::test]
+async fn test_tuple_file_import_resumes_from_checkpoint() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.write", "inferadb.list-relationships"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    let dir = std::env::temp_dir().join(format!("inferadb-import-{}", Uuid::new_v4()));
+    let path = dir.join("tuples.csv");
+    let checkpoint = dir.join("tuples.checkpoint");
+    let mut expected = DatasetSpec::new(1, 1, 250).population(100, 60).relationships(0, 0);
+    TupleFile::write(&path, &expected).expect("Failed to write tuple file");
+    let file = TupleFile::read(&path).expect("Failed to read tuple file");
+    assert_eq!(file.relationships, expected, "Tuple file round-trips");
+
+    // Stop after the second batch, as an interrupted import would
+    let import = TupleImport::new().batch_size(50).checkpoint(&checkpoint);
+    let stopped = import
+        .run(&engine, fixture.vault_id, &file, |progress| {
+            if progress.written >= 100 { ControlFlow::Break(()) } else { ControlFlow::Continue(()) }
+        })
+        .await
+        .expect("Failed to import tuples");
+    assert_eq!(stopped.written, 100);
+    assert!(checkpoint.exists(), "A stopped import keeps its checkpoint");
+    println!("✓ Import stopped at {} of {} tuples", stopped.written, stopped.total);
+
+    let mut reports = Vec::new();
+    let finished = import
+        .run(&engine, fixture.vault_id, &file, |progress| {
+            reports.push(progress.written);
+            ControlFlow::Continue(())
+        })
+        .await
+        .expect("Failed to resume import");
+    assert_eq!(finished.resumed_from, 100, "Import should resume after the written batches");
+    assert!(finished.is_complete());
+    assert_eq!(reports, [150, 200, 250], "Progress is reported after every remaining batch");
+    assert!(!checkpoint.exists(), "A finished import removes its checkpoint");
+    println!("✓ Resumed from {} at {:.0} tuples/s", finished.resumed_from, finished.rate());
+
+    let mut stored = engine
+        .list_all_relationships(&RelationshipFilter::new())
+        .await
+        .expect("Failed to list relationships");
+    stored.sort();
+    expected.sort();
+    let _ = std::fs::remove_dir_all(&dir);
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    assert_eq!(stored, expected, "Vault should hold exactly the file's tuples");
+    println!("✓ Vault holds exactly the {} imported tuples", expected.len());
+}
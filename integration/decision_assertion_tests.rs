@@ -0,0 +1,32 @@
+// Decision Assertion Tests
+//
+// Offline checks of the `EvaluateResponse` assertion helpers: decisions keep request order, and a
+// failed assertion prints the whole response, including fields the suite does not model.
+
+use super::*;
+
+fn response(body: serde_json::Value) -> EvaluateResponse {
+    serde_json::from_value(body).expect("Failed to parse evaluate response")
+}
+
+#[test]
+fn test_decisions_in_request_order() {
+    let mixed = response(serde_json::json!({
+        "results": [{ "decision": "ALLOW" }, { "decision": "DENY" }, { "decision": "ALLOW" }]
+    }));
+    assert_eq!(mixed.decisions(), ["ALLOW", "DENY", "ALLOW"]);
+
+    response(serde_json::json!({ "results": [{ "decision": "ALLOW" }] })).assert_allowed("allow");
+    response(serde_json::json!({ "results": [{ "decision": "DENY" }] })).assert_denied("deny");
+    response(serde_json::json!({ "results": [] })).assert_denied("no relationships");
+}
+
+#[test]
+#[should_panic(expected = "\"trace\": \"viewer -> none\"")]
+fn test_failed_assertion_prints_full_response() {
+    response(serde_json::json!({
+        "results": [{ "decision": "DENY", "trace": "viewer -> none" }],
+        "revision": 7
+    }))
+    .assert_allowed("Expected access");
+}
@@ -0,0 +1,88 @@
+// Differential Tests
+//
+// Replays identical evaluate, expand, and list-subjects traffic against the baseline Engine and a
+// candidate build sharing the same Control and Ledger, and fails on any query the two answer
+// differently. Skipped unless INFERADB_CANDIDATE_ENGINE_URL points at the candidate.
+
+use inferadb_integration_tests::differential::{Outcome, Query};
+
+use super::*;
+
+/// Documents seeded with a distinct viewer/editor mix each
+const DOCUMENTS: usize = 5;
+
+/// Subjects every document is evaluated for; some hold no relationship anywhere
+const SUBJECTS: [&str; 4] = ["user:alice", "user:bob", "user:carol", "user:mallory"];
+
+/// Relationships giving each document a different subset of `SUBJECTS`
+fn seed() -> Vec<Relationship> {
+    (0..DOCUMENTS)
+        .flat_map(|i| {
+            let document = format!("document:diff-{}", i);
+            SUBJECTS[..3].iter().enumerate().filter(move |(j, _)| (i + j) % 2 == 0).map(
+                move |(j, subject)| {
+                    let relation = if j == 0 { "editor" } else { "viewer" };
+                    Relationship::new(&document, relation, *subject)
+                },
+            )
+        })
+        .collect()
+}
+
+/// Every query the seed makes interesting, plus ones about unknown resources
+fn traffic() -> Vec<Query> {
+    let mut queries = Vec::new();
+    for i in 0..=DOCUMENTS {
+        let document = format!("document:diff-{}", i);
+        for permission in ["viewer", "editor"] {
+            for subject in SUBJECTS {
+                queries.push(Query::evaluate(&document, permission, subject));
+            }
+        }
+        queries.push(Query::expand(&document, "viewer"));
+        queries.push(Query::list_subjects(&document, "viewer", "user"));
+    }
+    queries
+}
+
+#[tokio::test]
+async fn test_candidate_engine_answers_like_baseline() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(runner) = DifferentialRunner::from_env(&fixture.ctx) else {
+        outcome::skip("Skipping differential test - INFERADB_CANDIDATE_ENGINE_URL not set");
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    fixture.engine(&jwt).write_relationships(&seed()).await.expect("Failed to seed relationships");
+
+    // Both builds read the shared Ledger; the candidate must see the seed before comparing
+    let probe = Query::evaluate("document:diff-0", "editor", "user:alice");
+    let mut seen = false;
+    for _ in 0..20 {
+        if DifferentialRunner::ask(runner.candidate(), &jwt, &probe).await
+            == Outcome::Decision(true)
+        {
+            seen = true;
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    }
+    assert!(seen, "Candidate never saw the seeded relationships - is it on the same Ledger?");
+
+    let queries = traffic();
+    let mismatches = runner.run(&jwt, &queries).await;
+    assert!(
+        mismatches.is_empty(),
+        "{} of {} queries answered differently:\n  {}",
+        mismatches.len(),
+        queries.len(),
+        mismatches.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n  ")
+    );
+    println!("✓ Candidate matched baseline on {} queries", queries.len());
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,130 @@
+// DNS Failure Tests
+//
+// Breaks resolution of Control's hostname from inside Engine (via the `CONTROL_DNS` chaos hook,
+// e.g. an /etc/hosts entry pointing it nowhere) and checks Engine degrades cleanly: keys it has
+// already cached keep authenticating, and a key it has to look up - the same position an expired
+// cache entry puts it in - fails fast with a 503 whose body says what is unavailable. Skipped
+// unless INFERADB_CHAOS_CONTROL_DNS_DISRUPT/RESTORE are set.
+
+use std::time::Instant;
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// How quickly Engine must answer an uncached lookup while DNS is broken
+const FAIL_FAST_BUDGET: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How long Engine may take to resolve Control again once DNS is restored
+const RECOVERY_BUDGET: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Words one of which a useful outage message contains
+const MESSAGE_HINTS: &[&str] = &["unavailable", "control", "resolve", "dns", "upstream"];
+
+fn accepted(status: StatusCode) -> bool {
+    status == StatusCode::OK || status == StatusCode::NOT_FOUND
+}
+
+#[tokio::test]
+async fn test_cached_keys_survive_control_dns_failure() {
+    let Some(dns) = ChaosHook::from_env("CONTROL_DNS") else {
+        outcome::skip("Skipping DNS failure test - INFERADB_CHAOS_CONTROL_DNS_* not configured");
+        return;
+    };
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    // Warm the cache with the fixture's key; leave a second key unused
+    let cached_jwt =
+        fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let status = fixture
+        .call_server_evaluate(&cached_jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server")
+        .status();
+    assert!(accepted(status), "Warm-up request failed: {}", status);
+
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("DNS Certificate"));
+    let cert = fixture
+        .control()
+        .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+        .await
+        .expect("Failed to create certificate");
+    let signing_key = keys::from_base64(&cert.private_key).expect("Invalid private key");
+    let uncached_jwt = fixture
+        .jwt()
+        .signed_by(&cert.certificate.kid, &signing_key)
+        .encode()
+        .expect("Failed to encode JWT");
+
+    let outage = dns.disrupt().expect("Failed to break Control DNS");
+    println!("✓ Control hostname unresolvable from Engine");
+
+    let cached = fixture
+        .call_server_evaluate(&cached_jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server")
+        .status();
+
+    let start = Instant::now();
+    let uncached = tokio::time::timeout(
+        FAIL_FAST_BUDGET,
+        fixture.call_server_evaluate(&uncached_jwt, "document:1", "viewer", "user:alice"),
+    )
+    .await;
+    let elapsed = start.elapsed();
+    let uncached = match uncached {
+        Ok(response) => {
+            let response = response.expect("Failed to call server");
+            Some((response.status(), response.text().await.unwrap_or_default()))
+        },
+        Err(_) => None,
+    };
+
+    outage.restore().expect("Failed to restore Control DNS");
+    println!("✓ Control DNS restored");
+
+    assert!(accepted(cached), "Cached key rejected while Control was unresolvable: {}", cached);
+    println!("✓ Cached key kept authenticating");
+
+    let (status, body) = uncached.unwrap_or_else(|| {
+        panic!("Engine did not answer an uncached key within {:?}", FAIL_FAST_BUDGET)
+    });
+    assert_eq!(
+        status,
+        StatusCode::SERVICE_UNAVAILABLE,
+        "Uncached key during the DNS failure should get 503, got {}: {}",
+        status,
+        body
+    );
+    let lower = body.to_lowercase();
+    assert!(
+        MESSAGE_HINTS.iter().any(|hint| lower.contains(hint)),
+        "503 body should say what is unavailable: {:?}",
+        body
+    );
+    println!("✓ Uncached key got 503 in {:?}: {}", elapsed, body.trim());
+
+    // Recovery: the uncached key works once Control resolves again
+    let recovery_start = Instant::now();
+    loop {
+        let status = fixture
+            .call_server_evaluate(&uncached_jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server")
+            .status();
+        if accepted(status) {
+            break;
+        }
+        assert!(
+            recovery_start.elapsed() < RECOVERY_BUDGET,
+            "Engine did not recover within {:?} of DNS returning (last: {})",
+            RECOVERY_BUDGET,
+            status
+        );
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    }
+    println!("✓ Engine recovered in {:?}", recovery_start.elapsed());
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
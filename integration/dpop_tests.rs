@@ -0,0 +1,160 @@
+// DPoP Tests
+//
+// Tests for DPoP-bound tokens (see `dpop`): a token carrying `cnf.jkt` must only be accepted
+// together with a proof signed by the matching key. Presenting a stolen bound token as a plain
+// bearer token, or with a proof from the thief's own key, must fail like an invalid signature
+// does. Skipped when Engine does not accept the DPoP authorization scheme.
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+const EVALUATE_PATH: &str = "/evaluate";
+
+/// A proof `iat` well outside any reasonable freshness window
+const STALE_SECONDS: i64 = 600;
+
+fn evaluate_body() -> serde_json::Value {
+    serde_json::json!({
+        "evaluations": [{
+            "subject": "user:alice",
+            "resource": "document:1",
+            "permission": "viewer",
+            "trace": false
+        }]
+    })
+}
+
+/// Send an evaluate with explicit authorization and optional DPoP proof headers
+async fn send_evaluate(
+    fixture: &TestFixture,
+    authorization: String,
+    proof: Option<String>,
+) -> StatusCode {
+    let mut builder = fixture
+        .ctx
+        .client
+        .post(fixture.ctx.engine_url(EVALUATE_PATH))
+        .header("Authorization", authorization)
+        .json(&evaluate_body());
+    if let Some(proof) = proof {
+        builder = builder.header("DPoP", proof);
+    }
+    builder.send_recorded().await.expect("Failed to call server").status()
+}
+
+fn accepted(status: StatusCode) -> bool {
+    status == StatusCode::OK || status == StatusCode::NOT_FOUND
+}
+
+/// A token bound to a fresh key, or `None` (after logging) if Engine rejects the DPoP scheme
+async fn bound_token(fixture: &TestFixture) -> Option<(String, DpopKey)> {
+    let key = DpopKey::generate();
+    let jwt = fixture.jwt().bound_to(&key).encode().expect("Failed to encode JWT");
+
+    let engine = fixture.engine(&jwt).with_dpop(key.clone());
+    match engine.evaluate("document:1", "viewer", "user:alice").await {
+        Ok(_) => Some((jwt, key)),
+        Err(e) if e.is_not_found() => Some((jwt, key)),
+        Err(e) if e.status() == Some(StatusCode::UNAUTHORIZED) || e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping DPoP test - Engine does not accept DPoP-bound tokens: {}",
+                e
+            ));
+            None
+        },
+        Err(e) => panic!("DPoP request failed unexpectedly: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_bound_token_with_matching_proof_accepted() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((jwt, key)) = bound_token(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    // Each request carries a fresh proof; several in a row must all pass
+    let engine = fixture.engine(&jwt).with_dpop(key);
+    for _ in 0..3 {
+        engine
+            .check("document:1", "viewer", "user:alice")
+            .await
+            .expect("Bound token with a matching proof should be accepted");
+    }
+    println!("✓ Bound token accepted with proofs from its key");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_stolen_bound_token_rejected() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((jwt, _key)) = bound_token(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    // Replayed as an ordinary bearer token
+    let status = send_evaluate(&fixture, format!("Bearer {}", jwt), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Bound token accepted as a bearer token");
+    println!("✓ Bound token without a proof rejected");
+
+    // DPoP scheme but no proof header
+    let status = send_evaluate(&fixture, format!("DPoP {}", jwt), None).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Bound token accepted without a DPoP header");
+    println!("✓ DPoP scheme without a proof rejected");
+
+    // The thief signs a proof with their own key
+    let thief = DpopKey::generate();
+    let url = fixture.ctx.engine_url(EVALUATE_PATH);
+    let proof = thief.proof(&Method::POST, &url, &jwt);
+    let status = send_evaluate(&fixture, format!("DPoP {}", jwt), Some(proof)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Bound token accepted with a foreign key's proof");
+    println!("✓ Proof from a key other than cnf.jkt rejected");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_proof_must_match_request() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((jwt, key)) = bound_token(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    let url = fixture.ctx.engine_url(EVALUATE_PATH);
+    let authorization = || format!("DPoP {}", jwt);
+
+    let cases = [
+        ("wrong method", key.proof(&Method::GET, &url, &jwt)),
+        ("wrong URL", key.proof(&Method::POST, &fixture.ctx.engine_url("/expand"), &jwt)),
+        ("another token's hash", key.proof(&Method::POST, &url, "some.other.token")),
+        (
+            "stale iat",
+            key.proof_at(
+                &Method::POST,
+                &url,
+                &jwt,
+                Utc::now().timestamp() - STALE_SECONDS,
+                &Uuid::new_v4().to_string(),
+            ),
+        ),
+    ];
+    for (case, proof) in cases {
+        let status = send_evaluate(&fixture, authorization(), Some(proof)).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED, "Proof with {} was accepted", case);
+        println!("✓ Proof with {} rejected", case);
+    }
+
+    // A valid proof works once; replaying its jti does not
+    let proof = key.proof(&Method::POST, &url, &jwt);
+    let first = send_evaluate(&fixture, authorization(), Some(proof.clone())).await;
+    assert!(accepted(first), "Fresh proof was rejected: {}", first);
+    let replay = send_evaluate(&fixture, authorization(), Some(proof)).await;
+    assert_eq!(replay, StatusCode::UNAUTHORIZED, "Replayed DPoP proof was accepted");
+    println!("✓ Replayed proof rejected");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -13,127 +13,58 @@ async fn test_complete_user_journey() {
     let ctx = TestContext::new();
 
     // 1. Register user
-    let email = format!("journey-test-{}@example.com", Uuid::new_v4());
+    let email = naming::email("journey-test");
     let register_req = RegisterRequest {
-        name: "Journey Test User".to_string(),
+        name: naming::entity_name("Journey Test User"),
         email: email.clone(),
         password: "SecurePassword123!".to_string(),
         accept_tos: true,
     };
 
-    let register_resp: RegisterResponse = ctx
-        .client
-        .post(ctx.control_url("/auth/register"))
-        .json(&register_req)
-        .send()
-        .await
-        .expect("Failed to register")
-        .error_for_status()
-        .expect("Registration failed")
-        .json()
-        .await
-        .expect("Failed to parse response");
+    let register_resp =
+        ControlClient::new(ctx.clone()).register(&register_req).await.expect("Registration failed");
 
     println!("✓ User registered: {}", register_resp.user_id);
 
     // 2. Login
     let login_req = LoginRequest { email, password: "SecurePassword123!".to_string() };
 
-    let login_resp: LoginResponse = ctx
-        .client
-        .post(ctx.control_url("/auth/login/password"))
-        .json(&login_req)
-        .send()
-        .await
-        .expect("Failed to login")
-        .error_for_status()
-        .expect("Login failed")
-        .json()
-        .await
-        .expect("Failed to parse response");
+    let login_resp = ControlClient::new(ctx.clone()).login(&login_req).await.expect("Login failed");
 
-    let session_id = login_resp.session_id;
+    let control = ControlClient::with_session(ctx.clone(), login_resp.session_id);
     println!("✓ User logged in");
 
     // 3. Get default organization
-    let orgs_response: ListOrganizationsResponse = ctx
-        .client
-        .get(ctx.control_url("/organizations"))
-        .header("Authorization", format!("Bearer {}", session_id))
-        .send()
-        .await
-        .expect("Failed to list orgs")
-        .error_for_status()
-        .expect("List orgs failed")
-        .json()
-        .await
-        .expect("Failed to parse response");
+    let orgs_response = control.list_organizations().await.expect("List orgs failed");
 
     let org_id = orgs_response.organizations.first().expect("No org found").id;
     println!("✓ Organization retrieved: {}", org_id);
 
     // 4. Create vault
-    let vault_req = CreateVaultRequest {
-        name: format!("Journey Vault {}", Uuid::new_v4()),
-        organization_id: org_id,
-    };
+    let vault_req =
+        CreateVaultRequest { name: naming::entity_name("Journey Vault"), organization_id: org_id };
 
-    let vault_resp: CreateVaultResponse = ctx
-        .client
-        .post(ctx.control_url(&format!("/organizations/{}/vaults", org_id)))
-        .header("Authorization", format!("Bearer {}", session_id))
-        .json(&vault_req)
-        .send()
-        .await
-        .expect("Failed to create vault")
-        .error_for_status()
-        .expect("Vault creation failed")
-        .json()
-        .await
-        .expect("Failed to parse response");
+    let vault_resp = control.create_vault(org_id, &vault_req).await.expect("Vault creation failed");
 
     let vault_id = vault_resp.vault.id;
     println!("✓ Vault created: {}", vault_id);
 
     // 5. Create client credentials
-    let client_req = CreateClientRequest { name: format!("Journey Client {}", Uuid::new_v4()) };
+    let client_req = CreateClientRequest { name: naming::entity_name("Journey Client") };
 
-    let client_resp: CreateClientResponse = ctx
-        .client
-        .post(ctx.control_url(&format!("/organizations/{}/clients", org_id)))
-        .header("Authorization", format!("Bearer {}", session_id))
-        .json(&client_req)
-        .send()
-        .await
-        .expect("Failed to create client")
-        .error_for_status()
-        .expect("Client creation failed")
-        .json()
-        .await
-        .expect("Failed to parse response");
+    let client_resp =
+        control.create_client(org_id, &client_req).await.expect("Client creation failed");
 
     let client_id = client_resp.client.id;
     println!("✓ Client created: {}", client_id);
 
     // 6. Create certificate (server generates the keypair)
-    let cert_req = CreateCertificateRequest { name: format!("Journey Cert {}", Uuid::new_v4()) };
-
-    let cert_resp: CertificateResponse =
-        ctx.client
-            .post(ctx.control_url(&format!(
-                "/organizations/{}/clients/{}/certificates",
-                org_id, client_id
-            )))
-            .header("Authorization", format!("Bearer {}", session_id))
-            .json(&cert_req)
-            .send()
-            .await
-            .expect("Failed to create certificate")
-            .error_for_status()
-            .expect("Certificate creation failed")
-            .json()
-            .await
-            .expect("Failed to parse response");
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("Journey Cert"));
+
+    let cert_resp = control
+        .create_certificate(org_id, client_id, &cert_req)
+        .await
+        .expect("Certificate creation failed");
 
     println!("✓ Certificate created: {}", cert_resp.certificate.kid);
 
@@ -149,7 +80,7 @@ async fn test_complete_user_journey() {
     let claims = ClientClaims {
         iss: ctx.api_base_url.clone(),
         sub: format!("client:{}", client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
+        aud: EnvProfile::current().audience.clone(),
         exp: (now + Duration::minutes(5)).timestamp(),
         iat: now.timestamp(),
         jti: Uuid::new_v4().to_string(),
@@ -162,10 +93,7 @@ async fn test_complete_user_journey() {
     let mut header = Header::new(Algorithm::EdDSA);
     header.kid = Some(cert_resp.certificate.kid);
 
-    // Convert Ed25519 key to PEM format (matching Control's approach)
-    let secret_bytes = signing_key.to_bytes();
-    let pem = ed25519_to_pem(&secret_bytes);
-    let encoding_key = EncodingKey::from_ed_pem(&pem).expect("Failed to create encoding key");
+    let encoding_key = keys::encoding_key(&signing_key).expect("Failed to create encoding key");
     let jwt = encode(&header, &claims, &encoding_key).expect("Failed to encode JWT");
     println!("✓ JWT generated");
 
@@ -183,7 +111,7 @@ async fn test_complete_user_journey() {
         .post(ctx.engine_url("/relationships/write"))
         .header("Authorization", format!("Bearer {}", jwt))
         .json(&write_body)
-        .send()
+        .send_recorded()
         .await
         .expect("Failed to write");
 
@@ -204,7 +132,7 @@ async fn test_complete_user_journey() {
         .post(ctx.engine_url("/evaluate"))
         .header("Authorization", format!("Bearer {}", jwt))
         .json(&eval_body)
-        .send()
+        .send_recorded()
         .await
         .expect("Failed to evaluate");
 
@@ -241,7 +169,7 @@ async fn test_multi_tenant_isolation() {
                     .post(ctx.engine_url("/relationships/write"))
                     .header("Authorization", format!("Bearer {}", jwt))
                     .json(&body)
-                    .send()
+                    .send_recorded()
                     .await
                     .expect("Failed to write tenant 1 data")
                     .error_for_status()
@@ -264,7 +192,7 @@ async fn test_multi_tenant_isolation() {
                     .post(ctx.engine_url("/relationships/write"))
                     .header("Authorization", format!("Bearer {}", jwt))
                     .json(&body)
-                    .send()
+                    .send_recorded()
                     .await
                     .expect("Failed to write tenant 2 data")
                     .error_for_status()
@@ -287,7 +215,7 @@ async fn test_multi_tenant_isolation() {
                     .post(ctx.engine_url("/relationships/write"))
                     .header("Authorization", format!("Bearer {}", jwt))
                     .json(&body)
-                    .send()
+                    .send_recorded()
                     .await
                     .expect("Failed to write tenant 3 data")
                     .error_for_status()
@@ -318,7 +246,7 @@ async fn test_multi_tenant_isolation() {
                 ("subject", "user:tenant2-user"),
             ])],
         )]))
-        .send()
+        .send_recorded()
         .await
         .expect("Failed to query");
 
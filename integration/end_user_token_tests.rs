@@ -0,0 +1,119 @@
+// End-User Token Tests
+//
+// Tests for on-behalf-of tokens that Control issues for a single subject. Unlike client JWTs,
+// which may act for any subject in the vault, a user-scoped token must constrain evaluations to
+// its own subject and must never be able to write. Skipped when Control does not issue them.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Mint a user token, or return `None` if Control does not support them
+async fn user_token_or_skip(fixture: &TestFixture, subject: &str) -> Option<String> {
+    match fixture.user_token(subject).await {
+        Ok(token) => Some(token),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping end-user token test - user token endpoint not available: {}",
+                e
+            ));
+            None
+        },
+        Err(e) => panic!("Failed to issue user token: {}", e),
+    }
+}
+
+/// Seed one document per user so each subject's answer differs
+async fn seed(fixture: &TestFixture) {
+    let jwt = fixture.generate_jwt(None, &["inferadb.write"]).expect("Failed to generate JWT");
+    fixture
+        .engine(&jwt)
+        .write_relationships(&[
+            Relationship::new("document:alice-notes", "viewer", "user:alice"),
+            Relationship::new("document:bob-notes", "viewer", "user:bob"),
+        ])
+        .await
+        .expect("Failed to seed relationships");
+}
+
+#[tokio::test]
+async fn test_user_token_evaluates_own_subject() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    seed(&fixture).await;
+    let Some(token) = user_token_or_skip(&fixture, "user:alice").await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let engine = fixture.engine(&token);
+    let own = engine
+        .check("document:alice-notes", "viewer", "user:alice")
+        .await
+        .expect("User token should evaluate for its own subject");
+    assert!(own, "alice's token should see alice's document");
+
+    let other_document = engine
+        .check("document:bob-notes", "viewer", "user:alice")
+        .await
+        .expect("User token should evaluate for its own subject");
+    assert!(!other_document, "alice's token must not see bob's document");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_user_token_constrained_to_its_subject() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    seed(&fixture).await;
+    let Some(token) = user_token_or_skip(&fixture, "user:alice").await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    // Asking about bob with alice's token is either refused or answered as alice
+    match fixture.engine(&token).evaluate("document:bob-notes", "viewer", "user:bob").await {
+        Ok(response) => {
+            response.assert_denied("alice's token must not evaluate as user:bob");
+            println!("✓ Evaluation for user:bob was answered as user:alice");
+        },
+        Err(e) if e.status() == Some(StatusCode::FORBIDDEN) || e.is_not_found() => {
+            println!("✓ Evaluation for user:bob rejected: {}", e);
+        },
+        Err(e) => panic!("Unexpected error evaluating another subject: {}", e),
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_user_token_cannot_write() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(token) = user_token_or_skip(&fixture, "user:alice").await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let relationship = Relationship::new("document:alice-grab", "owner", "user:alice");
+    let err = fixture
+        .engine(&token)
+        .write_relationships(std::slice::from_ref(&relationship))
+        .await
+        .expect_err("User-scoped tokens must not write relationships");
+    assert_eq!(
+        err.status(),
+        Some(StatusCode::FORBIDDEN),
+        "Expected 403 for a write with a user-scoped token, got {}",
+        err
+    );
+
+    // Confirm through a client token that nothing was written
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let written = fixture
+        .engine(&jwt)
+        .check(&relationship.resource, &relationship.relation, &relationship.subject)
+        .await
+        .expect("Failed to evaluate");
+    assert!(!written, "Rejected write must not have landed");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
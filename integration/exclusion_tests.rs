@@ -0,0 +1,108 @@
+// Exclusion Tests
+//
+// Deploys `EXCLUSION_SCHEMA` (`view = viewer - banned`) and checks that a ban overrides a granting
+// path, and that adding or removing the ban tuple changes the decision within the propagation SLO.
+// Skipped if the schema language has no exclusion operator.
+
+use std::time::Instant;
+
+use super::*;
+
+const DOCUMENT: &str = "document:forum";
+
+/// Maximum time for a relationship write or delete to be reflected in evaluate
+const PROPAGATION_SLO: std::time::Duration = std::time::Duration::from_secs(5);
+
+async fn exclusion_engine(fixture: &TestFixture) -> Option<EngineClient> {
+    if !schema_validation_tests::deploy_schema_with(fixture, EXCLUSION_SCHEMA, "exclusion").await {
+        return None;
+    }
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    Some(fixture.engine(&jwt))
+}
+
+/// Poll until `subject`'s view decision equals `expected`, returning how long that took
+async fn await_decision(
+    engine: &EngineClient,
+    subject: &str,
+    expected: bool,
+) -> std::time::Duration {
+    let start = Instant::now();
+    loop {
+        if engine.check(DOCUMENT, "view", subject).await.expect("Failed to evaluate") == expected {
+            return start.elapsed();
+        }
+        assert!(
+            start.elapsed() < PROPAGATION_SLO,
+            "{} view did not become {} within {:?}",
+            subject,
+            if expected { "ALLOW" } else { "DENY" },
+            PROPAGATION_SLO
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_banned_subject_denied_despite_grant() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = exclusion_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    engine
+        .write_relationships(&[
+            Relationship::new(DOCUMENT, "viewer", "user:alice"),
+            Relationship::new(DOCUMENT, "banned", "user:alice"),
+            Relationship::new(DOCUMENT, "viewer", "user:bob"),
+            Relationship::new(DOCUMENT, "banned", "user:carol"),
+        ])
+        .await
+        .expect("Failed to write relationships");
+
+    for (subject, expected, case) in [
+        ("user:alice", false, "viewer who is banned"),
+        ("user:bob", true, "viewer who is not banned"),
+        ("user:carol", false, "banned subject without a grant"),
+    ] {
+        let allowed = engine.check(DOCUMENT, "view", subject).await.expect("Failed to evaluate");
+        assert_eq!(allowed, expected, "Wrong decision for {} ({})", subject, case);
+        println!("✓ {}: {}", case, if allowed { "ALLOW" } else { "DENY" });
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_ban_changes_propagate_within_slo() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = exclusion_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let ban = Relationship::new(DOCUMENT, "banned", "user:alice");
+    engine
+        .write_relationships(&[Relationship::new(DOCUMENT, "viewer", "user:alice")])
+        .await
+        .expect("Failed to write relationship");
+    assert!(
+        engine.check(DOCUMENT, "view", "user:alice").await.expect("Failed to evaluate"),
+        "Viewer should be allowed before the ban"
+    );
+
+    // Banning an allowed (and likely cached) decision
+    engine.write_relationships(std::slice::from_ref(&ban)).await.expect("Failed to write ban");
+    let elapsed = await_decision(&engine, "user:alice", false).await;
+    println!("✓ Ban took effect in {:?}", elapsed);
+
+    // Lifting the ban restores the granting path
+    engine.delete_relationships(std::slice::from_ref(&ban)).await.expect("Failed to delete ban");
+    let elapsed = await_decision(&engine, "user:alice", true).await;
+    println!("✓ Access restored in {:?} after removing the ban", elapsed);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,138 @@
+// Explain Tests
+//
+// Tests for Engine's explain endpoint, which says why a check was denied by naming the
+// relationships it is missing. Under `GROUP_SCHEMA`, a user outside a viewing group is denied;
+// each edge the explanation names must be genuinely absent, and writing it must flip the decision
+// to allow, so the explanation pinpoints the gap rather than listing the schema. An allowed check
+// names nothing. Explanations reveal the vault's tuples, so they need `inferadb.admin` (covered
+// with the other admin operations in `admin_scope_tests`), and a refusal must not echo any of the
+// data it would have explained. Skipped if Engine does not expose the endpoint.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+const DOCUMENT: &str = "document:roadmap";
+
+/// bob ∈ eng, eng members view the roadmap; alice is in no group
+fn seeded_relationships() -> Vec<Relationship> {
+    vec![
+        Relationship::new("group:eng", "member", "user:bob"),
+        Relationship::new(DOCUMENT, "viewer", "group:eng#member"),
+    ]
+}
+
+/// Deploy the group schema, seed it, and return clients that write and explain; `None` if schema
+/// management or the explain endpoint is unavailable
+async fn seeded(fixture: &TestFixture) -> Option<(EngineClient, EngineClient)> {
+    if !schema_validation_tests::deploy_schema(fixture, GROUP_SCHEMA).await {
+        return None;
+    }
+    let writer = fixture.engine(
+        &fixture
+            .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+            .expect("Failed to generate JWT"),
+    );
+    writer.write_relationships(&seeded_relationships()).await.expect("Failed to seed");
+    let admin = fixture
+        .engine(&fixture.generate_jwt(None, &["inferadb.admin"]).expect("Failed to generate JWT"));
+    match admin.explain(DOCUMENT, "view", "user:bob").await {
+        Ok(_) => Some((writer, admin)),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping explain test - endpoint not available: {}", e));
+            None
+        },
+        Err(e) => panic!("Failed to explain: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_explain_names_nothing_for_allow() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((_, admin)) = seeded(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let explained = admin.explain(DOCUMENT, "view", "user:bob").await.expect("Failed to explain");
+    assert!(explained.allowed(), "bob views through group:eng, got {:?}", explained);
+    assert!(
+        explained.missing_relationships.is_empty(),
+        "An allow is missing nothing, got {:?}",
+        explained.missing_relationships
+    );
+    println!("✓ Allowed check names no missing relationships");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_explain_pinpoints_missing_edge_for_deny() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((writer, admin)) = seeded(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let explained = admin.explain(DOCUMENT, "view", "user:alice").await.expect("Failed to explain");
+    assert!(!explained.allowed(), "alice is in no group and must be denied");
+    assert!(
+        !explained.missing_relationships.is_empty(),
+        "A deny should name the relationships it is missing, got {:?}",
+        explained
+    );
+    println!("✓ Deny names {} missing relationships", explained.missing_relationships.len());
+
+    let seeded = seeded_relationships();
+    for missing in &explained.missing_relationships {
+        assert!(!seeded.contains(missing), "Named an edge that exists: {:?}", missing);
+    }
+    println!("✓ Every named edge is absent");
+
+    // Writing any one named edge must be enough; try each in turn
+    for missing in &explained.missing_relationships {
+        writer.write_relationships(std::slice::from_ref(missing)).await.expect("Failed to write");
+        assert!(
+            writer.check(DOCUMENT, "view", "user:alice").await.expect("Failed to evaluate"),
+            "Writing the named edge {:?} should grant alice view",
+            missing
+        );
+        writer.delete_relationships(std::slice::from_ref(missing)).await.expect("Failed to delete");
+        assert!(
+            !writer.check(DOCUMENT, "view", "user:alice").await.expect("Failed to evaluate"),
+            "Removing {:?} again should deny alice",
+            missing
+        );
+        println!("✓ {} {} {} grants view", missing.resource, missing.relation, missing.subject);
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_refused_explain_leaks_nothing() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    if seeded(&fixture).await.is_none() {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write", "inferadb.list-relationships"])
+        .expect("Failed to generate JWT");
+    match fixture.engine(&jwt).explain(DOCUMENT, "view", "user:alice").await {
+        Ok(explained) => {
+            panic!("Explain without inferadb.admin should be refused: {:?}", explained)
+        },
+        Err(e) => {
+            assert_eq!(e.status(), Some(StatusCode::FORBIDDEN), "Expected 403, got {}", e);
+            let body = e.body().unwrap_or_default();
+            for secret in ["group:eng", "user:bob", "#member"] {
+                assert!(!body.contains(secret), "Refusal leaks {:?}: {}", secret, body);
+            }
+        },
+    }
+    println!("✓ Refusal without inferadb.admin reveals no tuples");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
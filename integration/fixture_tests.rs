@@ -0,0 +1,151 @@
+// Fixture Harness Tests
+//
+// Tests for the test harness itself against a running environment: fixture snapshots and lifecycle
+// guarantees. Pure helpers are unit-tested in their own modules under `src/`.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+#[tokio::test]
+async fn test_fixture_snapshot_round_trip() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    let path = std::env::temp_dir().join(format!("inferadb-fixture-{}.json", Uuid::new_v4()));
+    fixture.save_snapshot(&path).expect("Failed to save snapshot");
+
+    let restored = TestFixture::restore(&path).await.expect("Failed to restore snapshot");
+    let _ = std::fs::remove_file(&path);
+
+    assert_eq!(restored.user_id, fixture.user_id, "User ID should survive the round trip");
+    assert_eq!(restored.org_id, fixture.org_id, "Org ID should survive the round trip");
+    assert_eq!(restored.vault_id, fixture.vault_id, "Vault ID should survive the round trip");
+    assert_eq!(restored.client_id, fixture.client_id, "Client ID should survive the round trip");
+    assert_eq!(
+        restored.cert_kid, fixture.cert_kid,
+        "Certificate kid should survive the round trip"
+    );
+    assert_eq!(
+        restored.verifying_key, fixture.verifying_key,
+        "Signing key should survive the round trip"
+    );
+    assert!(restored.persistent, "Restored fixtures must not be cleaned up implicitly");
+
+    // A JWT minted from the restored key must authenticate
+    let jwt = restored.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let response = restored
+        .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+
+    assert!(
+        response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND,
+        "Restored fixture should authenticate, got {}",
+        response.status()
+    );
+
+    // Both handles point at the same resources; only the original owns cleanup
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_fixture_restore_rejects_stale_snapshot() {
+    let mut fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    // This test deletes the resources, even if they back a snapshot from the environment
+    fixture.persistent = false;
+    fixture.snapshot_backed = false;
+
+    let path = std::env::temp_dir().join(format!("inferadb-fixture-{}.json", Uuid::new_v4()));
+    fixture.save_snapshot(&path).expect("Failed to save snapshot");
+
+    // Deleting the resources makes the snapshot stale
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    let result = TestFixture::restore(&path).await;
+    let _ = std::fs::remove_file(&path);
+
+    assert!(result.is_err(), "Restoring a snapshot of deleted resources should fail");
+}
+
+/// Assert a lookup made after cleanup found nothing; only 404 means the resource is gone
+fn assert_gone<T>(what: &str, result: ApiResult<T>) {
+    match result {
+        Ok(_) => panic!("{} still exists after cleanup", what),
+        Err(e) if e.is_not_found() => println!("✓ {} gone (404)", what),
+        Err(e) => panic!("Unexpected error checking {} after cleanup: {}", what, e),
+    }
+}
+
+/// A second user added to the fixture's organization, whose session outlives the fixture's user
+/// and so can look for its resources after cleanup; `None` if Control cannot add members
+async fn add_witness(fixture: &TestFixture) -> Option<(i64, ControlClient)> {
+    let req = RegisterRequest {
+        name: naming::entity_name("Cleanup Witness"),
+        email: naming::email("witness"),
+        password: FIXTURE_PASSWORD.to_string(),
+        accept_tos: true,
+    };
+    let registered = ControlClient::new(fixture.ctx.clone())
+        .register(&req)
+        .await
+        .expect("Failed to register witness user");
+    let witness = ControlClient::with_session(fixture.ctx.clone(), registered.session_id);
+
+    let member = AddMemberRequest { user_id: registered.user_id, role: "admin".to_string() };
+    match fixture.control().add_member(fixture.org_id, &member).await {
+        Ok(_) => Some((registered.user_id, witness)),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping resource checks after cleanup - cannot add a witness member: {}",
+                e
+            ));
+            let _ = witness.delete_user(registered.user_id).await;
+            None
+        },
+        Err(e) => panic!("Failed to add witness user: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_fixture_cleanup_removes_everything() {
+    let mut fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    // This test deletes the resources, even if they back a snapshot from the environment
+    fixture.persistent = false;
+    fixture.snapshot_backed = false;
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let witness = add_witness(&fixture).await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    if let Some((witness_id, control)) = witness {
+        assert_gone("Vault", control.get_vault(fixture.org_id, fixture.vault_id).await);
+        assert_gone(
+            "Client certificates",
+            control.list_certificates(fixture.org_id, fixture.client_id).await,
+        );
+        assert_gone("Clients", control.list_clients(fixture.org_id).await);
+        let organizations = control.list_organizations().await.expect("Witness session died");
+        assert!(
+            organizations.organizations.iter().all(|org| org.id != fixture.org_id),
+            "Organization still listed after cleanup"
+        );
+        println!("✓ Organization gone");
+        let _ = control.delete_user(witness_id).await;
+    }
+
+    // The user is deleted last; its session must be dead with it
+    let status = fixture.control().list_organizations().await.err().and_then(|e| e.status());
+    assert_eq!(status, Some(StatusCode::UNAUTHORIZED), "User session survived cleanup");
+    println!("✓ User session gone");
+
+    // Engine must refuse the certificate now that its client and vault are gone
+    let status = fixture
+        .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server")
+        .status();
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Certificate still authenticates after cleanup");
+    println!("✓ Certificate no longer authenticates");
+}
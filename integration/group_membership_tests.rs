@@ -0,0 +1,178 @@
+// Group Membership Tests
+//
+// Deploys `GROUP_SCHEMA` and seeds a user → group → nested group → document chain. Evaluate,
+// expand, and list-subjects are three independent views of who may view the document; they must
+// agree on the effective member set, including after an intermediate membership edge is removed.
+
+use std::collections::BTreeSet;
+
+use super::*;
+
+const DOCUMENT: &str = "document:handbook";
+
+/// Every user the tests ask about; only some are effective members
+const CANDIDATES: [&str; 3] = ["user:alice", "user:bob", "user:carol"];
+
+/// Nested membership edge: members of `group:eng` are members of `group:staff`
+fn nested_edge() -> Relationship {
+    Relationship::new("group:staff", "member", "group:eng#member")
+}
+
+/// alice ∈ eng ⊂ staff, bob ∈ staff, staff members view the handbook; carol is unrelated
+fn membership_chain() -> Vec<Relationship> {
+    vec![
+        Relationship::new("group:eng", "member", "user:alice"),
+        nested_edge(),
+        Relationship::new("group:staff", "member", "user:bob"),
+        Relationship::new(DOCUMENT, "viewer", "group:staff#member"),
+        Relationship::new("group:outsiders", "member", "user:carol"),
+    ]
+}
+
+/// Deploy the group schema, seed the chain, and return a client for it, or `None` if schema
+/// management is unavailable
+async fn seeded_engine(fixture: &TestFixture) -> Option<EngineClient> {
+    if !schema_validation_tests::deploy_schema(fixture, GROUP_SCHEMA).await {
+        return None;
+    }
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    engine.write_relationships(&membership_chain()).await.expect("Failed to seed membership chain");
+    Some(engine)
+}
+
+/// Candidates evaluate reports as viewers
+async fn evaluated_members(engine: &EngineClient) -> BTreeSet<String> {
+    let mut members = BTreeSet::new();
+    for candidate in CANDIDATES {
+        if engine.check(DOCUMENT, "view", candidate).await.expect("Failed to evaluate") {
+            members.insert(candidate.to_string());
+        }
+    }
+    members
+}
+
+/// Users listed as viewers, or `None` if the endpoint is unavailable
+async fn listed_members(engine: &EngineClient) -> Option<BTreeSet<String>> {
+    match engine.list_subjects(DOCUMENT, "view", "user").await {
+        Ok(response) => Some(response.subjects.into_iter().collect()),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("List subjects not available: {}", e));
+            None
+        },
+        Err(e) => panic!("Failed to list subjects: {}", e),
+    }
+}
+
+/// Users at the leaves of the expanded tree, or `None` if the endpoint is unavailable
+async fn expanded_members(engine: &EngineClient) -> Option<BTreeSet<String>> {
+    match engine.expand(DOCUMENT, "view").await {
+        Ok(tree) => {
+            let mut members = BTreeSet::new();
+            collect_users(&tree, &mut members);
+            Some(members)
+        },
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Expand not available: {}", e));
+            None
+        },
+        Err(e) => panic!("Failed to expand: {}", e),
+    }
+}
+
+/// Collect every `user:` subject in an expand tree, whatever its node layout
+fn collect_users(node: &serde_json::Value, users: &mut BTreeSet<String>) {
+    match node {
+        serde_json::Value::String(s) if s.starts_with("user:") => {
+            users.insert(s.clone());
+        },
+        serde_json::Value::Array(items) => items.iter().for_each(|item| collect_users(item, users)),
+        serde_json::Value::Object(fields) => {
+            fields.values().for_each(|value| collect_users(value, users))
+        },
+        _ => {},
+    }
+}
+
+/// Assert evaluate, expand, and list-subjects all report exactly `expected`
+async fn assert_views_agree(engine: &EngineClient, expected: &[&str]) {
+    let expected: BTreeSet<String> = expected.iter().map(|s| s.to_string()).collect();
+
+    let evaluated = evaluated_members(engine).await;
+    assert_eq!(evaluated, expected, "Evaluate disagrees on the effective member set");
+    println!("✓ Evaluate: {:?}", evaluated);
+
+    if let Some(listed) = listed_members(engine).await {
+        assert_eq!(listed, expected, "List subjects disagrees with evaluate");
+        println!("✓ List subjects: {:?}", listed);
+    }
+
+    if let Some(expanded) = expanded_members(engine).await {
+        assert_eq!(expanded, expected, "Expand disagrees with evaluate");
+        println!("✓ Expand: {:?}", expanded);
+    }
+}
+
+#[tokio::test]
+async fn test_nested_group_members_inherit_access() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = seeded_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    assert!(
+        engine.check(DOCUMENT, "view", "user:alice").await.expect("Failed to evaluate"),
+        "Member of a nested group should view the document"
+    );
+    assert!(
+        engine.check(DOCUMENT, "view", "user:bob").await.expect("Failed to evaluate"),
+        "Direct member of the granted group should view the document"
+    );
+    assert!(
+        !engine.check(DOCUMENT, "view", "user:carol").await.expect("Failed to evaluate"),
+        "Member of an unrelated group must not view the document"
+    );
+    println!("✓ Access flows through two levels of group membership");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_evaluate_expand_and_list_subjects_agree() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = seeded_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    assert_views_agree(&engine, &["user:alice", "user:bob"]).await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_removing_nested_edge_updates_every_view() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = seeded_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    engine.delete_relationships(&[nested_edge()]).await.expect("Failed to delete nested edge");
+
+    // Allow for evaluation cache invalidation before comparing the views
+    for _ in 0..10 {
+        if !engine.check(DOCUMENT, "view", "user:alice").await.expect("Failed to evaluate") {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    }
+
+    assert_views_agree(&engine, &["user:bob"]).await;
+    println!("✓ Removing eng ⊂ staff revoked alice in every view");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,143 @@
+// gRPC Stream Limit Tests
+//
+// Opens Engine's maximum number of concurrent streams (INFERADB_GRPC_MAX_STREAMS) on one HTTP/2
+// connection, held open with a server-streaming call (INFERADB_GRPC_STREAM_METHOD), then a few
+// more. Streams beyond the limit must be refused promptly with RESOURCE_EXHAUSTED, never left
+// waiting, and a second connection must be served normally while the first is full. Engine's
+// per-connection limit has to sit at or below the HTTP/2 SETTINGS limit it advertises, or clients
+// queue the excess calls themselves and never reach it; here that shows as a stall. Once the held
+// streams close, the crowded connection is served again. Skipped when gRPC is not reachable or the
+// stream method cannot be held open.
+
+use tokio::task::JoinSet;
+
+use super::*;
+
+/// Concurrent streams Engine allows per connection, unless INFERADB_GRPC_MAX_STREAMS overrides it
+const DEFAULT_MAX_STREAMS: usize = 100;
+
+/// Streams opened past the limit
+const EXCESS_STREAMS: usize = 10;
+
+/// How long any call may take to be answered before it counts as stalled
+const STALL_BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Unary call used to check a connection is being served
+const UNARY_METHOD: &str = "Evaluate";
+
+fn stream_method() -> String {
+    std::env::var("INFERADB_GRPC_STREAM_METHOD").unwrap_or_else(|_| "Watch".to_string())
+}
+
+/// How one attempt to open a stream ended
+enum Attempt {
+    Held(GrpcStream),
+    Exhausted,
+    Refused(Option<i32>),
+    Stalled,
+}
+
+async fn attempt(probe: GrpcProbe, method: String, jwt: String) -> Attempt {
+    match tokio::time::timeout(STALL_BUDGET, probe.open(&method, &jwt)).await {
+        Err(_) => Attempt::Stalled,
+        Ok(Some(stream)) if stream.status == grpc_status::OK => Attempt::Held(stream),
+        Ok(Some(stream)) if stream.status == grpc_status::RESOURCE_EXHAUSTED => Attempt::Exhausted,
+        Ok(other) => Attempt::Refused(other.map(|stream| stream.status)),
+    }
+}
+
+/// A unary call on `probe`'s connection, which must be answered within the stall budget
+async fn served(probe: &GrpcProbe, jwt: &str) -> Option<i32> {
+    tokio::time::timeout(STALL_BUDGET, probe.call(UNARY_METHOD, jwt))
+        .await
+        .unwrap_or_else(|_| panic!("{} stalled for {:?}", UNARY_METHOD, STALL_BUDGET))
+}
+
+#[tokio::test]
+async fn test_stream_limit_refuses_excess_without_stalling() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.jwt().encode().expect("Failed to encode JWT");
+    let limit = std::env::var("INFERADB_GRPC_MAX_STREAMS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_STREAMS);
+    let method = stream_method();
+    let crowded = GrpcProbe::new(&fixture.ctx);
+
+    let first = match attempt(crowded.clone(), method.clone(), jwt.clone()).await {
+        Attempt::Held(stream) => stream,
+        Attempt::Refused(None | Some(grpc_status::UNIMPLEMENTED)) => {
+            outcome::skip(format!(
+                "Skipping stream limit test - {} not reachable over gRPC",
+                method
+            ));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Attempt::Refused(Some(code)) => {
+            outcome::skip(format!(
+                "Skipping stream limit test - {} cannot be held open (grpc-status {})",
+                method, code
+            ));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Attempt::Exhausted | Attempt::Stalled => {
+            panic!("A single {} stream was not served", method)
+        },
+    };
+
+    let mut attempts = JoinSet::new();
+    for _ in 1..limit + EXCESS_STREAMS {
+        attempts.spawn(attempt(crowded.clone(), method.clone(), jwt.clone()));
+    }
+    let mut held = vec![first];
+    let (mut exhausted, mut stalled, mut refused) = (0, 0, Vec::new());
+    while let Some(outcome) = attempts.join_next().await {
+        match outcome.expect("Stream task panicked") {
+            Attempt::Held(stream) => held.push(stream),
+            Attempt::Exhausted => exhausted += 1,
+            Attempt::Refused(code) => refused.push(code),
+            Attempt::Stalled => stalled += 1,
+        }
+    }
+    println!(
+        "  {} streams held, {} RESOURCE_EXHAUSTED, {} stalled, {} otherwise refused",
+        held.len(),
+        exhausted,
+        stalled,
+        refused.len()
+    );
+
+    // The other connection is served while this one is full
+    let other = GrpcProbe::new(&fixture.ctx);
+    let answer = served(&other, &jwt).await;
+    assert!(
+        answer.is_some() && answer != Some(grpc_status::RESOURCE_EXHAUSTED),
+        "A second connection should be served while the first is full, got {:?}",
+        answer
+    );
+    println!("✓ Second connection served: grpc-status {:?}", answer);
+
+    // Release the held streams; the crowded connection recovers
+    let held_count = held.len();
+    drop(held);
+    let recovered = served(&crowded, &jwt).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    assert_eq!(stalled, 0, "{} streams beyond the limit stalled instead of failing", stalled);
+    assert!(refused.is_empty(), "Streams refused other than RESOURCE_EXHAUSTED: {:?}", refused);
+    assert!(
+        held_count <= limit,
+        "Engine held {} streams, past the limit of {}; set INFERADB_GRPC_MAX_STREAMS to its limit",
+        held_count,
+        limit
+    );
+    println!("✓ {} streams past the limit refused with RESOURCE_EXHAUSTED", exhausted);
+    assert!(
+        recovered.is_some() && recovered != Some(grpc_status::RESOURCE_EXHAUSTED),
+        "Crowded connection not served after its streams closed, got {:?}",
+        recovered
+    );
+    println!("✓ Crowded connection served again once streams closed");
+}
@@ -0,0 +1,220 @@
+// Header Robustness Tests
+//
+// Proxies and client libraries in real deployments send headers the suite otherwise never uses:
+// exotic or malformed Accept-Language and Accept values, and Authorization repeated with two
+// different bearer tokens. Engine must handle each deterministically - the same answer every
+// time, never a server error - and must not let a second, valid token rescue an invalid one.
+
+use reqwest::{
+    Method, RequestBuilder, StatusCode,
+    header::{ACCEPT, ACCEPT_LANGUAGE, AUTHORIZATION, CONTENT_TYPE},
+};
+
+use super::*;
+
+/// Times each unusual request is repeated to check the outcome is stable
+const REPEATS: usize = 3;
+
+const ACCEPT_LANGUAGES: &[&str] = &[
+    "*",
+    "zz-ZZ",
+    "tr-TR",
+    "",
+    "en;q=abc",
+    "de-CH-1996, fr;q=0.9, *;q=0.1",
+    "x-klingon, en-GB;q=0.8, en;q=0.7, ja;q=0.5, zh-Hant-TW;q=0.3",
+];
+
+const ACCEPTS: &[&str] = &[
+    "*/*",
+    "application/json; charset=utf-8",
+    "application/*",
+    "text/html",
+    "application/xml",
+    ";;;",
+];
+
+fn evaluate(fixture: &SharedFixture) -> RequestBuilder {
+    fixture.request(Method::POST, &TestContext::new().engine_url("/evaluate")).json(
+        &serde_json::json!({
+            "evaluations": [{
+                "subject": "user:alice",
+                "resource": "document:1",
+                "permission": "viewer",
+                "trace": false
+            }]
+        }),
+    )
+}
+
+/// Send `build()` `REPEATS` times, asserting every attempt gets the same status
+async fn stable_status(what: &str, build: impl Fn() -> RequestBuilder) -> StatusCode {
+    let mut statuses = Vec::with_capacity(REPEATS);
+    for _ in 0..REPEATS {
+        statuses.push(build().send_recorded().await.expect("Failed to call server").status());
+    }
+    assert!(
+        statuses.windows(2).all(|w| w[0] == w[1]),
+        "{} answered inconsistently: {:?}",
+        what,
+        statuses
+    );
+    assert!(!statuses[0].is_server_error(), "{} caused a server error: {}", what, statuses[0]);
+    statuses[0]
+}
+
+fn accepted(status: StatusCode) -> bool {
+    status == StatusCode::OK || status == StatusCode::NOT_FOUND
+}
+
+#[tokio::test]
+async fn test_accept_language_does_not_change_result() {
+    let fixture = SharedFixture::get().await;
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let bearer = format!("Bearer {}", jwt);
+
+    let baseline = evaluate(fixture)
+        .header(AUTHORIZATION, &bearer)
+        .send_recorded()
+        .await
+        .expect("Failed to call server");
+    let baseline_status = baseline.status();
+    let baseline_body: serde_json::Value = baseline.json().await.unwrap_or_default();
+
+    for language in ACCEPT_LANGUAGES {
+        let what = format!("Accept-Language {:?}", language);
+        let status = stable_status(&what, || {
+            evaluate(fixture).header(AUTHORIZATION, &bearer).header(ACCEPT_LANGUAGE, *language)
+        })
+        .await;
+        assert_eq!(status, baseline_status, "{} changed the status", what);
+
+        let body: serde_json::Value = evaluate(fixture)
+            .header(AUTHORIZATION, &bearer)
+            .header(ACCEPT_LANGUAGE, *language)
+            .send_recorded()
+            .await
+            .expect("Failed to call server")
+            .json()
+            .await
+            .unwrap_or_default();
+        assert_eq!(body, baseline_body, "{} changed the response body", what);
+        println!("✓ {} ignored", what);
+    }
+}
+
+#[tokio::test]
+async fn test_unusual_accept_answered_with_json_or_406() {
+    let fixture = SharedFixture::get().await;
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let bearer = format!("Bearer {}", jwt);
+
+    for accept in ACCEPTS {
+        let what = format!("Accept {:?}", accept);
+        let status = stable_status(&what, || {
+            evaluate(fixture).header(AUTHORIZATION, &bearer).header(ACCEPT, *accept)
+        })
+        .await;
+
+        if status == StatusCode::NOT_ACCEPTABLE {
+            println!("✓ {} refused with 406", what);
+            continue;
+        }
+        assert!(
+            accepted(status),
+            "{} should be served as JSON or refused with 406, got {}",
+            what,
+            status
+        );
+
+        let response = evaluate(fixture)
+            .header(AUTHORIZATION, &bearer)
+            .header(ACCEPT, *accept)
+            .send_recorded()
+            .await
+            .expect("Failed to call server");
+        let content_type = response
+            .headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .unwrap_or_default()
+            .to_string();
+        assert!(
+            content_type.starts_with("application/json"),
+            "{} served as {:?}",
+            what,
+            content_type
+        );
+        println!("✓ {} served as JSON", what);
+    }
+}
+
+#[tokio::test]
+async fn test_duplicate_authorization_headers() {
+    let fixture = SharedFixture::get().await;
+    let valid = format!(
+        "Bearer {}",
+        fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT")
+    );
+    let invalid = format!(
+        "Bearer {}",
+        fixture.generate_invalid_jwt().expect("Failed to generate invalid JWT")
+    );
+    let twice = |first: &str, second: &str| {
+        let (first, second) = (first.to_string(), second.to_string());
+        move || evaluate(fixture).header(AUTHORIZATION, &first).header(AUTHORIZATION, &second)
+    };
+
+    let both_valid = stable_status("Two valid tokens", twice(&valid, &valid)).await;
+    let valid_first = stable_status("Valid then invalid token", twice(&valid, &invalid)).await;
+    let invalid_first = stable_status("Invalid then valid token", twice(&invalid, &valid)).await;
+
+    // Either the duplicate is refused outright, or exactly one position takes precedence
+    assert!(
+        !(accepted(valid_first) && accepted(invalid_first)),
+        "A valid token in either position rescued an invalid one ({} / {})",
+        valid_first,
+        invalid_first
+    );
+    let precedence = match (accepted(valid_first), accepted(invalid_first)) {
+        (true, false) => "first header wins",
+        (false, true) => "last header wins",
+        _ if accepted(both_valid) => "any invalid token rejects",
+        _ => "duplicates refused",
+    };
+    let refused = if accepted(valid_first) {
+        vec![invalid_first]
+    } else if accepted(invalid_first) {
+        vec![valid_first]
+    } else {
+        vec![valid_first, invalid_first]
+    };
+    for status in refused {
+        assert!(
+            status == StatusCode::BAD_REQUEST || status == StatusCode::UNAUTHORIZED,
+            "Refused duplicate Authorization should be 400 or 401, got {}",
+            status
+        );
+    }
+    if precedence.ends_with("wins") {
+        assert!(
+            accepted(both_valid),
+            "Two copies of a valid token rejected under {}: {}",
+            precedence,
+            both_valid
+        );
+    }
+    println!("✓ Duplicate Authorization handled deterministically ({})", precedence);
+
+    // Two tokens folded into one header value are never a valid credential
+    let folded = format!("{}, {}", valid, valid);
+    let status =
+        stable_status("Comma-joined tokens", || evaluate(fixture).header(AUTHORIZATION, &folded))
+            .await;
+    assert!(
+        status == StatusCode::BAD_REQUEST || status == StatusCode::UNAUTHORIZED,
+        "Comma-joined tokens should be rejected, got {}",
+        status
+    );
+    println!("✓ Comma-joined tokens rejected with {}", status);
+}
@@ -0,0 +1,254 @@
+// ID Format Contract Tests
+//
+// Control identifies users, sessions, organizations, vaults, clients, and certificates with i64
+// Snowflake IDs, sent as JSON numbers; the typed client deserializes every one of them as `i64`, so
+// an ID that turns into a string or a UUID fails the read. Snowflakes are positive and ordered by
+// creation time, which these tests check for each kind of ID. Client JWTs carry the same IDs as
+// decimal strings (`vault_id`, `org_id`, and the client in `sub`) next to a UUID `jti`. Engine
+// also takes the ID claims as JSON numbers where the profile says so
+// (INFERADB_NUMERIC_ID_CLAIMS_ACCEPTED), and rejects every other spelling of the number.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use super::*;
+
+fn assert_snowflake(kind: &str, id: i64) {
+    assert!(id > 0, "{} ID {} is not a positive Snowflake", kind, id);
+}
+
+/// Assert `later` was minted after `earlier`, as Snowflakes of one kind must be
+fn assert_ordered(kind: &str, earlier: i64, later: i64) {
+    assert_snowflake(kind, later);
+    assert!(later > earlier, "{} ID {} created after {} but not greater", kind, later, earlier);
+}
+
+/// The claims of a client JWT, read without verifying it
+fn claims_of(jwt: &str) -> ClientClaims {
+    let payload = jwt.split('.').nth(1).expect("JWT should have a payload");
+    let bytes = URL_SAFE_NO_PAD.decode(payload).expect("JWT payload should be base64url");
+    serde_json::from_slice(&bytes).expect("JWT payload should hold client claims")
+}
+
+#[tokio::test]
+async fn test_control_ids_are_ordered_snowflakes() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    for (kind, id) in [
+        ("user", fixture.user_id),
+        ("session", fixture.session_id),
+        ("organization", fixture.org_id),
+        ("vault", fixture.vault_id),
+        ("client", fixture.client_id),
+        ("certificate", fixture.cert_id),
+    ] {
+        assert_snowflake(kind, id);
+    }
+
+    // Each read answers with the IDs the fixture was provisioned with
+    let user = control.get_current_user().await.expect("Failed to read current user");
+    assert_eq!(user.id, fixture.user_id, "Current user ID differs from registration");
+    let organizations = control.list_organizations().await.expect("Failed to list organizations");
+    assert!(
+        organizations.organizations.iter().any(|org| org.id == fixture.org_id),
+        "Organization {} missing from listing",
+        fixture.org_id
+    );
+    let vault =
+        control.get_vault(fixture.org_id, fixture.vault_id).await.expect("Failed to read vault");
+    assert_eq!((vault.id, vault.organization_id), (fixture.vault_id, fixture.org_id));
+    let clients = control.list_clients(fixture.org_id).await.expect("Failed to list clients");
+    assert!(
+        clients.clients.iter().any(
+            |client| client.id == fixture.client_id && client.organization_id == fixture.org_id
+        ),
+        "Client {} missing from listing",
+        fixture.client_id
+    );
+    let certificates = control
+        .list_certificates(fixture.org_id, fixture.client_id)
+        .await
+        .expect("Failed to list certificates");
+    assert!(
+        certificates.certificates.iter().any(|cert| cert.id == fixture.cert_id),
+        "Certificate {} missing from listing",
+        fixture.cert_id
+    );
+    println!("✓ Every response carries numeric Snowflake IDs matching the fixture's");
+
+    // A second of each kind is minted later, so its ID is greater
+    let vault_req = CreateVaultRequest {
+        name: naming::entity_name("ID Format Vault"),
+        organization_id: fixture.org_id,
+    };
+    let second_vault = control
+        .create_vault(fixture.org_id, &vault_req)
+        .await
+        .expect("Failed to create vault")
+        .vault;
+    assert_eq!(second_vault.organization_id, fixture.org_id);
+    assert_ordered("vault", fixture.vault_id, second_vault.id);
+
+    let second_client = control
+        .create_client(
+            fixture.org_id,
+            &CreateClientRequest { name: naming::entity_name("ID Format Client") },
+        )
+        .await
+        .expect("Failed to create client")
+        .client;
+    assert_eq!(second_client.organization_id, fixture.org_id);
+    assert_ordered("client", fixture.client_id, second_client.id);
+
+    let rotation = control
+        .rotate_certificate(
+            fixture.org_id,
+            fixture.client_id,
+            fixture.cert_id,
+            &RotateCertificateRequest {
+                name: naming::entity_name("ID Format Rotation"),
+                grace_period_seconds: 0,
+            },
+        )
+        .await
+        .expect("Failed to rotate certificate");
+    assert_eq!(rotation.rotated_from.id, fixture.cert_id);
+    assert_ordered("certificate", fixture.cert_id, rotation.certificate.id);
+    println!("✓ IDs minted later are greater, for vaults, clients, and certificates");
+
+    let _ = control.delete_client(fixture.org_id, second_client.id).await;
+    let _ = control.delete_vault(fixture.org_id, second_vault.id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_jwt_claims_carry_ids_as_strings() {
+    let fixture = SharedFixture::get().await;
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let claims = claims_of(&jwt);
+
+    assert_eq!(claims.vault_id, fixture.vault_id().to_string());
+    assert_eq!(claims.org_id, fixture.org_id().to_string());
+    assert_eq!(claims.sub, format!("client:{}", fixture.client_id()));
+    let jti = Uuid::parse_str(&claims.jti)
+        .unwrap_or_else(|e| panic!("jti {:?} is not a UUID: {}", claims.jti, e));
+    assert_eq!(jti.get_version_num(), 4, "jti should be a random (v4) UUID");
+    println!("✓ vault_id, org_id, and sub carry decimal IDs; jti is a v4 UUID");
+
+    let second = claims_of(
+        &fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT"),
+    );
+    assert_ne!(second.jti, claims.jti, "Every token gets its own jti");
+}
+
+/// A spelling of the ID claims, and whether Engine is expected to accept it
+struct IdClaimCase {
+    label: &'static str,
+    vault_id: Value,
+    org_id: Value,
+    accepted: bool,
+}
+
+fn id_claim_cases(vault_id: i64, org_id: i64, profile: &EnvProfile) -> Vec<IdClaimCase> {
+    let vault = vault_id.to_string();
+    let org = org_id.to_string();
+    vec![
+        IdClaimCase {
+            label: "decimal strings",
+            vault_id: vault.clone().into(),
+            org_id: org.clone().into(),
+            accepted: true,
+        },
+        IdClaimCase {
+            label: "JSON numbers",
+            vault_id: vault_id.into(),
+            org_id: org_id.into(),
+            accepted: profile.numeric_id_claims_accepted,
+        },
+        IdClaimCase {
+            label: "numeric vault_id, string org_id",
+            vault_id: vault_id.into(),
+            org_id: org.clone().into(),
+            accepted: profile.numeric_id_claims_accepted,
+        },
+        IdClaimCase {
+            label: "hexadecimal string",
+            vault_id: format!("{:#x}", vault_id).into(),
+            org_id: org.clone().into(),
+            accepted: false,
+        },
+        IdClaimCase {
+            label: "explicit plus sign",
+            vault_id: format!("+{}", vault).into(),
+            org_id: org.clone().into(),
+            accepted: false,
+        },
+        IdClaimCase {
+            label: "padded with whitespace",
+            vault_id: format!(" {} ", vault).into(),
+            org_id: org.clone().into(),
+            accepted: false,
+        },
+        IdClaimCase {
+            label: "decimal point",
+            vault_id: format!("{}.0", vault).into(),
+            org_id: org.clone().into(),
+            accepted: false,
+        },
+        IdClaimCase {
+            label: "UUID",
+            vault_id: Uuid::new_v4().to_string().into(),
+            org_id: org.clone().into(),
+            accepted: false,
+        },
+        IdClaimCase {
+            label: "empty string",
+            vault_id: "".into(),
+            org_id: org.into(),
+            accepted: false,
+        },
+    ]
+}
+
+#[tokio::test]
+async fn test_engine_id_claim_representations() {
+    let fixture = SharedFixture::get().await;
+    let profile = EnvProfile::current();
+
+    let mut mismatches = Vec::new();
+    for case in id_claim_cases(fixture.vault_id(), fixture.org_id(), profile) {
+        let jwt = fixture
+            .jwt()
+            .claim("vault_id", case.vault_id)
+            .claim("org_id", case.org_id)
+            .encode()
+            .expect("Failed to encode JWT");
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+
+        let status = response.status();
+        let accepted = status == StatusCode::OK || status == StatusCode::NOT_FOUND;
+        let rejected = status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN;
+        if (case.accepted && accepted) || (!case.accepted && rejected) {
+            println!("✓ {}: {}", case.label, status);
+        } else {
+            mismatches.push(format!(
+                "{}: expected {}, got {}",
+                case.label,
+                if case.accepted { "acceptance" } else { "rejection" },
+                status
+            ));
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "ID claim handling differs from profile (numeric accepted: {}):\n  {}",
+        profile.numeric_id_claims_accepted,
+        mismatches.join("\n  ")
+    );
+}
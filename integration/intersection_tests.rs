@@ -0,0 +1,144 @@
+// Intersection Tests
+//
+// Deploys `INTERSECTION_SCHEMA` (`view = viewer & cleared`) and checks that evaluate requires both
+// edges, that satisfying only one of them (initially or after the other is removed) yields DENY,
+// and that expand exposes the intersection rather than flattening it into a union. Skipped if the
+// schema language has no intersection operator.
+
+use super::*;
+
+const DOCUMENT: &str = "document:dossier";
+
+async fn intersection_engine(fixture: &TestFixture) -> Option<EngineClient> {
+    if !schema_validation_tests::deploy_schema_with(fixture, INTERSECTION_SCHEMA, "intersection")
+        .await
+    {
+        return None;
+    }
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    Some(fixture.engine(&jwt))
+}
+
+/// Whether any key or string value in an expand tree names an intersection node
+fn mentions_intersection(node: &serde_json::Value) -> bool {
+    match node {
+        serde_json::Value::String(s) => s.to_lowercase().contains("intersection"),
+        serde_json::Value::Array(items) => items.iter().any(mentions_intersection),
+        serde_json::Value::Object(fields) => fields.iter().any(|(key, value)| {
+            key.to_lowercase().contains("intersection") || mentions_intersection(value)
+        }),
+        _ => false,
+    }
+}
+
+#[tokio::test]
+async fn test_intersection_requires_both_edges() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = intersection_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    engine
+        .write_relationships(&[
+            Relationship::new(DOCUMENT, "viewer", "user:alice"),
+            Relationship::new(DOCUMENT, "cleared", "user:alice"),
+            Relationship::new(DOCUMENT, "viewer", "user:bob"),
+            Relationship::new(DOCUMENT, "cleared", "user:carol"),
+        ])
+        .await
+        .expect("Failed to write relationships");
+
+    for (subject, expected, case) in [
+        ("user:alice", true, "viewer and cleared"),
+        ("user:bob", false, "viewer only"),
+        ("user:carol", false, "cleared only"),
+        ("user:dave", false, "neither"),
+    ] {
+        let allowed = engine.check(DOCUMENT, "view", subject).await.expect("Failed to evaluate");
+        assert_eq!(allowed, expected, "Wrong decision for {} ({})", subject, case);
+        println!("✓ {}: {}", case, if allowed { "ALLOW" } else { "DENY" });
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_removing_one_edge_denies() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = intersection_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let cleared = Relationship::new(DOCUMENT, "cleared", "user:alice");
+    engine
+        .write_relationships(&[
+            Relationship::new(DOCUMENT, "viewer", "user:alice"),
+            cleared.clone(),
+        ])
+        .await
+        .expect("Failed to write relationships");
+    assert!(
+        engine.check(DOCUMENT, "view", "user:alice").await.expect("Failed to evaluate"),
+        "Subject holding both edges should be allowed"
+    );
+
+    engine.delete_relationships(&[cleared]).await.expect("Failed to delete relationship");
+
+    // Allow for evaluation cache invalidation
+    let mut allowed = true;
+    for _ in 0..10 {
+        allowed = engine.check(DOCUMENT, "view", "user:alice").await.expect("Failed to evaluate");
+        if !allowed {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    }
+    assert!(!allowed, "Viewer without clearance must be denied once the edge is removed");
+    println!("✓ Removing one side of the intersection denied access");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_expand_reflects_intersection() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = intersection_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    engine
+        .write_relationships(&[
+            Relationship::new(DOCUMENT, "viewer", "user:alice"),
+            Relationship::new(DOCUMENT, "cleared", "user:alice"),
+            Relationship::new(DOCUMENT, "viewer", "user:bob"),
+        ])
+        .await
+        .expect("Failed to write relationships");
+
+    let tree = match engine.expand(DOCUMENT, "view").await {
+        Ok(tree) => tree,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping expand intersection test - expand not available: {}",
+                e
+            ));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to expand: {}", e),
+    };
+
+    assert!(
+        mentions_intersection(&tree),
+        "Expand of an intersection permission should contain an intersection node: {}",
+        tree
+    );
+    println!("✓ Expand tree contains an intersection node");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,146 @@
+// Invalidation Fallback Tests
+//
+// Blocks Control's cache invalidation webhooks to Engine (via the `INVALIDATION_WEBHOOK` chaos
+// hook) so every delivery fails, then revokes a certificate Engine has cached. With no
+// invalidation arriving, Engine may keep accepting the revoked key, but only until its key cache
+// entry expires: the revocation must take effect within the TTL Engine reports
+// (`engine_cache_ttl_seconds{cache="keys"}`), and Engine must stay healthy while the webhooks
+// fail. Skipped unless INFERADB_CHAOS_INVALIDATION_WEBHOOK_DISRUPT/RESTORE are set and Engine
+// exports its key cache TTL.
+
+use std::time::Instant;
+
+use inferadb_integration_tests::metrics::MetricsSnapshot;
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Metric reporting Engine's cache TTLs, by `cache` label
+const TTL_METRIC: &str = "engine_cache_ttl_seconds";
+
+/// Longest key cache TTL the test is willing to wait out
+const MAX_TTL: std::time::Duration = std::time::Duration::from_secs(20 * 60);
+
+/// Allowance beyond the TTL for expiry sweeps and polling
+const SLACK: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Gap between evaluates while waiting for the revocation to take effect
+const POLL: tokio::time::Duration = tokio::time::Duration::from_secs(2);
+
+/// Engine's key cache TTL, if it exports one
+async fn key_cache_ttl(fixture: &TestFixture) -> Option<std::time::Duration> {
+    let snapshot = MetricsSnapshot::fetch(&fixture.ctx).await?;
+    snapshot
+        .samples
+        .iter()
+        .find(|s| s.name == TTL_METRIC && s.labels.get("cache").map(String::as_str) == Some("keys"))
+        .map(|s| std::time::Duration::from_secs_f64(s.value))
+}
+
+#[tokio::test]
+async fn test_revocation_expires_from_cache_when_invalidation_fails() {
+    let Some(webhooks) = ChaosHook::from_env("INVALIDATION_WEBHOOK") else {
+        outcome::skip(
+            "Skipping invalidation fallback test - INFERADB_CHAOS_INVALIDATION_WEBHOOK_* not \
+             configured",
+        );
+        return;
+    };
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let ttl = match key_cache_ttl(&fixture).await {
+        Some(ttl) if ttl <= MAX_TTL => ttl,
+        Some(ttl) => {
+            outcome::skip(format!(
+                "Skipping invalidation fallback test - key cache TTL {:?} is too long",
+                ttl
+            ));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        None => {
+            outcome::skip("Skipping invalidation fallback test - Engine exports no key cache TTL");
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+    };
+    println!("  key cache TTL: {:?}", ttl);
+
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("Fallback Certificate"));
+    let cert = fixture
+        .control()
+        .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+        .await
+        .expect("Failed to create certificate");
+    let signing_key = keys::from_base64(&cert.private_key).expect("Invalid private key");
+    let evaluate = || async {
+        let jwt = fixture
+            .jwt()
+            .signed_by(&cert.certificate.kid, &signing_key)
+            .encode()
+            .expect("Failed to encode JWT");
+        fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server")
+            .status()
+    };
+
+    // Cache the key; the TTL runs from here at the latest
+    let cached_at = Instant::now();
+    let status = evaluate().await;
+    assert!(
+        status == StatusCode::OK || status == StatusCode::NOT_FOUND,
+        "New certificate should be accepted, got {}",
+        status
+    );
+    println!("✓ Certificate cached by Engine");
+
+    let outage = webhooks.disrupt().expect("Failed to block invalidation webhooks");
+    println!("✓ Invalidation webhooks blocked");
+    fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, cert.certificate.id)
+        .await
+        .expect("Failed to revoke certificate");
+    let revoked_at = Instant::now();
+
+    let deadline = ttl + SLACK;
+    let mut stale = 0;
+    let rejected = loop {
+        let status = evaluate().await;
+        assert!(
+            !status.is_server_error(),
+            "Engine answered {} while invalidation webhooks were failing",
+            status
+        );
+        if status == StatusCode::UNAUTHORIZED {
+            break true;
+        }
+        stale += 1;
+        if cached_at.elapsed() >= deadline {
+            break false;
+        }
+        tokio::time::sleep(POLL).await;
+    };
+    let elapsed = revoked_at.elapsed();
+
+    outage.restore().expect("Failed to unblock invalidation webhooks");
+    println!("✓ Invalidation webhooks unblocked");
+
+    assert!(
+        rejected,
+        "Revoked certificate still accepted {:?} after revocation; the {:?} TTL did not expire it",
+        elapsed, ttl
+    );
+    if stale == 0 {
+        println!("✓ Revocation took effect at once; it reached Engine without the webhook");
+    } else {
+        println!(
+            "✓ Revocation took effect after {:?} ({} stale answers), within the {:?} TTL",
+            elapsed, stale, ttl
+        );
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
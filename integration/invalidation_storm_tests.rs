@@ -0,0 +1,131 @@
+// Invalidation Storm Tests
+//
+// Churns certificates (100 rapid create/revoke cycles) while background evaluate traffic runs,
+// flooding the cache invalidation pipeline. Engine must stay healthy throughout (no 5xx, bounded
+// latency) and end in the correct state: every revoked key rejected, the untouched key accepted.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Create/revoke cycles to run
+const CYCLES: usize = 100;
+
+/// Maximum p99 latency of background traffic during the storm (milliseconds)
+const STORM_P99_BUDGET_MS: f64 = 2000.0;
+
+#[tokio::test]
+async fn test_certificate_churn_under_evaluate_traffic() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+
+    // Background traffic with the fixture's own, never-revoked certificate
+    let stop = Arc::new(AtomicBool::new(false));
+    let traffic = {
+        let stop = Arc::clone(&stop);
+        let engine = fixture.engine(&jwt);
+        tokio::spawn(async move {
+            let mut samples = Vec::new();
+            while !stop.load(Ordering::Relaxed) {
+                let start = Instant::now();
+                // A request that never got an answer counts as unavailability
+                let status = match engine.evaluate("document:1", "viewer", "user:alice").await {
+                    Ok(_) => StatusCode::OK,
+                    Err(e) => e.status().unwrap_or(StatusCode::SERVICE_UNAVAILABLE),
+                };
+                samples.push((status, start.elapsed().as_secs_f64() * 1000.0));
+            }
+            samples
+        })
+    };
+
+    // Churn: each cycle creates a certificate, mints a token with it, and revokes it
+    let storm_start = Instant::now();
+    let mut revoked_tokens = Vec::with_capacity(CYCLES);
+    for cycle in 0..CYCLES {
+        let cert_req = CreateCertificateRequest::new(format!("Storm Certificate {}", cycle));
+        let cert = control
+            .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+            .await
+            .unwrap_or_else(|e| panic!("Cycle {}: failed to create certificate: {}", cycle, e));
+        let signing_key = keys::from_base64(&cert.private_key).expect("Invalid private key");
+        revoked_tokens.push(
+            fixture
+                .jwt()
+                .signed_by(&cert.certificate.kid, &signing_key)
+                .encode()
+                .expect("Failed to encode JWT"),
+        );
+
+        control
+            .revoke_certificate(fixture.org_id, fixture.client_id, cert.certificate.id)
+            .await
+            .unwrap_or_else(|e| panic!("Cycle {}: failed to revoke certificate: {}", cycle, e));
+    }
+    println!("✓ {} create/revoke cycles in {:?}", CYCLES, storm_start.elapsed());
+
+    stop.store(true, Ordering::Relaxed);
+    let samples = traffic.await.expect("Background traffic task failed");
+
+    // Engine stayed healthy
+    let server_errors: Vec<StatusCode> =
+        samples.iter().map(|(status, _)| *status).filter(StatusCode::is_server_error).collect();
+    assert!(
+        server_errors.is_empty(),
+        "{} of {} background requests failed with 5xx during the storm: {:?}",
+        server_errors.len(),
+        samples.len(),
+        server_errors
+    );
+
+    let mut latencies: Vec<f64> = samples.iter().map(|(_, ms)| *ms).collect();
+    latencies.sort_by(f64::total_cmp);
+    let p99 = scenario::percentile(&latencies, 0.99).expect("No background traffic recorded");
+    println!("✓ {} background requests, no 5xx, p99 {:.1}ms", samples.len(), p99);
+    assert!(
+        p99 <= STORM_P99_BUDGET_MS,
+        "Background p99 {:.1}ms exceeds {:.0}ms during the storm",
+        p99,
+        STORM_P99_BUDGET_MS
+    );
+
+    // Final state: the untouched key works, every revoked key is refused
+    tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
+    let response = fixture
+        .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+    assert!(
+        response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND,
+        "Fixture certificate should still be accepted after the storm, got {}",
+        response.status()
+    );
+
+    let mut still_accepted = Vec::new();
+    for (cycle, token) in revoked_tokens.iter().enumerate() {
+        let response = fixture
+            .call_server_evaluate(token, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        if response.status() != StatusCode::UNAUTHORIZED {
+            still_accepted.push(format!("cycle {}: {}", cycle, response.status()));
+        }
+    }
+    assert!(
+        still_accepted.is_empty(),
+        "Revoked certificates still accepted after the storm: {:?}",
+        still_accepted
+    );
+    println!("✓ All {} revoked certificates rejected", CYCLES);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
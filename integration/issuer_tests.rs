@@ -0,0 +1,74 @@
+// Issuer Validation Tests
+//
+// Mints tokens whose `iss` is a variation of the API base URL (with and without a `/v1` suffix,
+// different host casing, http vs https) and asserts Engine accepts exactly the issuers on the
+// active `EnvProfile` allow-list.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Issuer variations derived from the API base URL that fixtures sign with
+fn issuer_variants(base: &str) -> Vec<(&'static str, String)> {
+    vec![
+        ("API base URL", base.to_string()),
+        ("with /v1 suffix", format!("{}/v1", base)),
+        ("with trailing slash", format!("{}/", base)),
+        ("uppercase host", uppercase_host(base)),
+        ("scheme variation", swap_scheme(base)),
+        ("unrelated issuer", "https://issuer.example.com".to_string()),
+    ]
+}
+
+#[tokio::test]
+async fn test_issuer_allow_list_matrix() {
+    let fixture = SharedFixture::get().await;
+    let profile = EnvProfile::current();
+
+    let mut mismatches = Vec::new();
+    for (label, issuer) in issuer_variants(fixture.api_base_url()) {
+        // Skip variants that collapse onto the original (e.g. an already-uppercase host)
+        if label != "API base URL" && issuer == fixture.api_base_url() {
+            continue;
+        }
+
+        let jwt = fixture.jwt().issuer(&issuer).encode().expect("Failed to encode JWT");
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+
+        let status = response.status();
+        let expected = profile.accepts_issuer(&issuer);
+        let accepted = status == StatusCode::OK || status == StatusCode::NOT_FOUND;
+        let rejected = status == StatusCode::UNAUTHORIZED;
+
+        if (expected && accepted) || (!expected && rejected) {
+            println!("✓ {} ({}): {}", label, issuer, status);
+        } else {
+            mismatches.push(format!(
+                "{} ({}): expected {}, got {}",
+                label,
+                issuer,
+                if expected { "200/404" } else { "401" },
+                status
+            ));
+        }
+    }
+
+    assert!(
+        mismatches.is_empty(),
+        "Issuer validation differs from allow-list {:?}:\n  {}",
+        profile.issuer_allow_list,
+        mismatches.join("\n  ")
+    );
+}
+
+#[test]
+fn test_uppercase_host_preserves_scheme_port_and_path() {
+    assert_eq!(
+        uppercase_host("https://inferadb-api.tail27bf77.ts.net"),
+        "https://INFERADB-API.TAIL27BF77.TS.NET"
+    );
+    assert_eq!(uppercase_host("http://localhost:9090/v1"), "http://LOCALHOST:9090/v1");
+}
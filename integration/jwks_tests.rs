@@ -0,0 +1,221 @@
+// JWKS Tests
+//
+// Tests for the JWKS endpoint publishing client public keys: every published key must match the
+// certificate it names, new certificates must appear, revoked ones must disappear or be marked
+// revoked, and the endpoint must be cacheable without letting revocations linger. The endpoint
+// is looked up at the candidate locations below; skipped when none serves this client's keys.
+
+use std::time::Instant;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use reqwest::{
+    StatusCode,
+    header::{CACHE_CONTROL, ETAG, HeaderMap, IF_NONE_MATCH},
+};
+
+use super::*;
+
+/// How long a key change may take to show up in the JWKS
+const PROPAGATION_BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Longest `max-age` that still lets revocations reach verifiers promptly
+const MAX_CACHE_SECONDS: u64 = 3600;
+
+/// Private members that must never appear in a published key
+const PRIVATE_MEMBERS: &[&str] = &["d", "p", "q", "dp", "dq", "qi", "k"];
+
+/// Candidate JWKS URLs, client-scoped first
+fn candidate_urls(fixture: &TestFixture) -> Vec<String> {
+    vec![
+        fixture.ctx.control_url(&format!(
+            "/organizations/{}/clients/{}/jwks.json",
+            fixture.org_id, fixture.client_id
+        )),
+        fixture.ctx.control_url("/.well-known/jwks.json"),
+        fixture.ctx.engine_url("/.well-known/jwks.json"),
+        fixture.ctx.root_url("/.well-known/jwks.json"),
+    ]
+}
+
+async fn fetch(fixture: &TestFixture, url: &str) -> Option<(HeaderMap, Jwks)> {
+    let response = fixture.ctx.client.get(url).send_recorded().await.ok()?;
+    if response.status() != StatusCode::OK {
+        return None;
+    }
+    let headers = response.headers().clone();
+    let jwks = response.json().await.ok()?;
+    Some((headers, jwks))
+}
+
+/// The JWKS URL that publishes the fixture's key, or `None` (after logging) if there is none
+async fn locate_jwks(fixture: &TestFixture) -> Option<String> {
+    for url in candidate_urls(fixture) {
+        if let Some((_, jwks)) = fetch(fixture, &url).await
+            && jwks.keys.iter().any(|key| key.kid.as_deref() == Some(fixture.cert_kid.as_str()))
+        {
+            println!("✓ JWKS served at {}", url);
+            return Some(url);
+        }
+    }
+    outcome::skip("Skipping JWKS test - no endpoint publishes this client's keys");
+    None
+}
+
+fn find<'a>(jwks: &'a Jwks, kid: &str) -> Option<&'a Jwk> {
+    jwks.keys.iter().find(|key| key.kid.as_deref() == Some(kid))
+}
+
+/// Assert the published key for `kid` is the Ed25519 public half of `signing_key`
+fn assert_key_matches(jwks: &Jwks, kid: &str, signing_key: &SigningKey) {
+    let key = find(jwks, kid).unwrap_or_else(|| panic!("JWKS does not publish {}", kid));
+    assert_eq!(key.kty, "OKP", "Key {} has wrong kty", kid);
+    assert_eq!(key.crv.as_deref(), Some("Ed25519"), "Key {} has wrong crv", kid);
+    assert_eq!(
+        key.x.as_deref(),
+        Some(URL_SAFE_NO_PAD.encode(signing_key.verifying_key().to_bytes()).as_str()),
+        "Published key material for {} does not match its certificate",
+        kid
+    );
+    for member in PRIVATE_MEMBERS {
+        assert!(
+            !key.extra.contains_key(*member),
+            "Key {} exposes private member {:?}",
+            kid,
+            member
+        );
+    }
+}
+
+/// Poll the JWKS until `done` holds
+async fn await_jwks(
+    fixture: &TestFixture,
+    url: &str,
+    what: &str,
+    done: impl Fn(&Jwks) -> bool,
+) -> Jwks {
+    let start = Instant::now();
+    loop {
+        let (_, jwks) = fetch(fixture, url).await.expect("JWKS endpoint stopped responding");
+        if done(&jwks) {
+            return jwks;
+        }
+        assert!(start.elapsed() < PROPAGATION_BUDGET, "{} after {:?}", what, PROPAGATION_BUDGET);
+        tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+    }
+}
+
+async fn create_certificate(fixture: &TestFixture) -> (CertificateInfo, SigningKey) {
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("JWKS Certificate"));
+    let cert = fixture
+        .control()
+        .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+        .await
+        .expect("Failed to create certificate");
+    let signing_key = keys::from_base64(&cert.private_key).expect("Invalid private key");
+    (cert.certificate, signing_key)
+}
+
+#[tokio::test]
+async fn test_jwks_matches_certificates() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(url) = locate_jwks(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let (_, jwks) = fetch(&fixture, &url).await.expect("Failed to fetch JWKS");
+    assert_key_matches(&jwks, &fixture.cert_kid, &fixture.signing_key);
+    println!("✓ Fixture key published with matching material");
+
+    let (cert, signing_key) = create_certificate(&fixture).await;
+    let jwks = await_jwks(&fixture, &url, "New certificate not published", |jwks| {
+        find(jwks, &cert.kid).is_some()
+    })
+    .await;
+    assert_key_matches(&jwks, &cert.kid, &signing_key);
+    assert_key_matches(&jwks, &fixture.cert_kid, &fixture.signing_key);
+    println!("✓ New certificate published with matching material");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_revoked_key_removed_or_marked() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(url) = locate_jwks(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let (cert, _) = create_certificate(&fixture).await;
+    await_jwks(&fixture, &url, "New certificate not published", |jwks| {
+        find(jwks, &cert.kid).is_some()
+    })
+    .await;
+
+    fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, cert.id)
+        .await
+        .expect("Failed to revoke certificate");
+    let jwks = await_jwks(&fixture, &url, "Revoked key still published as valid", |jwks| {
+        find(jwks, &cert.kid).is_none_or(Jwk::is_marked_revoked)
+    })
+    .await;
+    println!(
+        "✓ Revoked key {}",
+        if find(&jwks, &cert.kid).is_some() { "marked revoked" } else { "removed" }
+    );
+
+    let fixture_key = find(&jwks, &fixture.cert_kid).expect("Unrevoked key disappeared");
+    assert!(!fixture_key.is_marked_revoked(), "Unrevoked key was marked revoked");
+    println!("✓ Other keys unaffected");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_jwks_cache_headers() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(url) = locate_jwks(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let (headers, _) = fetch(&fixture, &url).await.expect("Failed to fetch JWKS");
+    let cache_control = headers
+        .get(CACHE_CONTROL)
+        .and_then(|v| v.to_str().ok())
+        .expect("JWKS response should carry Cache-Control")
+        .to_lowercase();
+    assert!(!cache_control.contains("private"), "JWKS should be publicly cacheable");
+    let max_age = cache_control
+        .split(',')
+        .find_map(|directive| directive.trim().strip_prefix("max-age="))
+        .and_then(|v| v.parse::<u64>().ok())
+        .unwrap_or_else(|| panic!("Cache-Control should set max-age: {}", cache_control));
+    assert!(
+        max_age <= MAX_CACHE_SECONDS,
+        "max-age {}s would let revoked keys linger (limit {}s)",
+        max_age,
+        MAX_CACHE_SECONDS
+    );
+    println!("✓ Cache-Control: {}", cache_control);
+
+    // Revalidation with the ETag, where one is sent
+    if let Some(etag) = headers.get(ETAG) {
+        let status = fixture
+            .ctx
+            .client
+            .get(&url)
+            .header(IF_NONE_MATCH, etag)
+            .send_recorded()
+            .await
+            .expect("Failed to revalidate JWKS")
+            .status();
+        assert_eq!(status, StatusCode::NOT_MODIFIED, "Unchanged JWKS should revalidate with 304");
+        println!("✓ If-None-Match revalidates with 304");
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,443 @@
+// Kubernetes Tests
+//
+// Disrupts the Engine deployment through the Kubernetes API while evaluate traffic runs: killing
+// one pod of a multi-replica deployment, and rolling every pod to a new one. The surviving or
+// replacement pods must carry the traffic, and JWTs issued before the disruption must keep
+// authenticating on pods that have never seen them. Also compares every replica's build and
+// configuration, since replicas running mixed versions or cache TTLs make propagation timing
+// differ by which pod answers. Where Engine pre-warms its auth cache at startup
+// (`EnvProfile::auth_cache_prewarmed`), each replica fresh from a restart must serve its first
+// request for a certificate that predates the restart at warm-cache latency, measured on that pod
+// against clients created after the restart, which no replica could pre-warm. Requires the
+// `k8s` feature; skipped without a cluster, without the Engine deployment (see
+// `inferadb_integration_tests::k8s`), or with fewer than two replicas.
+
+use std::time::Instant;
+
+use inferadb_integration_tests::{k8s::K8sCluster, metrics::fingerprint_drift};
+use reqwest::StatusCode;
+
+use super::*;
+
+/// How long traffic runs after the kill
+const TRAFFIC_WINDOW: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// How long the deployment may take to replace the pod
+const ROLLOUT_BUDGET: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Fraction of requests that must succeed while a replica is lost
+const MIN_AVAILABILITY: f64 = 0.95;
+
+/// How long a rolling restart may take to finish
+const ROLLING_RESTART_BUDGET: std::time::Duration = std::time::Duration::from_secs(300);
+
+/// Fraction of requests allowed to fail during a rolling restart, unless
+/// INFERADB_ROLLOUT_MAX_ERROR_RATE overrides it
+const DEFAULT_ROLLOUT_MAX_ERROR_RATE: f64 = 0.01;
+
+/// Evaluates timed per replica with a warm cache, for its baseline
+const WARM_SAMPLES: usize = 50;
+
+/// Clients created after the restart; their first request on each replica is its cache miss
+const COLD_CONTROLS: usize = 3;
+
+#[tokio::test]
+async fn test_engine_pod_kill_keeps_serving() {
+    let Some(cluster) = K8sCluster::connect().await else {
+        outcome::skip("Skipping pod kill test - no Kubernetes cluster with an Engine deployment");
+        return;
+    };
+    let replicas = cluster.engine_replicas().await.expect("Failed to read Engine replicas");
+    if replicas < 2 {
+        outcome::skip(format!(
+            "Skipping pod kill test - Engine runs {} replica(s), need 2",
+            replicas
+        ));
+        return;
+    }
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let accepted = |status: StatusCode| status == StatusCode::OK || status == StatusCode::NOT_FOUND;
+
+    // Warm every replica's certificate cache before losing one
+    for _ in 0..replicas * 4 {
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:k8s", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        assert!(accepted(response.status()), "Warm-up failed with {}", response.status());
+    }
+
+    let killed = cluster.kill_engine_pod().await.expect("Failed to kill Engine pod");
+    println!("✓ Killed Engine pod {}", killed);
+
+    let (mut requests, mut failures) = (0usize, 0usize);
+    let start = Instant::now();
+    while start.elapsed() < TRAFFIC_WINDOW {
+        requests += 1;
+        match fixture.call_server_evaluate(&jwt, "document:k8s", "viewer", "user:alice").await {
+            Ok(response) if accepted(response.status()) => {},
+            _ => failures += 1,
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+    }
+    let availability = (requests - failures) as f64 / requests as f64;
+
+    cluster
+        .wait_for_engine_rollout(ROLLOUT_BUDGET)
+        .await
+        .expect("Engine deployment did not replace the killed pod");
+    let pods = cluster.engine_pods().await.expect("Failed to list Engine pods");
+    assert!(
+        pods.iter().all(|pod| pod.name != killed),
+        "Killed pod {} is still listed: {:?}",
+        killed,
+        pods
+    );
+    println!("✓ Deployment healed to {} ready replicas", replicas);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    assert!(
+        availability >= MIN_AVAILABILITY,
+        "Availability {:.2}% after losing {} fell below {:.0}% ({} of {} requests failed)",
+        availability * 100.0,
+        killed,
+        MIN_AVAILABILITY * 100.0,
+        failures,
+        requests
+    );
+    println!(
+        "✓ {:.2}% of {} requests served while a replica was down",
+        availability * 100.0,
+        requests
+    );
+}
+
+#[tokio::test]
+async fn test_rolling_restart_keeps_serving_in_flight_tokens() {
+    let Some(cluster) = K8sCluster::connect().await else {
+        outcome::skip(
+            "Skipping rolling restart test - no Kubernetes cluster with an Engine deployment",
+        );
+        return;
+    };
+    let replicas = cluster.engine_replicas().await.expect("Failed to read Engine replicas");
+    if replicas < 2 {
+        outcome::skip(format!(
+            "Skipping rolling restart test - Engine runs {} replica(s), need 2",
+            replicas
+        ));
+        return;
+    }
+    let max_error_rate = std::env::var("INFERADB_ROLLOUT_MAX_ERROR_RATE")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_ROLLOUT_MAX_ERROR_RATE);
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let writer = fixture.generate_jwt(None, &["inferadb.write"]).expect("Failed to generate JWT");
+    fixture
+        .engine(&writer)
+        .write_relationships(&[Relationship::new("document:rollout", "viewer", "user:alice")])
+        .await
+        .expect("Failed to write relationship");
+
+    // Issued before the restart: every new pod must validate it from a cold cache
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    engine
+        .evaluate("document:rollout", "viewer", "user:alice")
+        .await
+        .expect("Evaluate failed before the restart")
+        .assert_allowed("before the restart");
+
+    let load = LoadRunner::new(8).start(move || {
+        let engine = engine.clone();
+        async move { engine.evaluate("document:rollout", "viewer", "user:alice").await }
+    });
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+
+    let before: Vec<String> = cluster
+        .engine_pods()
+        .await
+        .expect("Failed to list Engine pods")
+        .into_iter()
+        .map(|pod| pod.name)
+        .collect();
+    cluster.rolling_restart_engine().await.expect("Failed to start rolling restart");
+    println!("✓ Rolling restart started across {} replicas", replicas);
+    let rollout = cluster.wait_for_engine_rollout(ROLLING_RESTART_BUDGET).await;
+
+    // Keep the load on the new pods for a moment before judging
+    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+    let stats = load.stop().await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    rollout.expect("Rolling restart did not finish");
+    let after = cluster.engine_pods().await.expect("Failed to list Engine pods");
+    assert!(
+        after.iter().all(|pod| !before.contains(&pod.name)),
+        "Old pods survived the rolling restart: {:?}",
+        after
+    );
+    println!(
+        "  {} requests: {} ok, {} auth failures, {} errors {:?}",
+        stats.requests, stats.successes, stats.auth_failures, stats.errors, stats.failure_statuses
+    );
+    assert_eq!(
+        stats.auth_failures, 0,
+        "Pre-restart JWT was refused during the rollout: {:?}",
+        stats.sample_errors
+    );
+    assert!(
+        stats.failure_rate() <= max_error_rate,
+        "Error rate {:.2}% exceeded {:.2}% during the rollout: {:?}",
+        stats.failure_rate() * 100.0,
+        max_error_rate * 100.0,
+        stats.sample_errors
+    );
+    println!(
+        "✓ {:.2}% errors across {} requests while every replica restarted",
+        stats.failure_rate() * 100.0,
+        stats.requests
+    );
+}
+
+#[tokio::test]
+async fn test_engine_replicas_share_build_and_config() {
+    let Some(cluster) = K8sCluster::connect().await else {
+        outcome::skip(
+            "Skipping config drift test - no Kubernetes cluster with an Engine deployment",
+        );
+        return;
+    };
+    let pods: Vec<_> = cluster
+        .engine_pods()
+        .await
+        .expect("Failed to list Engine pods")
+        .into_iter()
+        .filter(|pod| pod.ready && !pod.terminating)
+        .collect();
+    if pods.len() < 2 {
+        outcome::skip(format!(
+            "Skipping config drift test - {} ready Engine replica(s), need 2",
+            pods.len()
+        ));
+        return;
+    }
+
+    let mut fingerprints = Vec::new();
+    for pod in &pods {
+        let fingerprint = cluster
+            .engine_pod_fingerprint(&pod.name)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to fingerprint {}: {:#}", pod.name, e));
+        fingerprints.push((pod.name.clone(), fingerprint));
+    }
+    if fingerprints.iter().all(|(_, fingerprint)| fingerprint.is_empty()) {
+        outcome::skip("Skipping config drift test - Engine exports no build info or TTL metrics");
+        return;
+    }
+
+    let drift = fingerprint_drift(&fingerprints);
+    assert!(
+        drift.is_empty(),
+        "Engine replicas disagree on {} setting(s):\n{}",
+        drift.len(),
+        drift
+            .iter()
+            .map(|(key, values)| format!("  {}: {:?}", key, values))
+            .collect::<Vec<_>>()
+            .join("\n")
+    );
+    println!(
+        "✓ {} Engine replicas agree on {} build/config values",
+        pods.len(),
+        fingerprints[0].1.len()
+    );
+}
+
+/// Time one evaluate sent straight to an Engine pod, in milliseconds
+async fn timed_pod_evaluate(fixture: &TestFixture, url: &str, jwt: &str) -> reqwest::Result<f64> {
+    let body = serde_json::json!({
+        "evaluations": [{
+            "subject": "user:alice",
+            "resource": "document:prewarm",
+            "permission": "viewer",
+            "trace": false
+        }]
+    });
+    let started = Instant::now();
+    let response =
+        fixture.ctx.client.post(url).bearer_auth(jwt).json(&body).send_recorded().await?;
+    let elapsed = started.elapsed().as_secs_f64() * 1000.0;
+    assert!(
+        matches!(response.status(), StatusCode::OK | StatusCode::NOT_FOUND),
+        "Evaluate on {} failed with {}",
+        url,
+        response.status()
+    );
+    Ok(elapsed)
+}
+
+/// One fresh replica's latencies: its first request for the pre-restart certificate, the median
+/// first request of the control clients, and its warm p99
+struct PodLatency {
+    pod: String,
+    first_ms: f64,
+    cold_ms: f64,
+    warm_p99: f64,
+}
+
+impl PodLatency {
+    /// Closer to a hit than to a miss, so a replica that had to fetch the certificate shows;
+    /// `None` where a miss is not measurably slower than a hit on this pod
+    fn limit_ms(&self) -> Option<f64> {
+        (self.cold_ms > self.warm_p99).then(|| self.warm_p99 + (self.cold_ms - self.warm_p99) / 2.0)
+    }
+}
+
+/// Measure one fresh replica; `prewarmed` must not have been sent to it yet
+async fn measure_pod(
+    fixture: &TestFixture,
+    pod: &str,
+    url: &str,
+    prewarmed: &str,
+    controls: &[String],
+) -> PodLatency {
+    let time = async |jwt: &str| {
+        timed_pod_evaluate(fixture, url, jwt)
+            .await
+            .unwrap_or_else(|e| panic!("Failed to reach {}: {}", url, e))
+    };
+    let first_ms = time(prewarmed).await;
+
+    let mut cold_ms = Vec::with_capacity(controls.len());
+    for jwt in controls {
+        cold_ms.push(time(jwt).await);
+    }
+    cold_ms.sort_by(f64::total_cmp);
+
+    let mut warm_ms = Vec::with_capacity(WARM_SAMPLES);
+    for _ in 0..WARM_SAMPLES {
+        warm_ms.push(time(prewarmed).await);
+    }
+    warm_ms.sort_by(f64::total_cmp);
+
+    PodLatency {
+        pod: pod.to_string(),
+        first_ms,
+        cold_ms: scenario::percentile(&cold_ms, 0.50).unwrap_or_default(),
+        warm_p99: scenario::percentile(&warm_ms, 0.99).unwrap_or_default(),
+    }
+}
+
+#[tokio::test]
+async fn test_prewarmed_replicas_serve_first_requests_warm() {
+    if !EnvProfile::current().auth_cache_prewarmed {
+        outcome::skip("Skipping pre-warm test - Engine is not expected to pre-warm its auth cache");
+        return;
+    }
+    let Some(cluster) = K8sCluster::connect().await else {
+        outcome::skip("Skipping pre-warm test - no Kubernetes cluster with an Engine deployment");
+        return;
+    };
+
+    // The certificate exists before the restart, so startup pre-warming can load it
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let path = format!("/{}/evaluate", fixture.ctx.api_version);
+
+    // JWTs cannot go through the pod proxy, so the runner must reach pod IPs
+    let pods = cluster.engine_pods().await.expect("Failed to list Engine pods");
+    let reachable = match pods.iter().find(|pod| pod.ready && !pod.terminating) {
+        Some(pod) => match cluster.engine_pod_url(pod, &path) {
+            Some(url) => timed_pod_evaluate(&fixture, &url, &jwt).await.is_ok(),
+            None => false,
+        },
+        None => false,
+    };
+    if !reachable {
+        outcome::skip("Skipping pre-warm test - Engine pod IPs are not reachable from the runner");
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    cluster.rolling_restart_engine().await.expect("Failed to start rolling restart");
+    cluster
+        .wait_for_engine_rollout(ROLLING_RESTART_BUDGET)
+        .await
+        .expect("Rolling restart did not finish");
+    let pods: Vec<_> = cluster
+        .engine_pods()
+        .await
+        .expect("Failed to list Engine pods")
+        .into_iter()
+        .filter(|pod| pod.ready && !pod.terminating)
+        .collect();
+    println!("✓ {} fresh replicas ready", pods.len());
+
+    // Controls: clients whose certificates no replica could have loaded at startup
+    let mut controls = Vec::with_capacity(COLD_CONTROLS);
+    let mut control_jwts = Vec::with_capacity(COLD_CONTROLS);
+    for _ in 0..COLD_CONTROLS {
+        let client = fixture.add_client().await.expect("Failed to create control client");
+        control_jwts
+            .push(client.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT"));
+        controls.push(client);
+    }
+
+    let mut latencies = Vec::with_capacity(pods.len());
+    for pod in &pods {
+        let url = cluster
+            .engine_pod_url(pod, &path)
+            .unwrap_or_else(|| panic!("Ready pod {} has no IP", pod.name));
+        let latency = measure_pod(&fixture, &pod.name, &url, &jwt, &control_jwts).await;
+        println!(
+            "  {}: first {:.1} ms, control miss {:.1} ms, warm p99 {:.1} ms",
+            latency.pod, latency.first_ms, latency.cold_ms, latency.warm_p99
+        );
+        latencies.push(latency);
+    }
+
+    let control = fixture.control();
+    for client in &controls {
+        let _ = control.delete_client(fixture.org_id, client.client_id).await;
+    }
+    fixture.cleanup().await.expect("Failed to cleanup");
+
+    let measured: Vec<_> =
+        latencies.iter().filter(|latency| latency.limit_ms().is_some()).collect();
+    if measured.is_empty() {
+        outcome::skip(
+            "Skipping pre-warm test - on no replica is a cache miss measurably slower than a hit",
+        );
+        return;
+    }
+    let slow: Vec<String> = measured
+        .iter()
+        .filter_map(|latency| {
+            let limit_ms = latency.limit_ms()?;
+            (latency.first_ms > limit_ms).then(|| {
+                format!(
+                    "{}: first {:.1} ms > {:.1} ms (warm p99 {:.1} ms, control miss {:.1} ms)",
+                    latency.pod, latency.first_ms, limit_ms, latency.warm_p99, latency.cold_ms
+                )
+            })
+        })
+        .collect();
+    assert!(
+        slow.is_empty(),
+        "{} of {} fresh replicas served the pre-restart certificate's first request cold:\n  {}",
+        slow.len(),
+        measured.len(),
+        slow.join("\n  ")
+    );
+    println!(
+        "✓ {} fresh replicas served their first request warm ({} without a measurable miss)",
+        measured.len(),
+        latencies.len() - measured.len()
+    );
+}
@@ -0,0 +1,112 @@
+// Latency Attribution Tests
+//
+// Uses Engine's `Server-Timing` (or `X-InferaDB-{Phase}-Ms`) headers to break evaluate latency
+// into auth, evaluate, and ledger phases, and asserts that token validation stays cheap once the
+// vault and key caches are warm, within the profile's budget (INFERADB_WARM_AUTH_BUDGET_MS).
+// Skipped when Engine does not emit timing headers.
+
+use reqwest::header::{HeaderMap, HeaderValue};
+
+use super::*;
+
+/// Evaluations sampled after warm-up
+const SAMPLES: usize = 20;
+
+/// Run `SAMPLES` evaluations after a warm-up call, or `None` if Engine reports no timing
+async fn sample_warm_timings(engine: &EngineClient) -> Option<Vec<Timed<EvaluateResponse>>> {
+    let warm_up = engine
+        .evaluate_timed("document:1", "viewer", "user:alice")
+        .await
+        .expect("Warm-up evaluation failed");
+    if warm_up.server_timing.is_empty() {
+        outcome::skip("Skipping latency attribution test - Engine emits no Server-Timing headers");
+        return None;
+    }
+
+    let mut samples = Vec::with_capacity(SAMPLES);
+    for _ in 0..SAMPLES {
+        samples.push(
+            engine
+                .evaluate_timed("document:1", "viewer", "user:alice")
+                .await
+                .expect("Evaluation failed"),
+        );
+    }
+    Some(samples)
+}
+
+#[tokio::test]
+async fn test_warm_cache_auth_overhead_within_budget() {
+    let fixture = SharedFixture::get().await;
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let Some(samples) = sample_warm_timings(&fixture.engine(&jwt)).await else {
+        return;
+    };
+
+    let mut auth_ms: Vec<f64> =
+        samples.iter().filter_map(|timed| timed.server_timing.get(ServerTiming::AUTH)).collect();
+    if auth_ms.is_empty() {
+        outcome::skip(format!(
+            "Skipping auth budget test - Server-Timing has no '{}' phase",
+            ServerTiming::AUTH
+        ));
+        return;
+    }
+    auth_ms.sort_by(f64::total_cmp);
+
+    let budget_ms = EnvProfile::current().warm_auth_budget_ms;
+    let p95 = scenario::percentile(&auth_ms, 0.95).expect("No auth samples");
+    println!("✓ Warm auth phase: p50 {:.2}ms, p95 {:.2}ms", auth_ms[auth_ms.len() / 2], p95);
+    assert!(
+        p95 <= budget_ms,
+        "Warm-cache auth p95 {:.2}ms exceeds {:.1}ms budget; samples: {:?}",
+        p95,
+        budget_ms,
+        auth_ms
+    );
+}
+
+#[tokio::test]
+async fn test_server_timing_within_round_trip() {
+    let fixture = SharedFixture::get().await;
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let Some(samples) = sample_warm_timings(&fixture.engine(&jwt)).await else {
+        return;
+    };
+
+    for timed in &samples {
+        let round_trip_ms = timed.elapsed.as_secs_f64() * 1000.0;
+        for phase in [ServerTiming::AUTH, ServerTiming::EVALUATE, ServerTiming::LEDGER] {
+            if let Some(duration) = timed.server_timing.get(phase) {
+                assert!(
+                    duration >= 0.0 && duration <= round_trip_ms,
+                    "'{}' phase {:.2}ms is outside the {:.2}ms round trip: {:?}",
+                    phase,
+                    duration,
+                    round_trip_ms,
+                    timed.server_timing
+                );
+            }
+        }
+    }
+    println!("✓ {} responses reported phases within their round trip", samples.len());
+}
+
+#[test]
+fn test_server_timing_header_parsing() {
+    let mut headers = HeaderMap::new();
+    headers.append(
+        "server-timing",
+        HeaderValue::from_static("auth;dur=0.8;desc=\"jwt\", evaluate;dur=2.5, cache;desc=hit"),
+    );
+    headers.append("server-timing", HeaderValue::from_static("Ledger;dur=\"1.25\""));
+    headers.append("x-inferadb-auth-ms", HeaderValue::from_static("0.2"));
+    headers.append("x-request-id", HeaderValue::from_static("abc"));
+
+    let timing = ServerTiming::from_headers(&headers);
+    assert_eq!(timing.get(ServerTiming::AUTH), Some(1.0), "auth is summed across both sources");
+    assert_eq!(timing.get(ServerTiming::EVALUATE), Some(2.5));
+    assert_eq!(timing.get(ServerTiming::LEDGER), Some(1.25), "names are case-insensitive");
+    assert_eq!(timing.get("cache"), None, "entries without dur are ignored");
+    assert!(ServerTiming::from_headers(&HeaderMap::new()).is_empty());
+}
@@ -44,25 +44,15 @@ async fn test_ledger_cache_invalidation_on_vault_update() {
         "description": format!("Updated at {}", Instant::now().elapsed().as_secs())
     });
 
-    let update_response =
-        fixture
-            .ctx
-            .client
-            .patch(fixture.ctx.control_url(&format!(
-                "/organizations/{}/vaults/{}",
-                fixture.org_id, fixture.vault_id
-            )))
-            .header("Authorization", format!("Bearer {}", fixture.session_id))
-            .json(&update_payload)
-            .send()
-            .await
-            .expect("Failed to update vault");
+    let update_result =
+        fixture.control().update_vault(fixture.org_id, fixture.vault_id, &update_payload).await;
 
-    if update_response.status() == StatusCode::METHOD_NOT_ALLOWED
-        || update_response.status() == StatusCode::NOT_FOUND
+    if let Err(e) = &update_result
+        && matches!(e.status(), Some(StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_FOUND))
     {
-        println!(
-            "⚠ Vault update endpoint not available, testing with certificate revocation instead"
+        outcome::skip(
+            "Skipping vault update invalidation test - endpoint not available; certificate \
+             revocation covers invalidation instead",
         );
         fixture.cleanup().await.expect("Failed to cleanup");
         return;
@@ -101,13 +91,16 @@ async fn test_ledger_cache_invalidation_on_vault_update() {
                 elapsed.as_millis()
             );
         } else {
-            println!(
-                "⚠ Cache invalidation took {}ms (target: <1000ms) - may be acceptable depending on environment",
+            outcome::warn(format!(
+                "Cache invalidation took {}ms (target: <1000ms) - may be acceptable depending on \
+                 environment",
                 elapsed.as_millis()
-            );
+            ));
         }
     } else {
-        println!("⚠ Could not verify cache invalidation - requests may not reflect updated state");
+        outcome::warn(
+            "Could not verify cache invalidation - requests may not reflect updated state",
+        );
     }
 
     fixture.cleanup().await.expect("Failed to cleanup");
@@ -135,17 +128,8 @@ async fn test_relationship_write_cache_consistency() {
         .expect("Failed to check relationship");
 
     // Parse response to verify relationship doesn't exist
-    let before_body: serde_json::Value =
-        check_before.json().await.expect("Failed to parse response");
-    let allowed_before = before_body
-        .get("results")
-        .and_then(|r| r.as_array())
-        .and_then(|arr| arr.first())
-        .and_then(|r| r.get("decision"))
-        .and_then(|d| d.as_str())
-        .unwrap_or("DENY");
-
-    assert_eq!(allowed_before, "DENY", "Relationship should not exist before write");
+    let before: EvaluateResponse = check_before.json().await.expect("Failed to parse response");
+    before.assert_denied("Relationship should not exist before write");
     println!("✓ Verified relationship doesn't exist before write");
 
     // Write the relationship
@@ -163,7 +147,7 @@ async fn test_relationship_write_cache_consistency() {
         .post(fixture.ctx.engine_url("/relationships/write"))
         .header("Authorization", format!("Bearer {}", jwt))
         .json(&write_body)
-        .send()
+        .send_recorded()
         .await
         .expect("Failed to write relationship");
 
@@ -186,17 +170,9 @@ async fn test_relationship_write_cache_consistency() {
             .await
             .expect("Failed to check relationship");
 
-        let after_body: serde_json::Value =
-            check_after.json().await.expect("Failed to parse response");
-        let allowed_after = after_body
-            .get("results")
-            .and_then(|r| r.as_array())
-            .and_then(|arr| arr.first())
-            .and_then(|r| r.get("decision"))
-            .and_then(|d| d.as_str())
-            .unwrap_or("DENY");
-
-        if allowed_after == "ALLOW" {
+        let after: EvaluateResponse = check_after.json().await.expect("Failed to parse response");
+
+        if after.allowed() {
             read_success = true;
             println!("✓ Relationship visible after {}ms", start.elapsed().as_millis());
             break;
@@ -239,21 +215,12 @@ async fn test_certificate_revocation_invalidates_cache() {
     let start = Instant::now();
 
     // Revoke the certificate via Control
-    let revoke_response = fixture
-        .ctx
-        .client
-        .delete(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}/certificates/{}",
-            fixture.org_id, fixture.client_id, fixture.cert_id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
+    if let Err(e) = fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, fixture.cert_id)
         .await
-        .expect("Failed to revoke certificate");
-
-    if !revoke_response.status().is_success() {
-        let error_body = revoke_response.text().await.unwrap_or_default();
-        println!("⚠ Certificate revocation failed: {}, skipping test", error_body);
+    {
+        outcome::skip(format!("Skipping certificate revocation test - revocation failed: {}", e));
         return;
     }
 
@@ -290,24 +257,18 @@ async fn test_certificate_revocation_invalidates_cache() {
             println!("✅ Cache invalidation within target ({}ms <= 1000ms)", time.as_millis());
         } else {
             // Log but don't fail - network conditions may vary
-            println!("⚠ Cache invalidation slower than target ({}ms > 1000ms)", time.as_millis());
+            outcome::warn(format!(
+                "Cache invalidation slower than target ({}ms > 1000ms)",
+                time.as_millis()
+            ));
         }
     } else {
         // This is acceptable for certain deployment configurations (high cache TTL)
-        println!("⚠ Cache invalidation not detected within 2s - cache TTL may be higher");
+        outcome::warn("Cache invalidation not detected within 2s - cache TTL may be higher");
     }
 
     // Cleanup (certificate already deleted, client/vault remain)
-    let _ = fixture
-        .ctx
-        .client
-        .delete(fixture.ctx.control_url(&format!(
-            "/organizations/{}/clients/{}",
-            fixture.org_id, fixture.client_id
-        )))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
-        .await;
+    let _ = fixture.control().delete_client(fixture.org_id, fixture.client_id).await;
 }
 
 /// Test concurrent writes from multiple sources maintain cache consistency.
@@ -347,7 +308,7 @@ async fn test_concurrent_write_cache_consistency() {
                 .post(&engine_url)
                 .header("Authorization", format!("Bearer {}", jwt))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to write");
 
@@ -360,7 +321,7 @@ async fn test_concurrent_write_cache_consistency() {
     for handle in handles {
         let (i, success) = handle.await.expect("Task failed");
         if !success {
-            println!("⚠ Writer {} failed", i);
+            outcome::warn(format!("Writer {} failed", i));
             all_succeeded = false;
         }
     }
@@ -381,16 +342,8 @@ async fn test_concurrent_write_cache_consistency() {
             .await
             .expect("Failed to check relationship");
 
-        let body: serde_json::Value = check_response.json().await.expect("Failed to parse");
-        let decision = body
-            .get("results")
-            .and_then(|r| r.as_array())
-            .and_then(|arr| arr.first())
-            .and_then(|r| r.get("decision"))
-            .and_then(|d| d.as_str())
-            .unwrap_or("DENY");
-
-        assert_eq!(decision, "ALLOW", "Concurrent write {} should be visible", i);
+        let body: EvaluateResponse = check_response.json().await.expect("Failed to parse");
+        body.assert_allowed(&format!("Concurrent write {} should be visible", i));
     }
 
     println!("✓ All {} concurrent writes visible in cache", num_writers);
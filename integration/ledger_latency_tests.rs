@@ -0,0 +1,113 @@
+// Ledger Latency Tests
+//
+// Measures how long a Control mutation takes to reach Ledger: each mutation writes a unique marker
+// into the fixture's vault description, and its latency runs from sending the update to the
+// arrival of the WatchBlocks block carrying that marker (see `ledger`). Mutations are sent one at a
+// time, so each block is awaited before the next change. The p99 over `MUTATIONS` must stay within
+// INFERADB_LEDGER_P99_MS. Skipped unless INFERADB_LEDGER_URL is set and Control supports vault
+// updates; a first update whose block never arrives within `BLOCK_TIMEOUT` fails the test.
+
+use std::time::Instant;
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Mutations measured
+const MUTATIONS: usize = 100;
+
+/// p99 Control→Ledger latency allowed, unless INFERADB_LEDGER_P99_MS overrides it
+const DEFAULT_P99_MS: f64 = 1000.0;
+
+/// How long a single block may take before the mutation counts as lost
+const BLOCK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// A fresh marker, and the vault update that writes it
+fn marked_update() -> (String, serde_json::Value) {
+    let marker = format!("ledger-latency-{}", Uuid::new_v4().simple());
+    let update = serde_json::json!({ "description": marker });
+    (marker, update)
+}
+
+#[tokio::test]
+async fn test_control_to_ledger_block_latency() {
+    let Some(ledger) = LedgerClient::connect() else { return };
+    let p99_slo_ms = std::env::var("INFERADB_LEDGER_P99_MS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_P99_MS);
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let mut blocks = ledger.watch_blocks().await.expect("Failed to watch Ledger blocks");
+
+    // One unmeasured round trip, to check blocks can be matched at all
+    let (marker, update) = marked_update();
+    match control.update_vault(fixture.org_id, fixture.vault_id, &update).await {
+        Ok(()) => {},
+        Err(e)
+            if matches!(
+                e.status(),
+                Some(StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_FOUND)
+            ) =>
+        {
+            outcome::skip(format!(
+                "Skipping Ledger latency test - vault update not available: {}",
+                e
+            ));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to update vault: {}", e),
+    }
+    let first = blocks.next_containing(&marker, BLOCK_TIMEOUT).await.expect("Ledger stream failed");
+    assert!(first.is_some(), "No Ledger block carried the vault update within {:?}", BLOCK_TIMEOUT);
+    println!("✓ Control mutations are matched to their Ledger blocks");
+
+    let mut latencies_ms = Vec::with_capacity(MUTATIONS);
+    let mut control_ms = Vec::with_capacity(MUTATIONS);
+    for i in 0..MUTATIONS {
+        let (marker, update) = marked_update();
+        let started = Instant::now();
+        control
+            .update_vault(fixture.org_id, fixture.vault_id, &update)
+            .await
+            .unwrap_or_else(|e| panic!("Mutation {} failed: {}", i, e));
+        control_ms.push(started.elapsed().as_secs_f64() * 1000.0);
+        let block = blocks
+            .next_containing(&marker, BLOCK_TIMEOUT)
+            .await
+            .expect("Ledger stream failed")
+            .unwrap_or_else(|| {
+                panic!("Mutation {} never reached Ledger within {:?}", i, BLOCK_TIMEOUT)
+            });
+        latencies_ms.push(block.received_at.duration_since(started).as_secs_f64() * 1000.0);
+    }
+
+    latencies_ms.sort_by(f64::total_cmp);
+    control_ms.sort_by(f64::total_cmp);
+    let at = |sorted: &[f64], quantile| scenario::percentile(sorted, quantile).unwrap_or_default();
+    println!(
+        "  Control→Ledger over {} mutations: p50 {:.1} ms, p99 {:.1} ms, max {:.1} ms",
+        MUTATIONS,
+        at(&latencies_ms, 0.50),
+        at(&latencies_ms, 0.99),
+        at(&latencies_ms, 1.0)
+    );
+    println!(
+        "  Control response: p50 {:.1} ms, p99 {:.1} ms",
+        at(&control_ms, 0.50),
+        at(&control_ms, 0.99)
+    );
+
+    let p99 = at(&latencies_ms, 0.99);
+    assert!(
+        p99 <= p99_slo_ms,
+        "p99 Control→Ledger latency {:.1} ms exceeds the {:.0} ms SLO",
+        p99,
+        p99_slo_ms
+    );
+    println!("✓ p99 within the {:.0} ms SLO", p99_slo_ms);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
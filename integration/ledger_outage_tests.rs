@@ -0,0 +1,96 @@
+// Ledger Outage Tests
+//
+// Cuts Engine off from Ledger (via the `LEDGER` chaos hook) and presents a JWT whose certificate
+// Engine has never seen, so the key lookup cannot be served from cache. Engine must fail fast
+// with a degraded status instead of hanging until the client times out, and accept the token once
+// Ledger is reachable again. Skipped unless INFERADB_CHAOS_LEDGER_DISRUPT/RESTORE are set.
+
+use std::time::Instant;
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Statuses Engine may use to report that Ledger is unavailable
+const DEGRADED_STATUSES: &[StatusCode] =
+    &[StatusCode::SERVICE_UNAVAILABLE, StatusCode::GATEWAY_TIMEOUT];
+
+/// How quickly Engine must answer while Ledger is unreachable
+const FAIL_FAST_BUDGET: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// How long Engine may take to recover once Ledger returns
+const RECOVERY_BUDGET: std::time::Duration = std::time::Duration::from_secs(30);
+
+#[tokio::test]
+async fn test_uncached_key_fails_fast_during_ledger_outage() {
+    let Some(ledger) = ChaosHook::from_env("LEDGER") else {
+        outcome::skip("Skipping Ledger outage test - INFERADB_CHAOS_LEDGER_* not configured");
+        return;
+    };
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    // Create the certificate while Ledger is up, but never use it before the outage
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("Outage Certificate"));
+    let cert = fixture
+        .control()
+        .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+        .await
+        .expect("Failed to create certificate");
+    let signing_key = keys::from_base64(&cert.private_key).expect("Invalid private key");
+    let jwt = fixture
+        .jwt()
+        .signed_by(&cert.certificate.kid, &signing_key)
+        .encode()
+        .expect("Failed to encode JWT");
+
+    let outage = ledger.disrupt().expect("Failed to block Ledger");
+    println!("✓ Ledger blocked");
+
+    let start = Instant::now();
+    let result = tokio::time::timeout(
+        FAIL_FAST_BUDGET,
+        fixture.call_server_evaluate(&jwt, "document:1", "viewer", "user:alice"),
+    )
+    .await;
+    let elapsed = start.elapsed();
+
+    outage.restore().expect("Failed to unblock Ledger");
+    println!("✓ Ledger unblocked");
+
+    let response = result
+        .unwrap_or_else(|_| {
+            panic!("Engine did not answer within {:?} during the outage", FAIL_FAST_BUDGET)
+        })
+        .expect("Failed to call server");
+    assert!(
+        DEGRADED_STATUSES.contains(&response.status()),
+        "Expected {:?} for an uncached key during the outage, got {} after {:?}",
+        DEGRADED_STATUSES,
+        response.status(),
+        elapsed
+    );
+    println!("✓ Engine answered {} in {:?} during the outage", response.status(), elapsed);
+
+    // Recovery: the same token is accepted once Ledger is back
+    let recovery_start = Instant::now();
+    let mut last_status = None;
+    while recovery_start.elapsed() < RECOVERY_BUDGET {
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        if response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND {
+            println!("✓ Engine recovered in {:?}", recovery_start.elapsed());
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        }
+        last_status = Some(response.status());
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    }
+
+    panic!(
+        "Engine did not accept the new certificate within {:?} of Ledger returning (last: {:?})",
+        RECOVERY_BUDGET, last_status
+    );
+}
@@ -0,0 +1,109 @@
+// Metric Label Tests
+//
+// Offline checks of the `/metrics` parser and tenant label discovery, plus a live check that the
+// labels resolved for a fixture select exactly that fixture's series.
+
+use inferadb_integration_tests::metrics::Label;
+use reqwest::StatusCode;
+
+use super::*;
+
+const EXPOSITION: &str = r#"# HELP infera_requests_total Requests served
+# TYPE infera_requests_total counter
+infera_requests_total{org="org:7",client_id="12",vault_id="42",status="200"} 5
+infera_requests_total{org="org:7",client_id="12",vault_id="42",status="403"} 1
+infera_requests_total{org="org:7",client_id="120",vault_id="420",status="200"} 9
+infera_requests_total{org="org:7",client_id="12",vault_id="42",path="/a\"b"} 2
+infera_up 1
+"#;
+
+fn label(name: &str, value: &str) -> Label {
+    Label { name: name.to_string(), value: value.to_string() }
+}
+
+#[test]
+fn test_parse_exposition_with_labels() {
+    let snapshot = MetricsSnapshot::parse(EXPOSITION);
+    assert_eq!(snapshot.samples.len(), 5, "Comments must be skipped, samples kept");
+
+    let escaped = &snapshot.samples[3];
+    assert_eq!(escaped.labels.get("path").map(String::as_str), Some("/a\"b"));
+    assert_eq!(escaped.value, 2.0);
+
+    let bare = &snapshot.samples[4];
+    assert_eq!(bare.name, "infera_up");
+    assert!(bare.labels.is_empty());
+}
+
+#[test]
+fn test_resolve_tenant_labels() {
+    let snapshot = MetricsSnapshot::parse(EXPOSITION);
+    let labels = TenantLabels::resolve(&snapshot, 7, 12, 42);
+
+    assert_eq!(labels.org, Some(label("org", "org:7")), "Prefixed org value should resolve");
+    assert_eq!(labels.client, Some(label("client_id", "12")));
+    assert_eq!(labels.vault, Some(label("vault_id", "42")));
+    assert_eq!(snapshot.resolve_label("vault", 2), None, "IDs must not match as substrings");
+
+    // Only series for client 12 / vault 42, not 120 / 420
+    let client = labels.client.as_ref().expect("client label");
+    let vault = labels.vault.as_ref().expect("vault label");
+    assert_eq!(snapshot.sum("infera_requests_total", &[client, vault]), 8.0);
+    assert_eq!(snapshot.sum("infera_requests_total", &[]), 17.0);
+}
+
+#[tokio::test]
+async fn test_resolved_labels_select_fixture_series() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+
+    // Make sure the fixture's series exist before discovering labels
+    for _ in 0..5 {
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        assert!(
+            response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND,
+            "Evaluate failed: {}",
+            response.status()
+        );
+    }
+
+    let Some(labels) = fixture.metric_labels().await else {
+        outcome::skip("Skipping label discovery test - metrics not available");
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    let Some(vault) = labels.vault else {
+        outcome::skip("Skipping label discovery test - metrics carry no vault label");
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    println!("✓ Vault {} is labelled {}=\"{}\"", fixture.vault_id, vault.name, vault.value);
+
+    // Every series carrying the label belongs to this vault, and our traffic shows up under it
+    let before = MetricsSnapshot::fetch(&fixture.ctx).await.expect("Metrics disappeared");
+    for _ in 0..10 {
+        fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+    }
+    let after = MetricsSnapshot::fetch(&fixture.ctx).await.expect("Metrics disappeared");
+
+    let grown: Vec<&str> = after
+        .samples
+        .iter()
+        .filter(|s| s.name.ends_with("_total") && s.labels.get(&vault.name) == Some(&vault.value))
+        .filter(|s| after.sum(&s.name, &[&vault]) > before.sum(&s.name, &[&vault]))
+        .map(|s| s.name.as_str())
+        .collect();
+    assert!(
+        !grown.is_empty(),
+        "No counter labelled with the fixture's vault grew after 10 requests"
+    );
+    println!("✓ Vault-scoped counters grew: {:?}", grown);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,172 @@
+// Multi-Client Tests
+//
+// Tests for two clients with separate certificates bound to the same vault: metrics attribution
+// per client, revoking one client's certificate without affecting the other, and concurrent writes
+// from both identities landing in the shared vault.
+
+use std::sync::Arc;
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Sum of `_total` counters carrying the client's label, or `None` if metrics are unavailable
+///
+/// A client with no labelled series yet counts zero.
+async fn client_request_count(ctx: &TestContext, client_id: i64) -> Option<f64> {
+    let snapshot = MetricsSnapshot::fetch(ctx).await?;
+    let Some(label) = snapshot.resolve_label("client", client_id) else { return Some(0.0) };
+    Some(
+        snapshot
+            .samples
+            .iter()
+            .filter(|sample| sample.name.ends_with("_total"))
+            .filter(|sample| sample.labels.get(&label.name) == Some(&label.value))
+            .map(|sample| sample.value)
+            .sum(),
+    )
+}
+
+#[tokio::test]
+async fn test_metrics_attributed_per_client() {
+    let fixtures = multi_client_fixture().await.expect("Failed to create multi-client fixture");
+    let [primary, secondary] = fixtures.clients();
+
+    let before = (
+        client_request_count(&primary.ctx, primary.client_id).await,
+        client_request_count(&secondary.ctx, secondary.client_id).await,
+    );
+    let (Some(primary_before), Some(secondary_before)) = before else {
+        outcome::skip("Skipping per-client attribution test - metrics not available");
+        fixtures.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    // Only the secondary client sends traffic
+    let jwt = secondary.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    for _ in 0..20 {
+        let response = secondary
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        assert!(
+            response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND,
+            "Secondary client request failed: {}",
+            response.status()
+        );
+    }
+
+    let primary_delta =
+        client_request_count(&primary.ctx, primary.client_id).await.unwrap_or(primary_before)
+            - primary_before;
+    let secondary_delta =
+        client_request_count(&secondary.ctx, secondary.client_id).await.unwrap_or(secondary_before)
+            - secondary_before;
+
+    if secondary_delta == 0.0 && secondary_before == 0.0 {
+        outcome::warn("Metrics carry no client label, skipping per-client attribution check");
+    } else {
+        assert!(
+            secondary_delta >= 20.0,
+            "Secondary client's counters should grow by at least 20, grew by {}",
+            secondary_delta
+        );
+        assert_eq!(primary_delta, 0.0, "Idle primary client was attributed secondary's traffic");
+        println!("✓ 20 requests attributed to the secondary client only");
+    }
+
+    fixtures.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_revoking_one_client_leaves_the_other_working() {
+    let fixtures = multi_client_fixture().await.expect("Failed to create multi-client fixture");
+    let [primary, secondary] = fixtures.clients();
+
+    primary
+        .control()
+        .revoke_certificate(secondary.org_id, secondary.client_id, secondary.cert_id)
+        .await
+        .expect("Failed to revoke secondary certificate");
+
+    let secondary_jwt =
+        secondary.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let mut revoked = false;
+    for _ in 0..10 {
+        let response = secondary
+            .call_server_evaluate(&secondary_jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        if response.status() == StatusCode::UNAUTHORIZED {
+            revoked = true;
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    }
+    assert!(revoked, "Secondary client should be rejected after its certificate is revoked");
+
+    let primary_jwt =
+        primary.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let response = primary
+        .call_server_evaluate(&primary_jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+    assert!(
+        response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND,
+        "Primary client must be unaffected by the secondary's revocation, got {}",
+        response.status()
+    );
+    println!("✓ Revoking the secondary certificate left the primary client working");
+
+    fixtures.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_concurrent_writes_from_distinct_clients() {
+    let fixtures = multi_client_fixture().await.expect("Failed to create multi-client fixture");
+
+    let engines: Arc<Vec<EngineClient>> = Arc::new(
+        fixtures
+            .clients()
+            .iter()
+            .map(|fixture| {
+                let jwt = fixture
+                    .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+                    .expect("Failed to generate JWT");
+                fixture.engine(&jwt)
+            })
+            .collect(),
+    );
+
+    // 100 writes, alternating between the two identities
+    let mut handles = Vec::new();
+    for i in 0..100 {
+        let engines = Arc::clone(&engines);
+        handles.push(tokio::spawn(async move {
+            let relationship =
+                Relationship::new(format!("document:multi-{}", i), "viewer", "user:alice");
+            engines[i % 2].write_relationships(&[relationship]).await
+        }));
+    }
+    for handle in handles {
+        handle.await.expect("Task failed").expect("Concurrent write failed");
+    }
+
+    // Each client sees every write, including the other's
+    for (reader, engine) in engines.iter().enumerate() {
+        for i in 0..100 {
+            let allowed = engine
+                .check(&format!("document:multi-{}", i), "viewer", "user:alice")
+                .await
+                .expect("Failed to evaluate");
+            assert!(
+                allowed,
+                "Client {} cannot see document:multi-{} in the shared vault",
+                reader, i
+            );
+        }
+    }
+    println!("✓ 100 concurrent writes from two clients visible to both");
+
+    fixtures.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,169 @@
+// Multi-Vault Token Tests
+//
+// Where Engine accepts tokens scoped to more than one vault (experimental `multi-vault-tokens`), a
+// `vault_id` claim may list vaults or be `*` for every vault in the token's organization, and each
+// request names the vault it targets with `X-Vault-Id`. Every vault here holds the same tuple, so
+// the decision alone shows which vaults a token can reach: access must span exactly the claimed
+// vaults, and nothing outside them can be read or written.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// The tuple every vault is seeded with
+fn spanned() -> Relationship {
+    Relationship::new("document:spanned", "viewer", "user:alice")
+}
+
+/// An extra vault in `fixture`'s organization, seeded with `spanned`
+async fn seeded_vault(fixture: &TestFixture) -> i64 {
+    let vault_req = CreateVaultRequest {
+        name: naming::entity_name("Multi-Vault Token Vault"),
+        organization_id: fixture.org_id,
+    };
+    let vault_id = fixture
+        .control()
+        .create_vault(fixture.org_id, &vault_req)
+        .await
+        .expect("Failed to create vault")
+        .vault
+        .id;
+    seed(fixture, vault_id).await;
+    vault_id
+}
+
+async fn seed(fixture: &TestFixture, vault_id: i64) {
+    let jwt = fixture
+        .generate_jwt(Some(vault_id), &["inferadb.write"])
+        .expect("Failed to generate single-vault JWT");
+    fixture
+        .engine(&jwt)
+        .write_relationships(&[spanned()])
+        .await
+        .unwrap_or_else(|e| panic!("Failed to seed vault {}: {}", vault_id, e));
+}
+
+/// Whether `jwt` can read `vault_id`; panics on anything but an answer or a 403
+async fn can_read(fixture: &TestFixture, jwt: &str, vault_id: i64) -> bool {
+    let resource = spanned().resource;
+    match fixture.engine(jwt).for_vault(vault_id).evaluate(&resource, "viewer", "user:alice").await
+    {
+        Ok(response) => {
+            response.assert_allowed(&format!("seeded tuple in vault {}", vault_id));
+            true
+        },
+        Err(e) if e.status() == Some(StatusCode::FORBIDDEN) => false,
+        Err(e) => panic!("Unexpected response reading vault {}: {}", vault_id, e),
+    }
+}
+
+/// Whether `jwt` can write to `vault_id`; panics on anything but success or a 403
+async fn can_write(fixture: &TestFixture, jwt: &str, vault_id: i64) -> bool {
+    let relationship = Relationship::new("document:written", "viewer", "user:mallory");
+    match fixture.engine(jwt).for_vault(vault_id).write_relationships(&[relationship]).await {
+        Ok(()) => true,
+        Err(e) if e.status() == Some(StatusCode::FORBIDDEN) => false,
+        Err(e) => panic!("Unexpected response writing vault {}: {}", vault_id, e),
+    }
+}
+
+/// Whether `document:written` exists in `vault_id`, read with a single-vault token
+async fn was_written(fixture: &TestFixture, vault_id: i64) -> bool {
+    let jwt = fixture
+        .generate_jwt(Some(vault_id), &["inferadb.check"])
+        .expect("Failed to generate single-vault JWT");
+    fixture
+        .engine(&jwt)
+        .check("document:written", "viewer", "user:mallory")
+        .await
+        .expect("Failed to check vault")
+}
+
+#[tokio::test]
+async fn test_vault_list_token_spans_exactly_claimed_vaults() {
+    if !ExperimentalFeatures::require(Feature::MultiVaultTokens) {
+        return;
+    }
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    seed(&fixture, fixture.vault_id).await;
+    let vault_b = seeded_vault(&fixture).await;
+    let unclaimed = seeded_vault(&fixture).await;
+
+    let jwt = fixture
+        .jwt()
+        .vaults(&[fixture.vault_id, vault_b])
+        .scopes(&["inferadb.check", "inferadb.write"])
+        .encode()
+        .expect("Failed to encode JWT");
+
+    for vault_id in [fixture.vault_id, vault_b] {
+        assert!(
+            can_read(&fixture, &jwt, vault_id).await,
+            "Claimed vault {} not readable",
+            vault_id
+        );
+        assert!(
+            can_write(&fixture, &jwt, vault_id).await,
+            "Claimed vault {} not writable",
+            vault_id
+        );
+        assert!(was_written(&fixture, vault_id).await, "Write to vault {} did not land", vault_id);
+    }
+    println!("✓ Both listed vaults readable and writable");
+
+    assert!(!can_read(&fixture, &jwt, unclaimed).await, "Unlisted vault readable");
+    assert!(!can_write(&fixture, &jwt, unclaimed).await, "Unlisted vault writable");
+    assert!(!was_written(&fixture, unclaimed).await, "Rejected write reached the unlisted vault");
+    println!("✓ Vault in the same organization but not listed rejected with 403");
+
+    let control = fixture.control();
+    for vault_id in [vault_b, unclaimed] {
+        let _ = control.delete_vault(fixture.org_id, vault_id).await;
+    }
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_wildcard_token_spans_own_organization_only() {
+    if !ExperimentalFeatures::require(Feature::MultiVaultTokens) {
+        return;
+    }
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let other_org = TestFixture::create().await.expect("Failed to create second organization");
+    seed(&fixture, fixture.vault_id).await;
+    seed(&other_org, other_org.vault_id).await;
+    let vault_b = seeded_vault(&fixture).await;
+
+    let jwt = fixture
+        .jwt()
+        .all_vaults()
+        .scopes(&["inferadb.check", "inferadb.write"])
+        .encode()
+        .expect("Failed to encode JWT");
+
+    for vault_id in [fixture.vault_id, vault_b] {
+        assert!(can_read(&fixture, &jwt, vault_id).await, "Org vault {} not readable", vault_id);
+    }
+    println!("✓ Wildcard token reads every vault in its organization");
+
+    // Created after the token was minted, and still covered by it
+    let vault_c = seeded_vault(&fixture).await;
+    assert!(can_read(&fixture, &jwt, vault_c).await, "Vault created after minting not readable");
+    println!("✓ Vault created after the token was minted is covered");
+
+    let foreign = other_org.vault_id;
+    assert!(!can_read(&fixture, &jwt, foreign).await, "Another organization's vault readable");
+    assert!(!can_write(&fixture, &jwt, foreign).await, "Another organization's vault writable");
+    assert!(
+        !was_written(&other_org, foreign).await,
+        "Rejected write reached the other organization"
+    );
+    println!("✓ Another organization's vault rejected with 403");
+
+    let control = fixture.control();
+    for vault_id in [vault_b, vault_c] {
+        let _ = control.delete_vault(fixture.org_id, vault_id).await;
+    }
+    other_org.cleanup().await.expect("Failed to cleanup second organization");
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
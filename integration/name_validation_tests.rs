@@ -0,0 +1,265 @@
+// Name Validation Tests
+//
+// Vaults, clients, and certificates are all named by the caller, and Control applies one set of
+// rules to every name:
+//   - a name is 1 to 255 characters once trimmed (INFERADB_NAME_MAX_CHARS overrides the limit)
+//   - markup is plain text: stored and returned exactly as sent, or rejected, but never rewritten
+//   - certificate names are unique within their client (409), and free to repeat across clients
+// A rejected name is answered with 400 or 422 and a body that names the offending field, so a form
+// can point at it. Every resource is created by the fixture's organization and removed afterwards.
+
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use super::*;
+
+/// Longest name Control accepts, unless INFERADB_NAME_MAX_CHARS overrides it
+const DEFAULT_MAX_NAME_CHARS: usize = 255;
+
+/// Names a browser would run as script if rendered unescaped
+const MARKUP_NAMES: [&str; 3] =
+    ["<script>alert(1)</script>", "<img src=x onerror=alert(1)>", "\"><b>bold</b> & 'quoted'"];
+
+fn max_name_chars() -> usize {
+    std::env::var("INFERADB_NAME_MAX_CHARS")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_NAME_CHARS)
+}
+
+/// The named resources Control creates
+#[derive(Debug, Clone, Copy)]
+enum Named {
+    Vault,
+    Client,
+    Certificate,
+}
+
+const NAMED: [Named; 3] = [Named::Vault, Named::Client, Named::Certificate];
+
+impl Named {
+    /// Create one named `name`, returning its ID and the name Control reports for it
+    async fn create(self, fixture: &TestFixture, name: &str) -> ApiResult<(i64, String)> {
+        let control = fixture.control();
+        let name = name.to_string();
+        match self {
+            Self::Vault => {
+                let req = CreateVaultRequest { name, organization_id: fixture.org_id };
+                let vault = control.create_vault(fixture.org_id, &req).await?.vault;
+                Ok((vault.id, vault.name))
+            },
+            Self::Client => {
+                let client = control
+                    .create_client(fixture.org_id, &CreateClientRequest { name })
+                    .await?
+                    .client;
+                Ok((client.id, client.name))
+            },
+            Self::Certificate => {
+                let req = CreateCertificateRequest::new(name);
+                let certificate = control
+                    .create_certificate(fixture.org_id, fixture.client_id, &req)
+                    .await?
+                    .certificate;
+                Ok((certificate.id, certificate.name))
+            },
+        }
+    }
+
+    /// The name Control returns when `id` is read back
+    async fn read_name(self, fixture: &TestFixture, id: i64) -> Option<String> {
+        let control = fixture.control();
+        match self {
+            Self::Vault => control.get_vault(fixture.org_id, id).await.ok().map(|vault| vault.name),
+            Self::Client => control
+                .list_clients(fixture.org_id)
+                .await
+                .ok()?
+                .clients
+                .into_iter()
+                .find(|client| client.id == id)
+                .map(|client| client.name),
+            Self::Certificate => control
+                .list_certificates(fixture.org_id, fixture.client_id)
+                .await
+                .ok()?
+                .certificates
+                .into_iter()
+                .find(|certificate| certificate.id == id)
+                .map(|certificate| certificate.name),
+        }
+    }
+
+    async fn delete(self, fixture: &TestFixture, id: i64) {
+        let control = fixture.control();
+        let _ = match self {
+            Self::Vault => control.delete_vault(fixture.org_id, id).await,
+            Self::Client => control.delete_client(fixture.org_id, id).await,
+            Self::Certificate => {
+                control.revoke_certificate(fixture.org_id, fixture.client_id, id).await
+            },
+        };
+    }
+}
+
+/// Whether a validation error body points at `field`, in any of the shapes Control's errors take:
+/// `{"field": "name"}` entries (also `path`, `param`, or a JSON pointer), or an `errors`, `fields`,
+/// or `details` object keyed by field
+fn names_field(body: &Value, field: &str) -> bool {
+    match body {
+        Value::Object(entries) => entries.iter().any(|(key, value)| {
+            let points_at = ["field", "path", "param", "pointer"].contains(&key.as_str())
+                && value
+                    .as_str()
+                    .is_some_and(|value| value == field || value.trim_start_matches('/') == field);
+            let keyed = ["errors", "fields", "details"].contains(&key.as_str())
+                && value.get(field).is_some();
+            points_at || keyed || names_field(value, field)
+        }),
+        Value::Array(items) => items.iter().any(|item| names_field(item, field)),
+        _ => false,
+    }
+}
+
+/// Assert `result` is a validation error naming the `name` field
+fn assert_name_rejected(result: ApiResult<(i64, String)>, named: Named, case: &str) {
+    let error = match result {
+        Ok((id, _)) => panic!("{:?} with {} was accepted (id {})", named, case, id),
+        Err(e) => e,
+    };
+    assert!(
+        matches!(error.status(), Some(StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY)),
+        "{:?} with {} should be a validation error, got {}",
+        named,
+        case,
+        error
+    );
+    let body: Value =
+        error.body().and_then(|body| serde_json::from_str(body).ok()).unwrap_or_default();
+    assert!(
+        names_field(&body, "name"),
+        "{:?} with {} rejected without naming the field: {}",
+        named,
+        case,
+        error.body().unwrap_or_default()
+    );
+}
+
+#[tokio::test]
+async fn test_empty_names_rejected_with_field_detail() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    for named in NAMED {
+        for (case, name) in [("an empty name", ""), ("a whitespace-only name", "   \t")] {
+            assert_name_rejected(named.create(&fixture, name).await, named, case);
+        }
+        println!("✓ {:?}: empty and whitespace-only names rejected, naming the field", named);
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_name_length_limit() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let max = max_name_chars();
+
+    for named in NAMED {
+        // Multi-byte characters, so a limit counted in bytes shows up as a rejection
+        let longest = "é".repeat(max);
+        let (id, stored) = named
+            .create(&fixture, &longest)
+            .await
+            .unwrap_or_else(|e| panic!("{:?} with a {}-char name rejected: {}", named, max, e));
+        assert_eq!(stored, longest, "{:?} name at the limit was altered", named);
+        named.delete(&fixture, id).await;
+
+        for length in [max + 1, 1000.max(max + 1)] {
+            let case = format!("a {}-char name", length);
+            assert_name_rejected(named.create(&fixture, &"n".repeat(length)).await, named, &case);
+        }
+        println!(
+            "✓ {:?}: {}-char names accepted, longer ones rejected naming the field",
+            named, max
+        );
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_markup_names_never_rewritten() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    for named in NAMED {
+        for markup in MARKUP_NAMES {
+            match named.create(&fixture, markup).await {
+                Ok((id, stored)) => {
+                    let read = named.read_name(&fixture, id).await;
+                    named.delete(&fixture, id).await;
+                    assert_eq!(stored, markup, "{:?} name rewritten on create", named);
+                    assert_eq!(read.as_deref(), Some(markup), "{:?} name rewritten on read", named);
+                    println!("✓ {:?}: {:?} stored verbatim", named, markup);
+                },
+                Err(e) => {
+                    assert_name_rejected(Err(e), named, &format!("markup {:?}", markup));
+                    println!("✓ {:?}: {:?} rejected naming the field", named, markup);
+                },
+            }
+        }
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_duplicate_certificate_names_within_client() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let name = naming::entity_name("Duplicate Certificate");
+
+    let (first_id, _) = Named::Certificate
+        .create(&fixture, &name)
+        .await
+        .expect("Failed to create first certificate");
+
+    let duplicate = Named::Certificate.create(&fixture, &name).await;
+    let error = match duplicate {
+        Ok((id, _)) => panic!("Duplicate certificate name accepted within one client (id {})", id),
+        Err(e) => e,
+    };
+    assert_eq!(
+        error.status(),
+        Some(StatusCode::CONFLICT),
+        "Duplicate certificate name should conflict, got {}",
+        error
+    );
+    let body: Value =
+        error.body().and_then(|body| serde_json::from_str(body).ok()).unwrap_or_default();
+    assert!(
+        names_field(&body, "name"),
+        "Duplicate rejected without naming the field: {}",
+        error.body().unwrap_or_default()
+    );
+    println!("✓ Duplicate certificate name within a client rejected with 409, naming the field");
+
+    // The same name under another client is a different certificate
+    let other_client = control
+        .create_client(
+            fixture.org_id,
+            &CreateClientRequest { name: naming::entity_name("Duplicate Client") },
+        )
+        .await
+        .expect("Failed to create second client")
+        .client
+        .id;
+    let other = control
+        .create_certificate(fixture.org_id, other_client, &CreateCertificateRequest::new(&name))
+        .await;
+    let _ = control.delete_client(fixture.org_id, other_client).await;
+    other.expect("Same certificate name under another client should be accepted");
+    println!("✓ Same certificate name accepted under another client");
+
+    Named::Certificate.delete(&fixture, first_id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,215 @@
+// Ownership Transfer Tests
+//
+// Transfers an organization from the fixture's user to a second member. Afterwards the old owner
+// keeps admin access but loses what only an owner may do: deleting the organization and reading
+// billing. The organization's vaults, clients, and certificates are untouched by the change of
+// hands, and Engine keeps answering the existing client's JWTs throughout: a prober evaluates
+// continuously across the transfer and must never see a 401 or a server error. Only the owner can
+// transfer, and only to a member. Skipped if Control cannot add members or transfer ownership.
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use reqwest::StatusCode;
+
+use super::*;
+
+const OWNER_ROLE: &str = "owner";
+
+/// Role the second user holds before the transfer, and the old owner after it
+const ADMIN_ROLE: &str = "admin";
+
+/// Gap between the prober's evaluates
+const PROBE_INTERVAL: tokio::time::Duration = tokio::time::Duration::from_millis(100);
+
+/// A second user, registered and added to the fixture's organization as an admin; `None` (after
+/// recording a skip) if Control cannot add members directly
+async fn add_admin(fixture: &TestFixture) -> Option<(i64, ControlClient)> {
+    let req = RegisterRequest {
+        name: naming::entity_name("Successor"),
+        email: naming::email("successor"),
+        password: FIXTURE_PASSWORD.to_string(),
+        accept_tos: true,
+    };
+    let registered = ControlClient::new(fixture.ctx.clone())
+        .register(&req)
+        .await
+        .expect("Failed to register second user");
+    let successor = ControlClient::with_session(fixture.ctx.clone(), registered.session_id);
+
+    let member = AddMemberRequest { user_id: registered.user_id, role: ADMIN_ROLE.to_string() };
+    match fixture.control().add_member(fixture.org_id, &member).await {
+        Ok(_) => Some((registered.user_id, successor)),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping ownership test - cannot add members: {}", e));
+            let _ = successor.delete_user(registered.user_id).await;
+            None
+        },
+        Err(e) => panic!("Failed to add second user: {}", e),
+    }
+}
+
+/// The role `control`'s user holds in `org_id`
+async fn role_in(control: &ControlClient, org_id: i64) -> Option<String> {
+    control
+        .list_organizations()
+        .await
+        .expect("Failed to list organizations")
+        .organizations
+        .into_iter()
+        .find(|org| org.id == org_id)
+        .map(|org| org.role)
+}
+
+#[track_caller]
+fn assert_forbidden(result: ApiResult<impl std::fmt::Debug>, what: &str) {
+    match result {
+        Ok(response) => panic!("{} should be forbidden, got {:?}", what, response),
+        Err(e) => assert_eq!(e.status(), Some(StatusCode::FORBIDDEN), "{}: {}", what, e),
+    }
+}
+
+#[tokio::test]
+async fn test_ownership_transfer_demotes_old_owner() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let owner = fixture.control();
+    let Some((successor_id, successor)) = add_admin(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    assert_eq!(role_in(&owner, fixture.org_id).await.as_deref(), Some(OWNER_ROLE));
+    let billing_supported = match owner.get_billing(fixture.org_id).await {
+        Ok(_) => true,
+        Err(e) if e.is_unsupported() => false,
+        Err(e) => panic!("Owner should read billing: {}", e),
+    };
+
+    // Probe Engine with the existing client's JWT for the whole transfer
+    let jwt = fixture.jwt().encode().expect("Failed to encode JWT");
+    let engine = fixture.engine(&jwt);
+    let stop = Arc::new(AtomicBool::new(false));
+    let prober = tokio::spawn({
+        let stop = stop.clone();
+        async move {
+            let mut answers = 0;
+            let mut failures = Vec::new();
+            while !stop.load(Ordering::Relaxed) {
+                match engine.evaluate("document:1", "viewer", "user:alice").await {
+                    Ok(_) => {},
+                    Err(e) if e.status() == Some(StatusCode::NOT_FOUND) => {},
+                    Err(e) => failures.push(e.to_string()),
+                }
+                answers += 1;
+                tokio::time::sleep(PROBE_INTERVAL).await;
+            }
+            (answers, failures)
+        }
+    });
+
+    let req = TransferOwnershipRequest { user_id: successor_id };
+    match owner.transfer_ownership(fixture.org_id, &req).await {
+        Ok(()) => {},
+        Err(e) if e.is_unsupported() => {
+            stop.store(true, Ordering::Relaxed);
+            let _ = prober.await;
+            outcome::skip(format!("Skipping ownership test - transfer not available: {}", e));
+            let _ = successor.delete_user(successor_id).await;
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to transfer ownership: {}", e),
+    }
+    println!("✓ Ownership transferred");
+
+    assert_eq!(role_in(&successor, fixture.org_id).await.as_deref(), Some(OWNER_ROLE));
+    assert_eq!(
+        role_in(&owner, fixture.org_id).await.as_deref(),
+        Some(ADMIN_ROLE),
+        "Old owner should stay on as an admin"
+    );
+    println!("✓ Roles swapped");
+
+    assert_forbidden(owner.delete_organization(fixture.org_id).await, "Old owner deleting the org");
+    if billing_supported {
+        assert_forbidden(owner.get_billing(fixture.org_id).await, "Old owner reading billing");
+        successor.get_billing(fixture.org_id).await.expect("New owner should read billing");
+    }
+    println!("✓ Old owner lost owner-only operations");
+
+    // Everything the organization holds carries on
+    successor.get_vault(fixture.org_id, fixture.vault_id).await.expect("Vault should survive");
+    let clients = successor.list_clients(fixture.org_id).await.expect("Failed to list clients");
+    assert!(
+        clients.clients.iter().any(|client| client.id == fixture.client_id && client.is_active),
+        "Client should survive the transfer, still active"
+    );
+    owner.list_vaults(fixture.org_id).await.expect("Old owner should keep admin access to vaults");
+    println!("✓ Vaults and clients unaffected");
+
+    stop.store(true, Ordering::Relaxed);
+    let (answers, failures) = prober.await.expect("Prober panicked");
+    assert!(
+        failures.is_empty(),
+        "Engine refused the client's JWT {} of {} times across the transfer: {:?}",
+        failures.len(),
+        answers,
+        failures
+    );
+    println!("✓ Engine answered all {} evaluates across the transfer", answers);
+
+    // Only the new owner can delete the organization now
+    fixture.cleanup().await.expect("Failed to cleanup");
+    successor.delete_organization(fixture.org_id).await.expect("New owner should delete the org");
+    let _ = successor.delete_user(successor_id).await;
+}
+
+#[tokio::test]
+async fn test_only_owner_transfers_to_members() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let owner = fixture.control();
+    let Some((successor_id, successor)) = add_admin(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let req = TransferOwnershipRequest { user_id: successor_id };
+    match successor.transfer_ownership(fixture.org_id, &req).await {
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping ownership test - transfer not available: {}", e));
+            let _ = successor.delete_user(successor_id).await;
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        result => assert_forbidden(result, "An admin transferring ownership to themselves"),
+    }
+    assert_eq!(role_in(&owner, fixture.org_id).await.as_deref(), Some(OWNER_ROLE));
+    println!("✓ Admin cannot take ownership");
+
+    // A user outside the organization cannot be handed it
+    let outsider = TestFixture::create().await.expect("Failed to create outsider fixture");
+    let req = TransferOwnershipRequest { user_id: outsider.user_id };
+    match owner.transfer_ownership(fixture.org_id, &req).await {
+        Ok(()) => panic!("Ownership must not be transferred to a non-member"),
+        Err(e) => assert!(
+            matches!(
+                e.status(),
+                Some(
+                    StatusCode::BAD_REQUEST
+                        | StatusCode::NOT_FOUND
+                        | StatusCode::UNPROCESSABLE_ENTITY
+                )
+            ),
+            "Transfer to a non-member should be rejected, got {}",
+            e
+        ),
+    }
+    assert_eq!(role_in(&owner, fixture.org_id).await.as_deref(), Some(OWNER_ROLE));
+    assert_eq!(role_in(&outsider.control(), fixture.org_id).await, None);
+    println!("✓ Transfer to a non-member rejected");
+
+    outsider.cleanup().await.expect("Failed to cleanup outsider");
+    let _ = successor.delete_user(successor_id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,319 @@
+// Pagination Tests
+//
+// Fills one organization with enough clients (150) to span several pages of Control's client list
+// and walks it page by page, following `next_cursor`. Every client must come back exactly once,
+// and no page may exceed the requested limit. The walk is repeated while clients are deleted
+// between pages: a deleted client may or may not be listed, but it must never appear twice, and
+// no client that stayed must be skipped. The organization list is walked the same way. Skipped if
+// Control ignores the page limit.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use reqwest::StatusCode;
+use tokio::task::JoinSet;
+
+use super::*;
+
+/// Clients created in the test organization
+const CLIENTS: usize = 150;
+
+/// Organizations created for the organization list walk
+const ORGANIZATIONS: usize = 12;
+
+/// Items per page requested
+const PAGE_SIZE: u32 = 25;
+
+/// Every `DELETE_EVERY`th client is deleted during the concurrent walk
+const DELETE_EVERY: usize = 5;
+
+/// Concurrent create and delete requests
+const CONCURRENCY: usize = 10;
+
+/// Stop following cursors after this many pages; a cursor that never ends is a bug
+const MAX_PAGES: usize = 100;
+
+/// How often each ID was listed across every page of a walk
+fn occurrences(pages: &[Vec<i64>]) -> BTreeMap<i64, usize> {
+    let mut counts = BTreeMap::new();
+    for id in pages.iter().flatten() {
+        *counts.entry(*id).or_insert(0) += 1;
+    }
+    counts
+}
+
+#[track_caller]
+fn assert_pages_within_limit(pages: &[Vec<i64>], limit: u32) {
+    for (number, page) in pages.iter().enumerate() {
+        assert!(
+            page.len() <= limit as usize,
+            "Page {} has {} items, more than the limit of {}",
+            number + 1,
+            page.len(),
+            limit
+        );
+    }
+}
+
+/// Create `count` clients in the fixture's organization, `CONCURRENCY` at a time
+async fn create_clients(fixture: &TestFixture, count: usize) -> Vec<i64> {
+    let mut ids = Vec::with_capacity(count);
+    let mut tasks = JoinSet::new();
+    for i in 0..count {
+        if tasks.len() >= CONCURRENCY
+            && let Some(result) = tasks.join_next().await
+        {
+            ids.push(result.expect("Create task panicked"));
+        }
+        let control = fixture.control();
+        let org_id = fixture.org_id;
+        tasks.spawn(async move {
+            let req =
+                CreateClientRequest { name: naming::entity_name(&format!("Page Client {}", i)) };
+            control.create_client(org_id, &req).await.expect("Failed to create client").client.id
+        });
+    }
+    while let Some(result) = tasks.join_next().await {
+        ids.push(result.expect("Create task panicked"));
+    }
+    ids
+}
+
+/// One page of the fixture's client list: its IDs and the cursor of the next page
+async fn client_page(fixture: &TestFixture, cursor: Option<String>) -> (Vec<i64>, Option<String>) {
+    let mut page = PageRequest::new(PAGE_SIZE);
+    page.cursor = cursor;
+    let response = fixture
+        .control()
+        .list_clients_page(fixture.org_id, &page)
+        .await
+        .expect("Failed to list clients");
+    (
+        response.clients.iter().map(|client| client.id).collect(),
+        next_cursor(response.pagination.as_ref()),
+    )
+}
+
+/// Assert the first page honours the limit and, when `existing` items can't fit on it, points to a
+/// second one; `false` (after recording a skip) if they all fit, leaving nothing to page through
+fn paginates(first: &[i64], cursor: &Option<String>, existing: usize) -> bool {
+    assert!(
+        first.len() <= PAGE_SIZE as usize,
+        "First page holds {} items for a limit of {}",
+        first.len(),
+        PAGE_SIZE
+    );
+    if existing < PAGE_SIZE as usize {
+        outcome::skip(format!(
+            "Skipping pagination test - only {} items for a limit of {}",
+            existing, PAGE_SIZE
+        ));
+        return false;
+    }
+    assert!(
+        cursor.is_some(),
+        "First page of {} items ends without a cursor, though {} exist",
+        first.len(),
+        existing
+    );
+    true
+}
+
+#[tokio::test]
+async fn test_client_list_pages_cover_every_client_once() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let mut expected: BTreeSet<i64> = create_clients(&fixture, CLIENTS).await.into_iter().collect();
+    expected.insert(fixture.client_id);
+    println!("✓ Created {} clients", CLIENTS);
+
+    let (first, mut cursor) = client_page(&fixture, None).await;
+    if !paginates(&first, &cursor, expected.len()) {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+    let mut pages = vec![first];
+    while let Some(next) = cursor.take() {
+        assert!(pages.len() < MAX_PAGES, "Cursor still not exhausted after {} pages", MAX_PAGES);
+        let (page, following) = client_page(&fixture, Some(next)).await;
+        pages.push(page);
+        cursor = following;
+    }
+    println!("✓ Walked {} pages of up to {}", pages.len(), PAGE_SIZE);
+
+    assert_pages_within_limit(&pages, PAGE_SIZE);
+    let counts = occurrences(&pages);
+    let duplicated: Vec<_> = counts.iter().filter(|(_, n)| **n > 1).collect();
+    assert!(duplicated.is_empty(), "Clients listed more than once: {:?}", duplicated);
+    let listed: BTreeSet<i64> = counts.keys().copied().collect();
+    let missing: Vec<_> = expected.difference(&listed).collect();
+    assert!(missing.is_empty(), "{} clients never listed: {:?}", missing.len(), missing);
+    let unexpected: Vec<_> = listed.difference(&expected).collect();
+    assert!(unexpected.is_empty(), "Clients from elsewhere listed: {:?}", unexpected);
+    println!("✓ All {} clients listed exactly once", expected.len());
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_client_list_pages_stable_under_concurrent_deletion() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let created = create_clients(&fixture, CLIENTS).await;
+    let deleted: BTreeSet<i64> = created.iter().step_by(DELETE_EVERY).copied().collect();
+    let mut kept: BTreeSet<i64> =
+        created.iter().filter(|id| !deleted.contains(id)).copied().collect();
+    kept.insert(fixture.client_id);
+    println!("✓ Created {} clients; {} will be deleted mid-walk", CLIENTS, deleted.len());
+
+    let (first, mut cursor) = client_page(&fixture, None).await;
+    if !paginates(&first, &cursor, CLIENTS + 1) {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    // Delete while the walk continues; the deletions are finished before the third page
+    let mut deleter = Some({
+        let control = fixture.control();
+        let org_id = fixture.org_id;
+        let deleted = deleted.clone();
+        tokio::spawn(async move {
+            let mut tasks = JoinSet::new();
+            for client_id in deleted {
+                if tasks.len() >= CONCURRENCY
+                    && let Some(result) = tasks.join_next().await
+                {
+                    result.expect("Delete task panicked");
+                }
+                let control = control.clone();
+                tasks.spawn(async move {
+                    control.delete_client(org_id, client_id).await.expect("Failed to delete client")
+                });
+            }
+            while let Some(result) = tasks.join_next().await {
+                result.expect("Delete task panicked");
+            }
+        })
+    });
+
+    let mut pages = vec![first];
+    while let Some(next) = cursor.take() {
+        assert!(pages.len() < MAX_PAGES, "Cursor still not exhausted after {} pages", MAX_PAGES);
+        if pages.len() == 2
+            && let Some(deleter) = deleter.take()
+        {
+            deleter.await.expect("Deleter panicked");
+            println!("✓ {} clients deleted mid-walk", deleted.len());
+        }
+        let (page, following) = client_page(&fixture, Some(next)).await;
+        pages.push(page);
+        cursor = following;
+    }
+    if let Some(deleter) = deleter {
+        deleter.await.expect("Deleter panicked");
+    }
+    println!("✓ Walked {} pages during deletion", pages.len());
+
+    assert_pages_within_limit(&pages, PAGE_SIZE);
+    let counts = occurrences(&pages);
+    let duplicated: Vec<_> = counts.iter().filter(|(_, n)| **n > 1).collect();
+    assert!(duplicated.is_empty(), "Clients listed more than once: {:?}", duplicated);
+    let skipped: Vec<_> = kept.iter().filter(|id| !counts.contains_key(id)).collect();
+    assert!(
+        skipped.is_empty(),
+        "{} clients that were never deleted were skipped: {:?}",
+        skipped.len(),
+        skipped
+    );
+    let seen_deleted = deleted.iter().filter(|id| counts.contains_key(id)).count();
+    println!(
+        "✓ Surviving clients listed exactly once; {} of {} deleted clients were seen",
+        seen_deleted,
+        deleted.len()
+    );
+
+    // Once the walk is over, a fresh one must not list the deleted clients at all
+    let mut listed = BTreeSet::new();
+    let mut cursor = None;
+    for _ in 0..MAX_PAGES {
+        let (page, following) = client_page(&fixture, cursor).await;
+        listed.extend(page);
+        cursor = following;
+        if cursor.is_none() {
+            break;
+        }
+    }
+    let lingering: Vec<_> = deleted.intersection(&listed).collect();
+    assert!(lingering.is_empty(), "Deleted clients still listed: {:?}", lingering);
+    assert_eq!(listed, kept, "Fresh walk should list exactly the surviving clients");
+    println!("✓ Fresh walk lists exactly the {} surviving clients", kept.len());
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_organization_list_pages_cover_every_organization_once() {
+    const ORG_PAGE_SIZE: u32 = 5;
+
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let mut created = Vec::new();
+    for _ in 0..ORGANIZATIONS {
+        let req = CreateOrganizationRequest { name: naming::entity_name("Page Organization") };
+        match control.create_organization(&req).await {
+            Ok(response) => created.push(response.organization.id),
+            Err(e)
+                if e.is_unsupported()
+                    || matches!(
+                        e.status(),
+                        Some(StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS)
+                    ) =>
+            {
+                // Too few organizations to span pages means nothing to test
+                if created.len() < ORG_PAGE_SIZE as usize {
+                    outcome::skip(format!(
+                        "Skipping organization pagination test - cannot create organizations: {}",
+                        e
+                    ));
+                    for org_id in created {
+                        let _ = control.delete_organization(org_id).await;
+                    }
+                    fixture.cleanup().await.expect("Failed to cleanup");
+                    return;
+                }
+                break;
+            },
+            Err(e) => panic!("Failed to create organization: {}", e),
+        }
+    }
+    let mut expected: BTreeSet<i64> = created.iter().copied().collect();
+    expected.insert(fixture.org_id);
+    println!("✓ Created {} organizations", created.len());
+
+    let mut pages = Vec::new();
+    let mut page = PageRequest::new(ORG_PAGE_SIZE);
+    loop {
+        assert!(pages.len() < MAX_PAGES, "Cursor still not exhausted after {} pages", MAX_PAGES);
+        let response =
+            control.list_organizations_page(&page).await.expect("Failed to list organizations");
+        pages.push(response.organizations.iter().map(|org| org.id).collect::<Vec<_>>());
+        match next_cursor(response.pagination.as_ref()) {
+            Some(cursor) => page = page.cursor(cursor),
+            None => break,
+        }
+    }
+    if pages.len() == 1 && pages[0].len() > ORG_PAGE_SIZE as usize {
+        outcome::skip("Skipping organization pagination test - Control ignores the page limit");
+    } else {
+        assert_pages_within_limit(&pages, ORG_PAGE_SIZE);
+        let counts = occurrences(&pages);
+        let duplicated: Vec<_> = counts.iter().filter(|(_, n)| **n > 1).collect();
+        assert!(duplicated.is_empty(), "Organizations listed more than once: {:?}", duplicated);
+        let listed: BTreeSet<i64> = counts.keys().copied().collect();
+        assert_eq!(listed, expected, "Pages should list exactly the user's organizations");
+        println!("✓ {} organizations listed exactly once over {} pages", listed.len(), pages.len());
+    }
+
+    for org_id in created {
+        let _ = control.delete_organization(org_id).await;
+    }
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,137 @@
+// Partial Failure Tests
+//
+// Pins Engine's contract for an evaluate batch in which one item is malformed. Either the batch
+// succeeds with a per-item error for the bad entry and decisions for the rest, or the whole batch
+// is rejected with a 400 naming the bad entry. Whichever Engine chooses, it must choose it
+// consistently, wherever the bad item sits in the batch.
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+const MALFORMED_RESOURCE: &str = "not a valid resource";
+
+/// How Engine treated a batch containing a malformed item
+#[derive(Debug, PartialEq, Eq)]
+enum Contract {
+    /// 200 with an `error` on the malformed item
+    PerItem,
+    /// 400 for the whole batch
+    AllOrNothing,
+}
+
+fn evaluation(resource: &str, subject: &str) -> serde_json::Value {
+    serde_json::json!({
+        "subject": subject,
+        "resource": resource,
+        "permission": "viewer",
+        "trace": false
+    })
+}
+
+/// Send a batch of `[allowed, denied]` with the malformed item inserted at `position`, and check
+/// the response against whichever contract Engine follows
+async fn evaluate_with_malformed_at(engine: &EngineClient, position: usize) -> Contract {
+    let mut evaluations = vec![
+        evaluation("document:partial", "user:alice"),
+        evaluation("document:partial", "user:bob"),
+    ];
+    let mut expected = vec!["ALLOW", "DENY"];
+    evaluations.insert(position, evaluation(MALFORMED_RESOURCE, "user:alice"));
+    expected.insert(position, "");
+
+    let response = engine
+        .request(Method::POST, "/evaluate")
+        .json(&serde_json::json!({ "evaluations": evaluations }))
+        .send_recorded()
+        .await
+        .expect("Failed to send batch");
+
+    match response.status() {
+        StatusCode::OK => {
+            let body: EvaluateResponse = response.json().await.expect("Failed to parse response");
+            assert_eq!(body.results.len(), 3, "Batch must answer every item: {:?}", body);
+
+            let malformed = &body.results[position];
+            assert!(
+                malformed.error.is_some(),
+                "Malformed item {} lacks an error: {:?}",
+                position,
+                body
+            );
+            assert_ne!(malformed.decision, "ALLOW", "Malformed item must never be allowed");
+
+            for (i, (result, expected)) in body.results.iter().zip(&expected).enumerate() {
+                if i != position {
+                    assert!(
+                        result.error.is_none(),
+                        "Valid item {} reported an error: {:?}",
+                        i,
+                        body
+                    );
+                    assert_eq!(
+                        result.decision, *expected,
+                        "Valid item {} got the wrong decision",
+                        i
+                    );
+                }
+            }
+            Contract::PerItem
+        },
+        StatusCode::BAD_REQUEST => {
+            let body = response.text().await.unwrap_or_default();
+            assert!(
+                body.contains(MALFORMED_RESOURCE) || body.contains(&format!("[{}]", position)),
+                "Batch rejection should identify the malformed item (index {}): {}",
+                position,
+                body
+            );
+            Contract::AllOrNothing
+        },
+        status => panic!("Batch with one malformed item returned {}", status),
+    }
+}
+
+async fn seeded_engine(fixture: &TestFixture) -> EngineClient {
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    engine
+        .write_relationships(&[Relationship::new("document:partial", "viewer", "user:alice")])
+        .await
+        .expect("Failed to write relationship");
+    engine
+}
+
+#[tokio::test]
+async fn test_malformed_item_reported_without_losing_valid_decisions() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let engine = seeded_engine(&fixture).await;
+
+    match evaluate_with_malformed_at(&engine, 1).await {
+        Contract::PerItem => println!("✓ Per-item error; valid items still decided"),
+        Contract::AllOrNothing => println!("✓ All-or-nothing: batch rejected with 400"),
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_partial_failure_contract_independent_of_position() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let engine = seeded_engine(&fixture).await;
+
+    let mut contracts = Vec::new();
+    for position in 0..=2 {
+        contracts.push(evaluate_with_malformed_at(&engine, position).await);
+    }
+    assert!(
+        contracts.windows(2).all(|pair| pair[0] == pair[1]),
+        "Partial failure handling depends on where the bad item sits: {:?}",
+        contracts
+    );
+    println!("✓ {:?} contract holds for the first, middle, and last item", contracts[0]);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
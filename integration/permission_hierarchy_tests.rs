@@ -0,0 +1,117 @@
+// Permission Hierarchy Tests
+//
+// Deploys `HIERARCHY_SCHEMA` (owner ⊂ edit ⊂ view) and checks that Engine's rewrite rules resolve
+// implied permissions: an owner passes edit and view checks, access never flows upward from a
+// lower role, and deleting the owner tuple removes every permission it implied.
+
+use super::*;
+
+const PERMISSIONS: [&str; 3] = ["own", "edit", "view"];
+
+/// Deploy the hierarchy schema and return an engine client able to write and check, or `None` if
+/// schema management is unavailable
+async fn hierarchy_engine(fixture: &TestFixture) -> Option<EngineClient> {
+    if !schema_validation_tests::deploy_schema(fixture, HIERARCHY_SCHEMA).await {
+        return None;
+    }
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    Some(fixture.engine(&jwt))
+}
+
+/// Assert which of `own`, `edit`, and `view` a subject holds on a document
+async fn assert_permissions(engine: &EngineClient, subject: &str, expected: [bool; 3]) {
+    for (permission, expected) in PERMISSIONS.into_iter().zip(expected) {
+        let allowed =
+            engine.check("document:plan", permission, subject).await.expect("Failed to evaluate");
+        assert_eq!(
+            allowed,
+            expected,
+            "{} on document:plan: expected {}, got {}",
+            permission,
+            if expected { "ALLOW" } else { "DENY" },
+            if allowed { "ALLOW" } else { "DENY" }
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_owner_implies_edit_and_view() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = hierarchy_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    engine
+        .write_relationships(&[Relationship::new("document:plan", "owner", "user:alice")])
+        .await
+        .expect("Failed to write relationship");
+
+    assert_permissions(&engine, "user:alice", [true, true, true]).await;
+    println!("✓ Owner holds own, edit, and view");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_implied_permissions_do_not_flow_upward() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = hierarchy_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    engine
+        .write_relationships(&[
+            Relationship::new("document:plan", "editor", "user:bob"),
+            Relationship::new("document:plan", "viewer", "user:carol"),
+        ])
+        .await
+        .expect("Failed to write relationships");
+
+    assert_permissions(&engine, "user:bob", [false, true, true]).await;
+    println!("✓ Editor holds edit and view, not own");
+
+    assert_permissions(&engine, "user:carol", [false, false, true]).await;
+    println!("✓ Viewer holds view only");
+
+    assert_permissions(&engine, "user:dave", [false, false, false]).await;
+    println!("✓ Unrelated subject holds nothing");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_revoking_owner_removes_implied_access() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(engine) = hierarchy_engine(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let owner = Relationship::new("document:plan", "owner", "user:alice");
+    engine
+        .write_relationships(std::slice::from_ref(&owner))
+        .await
+        .expect("Failed to write relationship");
+    assert_permissions(&engine, "user:alice", [true, true, true]).await;
+
+    engine
+        .delete_relationships(std::slice::from_ref(&owner))
+        .await
+        .expect("Failed to delete relationship");
+
+    // Allow for evaluation cache invalidation before requiring the full revocation
+    for _ in 0..10 {
+        if !engine.check("document:plan", "view", "user:alice").await.expect("Failed to evaluate") {
+            break;
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+    }
+    assert_permissions(&engine, "user:alice", [false, false, false]).await;
+    println!("✓ Deleting the owner tuple removed own, edit, and view");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,180 @@
+// Private Key Transport Tests
+//
+// Control generates each certificate's keypair and hands the private key to the caller exactly
+// once: in the response that created it, either directly or by rotation. Those responses must carry
+// `Cache-Control: no-store` so no proxy or browser keeps a copy, and every later read of the
+// certificate (the client's certificate listing, the certificate itself where Control serves it,
+// and the organization's client listing) must be free of key material. Reads are checked both for
+// private-key fields and for the key's encoded value appearing anywhere in the body.
+
+use reqwest::{Method, StatusCode, header::CACHE_CONTROL};
+use serde_json::Value;
+
+use super::*;
+
+/// A response's status, `Cache-Control` header, and JSON body
+struct RawResponse {
+    status: StatusCode,
+    cache_control: Option<String>,
+    body: Value,
+}
+
+async fn send(
+    control: &ControlClient,
+    method: Method,
+    path: &str,
+    body: Option<Value>,
+) -> RawResponse {
+    let mut request = control.request(method, path);
+    if let Some(body) = body {
+        request = request.json(&body);
+    }
+    let response = request.send_recorded().await.expect("Failed to call Control");
+    let status = response.status();
+    let cache_control =
+        response.headers().get(CACHE_CONTROL).and_then(|v| v.to_str().ok()).map(str::to_lowercase);
+    let body = response.json().await.unwrap_or(Value::Null);
+    RawResponse { status, cache_control, body }
+}
+
+/// Field names that only ever hold private key material; `d` is a JWK's private component
+fn is_private_field(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.contains("private") || name.contains("secret") || name == "d"
+}
+
+/// Every place in `body` holding a private-key field or one of `private_keys`' values
+fn key_material(body: &Value, private_keys: &[&str], path: &str, found: &mut Vec<String>) {
+    match body {
+        Value::Object(fields) => {
+            for (name, value) in fields {
+                let path = format!("{}.{}", path, name);
+                if is_private_field(name) && !value.is_null() {
+                    found.push(format!("{} field", path));
+                }
+                key_material(value, private_keys, &path, found);
+            }
+        },
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                key_material(item, private_keys, &format!("{}[{}]", path, index), found);
+            }
+        },
+        Value::String(text) => {
+            if private_keys.iter().any(|key| text.contains(key)) {
+                found.push(format!("{} holds a private key", path));
+            }
+        },
+        _ => {},
+    }
+}
+
+/// The private key from a creation or rotation response, after checking its transport
+fn issued_key(response: &RawResponse, operation: &str) -> String {
+    assert!(response.status.is_success(), "{} failed: {}", operation, response.status);
+    let key = response.body["private_key"]
+        .as_str()
+        .filter(|key| !key.is_empty())
+        .unwrap_or_else(|| panic!("{} response should include private_key", operation))
+        .to_string();
+    keys::from_base64(&key).expect("private_key should be a base64 Ed25519 key");
+
+    let cache_control = response
+        .cache_control
+        .as_deref()
+        .unwrap_or_else(|| panic!("{} response should carry Cache-Control", operation));
+    assert!(
+        cache_control.split(',').any(|directive| directive.trim() == "no-store"),
+        "{} response Cache-Control should include no-store, got {:?}",
+        operation,
+        cache_control
+    );
+    println!("✓ {} returned the private key with Cache-Control: {}", operation, cache_control);
+    key
+}
+
+/// Read the certificate back every way Control offers and assert none of it carries key material
+async fn assert_reads_keyless(fixture: &TestFixture, cert_id: i64, private_keys: &[&str]) {
+    let control = fixture.control();
+    let certificates =
+        format!("/organizations/{}/clients/{}/certificates", fixture.org_id, fixture.client_id);
+    let reads = [
+        (certificates.clone(), true),
+        (format!("{}/{}", certificates, cert_id), false),
+        (format!("/organizations/{}/clients", fixture.org_id), true),
+    ];
+
+    for (path, required) in reads {
+        let response = send(&control, Method::GET, &path, None).await;
+        if !required
+            && matches!(response.status, StatusCode::NOT_FOUND | StatusCode::METHOD_NOT_ALLOWED)
+        {
+            println!("  GET {} not served", path);
+            continue;
+        }
+        assert!(response.status.is_success(), "GET {} failed: {}", path, response.status);
+
+        let mut found = Vec::new();
+        key_material(&response.body, private_keys, "$", &mut found);
+        assert!(found.is_empty(), "GET {} returned key material: {:?}", path, found);
+        println!("✓ GET {} carries no key material", path);
+    }
+}
+
+#[tokio::test]
+async fn test_private_key_returned_only_at_creation() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let req = CreateCertificateRequest::new(naming::entity_name("Transport Certificate"));
+    let created = send(
+        &control,
+        Method::POST,
+        &format!("/organizations/{}/clients/{}/certificates", fixture.org_id, fixture.client_id),
+        Some(serde_json::to_value(&req).expect("Request serializes")),
+    )
+    .await;
+    let private_key = issued_key(&created, "Certificate creation");
+    let cert_id = created.body["certificate"]["id"]
+        .as_i64()
+        .expect("Creation response should include certificate.id");
+
+    assert_reads_keyless(&fixture, cert_id, &[&private_key]).await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_rotation_returns_new_private_key_once() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let req = RotateCertificateRequest {
+        name: naming::entity_name("Transport Rotation"),
+        grace_period_seconds: 0,
+    };
+    let rotated = send(
+        &control,
+        Method::POST,
+        &format!(
+            "/organizations/{}/clients/{}/certificates/{}/rotate",
+            fixture.org_id, fixture.client_id, fixture.cert_id
+        ),
+        Some(serde_json::to_value(&req).expect("Request serializes")),
+    )
+    .await;
+    let private_key = issued_key(&rotated, "Certificate rotation");
+    let mut found = Vec::new();
+    key_material(&rotated.body["rotated_from"], &[], "$.rotated_from", &mut found);
+    assert!(found.is_empty(), "Rotation returned the old certificate's key material: {:?}", found);
+    println!("✓ Rotated-out certificate returned without key material");
+
+    let cert_id = rotated.body["certificate"]["id"]
+        .as_i64()
+        .expect("Rotation response should include certificate.id");
+    // Neither the rotated-out certificate's creation key nor the new one may come back on a read
+    let creation_key = keys::to_base64(&fixture.signing_key);
+    assert_reads_keyless(&fixture, cert_id, &[&creation_key, &private_key]).await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
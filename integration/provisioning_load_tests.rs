@@ -0,0 +1,114 @@
+// Provisioning Load Tests
+//
+// A capacity check for Control before a load-testing session: provisions `FIXTURES` fixtures at
+// once, bypassing the fixture throttle, so registration, login, and vault, client, and
+// certificate creation all arrive as one burst. Reports throughput and per-step latency, and
+// fails if more than INFERADB_PROVISIONING_MAX_ERROR_RATE of the fixtures could not be provisioned.
+// Failures are grouped by the step and status that stopped them, so a rate limit reads differently
+// from a server error.
+
+use std::{collections::BTreeMap, time::Instant};
+
+use tokio::task::JoinSet;
+
+use super::*;
+
+/// Fixtures provisioned in the burst
+const FIXTURES: usize = 20;
+
+/// Fraction of fixtures allowed to fail, unless INFERADB_PROVISIONING_MAX_ERROR_RATE overrides it
+const DEFAULT_MAX_ERROR_RATE: f64 = 0.05;
+
+/// The step and status that stopped a provisioning attempt
+fn failure_kind(error: &anyhow::Error) -> String {
+    let status = error.chain().find_map(|cause| cause.downcast_ref::<ApiError>()?.status());
+    match status {
+        Some(status) => format!("{} ({})", error, status),
+        None => error.to_string(),
+    }
+}
+
+fn millis(duration: std::time::Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
+
+/// Print p50/p99/max of one step across the provisioned fixtures
+fn report_step(name: &str, durations: impl Iterator<Item = std::time::Duration>) {
+    let mut sorted: Vec<f64> = durations.map(millis).collect();
+    sorted.sort_by(f64::total_cmp);
+    let at = |quantile| scenario::percentile(&sorted, quantile).unwrap_or_default();
+    println!(
+        "  {:<12} p50 {:>7.1} ms   p99 {:>7.1} ms   max {:>7.1} ms",
+        name,
+        at(0.50),
+        at(0.99),
+        at(1.0)
+    );
+}
+
+#[tokio::test]
+async fn test_concurrent_fixture_provisioning() {
+    let max_error_rate = std::env::var("INFERADB_PROVISIONING_MAX_ERROR_RATE")
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(DEFAULT_MAX_ERROR_RATE);
+
+    let started = Instant::now();
+    let mut tasks = JoinSet::new();
+    for _ in 0..FIXTURES {
+        tasks.spawn(TestFixture::provision_unthrottled());
+    }
+    let mut provisioned = Vec::new();
+    let mut failures: BTreeMap<String, usize> = BTreeMap::new();
+    while let Some(result) = tasks.join_next().await {
+        match result.expect("Provisioning task panicked") {
+            Ok(fixture) => provisioned.push(fixture),
+            Err(e) => *failures.entry(failure_kind(&e)).or_default() += 1,
+        }
+    }
+    let elapsed = started.elapsed();
+
+    let failed = FIXTURES - provisioned.len();
+    let error_rate = failed as f64 / FIXTURES as f64;
+    println!(
+        "  {} of {} fixtures in {:.2}s ({:.2} fixtures/s, error rate {:.1}%)",
+        provisioned.len(),
+        FIXTURES,
+        elapsed.as_secs_f64(),
+        provisioned.len() as f64 / elapsed.as_secs_f64(),
+        error_rate * 100.0
+    );
+    let timings: Vec<ProvisionTimings> = provisioned.iter().map(|(_, timings)| *timings).collect();
+    if !timings.is_empty() {
+        report_step("register", timings.iter().map(|t| t.register));
+        report_step("login", timings.iter().map(|t| t.login));
+        report_step("vault", timings.iter().map(|t| t.vault));
+        report_step("client", timings.iter().map(|t| t.client));
+        report_step("certificate", timings.iter().map(|t| t.certificate));
+    }
+    for (kind, count) in &failures {
+        println!("  {} × {}", count, kind);
+    }
+
+    // Clean up before asserting, so a failed check does not leave the burst's fixtures behind
+    let mut cleanups = JoinSet::new();
+    for (fixture, _) in provisioned {
+        cleanups.spawn(async move { fixture.cleanup().await });
+    }
+    while let Some(result) = cleanups.join_next().await {
+        if let Err(e) = result.expect("Cleanup task panicked") {
+            eprintln!("Failed to cleanup fixture: {:#}", e);
+        }
+    }
+
+    assert!(
+        error_rate <= max_error_rate,
+        "{} of {} concurrent fixtures failed ({:.1}% > {:.1}%): {:?}",
+        failed,
+        FIXTURES,
+        error_rate * 100.0,
+        max_error_rate * 100.0,
+        failures
+    );
+    println!("✓ Control provisioned {} concurrent fixtures within the error budget", FIXTURES);
+}
@@ -0,0 +1,198 @@
+// Proxy Header Spoofing Tests
+//
+// X-Forwarded-For, X-Real-IP, and Forwarded are only trustworthy when a proxy the deployment
+// controls sets them. Unless the environment says services sit behind such a proxy
+// (`EnvProfile::forwarded_headers_trusted`), clients can forge them freely, so rate limits must not
+// be keyed on them and the audit log must not record them as the caller's address.
+//
+// The rate limit tests drive the runner's address into the limit and wait for it to clear
+// afterwards; they are skipped when no limit is reached within `MAX_ATTEMPTS`.
+
+use std::time::Instant;
+
+use reqwest::{Method, RequestBuilder, StatusCode};
+
+use super::*;
+
+/// Requests sent while looking for a rate limit before giving up
+const MAX_ATTEMPTS: usize = 200;
+
+/// How long a tripped limit may take to clear so later tests are not throttled
+const RECOVERY_BUDGET: std::time::Duration = std::time::Duration::from_secs(120);
+
+/// Documentation-range addresses (RFC 5737) that no real client has
+const SPOOFED_PREFIX: &str = "203.0.113.";
+
+/// Attach every common forwarding header, all claiming `ip`
+fn spoofed(builder: RequestBuilder, ip: &str) -> RequestBuilder {
+    builder
+        .header("X-Forwarded-For", ip)
+        .header("X-Real-IP", ip)
+        .header("Forwarded", format!("for={}", ip))
+}
+
+fn spoofed_ip(n: usize) -> String {
+    format!("{}{}", SPOOFED_PREFIX, n % 254 + 1)
+}
+
+/// Whether forwarding headers are trusted here, logging the skip if so
+fn headers_trusted() -> bool {
+    if EnvProfile::current().forwarded_headers_trusted {
+        outcome::skip("Skipping proxy header test - environment trusts forwarding headers");
+        return true;
+    }
+    false
+}
+
+/// Trip a rate limit with one spoofed address, then check a fresh spoofed address is still limited
+///
+/// `build` must produce a request the limiter counts; each call gets a new request.
+async fn assert_limit_ignores_spoofing(service: &str, build: impl Fn() -> RequestBuilder) {
+    let mut tripped_after = None;
+    for attempt in 1..=MAX_ATTEMPTS {
+        let status = spoofed(build(), &spoofed_ip(0))
+            .send_recorded()
+            .await
+            .expect("Failed to send request")
+            .status();
+        if status == StatusCode::TOO_MANY_REQUESTS {
+            tripped_after = Some(attempt);
+            break;
+        }
+    }
+    let Some(attempts) = tripped_after else {
+        outcome::skip(format!(
+            "Skipping {} rate limit check - no 429 within {} requests",
+            service, MAX_ATTEMPTS
+        ));
+        return;
+    };
+    println!("✓ {} rate limited after {} requests", service, attempts);
+
+    // A new forged address on every request must not reset the budget
+    let mut escaped = Vec::new();
+    for n in 1..=10 {
+        let ip = spoofed_ip(n);
+        let status =
+            spoofed(build(), &ip).send_recorded().await.expect("Failed to send request").status();
+        if status != StatusCode::TOO_MANY_REQUESTS {
+            escaped.push((ip, status));
+        }
+    }
+
+    // Let the limit clear before asserting, so a failure does not throttle later tests
+    let start = Instant::now();
+    while build().send_recorded().await.expect("Failed to send request").status()
+        == StatusCode::TOO_MANY_REQUESTS
+    {
+        assert!(
+            start.elapsed() < RECOVERY_BUDGET,
+            "{} rate limit did not clear within {:?}",
+            service,
+            RECOVERY_BUDGET
+        );
+        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+    }
+
+    assert!(escaped.is_empty(), "{} rate limit keyed on forged headers: {:?}", service, escaped);
+    println!("✓ {} rate limit not bypassed by forged forwarding headers", service);
+}
+
+#[tokio::test]
+async fn test_control_rate_limit_ignores_forwarding_headers() {
+    if headers_trusted() {
+        return;
+    }
+    let control = ControlClient::new(TestContext::new());
+
+    // Failed logins for ever-new accounts, so only a per-address limit can trip
+    assert_limit_ignores_spoofing("Control", || {
+        control.request(Method::POST, "/auth/login/password").json(&LoginRequest {
+            email: naming::email("spoof"),
+            password: "not-the-password".to_string(),
+        })
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_engine_rate_limit_ignores_forwarding_headers() {
+    if headers_trusted() {
+        return;
+    }
+    let fixture = SharedFixture::get().await;
+    let invalid = fixture.generate_invalid_jwt().expect("Failed to generate invalid JWT");
+    let url = TestContext::new().engine_url("/evaluate");
+
+    // Rejected tokens, so nothing is evaluated and only the limiter can change the outcome
+    assert_limit_ignores_spoofing("Engine", || {
+        fixture
+            .request(Method::POST, &url)
+            .header("Authorization", format!("Bearer {}", invalid))
+            .json(&serde_json::json!({
+                "evaluations": [{
+                    "subject": "user:alice",
+                    "resource": "document:1",
+                    "permission": "viewer",
+                    "trace": false
+                }]
+            }))
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn test_audit_log_ignores_forwarding_headers() {
+    if headers_trusted() {
+        return;
+    }
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let ip = spoofed_ip(42);
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("Spoofed Certificate"));
+    let status = spoofed(
+        control
+            .request(
+                Method::POST,
+                &format!(
+                    "/organizations/{}/clients/{}/certificates",
+                    fixture.org_id, fixture.client_id
+                ),
+            )
+            .json(&cert_req),
+        &ip,
+    )
+    .send_recorded()
+    .await
+    .expect("Failed to create certificate")
+    .status();
+    assert!(status.is_success(), "Certificate creation with forged headers failed: {}", status);
+
+    let events = match control.list_audit_events(fixture.org_id).await {
+        Ok(log) => log.events,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping audit log check - Control has no audit log endpoint: {}",
+                e
+            ));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to list audit events: {}", e),
+    };
+    assert!(!events.is_empty(), "Audit log should record the certificate creation");
+
+    for event in &events {
+        let recorded = serde_json::to_string(event).expect("Failed to serialize audit event");
+        assert!(
+            !recorded.contains(&ip),
+            "Audit event recorded the forged address {}: {}",
+            ip,
+            recorded
+        );
+    }
+    println!("✓ {} audit events, none recording the forged address", events.len());
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,132 @@
+// Vault Purge Tests
+//
+// Tests for deleting every relationship in a vault at once: seeded data must be gone afterwards
+// (every check denies), and the operation must require an elevated scope (`inferadb.admin` or
+// `inferadb.vault.manage`). Skipped when Engine does not expose a purge endpoint.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+fn seed_relationships() -> Vec<Relationship> {
+    vec![
+        Relationship::new("document:purge-1", "viewer", "user:alice"),
+        Relationship::new("document:purge-1", "editor", "user:bob"),
+        Relationship::new("document:purge-2", "owner", "user:carol"),
+    ]
+}
+
+/// Seed the fixture's vault and confirm every tuple is visible
+async fn seed(fixture: &TestFixture) -> Vec<Relationship> {
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    let relationships = seed_relationships();
+    engine.write_relationships(&relationships).await.expect("Failed to seed relationships");
+
+    for r in &relationships {
+        let allowed =
+            engine.check(&r.resource, &r.relation, &r.subject).await.expect("Failed to check");
+        assert!(allowed, "Seeded relationship {:?} should be visible", r);
+    }
+
+    relationships
+}
+
+/// Assert none of the relationships grant access any more
+async fn assert_all_denied(fixture: &TestFixture, relationships: &[Relationship]) {
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    for r in relationships {
+        let allowed =
+            engine.check(&r.resource, &r.relation, &r.subject).await.expect("Failed to check");
+        assert!(!allowed, "Relationship {:?} should be denied after purge", r);
+    }
+}
+
+#[tokio::test]
+async fn test_purge_removes_all_relationships() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let relationships = seed(&fixture).await;
+
+    let jwt = fixture.generate_jwt(None, &["inferadb.admin"]).expect("Failed to generate JWT");
+    match fixture.engine(&jwt).purge_relationships().await {
+        Ok(()) => {},
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping vault purge test - endpoint not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Purge with inferadb.admin should succeed: {}", e),
+    }
+
+    assert_all_denied(&fixture, &relationships).await;
+    println!("✓ All {} relationships denied after purge", relationships.len());
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_purge_allowed_with_vault_manage_scope() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let relationships = seed(&fixture).await;
+
+    let jwt =
+        fixture.generate_jwt(None, &["inferadb.vault.manage"]).expect("Failed to generate JWT");
+    match fixture.engine(&jwt).purge_relationships().await {
+        Ok(()) => {},
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping vault purge test - endpoint not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Purge with inferadb.vault.manage should succeed: {}", e),
+    }
+
+    assert_all_denied(&fixture, &relationships).await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_purge_requires_elevated_scope() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let relationships = seed(&fixture).await;
+
+    for scopes in [&["inferadb.check"][..], &["inferadb.check", "inferadb.write"][..]] {
+        let jwt = fixture.generate_jwt(None, scopes).expect("Failed to generate JWT");
+        let err = fixture
+            .engine(&jwt)
+            .purge_relationships()
+            .await
+            .expect_err("Purge without an elevated scope must be refused");
+
+        if err.is_unsupported() {
+            outcome::skip(format!("Skipping vault purge test - endpoint not available: {}", err));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        }
+
+        assert_eq!(
+            err.status(),
+            Some(StatusCode::FORBIDDEN),
+            "Purge with scopes {:?} should be forbidden, got {}",
+            scopes,
+            err
+        );
+    }
+
+    // The refused purges must not have removed anything
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    for r in &relationships {
+        let allowed =
+            engine.check(&r.resource, &r.relation, &r.subject).await.expect("Failed to check");
+        assert!(allowed, "Relationship {:?} should survive a refused purge", r);
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,149 @@
+// Quota Tests
+//
+// Fills a newly registered organization up to its tier's limits (`EnvProfile::vault_limit` and
+// `relationship_limit`) and pins the error contract for going one past them: 403 with a JSON body
+// whose `code` is `quota_exceeded` and whose `message` names the exhausted resource and its limit.
+// A relationship batch that crosses the limit part-way must be rejected as a whole, leaving none
+// of its tuples behind. Skipped unless the limits are configured.
+
+use reqwest::StatusCode;
+use serde::Deserialize;
+
+use super::*;
+
+/// Relationships per write request while filling a vault
+const FILL_BATCH: usize = 500;
+
+/// Tuples in the batch that crosses the relationship limit
+const CROSSING_BATCH: usize = 10;
+
+/// The error body a quota rejection must carry
+#[derive(Debug, Deserialize)]
+struct QuotaError {
+    code: String,
+    message: String,
+}
+
+/// Assert `err` is a quota rejection naming `resource` and `limit`
+#[track_caller]
+fn assert_quota_exceeded(err: &ApiError, resource: &str, limit: usize) {
+    assert_eq!(err.status(), Some(StatusCode::FORBIDDEN), "Quota rejection status: {}", err);
+    let body = err.body().unwrap_or_default();
+    let quota: QuotaError = serde_json::from_str(body).unwrap_or_else(|e| {
+        panic!("Quota rejection body is not the pinned shape ({}): {}", e, body)
+    });
+    assert_eq!(quota.code, "quota_exceeded", "Quota rejection code: {}", body);
+    assert!(
+        quota.message.to_lowercase().contains(resource)
+            && quota.message.contains(&limit.to_string()),
+        "Quota message should name '{}' and the limit {}: {}",
+        resource,
+        limit,
+        quota.message
+    );
+    println!("✓ {} quota: 403 quota_exceeded: {}", resource, quota.message);
+}
+
+fn relationship(index: usize) -> Relationship {
+    Relationship::new(format!("document:quota-{}", index), "viewer", "user:quota")
+}
+
+#[tokio::test]
+async fn test_vault_quota_exceeded() {
+    let Some(limit) = EnvProfile::current().vault_limit else {
+        outcome::skip("Skipping vault quota test - INFERADB_VAULT_LIMIT not set");
+        return;
+    };
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let create = || CreateVaultRequest {
+        name: naming::entity_name("Quota Vault"),
+        organization_id: fixture.org_id,
+    };
+
+    // The fixture's own vault counts towards the limit
+    let mut created = Vec::new();
+    for _ in 1..limit {
+        let vault = control
+            .create_vault(fixture.org_id, &create())
+            .await
+            .expect("Vault within the quota should be created");
+        created.push(vault.vault.id);
+    }
+    println!("✓ Created vaults up to the limit of {}", limit);
+
+    let err = control
+        .create_vault(fixture.org_id, &create())
+        .await
+        .expect_err("Vault past the quota should be rejected");
+    assert_quota_exceeded(&err, "vault", limit);
+
+    let vaults = control.list_vaults(fixture.org_id).await.expect("Failed to list vaults");
+    let live = vaults.vaults.iter().filter(|vault| vault.deleted_at.is_none()).count();
+    assert_eq!(live, limit, "Rejected vault must not have been created");
+
+    for vault_id in created {
+        let _ = control.delete_vault(fixture.org_id, vault_id).await;
+    }
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_relationship_quota_exceeded_atomically() {
+    let Some(limit) = EnvProfile::current().relationship_limit else {
+        outcome::skip("Skipping relationship quota test - INFERADB_RELATIONSHIP_LIMIT not set");
+        return;
+    };
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.write", "inferadb.check", "inferadb.list-relationships"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    // Leave room for half the crossing batch
+    let headroom = CROSSING_BATCH / 2;
+    let filled = limit.saturating_sub(headroom);
+    let tuples: Vec<_> = (0..filled).map(relationship).collect();
+    for batch in tuples.chunks(FILL_BATCH) {
+        engine.write_relationships(batch).await.expect("Write within the quota should succeed");
+    }
+    println!("✓ Filled the vault to {} of {} relationships", filled, limit);
+
+    let crossing: Vec<_> = (filled..filled + CROSSING_BATCH).map(relationship).collect();
+    let err = engine
+        .write_relationships(&crossing)
+        .await
+        .expect_err("Batch crossing the quota should be rejected");
+    assert_quota_exceeded(&err, "relationship", limit);
+
+    for tuple in &crossing[..headroom] {
+        assert!(
+            !engine
+                .check(&tuple.resource, &tuple.relation, &tuple.subject)
+                .await
+                .expect("Failed to check"),
+            "{:?} fit under the quota but was kept from a rejected batch",
+            tuple
+        );
+    }
+    let stored = engine
+        .list_all_relationships(&RelationshipFilter::new().subject("user:quota"))
+        .await
+        .expect("Failed to list relationships")
+        .len();
+    assert_eq!(stored, filled, "Rejected batch must leave the vault unchanged");
+    println!("✓ Crossing batch rejected atomically");
+
+    // The headroom is still usable, filling the vault to the limit exactly
+    engine
+        .write_relationships(&crossing[..headroom])
+        .await
+        .expect("Batch that fits the quota exactly should succeed");
+    let err = engine
+        .write_relationships(&crossing[headroom..headroom + 1])
+        .await
+        .expect_err("Write past a full quota should be rejected");
+    assert_quota_exceeded(&err, "relationship", limit);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,248 @@
+// Relationship Filtering Tests
+//
+// Tests for listing relationships through `EngineClient` with resource, relation, and subject
+// filters: filter combinations, resource-type prefixes, empty results, and vault boundaries.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Scopes needed to seed and read relationships
+const READ_WRITE_SCOPES: &[&str] = &["inferadb.write", "inferadb.list-relationships"];
+
+/// Seeded tuples, unique per test run so filters only ever match this test's data
+struct Seed {
+    readme: String,
+    plan: String,
+    folder: String,
+    alice: String,
+    bob: String,
+    relationships: Vec<Relationship>,
+}
+
+impl Seed {
+    fn new() -> Self {
+        let run = Uuid::new_v4().simple().to_string();
+        let readme = format!("document:readme-{}", run);
+        let plan = format!("document:plan-{}", run);
+        let folder = format!("folder:root-{}", run);
+        let alice = format!("user:alice-{}", run);
+        let bob = format!("user:bob-{}", run);
+
+        let relationships = vec![
+            Relationship::new(&readme, "viewer", &alice),
+            Relationship::new(&readme, "editor", &bob),
+            Relationship::new(&plan, "viewer", &alice),
+            Relationship::new(&folder, "viewer", &alice),
+        ];
+
+        Self { readme, plan, folder, alice, bob, relationships }
+    }
+}
+
+/// List relationships, or return `None` if Engine does not expose the list endpoint
+async fn list_or_skip(
+    engine: &EngineClient,
+    filter: &RelationshipFilter,
+) -> Option<Vec<Relationship>> {
+    match engine.list_relationships(filter).await {
+        Ok(response) => {
+            let mut relationships = response.relationships;
+            relationships.sort();
+            Some(relationships)
+        },
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping relationship filter test - list endpoint not available: {}",
+                e
+            ));
+            None
+        },
+        Err(e) => panic!("Failed to list relationships: {}", e),
+    }
+}
+
+fn sorted(mut relationships: Vec<Relationship>) -> Vec<Relationship> {
+    relationships.sort();
+    relationships
+}
+
+#[tokio::test]
+async fn test_list_relationships_filter_combinations() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, READ_WRITE_SCOPES).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    let seed = Seed::new();
+    engine.write_relationships(&seed.relationships).await.expect("Failed to seed relationships");
+
+    let cases = vec![
+        (
+            "resource",
+            RelationshipFilter::new().resource(&seed.readme),
+            vec![
+                Relationship::new(&seed.readme, "viewer", &seed.alice),
+                Relationship::new(&seed.readme, "editor", &seed.bob),
+            ],
+        ),
+        (
+            "resource + relation",
+            RelationshipFilter::new().resource(&seed.readme).relation("viewer"),
+            vec![Relationship::new(&seed.readme, "viewer", &seed.alice)],
+        ),
+        (
+            "subject",
+            RelationshipFilter::new().subject(&seed.alice),
+            vec![
+                Relationship::new(&seed.readme, "viewer", &seed.alice),
+                Relationship::new(&seed.plan, "viewer", &seed.alice),
+                Relationship::new(&seed.folder, "viewer", &seed.alice),
+            ],
+        ),
+        (
+            "relation + subject",
+            RelationshipFilter::new().relation("editor").subject(&seed.bob),
+            vec![Relationship::new(&seed.readme, "editor", &seed.bob)],
+        ),
+        (
+            "resource + relation + subject",
+            RelationshipFilter::new().resource(&seed.plan).relation("viewer").subject(&seed.alice),
+            vec![Relationship::new(&seed.plan, "viewer", &seed.alice)],
+        ),
+    ];
+
+    for (label, filter, expected) in cases {
+        let Some(actual) = list_or_skip(&engine, &filter).await else {
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        };
+
+        assert_eq!(actual, sorted(expected), "Filter by {} returned unexpected tuples", label);
+        println!("✓ Filter by {} matched {} relationship(s)", label, actual.len());
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_list_relationships_by_resource_type_prefix() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, READ_WRITE_SCOPES).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    let seed = Seed::new();
+    engine.write_relationships(&seed.relationships).await.expect("Failed to seed relationships");
+
+    // A bare type ("document:") selects every object of that type
+    let filter = RelationshipFilter::new().resource("document:");
+    let actual = match engine.list_relationships(&filter).await {
+        Ok(response) => sorted(response.relationships),
+        Err(e) if e.is_unsupported() || e.status() == Some(StatusCode::BAD_REQUEST) => {
+            outcome::skip(format!("Skipping resource prefix filter test - not supported: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to list relationships: {}", e),
+    };
+
+    let expected = sorted(
+        seed.relationships
+            .iter()
+            .filter(|r| r.resource.starts_with("document:"))
+            .cloned()
+            .collect(),
+    );
+    assert_eq!(actual, expected, "Prefix filter should return only document relationships");
+    assert!(
+        !actual.iter().any(|r| r.resource == seed.folder),
+        "Prefix filter must not match other resource types"
+    );
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_list_relationships_empty_results() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, READ_WRITE_SCOPES).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    let seed = Seed::new();
+    engine.write_relationships(&seed.relationships).await.expect("Failed to seed relationships");
+
+    let cases = vec![
+        ("unknown subject", RelationshipFilter::new().subject("user:nobody")),
+        ("unknown resource", RelationshipFilter::new().resource("document:missing")),
+        (
+            "relation not on resource",
+            RelationshipFilter::new().resource(&seed.plan).relation("editor"),
+        ),
+    ];
+
+    for (label, filter) in cases {
+        let Some(actual) = list_or_skip(&engine, &filter).await else {
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        };
+
+        assert!(actual.is_empty(), "Filter by {} should return no tuples, got {:?}", label, actual);
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_list_relationships_cannot_escape_vault() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    // Seed vault A
+    let jwt_a = fixture.generate_jwt(None, READ_WRITE_SCOPES).expect("Failed to generate JWT");
+    let seed = Seed::new();
+    fixture
+        .engine(&jwt_a)
+        .write_relationships(&seed.relationships)
+        .await
+        .expect("Failed to seed relationships");
+
+    // Create vault B in the same organization
+    let vault_req = CreateVaultRequest {
+        name: naming::entity_name("Filter Vault B"),
+        organization_id: fixture.org_id,
+    };
+    let vault_b_id = fixture
+        .control()
+        .create_vault(fixture.org_id, &vault_req)
+        .await
+        .expect("Failed to create second vault")
+        .vault
+        .id;
+
+    let jwt_b = fixture
+        .generate_jwt(Some(vault_b_id), READ_WRITE_SCOPES)
+        .expect("Failed to generate JWT for vault B");
+    let engine_b = fixture.engine(&jwt_b);
+
+    // Filters naming vault A's tuples exactly must not reach them from vault B
+    let filters = vec![
+        RelationshipFilter::new(),
+        RelationshipFilter::new().resource(&seed.readme),
+        RelationshipFilter::new().subject(&seed.alice),
+        RelationshipFilter::new().resource(&seed.readme).relation("viewer").subject(&seed.alice),
+    ];
+
+    for filter in filters {
+        let Some(actual) = list_or_skip(&engine_b, &filter).await else {
+            break;
+        };
+
+        assert!(
+            actual.is_empty(),
+            "Vault B must not see vault A's relationships with filter {:?}, got {:?}",
+            filter,
+            actual
+        );
+    }
+
+    let _ = fixture.control().delete_vault(fixture.org_id, vault_b_id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,127 @@
+// Relationship Metadata Tests
+//
+// Tests for the bookkeeping Engine keeps per tuple: `created_at` must follow write order, and
+// timestamps and metadata must survive an export from one vault and import into another. Skipped
+// when listed tuples carry no `created_at` or export/import is not exposed.
+
+use super::*;
+
+/// Scopes needed to seed, read, and move relationships
+const SCOPES: &[&str] = &["inferadb.write", "inferadb.list-relationships"];
+
+/// Relationships written one at a time, in order
+const WRITES: usize = 5;
+
+/// List this run's tuples with their bookkeeping, or `None` if Engine records no `created_at`
+async fn stored_or_skip(engine: &EngineClient, resource: &str) -> Option<Vec<StoredRelationship>> {
+    let response = engine
+        .list_stored_relationships(&RelationshipFilter::new().resource(resource))
+        .await
+        .expect("Failed to list relationships");
+    if response.relationships.iter().any(|r| r.created_at.is_none()) {
+        outcome::skip("Skipping relationship metadata test - listed tuples carry no created_at");
+        return None;
+    }
+    Some(response.relationships)
+}
+
+#[tokio::test]
+async fn test_created_at_follows_write_order() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, SCOPES).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    let resource = format!("document:ordered-{}", Uuid::new_v4().simple());
+    let subjects: Vec<String> = (0..WRITES).map(|i| format!("user:writer-{}", i)).collect();
+    for subject in &subjects {
+        engine
+            .write_relationships(&[Relationship::new(&resource, "viewer", subject)])
+            .await
+            .expect("Failed to write relationship");
+        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+    }
+
+    let Some(stored) = stored_or_skip(&engine, &resource).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    assert_eq!(stored.len(), WRITES, "Expected every written tuple to be listed");
+
+    let timestamps: Vec<_> = subjects
+        .iter()
+        .map(|subject| {
+            stored
+                .iter()
+                .find(|r| &r.relationship.subject == subject)
+                .and_then(|r| r.created_at)
+                .unwrap_or_else(|| panic!("No stored tuple for {}", subject))
+        })
+        .collect();
+    for pair in timestamps.windows(2) {
+        assert!(
+            pair[0] <= pair[1],
+            "created_at went backwards across sequential writes: {:?}",
+            timestamps
+        );
+    }
+    println!("✓ created_at is monotone across {} sequential writes", WRITES);
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_metadata_survives_export_import() {
+    let source = TestFixture::create().await.expect("Failed to create source fixture");
+    let target = TestFixture::create().await.expect("Failed to create target fixture");
+    let source_engine =
+        source.engine(&source.generate_jwt(None, SCOPES).expect("Failed to generate JWT"));
+    let target_engine =
+        target.engine(&target.generate_jwt(None, SCOPES).expect("Failed to generate JWT"));
+
+    let resource = format!("document:exported-{}", Uuid::new_v4().simple());
+    source_engine
+        .write_relationships(&[
+            Relationship::new(&resource, "viewer", "user:alice"),
+            Relationship::new(&resource, "editor", "user:bob"),
+        ])
+        .await
+        .expect("Failed to write relationships");
+
+    let Some(mut original) = stored_or_skip(&source_engine, &resource).await else {
+        source.cleanup().await.expect("Failed to cleanup");
+        target.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let exported = match source_engine.export_relationships().await {
+        Ok(relationships) => relationships,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping export/import round trip - export not available: {}",
+                e
+            ));
+            source.cleanup().await.expect("Failed to cleanup");
+            target.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to export relationships: {}", e),
+    };
+    assert!(
+        original.iter().all(|r| exported.contains(r)),
+        "Export differs from the listed tuples' timestamps or metadata"
+    );
+
+    target_engine.import_relationships(&exported).await.expect("Failed to import relationships");
+
+    let mut imported = stored_or_skip(&target_engine, &resource)
+        .await
+        .expect("Imported tuples lost their created_at");
+    let key = |r: &StoredRelationship| r.relationship.clone();
+    original.sort_by_key(key);
+    imported.sort_by_key(key);
+    assert_eq!(imported, original, "Timestamps or metadata changed across export/import");
+    println!("✓ {} tuples kept created_at and metadata across export/import", original.len());
+
+    source.cleanup().await.expect("Failed to cleanup");
+    target.cleanup().await.expect("Failed to cleanup");
+}
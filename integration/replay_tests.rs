@@ -0,0 +1,64 @@
+// Shadow-Traffic Replay Tests
+//
+// Records a short Engine workload against one vault, round-trips it through a capture file, and
+// replays it against a fresh vault. With the captured writes replayed ahead of the reads, every
+// status and decision must match the capture.
+
+use inferadb_integration_tests::recorder;
+
+use super::*;
+
+#[tokio::test]
+async fn test_replayed_capture_matches_recorded_decisions() {
+    let source = TestFixture::create().await.expect("Failed to create source fixture");
+    let jwt = source
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = source.engine(&jwt);
+
+    let recording = recorder::start();
+    engine
+        .write_relationships(&[
+            Relationship::new("document:replay-1", "viewer", "user:alice"),
+            Relationship::new("document:replay-2", "editor", "user:bob"),
+        ])
+        .await
+        .expect("Failed to write relationships");
+    for (resource, permission, subject) in [
+        ("document:replay-1", "viewer", "user:alice"),
+        ("document:replay-1", "viewer", "user:bob"),
+        ("document:replay-2", "editor", "user:bob"),
+        ("document:replay-2", "editor", "user:alice"),
+    ] {
+        engine.evaluate(resource, permission, subject).await.expect("Failed to evaluate");
+    }
+    let exchanges = recording.finish();
+    assert_eq!(exchanges.len(), 5, "Expected one write and four evaluates to be recorded");
+    assert!(
+        exchanges.iter().skip(1).all(|exchange| exchange.response_body.is_some()),
+        "Evaluate responses should be captured for decision parity"
+    );
+    source.cleanup().await.expect("Failed to cleanup source fixture");
+
+    let path = std::env::temp_dir().join(format!("inferadb-capture-{}.jsonl", Uuid::new_v4()));
+    recorder::write_capture(&path, &exchanges).expect("Failed to write capture file");
+    let captured = recorder::read_capture(&path).expect("Failed to read capture file");
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(captured.len(), exchanges.len());
+
+    let target = TestFixture::create().await.expect("Failed to create target fixture");
+    let jwt = target
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let report = Replayer::new(target.ctx.clone(), jwt).speed(10.0).replay(&captured).await;
+
+    assert_eq!(report.replayed, captured.len());
+    assert!(
+        report.divergences.is_empty(),
+        "Replay diverged from the capture:\n  {}",
+        report.divergences.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n  ")
+    );
+    println!("✓ Replayed {} captured requests with matching decisions", report.replayed);
+
+    target.cleanup().await.expect("Failed to cleanup target fixture");
+}
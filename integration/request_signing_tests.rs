@@ -0,0 +1,169 @@
+// Request Signing Tests
+//
+// Tests for optional request signing on relationship writes (see `request_signing`): a valid proof
+// is accepted, incomplete or tampered proofs are rejected, and a proof cannot be replayed or used
+// outside the freshness window. Skipped when Engine accepts a forged signature, i.e. does not
+// verify signatures at all.
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+const WRITE_PATH: &str = "/relationships/write";
+
+/// A timestamp well outside any reasonable freshness window
+const STALE_SECONDS: i64 = 600;
+
+fn write_body(subject: &str) -> Vec<u8> {
+    serde_json::to_vec(&serde_json::json!({
+        "relationships": [Relationship::new("document:signed", "viewer", subject)]
+    }))
+    .expect("Failed to serialize body")
+}
+
+/// Send a write with the given raw body and optional proof headers
+async fn send_write(
+    engine: &EngineClient,
+    body: Vec<u8>,
+    proof: Option<&RequestProof>,
+) -> StatusCode {
+    let builder = engine
+        .request(Method::POST, WRITE_PATH)
+        .header("Content-Type", "application/json")
+        .body(body);
+    let builder = match proof {
+        Some(proof) => proof.apply(builder),
+        None => builder,
+    };
+    builder.send_recorded().await.expect("Failed to send write").status()
+}
+
+/// Engine client, URL, and signer for signed writes, or `None` if Engine ignores signatures
+async fn signing_setup(fixture: &TestFixture) -> Option<(EngineClient, String, RequestSigner)> {
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    let url = fixture.ctx.engine_url(WRITE_PATH);
+    let signer = RequestSigner::for_fixture(fixture);
+
+    // A signature over a different body must fail if Engine verifies signatures at all
+    let forged = signer.sign(&Method::POST, &url, &write_body("user:someone-else"));
+    let status = send_write(&engine, write_body("user:forger"), Some(&forged)).await;
+    if status.is_success() {
+        outcome::skip("Skipping request signing test - Engine accepted a forged signature");
+        return None;
+    }
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Forged signature should be rejected with 401");
+
+    Some((engine, url, signer))
+}
+
+#[tokio::test]
+async fn test_valid_signature_accepted() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((engine, url, signer)) = signing_setup(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let body = write_body("user:alice");
+    let proof = signer.sign(&Method::POST, &url, &body);
+    let status = send_write(&engine, body, Some(&proof)).await;
+    assert!(status.is_success(), "Correctly signed write was rejected: {}", status);
+
+    assert!(
+        engine.check("document:signed", "viewer", "user:alice").await.expect("Failed to evaluate"),
+        "Signed write should be visible to evaluate"
+    );
+    println!("✓ Signed write accepted and applied");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_incomplete_or_tampered_proof_rejected() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((engine, url, signer)) = signing_setup(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    // Signature without its nonce and timestamp
+    let body = write_body("user:alice");
+    let proof = signer.sign(&Method::POST, &url, &body);
+    let status = engine
+        .request(Method::POST, WRITE_PATH)
+        .header("Content-Type", "application/json")
+        .header(request_signing::SIGNATURE_HEADER, &proof.signature)
+        .body(body)
+        .send_recorded()
+        .await
+        .expect("Failed to send write")
+        .status();
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Signature without nonce/timestamp accepted");
+    println!("✓ Incomplete proof rejected");
+
+    // Valid proof for a different path
+    let body = write_body("user:alice");
+    let proof = signer.sign(&Method::POST, &fixture.ctx.engine_url("/relationships/delete"), &body);
+    let status = send_write(&engine, body, Some(&proof)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Proof for another path accepted");
+    println!("✓ Proof bound to another path rejected");
+
+    // Signature from a key other than the token's
+    let body = write_body("user:alice");
+    let proof = RequestSigner::new(keys::generate()).sign(&Method::POST, &url, &body);
+    let status = send_write(&engine, body, Some(&proof)).await;
+    assert_eq!(status, StatusCode::UNAUTHORIZED, "Proof signed by a foreign key accepted");
+    println!("✓ Proof from a foreign key rejected");
+
+    // No proof at all: signing is optional, so either outcome is valid, but never a server error
+    let status = send_write(&engine, write_body("user:bob"), None).await;
+    assert!(!status.is_server_error(), "Unsigned write caused a server error: {}", status);
+    println!(
+        "✓ Unsigned write {} (signing {})",
+        status,
+        if status.is_success() { "optional" } else { "required" }
+    );
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_replayed_proof_rejected() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((engine, url, signer)) = signing_setup(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    let body = write_body("user:alice");
+    let proof = signer.sign(&Method::POST, &url, &body);
+    let first = send_write(&engine, body.clone(), Some(&proof)).await;
+    assert!(first.is_success(), "Original signed write was rejected: {}", first);
+
+    let replay = send_write(&engine, body, Some(&proof)).await;
+    assert_eq!(replay, StatusCode::UNAUTHORIZED, "Replayed proof (same nonce) was accepted");
+    println!("✓ Replayed nonce rejected");
+
+    // Fresh nonce, but signed long ago
+    let body = write_body("user:bob");
+    let stale = signer.sign_at(
+        &Method::POST,
+        &url,
+        &body,
+        Utc::now().timestamp() - STALE_SECONDS,
+        &Uuid::new_v4().to_string(),
+    );
+    let status = send_write(&engine, body, Some(&stale)).await;
+    assert_eq!(
+        status,
+        StatusCode::UNAUTHORIZED,
+        "Proof signed {}s ago was accepted",
+        STALE_SECONDS
+    );
+    println!("✓ Stale proof rejected");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -55,32 +55,20 @@ async fn test_cached_data_allows_validation() {
 
 #[tokio::test]
 async fn test_graceful_degradation_with_network_timeout() {
-    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
-
-    // Create a JWT with a non-existent kid (will cause control lookup)
-    let now = Utc::now();
-    let claims = ClientClaims {
-        iss: fixture.ctx.api_base_url.clone(),
-        sub: format!("client:{}", fixture.client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
-        exp: (now + Duration::minutes(5)).timestamp(),
-        iat: now.timestamp(),
-        jti: Uuid::new_v4().to_string(),
-        vault_id: fixture.vault_id.to_string(),
-        org_id: fixture.org_id.to_string(),
-        scope: "inferadb.check inferadb.read inferadb.write inferadb.expand inferadb.list inferadb.list-relationships inferadb.list-subjects inferadb.list-resources".to_string(),
-        vault_role: "write".to_string(),
-    };
-
-    let mut header = Header::new(Algorithm::EdDSA);
-    // Use a kid that doesn't exist but has valid format (fake cert Snowflake ID)
-    header.kid =
-        Some(format!("org-{}-client-{}-cert-{}", fixture.org_id, fixture.client_id, 999999999i64));
-
-    let secret_bytes = fixture.signing_key.to_bytes();
-    let pem = ed25519_to_pem(&secret_bytes);
-    let encoding_key = EncodingKey::from_ed_pem(&pem).expect("Failed to create encoding key");
-    let jwt = encode(&header, &claims, &encoding_key).expect("Failed to encode JWT");
+    let fixture = SharedFixture::get().await;
+
+    // Create a JWT with a non-existent kid (will cause control lookup); the kid has a valid
+    // format but names a fake cert Snowflake ID
+    let jwt = fixture
+        .jwt()
+        .kid(Some(format!(
+            "org-{}-client-{}-cert-{}",
+            fixture.org_id(),
+            fixture.client_id(),
+            999999999i64
+        )))
+        .encode()
+        .expect("Failed to encode JWT");
 
     // This should fail with 401 (certificate not found)
     let response = fixture
@@ -94,8 +82,6 @@ async fn test_graceful_degradation_with_network_timeout() {
         "Expected 401 for non-existent certificate"
     );
     println!("✓ Server handled missing certificate gracefully");
-
-    fixture.cleanup().await.expect("Failed to cleanup");
 }
 
 #[tokio::test]
@@ -159,24 +145,15 @@ async fn test_partial_cache_coverage() {
 
     // Create a second vault (not yet cached)
     let vault2_req = CreateVaultRequest {
-        name: format!("Second Vault {}", Uuid::new_v4()),
+        name: naming::entity_name("Second Vault"),
         organization_id: fixture.org_id,
     };
 
-    let vault2_response: CreateVaultResponse = fixture
-        .ctx
-        .client
-        .post(fixture.ctx.control_url(&format!("/organizations/{}/vaults", fixture.org_id)))
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .json(&vault2_req)
-        .send()
+    let vault2_response = fixture
+        .control()
+        .create_vault(fixture.org_id, &vault2_req)
         .await
-        .expect("Failed to create second vault")
-        .error_for_status()
-        .expect("Vault creation failed")
-        .json()
-        .await
-        .expect("Failed to parse response");
+        .expect("Failed to create second vault");
 
     let vault2_id = vault2_response.vault.id;
 
@@ -198,46 +175,17 @@ async fn test_partial_cache_coverage() {
     println!("✓ Successfully handled mixed cached/uncached scenario");
 
     // Cleanup second vault
-    let _ = fixture
-        .ctx
-        .client
-        .delete(
-            fixture
-                .ctx
-                .control_url(&format!("/organizations/{}/vaults/{}", fixture.org_id, vault2_id)),
-        )
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
-        .await;
+    let _ = fixture.control().delete_vault(fixture.org_id, vault2_id).await;
 
     fixture.cleanup().await.expect("Failed to cleanup");
 }
 
 #[tokio::test]
 async fn test_error_handling_for_invalid_responses() {
-    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let fixture = SharedFixture::get().await;
 
     // Test with malformed JWT (no kid)
-    let now = Utc::now();
-    let claims = ClientClaims {
-        iss: fixture.ctx.api_base_url.clone(),
-        sub: format!("client:{}", fixture.client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
-        exp: (now + Duration::minutes(5)).timestamp(),
-        iat: now.timestamp(),
-        jti: Uuid::new_v4().to_string(),
-        vault_id: fixture.vault_id.to_string(),
-        org_id: fixture.org_id.to_string(),
-        scope: "inferadb.check inferadb.read inferadb.write inferadb.expand inferadb.list inferadb.list-relationships inferadb.list-subjects inferadb.list-resources".to_string(),
-        vault_role: "write".to_string(),
-    };
-
-    let header = Header::new(Algorithm::EdDSA); // No kid set
-
-    let secret_bytes = fixture.signing_key.to_bytes();
-    let pem = ed25519_to_pem(&secret_bytes);
-    let encoding_key = EncodingKey::from_ed_pem(&pem).expect("Failed to create encoding key");
-    let jwt = encode(&header, &claims, &encoding_key).expect("Failed to encode JWT");
+    let jwt = fixture.jwt().kid(None).encode().expect("Failed to encode JWT");
 
     // Call server with JWT without kid
     let response = fixture
@@ -248,8 +196,6 @@ async fn test_error_handling_for_invalid_responses() {
     // Should fail gracefully with 401
     assert_eq!(response.status(), StatusCode::UNAUTHORIZED, "Expected 401 for JWT without kid");
     println!("✓ Server handled malformed JWT gracefully");
-
-    fixture.cleanup().await.expect("Failed to cleanup");
 }
 
 #[tokio::test]
@@ -279,7 +225,7 @@ async fn test_concurrent_requests_with_mixed_cache_states() {
                 .post(ctx.engine_url("/evaluate"))
                 .header("Authorization", format!("Bearer {}", jwt_clone))
                 .json(&body)
-                .send()
+                .send_recorded()
                 .await
                 .expect("Failed to call server")
         });
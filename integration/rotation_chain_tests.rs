@@ -0,0 +1,168 @@
+// Rotation Chain Tests
+//
+// Rotates a client certificate twice in succession (A → B → C) and checks the chain end to end:
+// Control reports each certificate's true predecessor in `rotated_from`, Engine accepts exactly the
+// keys that are valid at each step (a successor only once its grace period ends, a predecessor only
+// until any cutoff Control sets), and revoking the root A leaves its descendants working.
+
+use std::time::Instant;
+
+use chrono::DateTime;
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Grace period for each rotation; long enough to observe, short enough to wait out
+const GRACE_SECONDS: i64 = 2;
+
+/// How long a key may take to be accepted after its grace period, or rejected after revocation
+const PROPAGATION_BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Validity boundaries closer than this to "now" are not asserted either way
+const BOUNDARY_MARGIN: Duration = Duration::seconds(1);
+
+/// One certificate in the chain and the key to sign with
+struct ChainKey {
+    name: &'static str,
+    id: i64,
+    kid: String,
+    signing_key: SigningKey,
+    valid_from: Option<DateTime<Utc>>,
+    valid_until: Option<DateTime<Utc>>,
+}
+
+impl ChainKey {
+    fn root(fixture: &TestFixture) -> Self {
+        Self {
+            name: "A",
+            id: fixture.cert_id,
+            kid: fixture.cert_kid.clone(),
+            signing_key: fixture.signing_key.clone(),
+            valid_from: None,
+            valid_until: None,
+        }
+    }
+
+    /// Whether Control's validity window includes now, or `None` too close to a boundary to tell
+    fn expected_valid(&self) -> Option<bool> {
+        let now = Utc::now();
+        let near = |t: DateTime<Utc>| (t - now).abs() < BOUNDARY_MARGIN;
+        if self.valid_from.is_some_and(near) || self.valid_until.is_some_and(near) {
+            return None;
+        }
+        Some(self.valid_from.is_none_or(|t| t < now) && self.valid_until.is_none_or(|t| now < t))
+    }
+
+    async fn accepted(&self, fixture: &TestFixture) -> bool {
+        let jwt = fixture
+            .jwt()
+            .signed_by(&self.kid, &self.signing_key)
+            .encode()
+            .expect("Failed to encode JWT");
+        let status = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server")
+            .status();
+        status == StatusCode::OK || status == StatusCode::NOT_FOUND
+    }
+
+    /// Poll until Engine's acceptance of this key matches `expected`
+    async fn await_accepted(&self, fixture: &TestFixture, expected: bool) {
+        let start = Instant::now();
+        while self.accepted(fixture).await != expected {
+            assert!(
+                start.elapsed() < PROPAGATION_BUDGET,
+                "Key {} still {} after {:?}",
+                self.name,
+                if expected { "rejected" } else { "accepted" },
+                PROPAGATION_BUDGET
+            );
+            tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+        }
+    }
+}
+
+/// Rotate `from`, asserting the lineage Control reports for the successor
+///
+/// Records any cutoff Control sets on `from` as part of the rotation.
+async fn rotate(fixture: &TestFixture, from: &mut ChainKey, name: &'static str) -> ChainKey {
+    let req = RotateCertificateRequest {
+        name: naming::entity_name(&format!("Chain Certificate {}", name)),
+        grace_period_seconds: GRACE_SECONDS,
+    };
+    let rotation = fixture
+        .control()
+        .rotate_certificate(fixture.org_id, fixture.client_id, from.id, &req)
+        .await
+        .unwrap_or_else(|e| panic!("Failed to rotate {} → {}: {}", from.name, name, e));
+
+    assert_eq!(
+        rotation.rotated_from.id, from.id,
+        "{}'s rotated_from should be {} (id {})",
+        name, from.name, from.id
+    );
+    assert_eq!(rotation.rotated_from.kid, from.kid, "{}'s rotated_from kid mismatch", name);
+    assert_ne!(rotation.certificate.id, from.id, "Rotation must create a new certificate");
+    println!("✓ {} → {}: rotated_from is {}", from.name, name, from.name);
+
+    from.valid_until = rotation.rotated_from.valid_until;
+    ChainKey {
+        name,
+        id: rotation.certificate.id,
+        kid: rotation.certificate.kid,
+        signing_key: keys::from_base64(&rotation.private_key).expect("Invalid private key"),
+        valid_from: Some(rotation.valid_from),
+        valid_until: None,
+    }
+}
+
+/// Assert Engine accepts exactly the keys whose validity window includes now
+async fn assert_valid_keys_accepted(fixture: &TestFixture, keys: &[&ChainKey]) {
+    for key in keys {
+        let Some(expected) = key.expected_valid() else {
+            continue;
+        };
+        assert_eq!(
+            key.accepted(fixture).await,
+            expected,
+            "Key {} should be {}",
+            key.name,
+            if expected { "accepted" } else { "rejected" }
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_rotation_chain_lineage_and_validity() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let mut a = ChainKey::root(&fixture);
+    assert_valid_keys_accepted(&fixture, &[&a]).await;
+
+    // A → B: B waits out its grace period
+    let mut b = rotate(&fixture, &mut a, "B").await;
+    assert!(!b.accepted(&fixture).await, "B must not authenticate during its grace period");
+    b.await_accepted(&fixture, true).await;
+    assert_valid_keys_accepted(&fixture, &[&a, &b]).await;
+    println!("✓ B became valid after its grace period");
+
+    // B → C: lineage points at B, not at the root
+    let c = rotate(&fixture, &mut b, "C").await;
+    assert!(!c.accepted(&fixture).await, "C must not authenticate during its grace period");
+    c.await_accepted(&fixture, true).await;
+    assert_valid_keys_accepted(&fixture, &[&a, &b, &c]).await;
+    println!("✓ C became valid after its grace period");
+
+    // Revoking the root does not cascade down the chain
+    fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, a.id)
+        .await
+        .expect("Failed to revoke root certificate");
+    a.await_accepted(&fixture, false).await;
+    assert!(c.accepted(&fixture).await, "Revoking root A must not invalidate C");
+    assert_valid_keys_accepted(&fixture, &[&b]).await;
+    println!("✓ Revoking A left C valid");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,123 @@
+// Rotation Cutoff Tests
+//
+// Once a rotated-out certificate's cutoff (`valid_until` on `rotated_from`) has passed, Engine must
+// reject it - and say why. The 401 for a superseded key has to be distinguishable from the one for
+// an explicitly revoked key, so operators can tell routine rotation fallout from an incident.
+// Skipped when Control sets no cutoff on rotated-out certificates.
+
+use std::time::Instant;
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Shortest grace period Control accepts; the cutoff is derived from it
+const GRACE_SECONDS: i64 = 1;
+
+/// How long Engine may keep accepting a key after its cutoff or revocation
+const PROPAGATION_BUDGET: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Wait until Engine rejects `jwt`, returning the rejection body
+async fn await_rejection(fixture: &TestFixture, jwt: &str, what: &str) -> String {
+    let start = Instant::now();
+    loop {
+        let response = fixture
+            .call_server_evaluate(jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        if response.status() == StatusCode::UNAUTHORIZED {
+            return response.text().await.unwrap_or_default();
+        }
+        assert!(
+            start.elapsed() < PROPAGATION_BUDGET,
+            "{} key still accepted {:?} later (status {})",
+            what,
+            PROPAGATION_BUDGET,
+            response.status()
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+    }
+}
+
+#[tokio::test]
+async fn test_rotated_out_key_rejected_as_superseded_after_cutoff() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let old_jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+
+    let req = RotateCertificateRequest {
+        name: naming::entity_name("Cutoff Certificate"),
+        grace_period_seconds: GRACE_SECONDS,
+    };
+    let rotation = fixture
+        .control()
+        .rotate_certificate(fixture.org_id, fixture.client_id, fixture.cert_id, &req)
+        .await
+        .expect("Failed to rotate certificate");
+
+    let Some(cutoff) = rotation.rotated_from.valid_until else {
+        outcome::skip(
+            "Skipping rotation cutoff test - Control sets no cutoff on rotated-out certificates",
+        );
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    println!("✓ Rotated-out certificate valid until {}", cutoff);
+
+    if let Ok(remaining) = (cutoff - Utc::now()).to_std() {
+        tokio::time::sleep(remaining).await;
+    }
+    let superseded = await_rejection(&fixture, &old_jwt, "Rotated-out").await;
+    println!("✓ Rotated-out key rejected after its cutoff");
+
+    // An explicitly revoked certificate, for comparison
+    let cert_req = CreateCertificateRequest::new(naming::entity_name("Revoked Certificate"));
+    let cert = fixture
+        .control()
+        .create_certificate(fixture.org_id, fixture.client_id, &cert_req)
+        .await
+        .expect("Failed to create certificate");
+    let signing_key = keys::from_base64(&cert.private_key).expect("Invalid private key");
+    let revoked_jwt = fixture
+        .jwt()
+        .signed_by(&cert.certificate.kid, &signing_key)
+        .encode()
+        .expect("Failed to encode JWT");
+    let status = fixture
+        .call_server_evaluate(&revoked_jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server")
+        .status();
+    assert!(
+        status == StatusCode::OK || status == StatusCode::NOT_FOUND,
+        "New certificate should authenticate before it is revoked: {}",
+        status
+    );
+    fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, cert.certificate.id)
+        .await
+        .expect("Failed to revoke certificate");
+    let revoked = await_rejection(&fixture, &revoked_jwt, "Revoked").await;
+    println!("✓ Revoked key rejected");
+
+    let superseded_lower = superseded.to_lowercase();
+    assert!(
+        ["superseded", "expired", "rotated"].iter().any(|word| superseded_lower.contains(word)),
+        "Rejection after cutoff should say the key was superseded or expired: {}",
+        superseded
+    );
+    assert!(
+        !superseded_lower.contains("revoked"),
+        "Rotated-out key should not be reported as revoked: {}",
+        superseded
+    );
+    assert!(
+        revoked.to_lowercase().contains("revoked"),
+        "Explicit revocation should be reported as revoked: {}",
+        revoked
+    );
+    assert_ne!(superseded, revoked, "Superseded and revoked rejections should differ");
+    println!("✓ Superseded rejection is distinct from revocation");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
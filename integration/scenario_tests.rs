@@ -0,0 +1,53 @@
+// Scenario Report Tests
+//
+// Offline checks of the scenario DSL's report math: percentile selection and SLO evaluation.
+
+use inferadb_integration_tests::scenario::{StepReport, percentile};
+
+use super::*;
+
+fn report(passed_iterations: usize, p95_ms: Option<f64>) -> ScenarioReport {
+    ScenarioReport {
+        scenario: "example".to_string(),
+        iterations: 10,
+        passed_iterations,
+        steps: vec![StepReport {
+            step: "evaluate".to_string(),
+            runs: 10,
+            failures: 10 - passed_iterations,
+            p50_ms: p95_ms.map(|p95| p95 / 2.0),
+            p95_ms,
+            max_ms: p95_ms,
+            errors: Vec::new(),
+        }],
+        violations: Vec::new(),
+        reproductions: Vec::new(),
+    }
+}
+
+#[test]
+fn test_percentile_uses_nearest_rank() {
+    let sorted: Vec<f64> = (1..=20).map(f64::from).collect();
+    assert_eq!(percentile(&sorted, 0.50), Some(10.0));
+    assert_eq!(percentile(&sorted, 0.95), Some(19.0));
+    assert_eq!(percentile(&sorted, 1.0), Some(20.0));
+    assert_eq!(percentile(&[7.0], 0.95), Some(7.0));
+    assert_eq!(percentile(&[], 0.95), None);
+}
+
+#[test]
+fn test_slo_check_flags_latency_and_failure_rate() {
+    let slo = Slo { p95_ms: Some(100.0), max_failure_rate: 0.1 };
+
+    let mut within = report(9, Some(80.0));
+    within.check(&slo);
+    assert!(within.passed(), "Unexpected violations: {:?}", within.violations);
+
+    let mut slow = report(10, Some(150.0));
+    slow.check(&slo);
+    assert_eq!(slow.violations.len(), 1, "Slow step should be the only violation");
+
+    let mut flaky = report(8, Some(80.0));
+    flaky.check(&slo);
+    assert_eq!(flaky.violations.len(), 1, "20% failures should breach a 10% budget");
+}
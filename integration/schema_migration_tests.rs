@@ -0,0 +1,320 @@
+// Schema Migration Tests
+//
+// Migrates a vault from `DOCUMENT_SCHEMA` to `MIGRATED_DOCUMENT_SCHEMA` (a new `commenter`
+// relation, `view` renamed to `read`) while a background task evaluates both permissions
+// continuously. No evaluate may fail with a 5xx at any point, and each decision must switch exactly
+// once: `view` from ALLOW to not granted, `read` from not granted to ALLOW, never flapping back.
+// Switchover latency is measured from the deploy call to the first evaluate after which only the
+// new semantics are observed.
+//
+// The downgrade test then rolls back: once tuples use a relation only the newer schema declares,
+// redeploying the older schema must either be refused, leaving the newer schema in force, or be
+// accepted with the orphaned tuples handled, never granting anything and never turning evaluates
+// or lists into server errors for the relations the older schema still has.
+
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use reqwest::StatusCode;
+
+use super::*;
+
+const DOCUMENT: &str = "document:handbook";
+
+/// Longest acceptable time from deploying the new schema to only observing its semantics
+const SWITCHOVER_SLO: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Traffic observed before the migration, establishing the old semantics
+const BASELINE: tokio::time::Duration = tokio::time::Duration::from_secs(1);
+
+/// Traffic kept running after the new semantics first appear, to catch a flap back
+const SETTLE: tokio::time::Duration = tokio::time::Duration::from_secs(2);
+
+/// How one evaluate was answered
+#[derive(Debug, Clone, PartialEq)]
+enum Outcome {
+    Allowed,
+    /// Denied, or rejected with a 4xx (an unknown permission may be either)
+    NotGranted,
+    ServerError(String),
+    Failed(String),
+}
+
+/// One round of evaluate traffic: both permissions, back to back
+#[derive(Debug, Clone)]
+struct Sample {
+    /// When both answers were in
+    answered: Instant,
+    view: Outcome,
+    read: Outcome,
+}
+
+async fn outcome(engine: &EngineClient, permission: &str) -> Outcome {
+    match engine.evaluate(DOCUMENT, permission, "user:alice").await {
+        Ok(response) if response.allowed() => Outcome::Allowed,
+        Ok(_) => Outcome::NotGranted,
+        Err(e) if e.status().is_some_and(|status| status.is_server_error()) => {
+            Outcome::ServerError(e.to_string())
+        },
+        Err(e) if e.status().is_some_and(|status| status.is_client_error()) => Outcome::NotGranted,
+        Err(e) => Outcome::Failed(e.to_string()),
+    }
+}
+
+/// Evaluate `view` and `read` until `stop` is set
+async fn evaluate_traffic(engine: EngineClient, stop: Arc<AtomicBool>) -> Vec<Sample> {
+    let mut samples = Vec::new();
+    while !stop.load(Ordering::SeqCst) {
+        let view = outcome(&engine, "view").await;
+        let read = outcome(&engine, "read").await;
+        samples.push(Sample { answered: Instant::now(), view, read });
+    }
+    samples
+}
+
+/// Index of the first sample whose `pick` outcome is `after`, requiring every earlier one to be
+/// `before` and every later one to stay `after`
+fn single_switch(
+    samples: &[Sample],
+    permission: &str,
+    pick: impl Fn(&Sample) -> &Outcome,
+    before: &Outcome,
+    after: &Outcome,
+) -> usize {
+    let switch = samples
+        .iter()
+        .position(|sample| pick(sample) == after)
+        .unwrap_or_else(|| panic!("'{}' never switched to {:?}", permission, after));
+    for (index, sample) in samples.iter().enumerate() {
+        let expected = if index < switch { before } else { after };
+        assert_eq!(
+            pick(sample),
+            expected,
+            "'{}' flapped at sample {} of {} (switched at {})",
+            permission,
+            index,
+            samples.len(),
+            switch
+        );
+    }
+    switch
+}
+
+#[tokio::test]
+async fn test_evaluate_during_schema_migration() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    if !schema_validation_tests::deploy_schema(&fixture, DOCUMENT_SCHEMA).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    engine
+        .write_relationships(&[Relationship::new(DOCUMENT, "viewer", "user:alice")])
+        .await
+        .expect("Failed to write relationship");
+    engine
+        .evaluate(DOCUMENT, "view", "user:alice")
+        .await
+        .expect("Failed to evaluate")
+        .assert_allowed("viewer under the original schema");
+
+    let stop = Arc::new(AtomicBool::new(false));
+    let traffic = tokio::spawn(evaluate_traffic(engine.clone(), Arc::clone(&stop)));
+    tokio::time::sleep(BASELINE).await;
+
+    let deploy_started = Instant::now();
+    assert!(
+        schema_validation_tests::deploy_schema(&fixture, MIGRATED_DOCUMENT_SCHEMA).await,
+        "Schema endpoint disappeared between deploys"
+    );
+    let deploy_took = deploy_started.elapsed();
+
+    while !engine.check(DOCUMENT, "read", "user:alice").await.unwrap_or(false) {
+        assert!(
+            deploy_started.elapsed() < SWITCHOVER_SLO,
+            "'read' not granted within {:?} of deploying the migrated schema",
+            SWITCHOVER_SLO
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    }
+    tokio::time::sleep(SETTLE).await;
+    stop.store(true, Ordering::SeqCst);
+    let samples = traffic.await.expect("Evaluate traffic task panicked");
+
+    let failures: Vec<_> = samples
+        .iter()
+        .flat_map(|sample| [&sample.view, &sample.read])
+        .filter_map(|outcome| match outcome {
+            Outcome::ServerError(e) | Outcome::Failed(e) => Some(e),
+            _ => None,
+        })
+        .collect();
+    assert!(
+        failures.is_empty(),
+        "{} of {} evaluates failed during the migration, first: {}",
+        failures.len(),
+        samples.len() * 2,
+        failures[0]
+    );
+    println!("✓ {} evaluates during the migration, no 5xx", samples.len() * 2);
+
+    let view_switch = single_switch(
+        &samples,
+        "view",
+        |sample| &sample.view,
+        &Outcome::Allowed,
+        &Outcome::NotGranted,
+    );
+    let read_switch = single_switch(
+        &samples,
+        "read",
+        |sample| &sample.read,
+        &Outcome::NotGranted,
+        &Outcome::Allowed,
+    );
+    let switched = &samples[view_switch.max(read_switch)];
+    assert!(
+        switched.answered >= deploy_started,
+        "Semantics changed before the migrated schema was deployed"
+    );
+    let switchover = switched.answered - deploy_started;
+    println!(
+        "✓ view → read switched cleanly {:?} after deploy (deploy call took {:?})",
+        switchover, deploy_took
+    );
+    assert!(
+        switchover <= SWITCHOVER_SLO,
+        "Switchover took {:?}, exceeding {:?}",
+        switchover,
+        SWITCHOVER_SLO
+    );
+
+    // The relation the migration added is usable
+    engine
+        .write_relationships(&[Relationship::new(DOCUMENT, "commenter", "user:bob")])
+        .await
+        .expect("Failed to write tuple with the new relation");
+    engine
+        .evaluate(DOCUMENT, "read", "user:bob")
+        .await
+        .expect("Failed to evaluate")
+        .assert_allowed("commenter under the migrated schema");
+    println!("✓ New 'commenter' relation grants 'read'");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_schema_downgrade_with_orphaned_tuples() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    if !schema_validation_tests::deploy_schema(&fixture, MIGRATED_DOCUMENT_SCHEMA).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write", "inferadb.list-relationships"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    engine
+        .write_relationships(&[
+            Relationship::new(DOCUMENT, "viewer", "user:alice"),
+            Relationship::new(DOCUMENT, "commenter", "user:bob"),
+        ])
+        .await
+        .expect("Failed to write relationships");
+    engine
+        .evaluate(DOCUMENT, "read", "user:bob")
+        .await
+        .expect("Failed to evaluate")
+        .assert_allowed("commenter under the newer schema");
+
+    let req = DeploySchemaRequest { definition: DOCUMENT_SCHEMA.to_string() };
+    let rollback_started = Instant::now();
+    match fixture.control().deploy_schema(fixture.org_id, fixture.vault_id, &req).await {
+        Err(e)
+            if matches!(
+                e.status(),
+                Some(
+                    StatusCode::BAD_REQUEST
+                        | StatusCode::CONFLICT
+                        | StatusCode::UNPROCESSABLE_ENTITY
+                )
+            ) =>
+        {
+            assert!(
+                e.body().is_some_and(|body| !body.trim().is_empty()),
+                "Refused rollback should say why: {}",
+                e
+            );
+            println!("✓ Rollback refused while tuples use 'commenter': {}", e);
+
+            engine
+                .evaluate(DOCUMENT, "read", "user:bob")
+                .await
+                .expect("Evaluate failed after refused rollback")
+                .assert_allowed("commenter after the rollback was refused");
+            println!("✓ Newer schema still in force");
+        },
+        Err(e) => panic!("Rollback failed instead of being accepted or refused: {}", e),
+        Ok(()) => {
+            println!("  Rollback accepted; checking orphaned 'commenter' tuples are handled");
+
+            // 'read' only exists in the newer schema; once it stops granting, the old one is active
+            while outcome(&engine, "read").await == Outcome::Allowed {
+                assert!(
+                    rollback_started.elapsed() < SWITCHOVER_SLO,
+                    "Newer schema still in force {:?} after the rollback was accepted",
+                    SWITCHOVER_SLO
+                );
+                tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            }
+            println!("✓ Old schema active {:?} after the rollback", rollback_started.elapsed());
+            let view = outcome(&engine, "view").await;
+            assert_eq!(view, Outcome::Allowed, "'viewer' must still grant 'view' after rollback");
+            println!("✓ Old relations still evaluate after the rollback");
+
+            let bob = engine.check(DOCUMENT, "view", "user:bob").await;
+            assert!(
+                matches!(bob, Ok(false))
+                    || bob.as_ref().is_err_and(|e| e.status().is_some_and(|s| s.is_client_error())),
+                "Orphaned 'commenter' tuple must not grant 'view': {:?}",
+                bob.map_err(|e| e.to_string())
+            );
+            println!("✓ Orphaned 'commenter' tuple grants nothing");
+
+            let listed = engine
+                .list_all_relationships(&RelationshipFilter::new().resource(DOCUMENT))
+                .await
+                .expect("Listing a vault with orphaned tuples failed");
+            let orphans = listed.iter().filter(|tuple| tuple.relation == "commenter").count();
+            println!(
+                "✓ Listing works with orphaned tuples ({} {})",
+                orphans,
+                if orphans == 0 { "hidden" } else { "still listed" }
+            );
+
+            let err = engine
+                .write_relationships(&[Relationship::new(DOCUMENT, "commenter", "user:carol")])
+                .await
+                .expect_err("Writes to a relation the rolled-back schema lacks should be rejected");
+            assert!(
+                err.status().is_some_and(|status| status.is_client_error()),
+                "Expected a 4xx for a write to 'commenter' after rollback, got {}",
+                err
+            );
+            println!("✓ New 'commenter' writes rejected after the rollback");
+        },
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,166 @@
+// Schema Validation Tests
+//
+// Tests that relationship writes are checked against the vault's schema: tuples using relations,
+// resource types, or subject types the schema does not declare must be rejected with an error
+// that names the offending element, while declared tuples are accepted.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Deploy `definition` to the fixture's vault
+///
+/// Returns false (after logging) if Control does not expose schema management.
+pub(super) async fn deploy_schema(fixture: &TestFixture, definition: &str) -> bool {
+    let req = DeploySchemaRequest { definition: definition.to_string() };
+    match fixture.control().deploy_schema(fixture.org_id, fixture.vault_id, &req).await {
+        Ok(()) => true,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping schema test - schema endpoint not available: {}", e));
+            false
+        },
+        Err(e) => panic!("Failed to deploy schema: {}", e),
+    }
+}
+
+/// Deploy a schema that uses an optional language `feature` (e.g. exclusion)
+///
+/// Like `deploy_schema`, but a 400/422 rejection of the definition is also treated as "not
+/// supported" rather than a failure.
+pub(super) async fn deploy_schema_with(
+    fixture: &TestFixture,
+    definition: &str,
+    feature: &str,
+) -> bool {
+    let req = DeploySchemaRequest { definition: definition.to_string() };
+    match fixture.control().deploy_schema(fixture.org_id, fixture.vault_id, &req).await {
+        Ok(()) => true,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping {} test - schema endpoint not available: {}",
+                feature, e
+            ));
+            false
+        },
+        Err(e)
+            if e.status() == Some(StatusCode::BAD_REQUEST)
+                || e.status() == Some(StatusCode::UNPROCESSABLE_ENTITY) =>
+        {
+            outcome::skip(format!(
+                "Skipping {} test - schema language rejected it: {}",
+                feature, e
+            ));
+            false
+        },
+        Err(e) => panic!("Failed to deploy schema: {}", e),
+    }
+}
+
+/// Write a single tuple that violates the schema and assert a schema-aware rejection
+async fn assert_write_rejected(fixture: &TestFixture, relationship: Relationship, offending: &str) {
+    let jwt = fixture.generate_jwt(None, &["inferadb.write"]).expect("Failed to generate JWT");
+
+    let err = fixture
+        .engine(&jwt)
+        .write_relationships(std::slice::from_ref(&relationship))
+        .await
+        .expect_err("Write violating the schema should be rejected");
+
+    assert!(
+        err.status() == Some(StatusCode::BAD_REQUEST)
+            || err.status() == Some(StatusCode::UNPROCESSABLE_ENTITY),
+        "Expected 400/422 for {:?}, got {}",
+        relationship,
+        err
+    );
+
+    let body = err.body().unwrap_or_default();
+    assert!(
+        body.contains(offending),
+        "Rejection of {:?} should name '{}', got: {}",
+        relationship,
+        offending,
+        body
+    );
+    println!("✓ Rejected undeclared '{}': {}", offending, body);
+}
+
+#[tokio::test]
+async fn test_write_with_declared_relation_accepted() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    if !deploy_schema(&fixture, DOCUMENT_SCHEMA).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    engine
+        .write_relationships(&[Relationship::new("document:spec", "editor", "user:alice")])
+        .await
+        .expect("Write matching the schema should succeed");
+
+    let response =
+        engine.evaluate("document:spec", "edit", "user:alice").await.expect("Failed to evaluate");
+    response.assert_allowed("Declared tuple should grant the derived permission");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_write_with_undeclared_relation_rejected() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    if !deploy_schema(&fixture, DOCUMENT_SCHEMA).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    assert_write_rejected(
+        &fixture,
+        Relationship::new("document:spec", "approver", "user:alice"),
+        "approver",
+    )
+    .await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_write_with_undeclared_resource_type_rejected() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    if !deploy_schema(&fixture, DOCUMENT_SCHEMA).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    assert_write_rejected(
+        &fixture,
+        Relationship::new("invoice:2024-001", "viewer", "user:alice"),
+        "invoice",
+    )
+    .await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_write_with_undeclared_subject_type_rejected() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    if !deploy_schema(&fixture, DOCUMENT_SCHEMA).await {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    // `viewer` only admits `user` subjects
+    assert_write_rejected(
+        &fixture,
+        Relationship::new("document:spec", "viewer", "team:engineering"),
+        "team",
+    )
+    .await;
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
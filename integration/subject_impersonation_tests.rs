@@ -0,0 +1,104 @@
+// Subject Impersonation Boundary Tests
+//
+// Pins the impersonation model: a client JWT authenticates the *client*, which may evaluate and
+// write on behalf of any subject inside its vault. It never authenticates as a subject, so
+// endpoints that act for the calling user reject it, and a client key cannot mint a token whose
+// `sub` names an end user.
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+/// Control endpoints that act for the authenticated user rather than a client
+const USER_SESSION_ENDPOINTS: &[(Method, &str)] =
+    &[(Method::GET, "/organizations"), (Method::GET, "/users/me"), (Method::GET, "/auth/sessions")];
+
+#[tokio::test]
+async fn test_client_jwt_acts_for_arbitrary_subjects() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    // None of these subjects has any relationship to the client or the user who created it
+    let subjects = vec![
+        "user:alice".to_string(),
+        "user:bob".to_string(),
+        format!("user:stranger-{}", Uuid::new_v4()),
+    ];
+
+    for subject in &subjects {
+        engine
+            .write_relationships(&[Relationship::new("document:impersonation", "viewer", subject)])
+            .await
+            .unwrap_or_else(|e| panic!("Client should write on behalf of {}: {}", subject, e));
+
+        let allowed = engine
+            .check("document:impersonation", "viewer", subject)
+            .await
+            .unwrap_or_else(|e| panic!("Client should evaluate on behalf of {}: {}", subject, e));
+        assert!(allowed, "Client evaluation for {} should see the tuple it wrote", subject);
+    }
+    println!("✓ Client JWT wrote and evaluated for {} unrelated subjects", subjects.len());
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_user_session_endpoints_reject_client_jwt() {
+    let fixture = SharedFixture::get().await;
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let ctx = TestContext::new();
+
+    let mut checked = 0;
+    for (method, path) in USER_SESSION_ENDPOINTS {
+        let response = fixture
+            .request(method.clone(), &ctx.control_url(path))
+            .header("Authorization", format!("Bearer {}", jwt))
+            .send_recorded()
+            .await
+            .expect("Failed to call Control");
+
+        let status = response.status();
+        if status == StatusCode::NOT_FOUND || status == StatusCode::METHOD_NOT_ALLOWED {
+            outcome::skip(format!("{} {} not available ({}), skipping", method, path, status));
+            continue;
+        }
+
+        assert!(
+            status == StatusCode::UNAUTHORIZED || status == StatusCode::FORBIDDEN,
+            "{} {} must not accept a client JWT as a user session, got {}",
+            method,
+            path,
+            status
+        );
+        println!("✓ {} {} rejected client JWT: {}", method, path, status);
+        checked += 1;
+    }
+
+    assert!(checked > 0, "No user-session endpoint was available to check");
+}
+
+#[tokio::test]
+async fn test_client_key_cannot_mint_end_user_token() {
+    let fixture = SharedFixture::get().await;
+
+    // Correctly signed by the client's registered key, but claiming to be an end user
+    for sub in ["user:alice", &format!("user:{}", Uuid::new_v4())] {
+        let jwt = fixture.jwt().claim("sub", sub).encode().expect("Failed to encode JWT");
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+
+        assert!(
+            response.status() == StatusCode::UNAUTHORIZED
+                || response.status() == StatusCode::FORBIDDEN,
+            "Client key signing sub={} must be rejected, got {}",
+            sub,
+            response.status()
+        );
+        println!("✓ Client-signed token with sub={} rejected: {}", sub, response.status());
+    }
+}
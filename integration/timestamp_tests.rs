@@ -0,0 +1,162 @@
+// Timestamp Tests
+//
+// Control's `created_at`/`updated_at` are deserialized as UTC RFC 3339 (see `timestamps`), so every
+// response read here already checks the format. These tests check the values: resources created one
+// after another carry non-decreasing `created_at` close to the runner's clock, and a modification
+// advances `updated_at` while leaving `created_at` alone, the same in a vault's listing as in the
+// vault itself.
+
+use chrono::DateTime;
+use reqwest::StatusCode;
+
+use super::*;
+
+/// How far Control's clock may sit from the runner's before timestamps count as wrong
+const CLOCK_TOLERANCE: Duration = Duration::seconds(60);
+
+/// Pause before a modification, so timestamps with whole-second resolution still move
+const RESOLUTION_PAUSE: tokio::time::Duration = tokio::time::Duration::from_millis(1100);
+
+fn assert_near_now(name: &str, timestamp: DateTime<Utc>, before: DateTime<Utc>) {
+    let after = Utc::now();
+    assert!(
+        timestamp >= before - CLOCK_TOLERANCE && timestamp <= after + CLOCK_TOLERANCE,
+        "{} {} outside the request window {} .. {}",
+        name,
+        timestamp,
+        before,
+        after
+    );
+}
+
+#[tokio::test]
+async fn test_created_at_follows_operation_order() {
+    let before = Utc::now();
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let vault = control
+        .get_vault(fixture.org_id, fixture.vault_id)
+        .await
+        .expect("Failed to read fixture vault");
+    assert_near_now("Fixture vault created_at", vault.created_at, before);
+
+    let vault_req = CreateVaultRequest {
+        name: naming::entity_name("Timestamp Vault"),
+        organization_id: fixture.org_id,
+    };
+    let second_vault = control
+        .create_vault(fixture.org_id, &vault_req)
+        .await
+        .expect("Failed to create vault")
+        .vault;
+    let client = control
+        .create_client(
+            fixture.org_id,
+            &CreateClientRequest { name: naming::entity_name("Timestamp Client") },
+        )
+        .await
+        .expect("Failed to create client")
+        .client;
+    let certificate = control
+        .create_certificate(
+            fixture.org_id,
+            client.id,
+            &CreateCertificateRequest::new(naming::entity_name("Timestamp Certificate")),
+        )
+        .await
+        .expect("Failed to create certificate")
+        .certificate;
+
+    let sequence = [
+        ("fixture vault", vault.created_at),
+        ("second vault", second_vault.created_at),
+        ("client", client.created_at),
+        ("certificate", certificate.created_at),
+    ];
+    for pair in sequence.windows(2) {
+        let [(earlier, earlier_at), (later, later_at)] = pair else { unreachable!() };
+        assert!(
+            later_at >= earlier_at,
+            "{} created_at {} precedes {} created_at {}, though created after it",
+            later,
+            later_at,
+            earlier,
+            earlier_at
+        );
+    }
+    for (name, created_at) in &sequence[1..] {
+        assert_near_now(&format!("{} created_at", name), *created_at, before);
+    }
+    println!("✓ created_at non-decreasing across vault, client, and certificate creation");
+
+    let _ = control.delete_client(fixture.org_id, client.id).await;
+    let _ = control.delete_vault(fixture.org_id, second_vault.id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_updated_at_tracks_modifications() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let original = control
+        .get_vault(fixture.org_id, fixture.vault_id)
+        .await
+        .expect("Failed to read fixture vault");
+    assert!(
+        original.updated_at >= original.created_at,
+        "updated_at {} precedes created_at {}",
+        original.updated_at,
+        original.created_at
+    );
+
+    tokio::time::sleep(RESOLUTION_PAUSE).await;
+    let before = Utc::now();
+    let update = serde_json::json!({ "description": format!("timestamp-{}", Uuid::new_v4()) });
+    match control.update_vault(fixture.org_id, fixture.vault_id, &update).await {
+        Ok(()) => {},
+        Err(e)
+            if matches!(
+                e.status(),
+                Some(StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_FOUND)
+            ) =>
+        {
+            outcome::skip(format!("Skipping updated_at test - vault update not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to update vault: {}", e),
+    }
+
+    let updated = control
+        .get_vault(fixture.org_id, fixture.vault_id)
+        .await
+        .expect("Failed to read updated vault");
+    assert_eq!(updated.created_at, original.created_at, "Update changed created_at");
+    assert!(
+        updated.updated_at > original.updated_at,
+        "updated_at did not advance: {} before, {} after",
+        original.updated_at,
+        updated.updated_at
+    );
+    assert_near_now("Updated vault updated_at", updated.updated_at, before);
+    println!("✓ Update advanced updated_at and left created_at alone");
+
+    let listed = control
+        .list_vaults(fixture.org_id)
+        .await
+        .expect("Failed to list vaults")
+        .vaults
+        .into_iter()
+        .find(|vault| vault.id == fixture.vault_id)
+        .expect("Fixture vault missing from listing");
+    assert_eq!(
+        (listed.created_at, listed.updated_at),
+        (updated.created_at, updated.updated_at),
+        "Listing and vault disagree on timestamps"
+    );
+    println!("✓ Listing reports the same timestamps as the vault");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -9,27 +9,9 @@
 // token validation.
 
 use reqwest::StatusCode;
-use serde::Deserialize;
 
 use super::*;
 
-/// Response from certificate rotation endpoint
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct RotateCertificateResponse {
-    pub certificate: CertificateInfo,
-    pub valid_from: String,
-    pub rotated_from: CertificateInfo,
-    pub private_key: String,
-}
-
-/// Response from certificate revocation endpoint
-#[derive(Debug, Deserialize)]
-#[allow(dead_code)]
-pub struct RevokeCertificateResponse {
-    pub message: String,
-}
-
 // =============================================================================
 // Full Token Lifecycle Test
 // =============================================================================
@@ -62,28 +44,21 @@ async fn test_full_token_lifecycle() {
         "Expected 200 or 404 for valid token, got {}",
         response.status()
     );
+    certificate_status_tests::assert_listing_in_sync(
+        &fixture,
+        &fixture.cert_kid,
+        &fixture.signing_key,
+        CertificateState::Active,
+    )
+    .await;
 
     // 3. Revoke the certificate
-    let revoke_url = fixture.ctx.control_url(&format!(
-        "/organizations/{}/clients/{}/certificates/{}",
-        fixture.org_id, fixture.client_id, fixture.cert_id
-    ));
-
-    let revoke_response = fixture
-        .ctx
-        .client
-        .delete(&revoke_url)
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
+    fixture
+        .control()
+        .revoke_certificate(fixture.org_id, fixture.client_id, fixture.cert_id)
         .await
         .expect("Failed to revoke certificate");
 
-    assert!(
-        revoke_response.status().is_success(),
-        "Certificate revocation failed with status {}",
-        revoke_response.status()
-    );
-
     // 4. Generate new JWT with the same (now revoked) key
     // The JWT is structurally valid but the key is revoked in Ledger
     let post_revoke_jwt = fixture
@@ -106,6 +81,15 @@ async fn test_full_token_lifecycle() {
         rejected_response.status()
     );
 
+    // 6. Control's listing agrees with Engine
+    certificate_status_tests::assert_listing_in_sync(
+        &fixture,
+        &fixture.cert_kid,
+        &fixture.signing_key,
+        CertificateState::Revoked,
+    )
+    .await;
+
     fixture.cleanup().await.expect("Failed to cleanup");
 }
 
@@ -140,33 +124,17 @@ async fn test_key_rotation_grace_period() {
     );
 
     // 2. Rotate the certificate with a 5-minute (300 second) grace period
-    let rotate_url = fixture.ctx.control_url(&format!(
-        "/organizations/{}/clients/{}/certificates/{}/rotate",
-        fixture.org_id, fixture.client_id, fixture.cert_id
-    ));
-
-    let rotate_response = fixture
-        .ctx
-        .client
-        .post(&rotate_url)
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .json(&serde_json::json!({
-            "name": format!("Rotated Certificate {}", Uuid::new_v4()),
-            "grace_period_seconds": 300
-        }))
-        .send()
+    let rotate_req = RotateCertificateRequest {
+        name: naming::entity_name("Rotated Certificate"),
+        grace_period_seconds: 300,
+    };
+
+    let rotation_result = fixture
+        .control()
+        .rotate_certificate(fixture.org_id, fixture.client_id, fixture.cert_id, &rotate_req)
         .await
         .expect("Failed to rotate certificate");
 
-    assert!(
-        rotate_response.status().is_success(),
-        "Certificate rotation failed with status {}",
-        rotate_response.status()
-    );
-
-    let rotation_result: RotateCertificateResponse =
-        rotate_response.json().await.expect("Failed to parse rotation response");
-
     // 3. Original key should still work immediately after rotation
     let post_rotate_original_jwt = fixture
         .generate_jwt(None, &["inferadb.check"])
@@ -199,7 +167,7 @@ async fn test_key_rotation_grace_period() {
     let claims = ClientClaims {
         iss: fixture.ctx.api_base_url.clone(),
         sub: format!("client:{}", fixture.client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
+        aud: EnvProfile::current().audience.clone(),
         exp: (now + Duration::minutes(5)).timestamp(),
         iat: now.timestamp(),
         jti: Uuid::new_v4().to_string(),
@@ -212,10 +180,8 @@ async fn test_key_rotation_grace_period() {
     let mut header = Header::new(Algorithm::EdDSA);
     header.kid = Some(rotation_result.certificate.kid.clone());
 
-    let new_secret_bytes = new_signing_key.to_bytes();
-    let new_pem = ed25519_to_pem(&new_secret_bytes);
     let new_encoding_key =
-        EncodingKey::from_ed_pem(&new_pem).expect("Failed to create encoding key for new key");
+        keys::encoding_key(&new_signing_key).expect("Failed to create encoding key for new key");
 
     let new_key_jwt =
         encode(&header, &claims, &new_encoding_key).expect("Failed to encode new JWT");
@@ -250,31 +216,10 @@ async fn test_key_rotation_grace_period() {
 /// included here for completeness of the token lifecycle test suite.
 #[tokio::test]
 async fn test_token_expiration_honored() {
-    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let fixture = SharedFixture::get().await;
 
     // Generate JWT that expired 10 minutes ago
-    let now = Utc::now();
-    let claims = ClientClaims {
-        iss: fixture.ctx.api_base_url.clone(),
-        sub: format!("client:{}", fixture.client_id),
-        aud: REQUIRED_AUDIENCE.to_string(),
-        exp: (now - Duration::minutes(10)).timestamp(), // Expired
-        iat: (now - Duration::minutes(15)).timestamp(),
-        jti: Uuid::new_v4().to_string(),
-        vault_id: fixture.vault_id.to_string(),
-        org_id: fixture.org_id.to_string(),
-        scope: "inferadb.check inferadb.read".to_string(),
-        vault_role: "read".to_string(),
-    };
-
-    let mut header = Header::new(Algorithm::EdDSA);
-    header.kid = Some(fixture.cert_kid.clone());
-
-    let secret_bytes = fixture.signing_key.to_bytes();
-    let pem = ed25519_to_pem(&secret_bytes);
-    let encoding_key = EncodingKey::from_ed_pem(&pem).expect("Failed to create encoding key");
-    let expired_jwt =
-        encode(&header, &claims, &encoding_key).expect("Failed to encode expired JWT");
+    let expired_jwt = fixture.jwt().expired().encode().expect("Failed to encode expired JWT");
 
     // Engine should reject expired tokens
     let response = fixture
@@ -288,8 +233,93 @@ async fn test_token_expiration_honored() {
         "Engine must reject expired tokens, got {}",
         response.status()
     );
+}
 
-    fixture.cleanup().await.expect("Failed to cleanup");
+/// Test: Engine rejects tokens whose `nbf` is in the future
+#[tokio::test]
+async fn test_not_yet_valid_token_rejected() {
+    let fixture = SharedFixture::get().await;
+
+    let jwt = fixture.jwt().not_yet_valid().encode().expect("Failed to encode JWT");
+    let response = fixture
+        .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+
+    assert_eq!(
+        response.status(),
+        StatusCode::UNAUTHORIZED,
+        "Engine must reject tokens before their nbf, got {}",
+        response.status()
+    );
+}
+
+/// Test: Pin the exact expiry enforcement boundary
+///
+/// Engine accepts a token until `exp + leeway`. Shifting the token back in time so it sits one
+/// second either side of that boundary shows whether enforcement matches the profile's leeway.
+#[tokio::test]
+async fn test_expiry_enforcement_boundary() {
+    let fixture = SharedFixture::get().await;
+    let leeway = EnvProfile::current().clock_leeway_seconds;
+
+    // Expires 1s from now, then moved back by the leeway: 1s before the effective boundary
+    let before = fixture
+        .jwt()
+        .expiring_in(1)
+        .time_shift(Duration::seconds(-leeway))
+        .encode()
+        .expect("Failed to encode JWT");
+
+    // Expired 1s ago, moved back by the leeway: 1s past the effective boundary
+    let after = fixture
+        .jwt()
+        .expiring_in(-1)
+        .time_shift(Duration::seconds(-leeway))
+        .encode()
+        .expect("Failed to encode JWT");
+
+    let before_response = fixture
+        .call_server_evaluate(&before, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+    let after_response = fixture
+        .call_server_evaluate(&after, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+
+    assert!(
+        before_response.status() == StatusCode::OK
+            || before_response.status() == StatusCode::NOT_FOUND,
+        "Token 1s before expiry (leeway {}s) should be accepted, got {}",
+        leeway,
+        before_response.status()
+    );
+    assert_eq!(
+        after_response.status(),
+        StatusCode::UNAUTHORIZED,
+        "Token 1s after expiry (leeway {}s) should be rejected, got {}",
+        leeway,
+        after_response.status()
+    );
+}
+
+/// Test: A token close to expiry is still accepted
+#[tokio::test]
+async fn test_near_expiry_token_accepted() {
+    let fixture = SharedFixture::get().await;
+
+    let jwt = fixture.jwt().expiring_in(30).encode().expect("Failed to encode JWT");
+    let response = fixture
+        .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+
+    assert!(
+        response.status() == StatusCode::OK || response.status() == StatusCode::NOT_FOUND,
+        "Token expiring in 30s should be accepted, got {}",
+        response.status()
+    );
 }
 
 // =============================================================================
@@ -303,42 +333,25 @@ async fn test_token_expiration_honored() {
 async fn test_certificate_revocation_idempotent() {
     let fixture = TestFixture::create().await.expect("Failed to create test fixture");
 
-    let revoke_url = fixture.ctx.control_url(&format!(
-        "/organizations/{}/clients/{}/certificates/{}",
-        fixture.org_id, fixture.client_id, fixture.cert_id
-    ));
+    let control = fixture.control();
 
     // First revocation should succeed
-    let first_revoke = fixture
-        .ctx
-        .client
-        .delete(&revoke_url)
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
+    control
+        .revoke_certificate(fixture.org_id, fixture.client_id, fixture.cert_id)
         .await
-        .expect("Failed to send first revocation request");
-
-    assert!(
-        first_revoke.status().is_success(),
-        "First revocation should succeed, got {}",
-        first_revoke.status()
-    );
+        .expect("First revocation should succeed");
 
     // Second revocation should fail with validation error (already revoked)
-    let second_revoke = fixture
-        .ctx
-        .client
-        .delete(&revoke_url)
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
+    let err = control
+        .revoke_certificate(fixture.org_id, fixture.client_id, fixture.cert_id)
         .await
-        .expect("Failed to send second revocation request");
+        .expect_err("Second revocation should fail");
 
     assert_eq!(
-        second_revoke.status(),
-        StatusCode::BAD_REQUEST,
+        err.status(),
+        Some(StatusCode::BAD_REQUEST),
         "Second revocation should fail with 400 (already revoked), got {}",
-        second_revoke.status()
+        err
     );
 
     fixture.cleanup().await.expect("Failed to cleanup");
@@ -356,47 +369,30 @@ async fn test_certificate_revocation_idempotent() {
 async fn test_cannot_rotate_revoked_certificate() {
     let fixture = TestFixture::create().await.expect("Failed to create test fixture");
 
+    let control = fixture.control();
+
     // First revoke the certificate
-    let revoke_url = fixture.ctx.control_url(&format!(
-        "/organizations/{}/clients/{}/certificates/{}",
-        fixture.org_id, fixture.client_id, fixture.cert_id
-    ));
-
-    let revoke_response = fixture
-        .ctx
-        .client
-        .delete(&revoke_url)
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .send()
+    control
+        .revoke_certificate(fixture.org_id, fixture.client_id, fixture.cert_id)
         .await
-        .expect("Failed to revoke certificate");
-
-    assert!(revoke_response.status().is_success(), "Revocation should succeed");
+        .expect("Revocation should succeed");
 
     // Attempt to rotate the revoked certificate
-    let rotate_url = fixture.ctx.control_url(&format!(
-        "/organizations/{}/clients/{}/certificates/{}/rotate",
-        fixture.org_id, fixture.client_id, fixture.cert_id
-    ));
-
-    let rotate_response = fixture
-        .ctx
-        .client
-        .post(&rotate_url)
-        .header("Authorization", format!("Bearer {}", fixture.session_id))
-        .json(&serde_json::json!({
-            "name": format!("Should Fail {}", Uuid::new_v4()),
-            "grace_period_seconds": 300
-        }))
-        .send()
+    let rotate_req = RotateCertificateRequest {
+        name: naming::entity_name("Should Fail"),
+        grace_period_seconds: 300,
+    };
+
+    let err = control
+        .rotate_certificate(fixture.org_id, fixture.client_id, fixture.cert_id, &rotate_req)
         .await
-        .expect("Failed to send rotation request");
+        .expect_err("Rotating a revoked certificate should fail");
 
     assert_eq!(
-        rotate_response.status(),
-        StatusCode::BAD_REQUEST,
+        err.status(),
+        Some(StatusCode::BAD_REQUEST),
         "Cannot rotate a revoked certificate, got {}",
-        rotate_response.status()
+        err
     );
 
     fixture.cleanup().await.expect("Failed to cleanup");
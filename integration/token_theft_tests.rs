@@ -0,0 +1,171 @@
+// Token Theft Tests
+//
+// Replays one client JWT from a second HTTP client, as a thief holding a copied token would: a
+// different user agent and, where Engine takes client addresses from forwarding headers
+// (`EnvProfile::forwarded_headers_trusted`), a different source IP, used moments after the
+// original. Where the environment runs reuse detection (`EnvProfile::token_reuse_detection`),
+// Engine must block the replay or flag it in the organization's audit log. Elsewhere the replay is
+// accepted, and the test pins that, so turning detection on is a visible change rather than a
+// silent one. Repeated use from the original client is never treated as theft.
+
+use std::time::Instant;
+
+use reqwest::{
+    StatusCode,
+    header::{HeaderMap, HeaderValue},
+};
+
+use super::*;
+
+const ORIGINAL_AGENT: &str = "inferadb-sdk/1.0 (token-theft-original)";
+const THIEF_AGENT: &str = "curl/8.5.0";
+
+/// Documentation-range address (RFC 5737) the thief claims through forwarding headers
+const THIEF_IP: &str = "203.0.113.66";
+
+/// How long a flag may take to reach the audit log
+const FLAG_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Evaluates the original client sends in a burst without looking suspicious
+const ORIGINAL_BURST: usize = 20;
+
+/// A context whose requests carry `user_agent` and, if given, claim `ip` in forwarding headers
+fn context(user_agent: &str, ip: Option<&str>) -> TestContext {
+    let mut headers = HeaderMap::new();
+    if let Some(ip) = ip {
+        let value = HeaderValue::from_str(ip).expect("Invalid IP header");
+        headers.insert("X-Forwarded-For", value.clone());
+        headers.insert("X-Real-IP", value);
+    }
+    let client = reqwest::Client::builder()
+        .user_agent(user_agent)
+        .default_headers(headers)
+        .timeout(std::time::Duration::from_secs(30))
+        .danger_accept_invalid_certs(true) // For dev self-signed certs
+        .build()
+        .expect("Failed to create HTTP client");
+    TestContext { client: HttpClient::new(client), ..TestContext::new() }
+}
+
+/// The original holder of `jwt` and a thief replaying it from elsewhere
+fn original_and_thief(jwt: &str) -> (EngineClient, EngineClient) {
+    let ip = EnvProfile::current().forwarded_headers_trusted.then_some(THIEF_IP);
+    (
+        EngineClient::new(context(ORIGINAL_AGENT, None), jwt),
+        EngineClient::new(context(THIEF_AGENT, ip), jwt),
+    )
+}
+
+/// Whether Engine accepted the token; a 404 for the unseeded resource still authenticated
+async fn authenticate(engine: &EngineClient) -> ApiResult<()> {
+    match engine.evaluate("document:1", "viewer", "user:alice").await {
+        Ok(_) => Ok(()),
+        Err(e) if e.status() == Some(StatusCode::NOT_FOUND) => Ok(()),
+        Err(e) => Err(e),
+    }
+}
+
+fn is_theft_flag(event: &AuditEvent) -> bool {
+    let action = event.action.to_lowercase();
+    ["reuse", "theft", "anomal", "suspicious"].iter().any(|marker| action.contains(marker))
+}
+
+/// Audit events flagging token theft; `None` if Control has no audit log
+async fn theft_flags(control: &ControlClient, org_id: i64) -> Option<Vec<AuditEvent>> {
+    match control.list_audit_events(org_id).await {
+        Ok(log) => Some(log.events.into_iter().filter(is_theft_flag).collect()),
+        Err(e) if e.is_unsupported() => None,
+        Err(e) => panic!("Failed to list audit events: {}", e),
+    }
+}
+
+#[tokio::test]
+async fn test_token_replayed_from_second_client() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.jwt().encode().expect("Failed to encode JWT");
+    let (original, thief) = original_and_thief(&jwt);
+    let profile = EnvProfile::current();
+
+    authenticate(&original).await.expect("Original client should be accepted");
+    let replay = authenticate(&thief).await;
+    println!(
+        "  replayed with user agent {:?}{}",
+        THIEF_AGENT,
+        if profile.forwarded_headers_trusted {
+            format!(" from {}", THIEF_IP)
+        } else {
+            String::new()
+        }
+    );
+
+    if !profile.token_reuse_detection {
+        replay.unwrap_or_else(|e| {
+            panic!(
+                "Engine refused the replayed token, but no reuse detection is configured; set \
+                 INFERADB_TOKEN_REUSE_DETECTION=true if it now detects theft: {}",
+                e
+            )
+        });
+        authenticate(&original).await.expect("Original client should still be accepted");
+        println!("✓ Pinned: replay from a second client accepted (no reuse detection configured)");
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    }
+
+    match replay {
+        Err(e) => {
+            assert!(
+                matches!(e.status(), Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)),
+                "A blocked replay should be 401 or 403, got {}",
+                e
+            );
+            println!("✓ Replay blocked: {}", e);
+        },
+        Ok(()) => {
+            // Not blocked, so it must at least be flagged
+            let control = fixture.control();
+            let started = Instant::now();
+            loop {
+                let Some(flags) = theft_flags(&control, fixture.org_id).await else {
+                    panic!("Replay was neither blocked nor flagged, and Control has no audit log")
+                };
+                if let Some(flag) = flags.first() {
+                    println!("✓ Replay flagged in the audit log: {}", flag.action);
+                    break;
+                }
+                assert!(
+                    started.elapsed() < FLAG_TIMEOUT,
+                    "Replay from a second client was neither blocked nor flagged within {:?}",
+                    FLAG_TIMEOUT
+                );
+                tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+            }
+        },
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_reuse_from_original_client_not_flagged() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.jwt().encode().expect("Failed to encode JWT");
+    let (original, _) = original_and_thief(&jwt);
+
+    for attempt in 1..=ORIGINAL_BURST {
+        authenticate(&original).await.unwrap_or_else(|e| {
+            panic!("Use {} of the token from its own client was refused: {}", attempt, e)
+        });
+    }
+    println!("✓ {} uses from the original client accepted", ORIGINAL_BURST);
+
+    match theft_flags(&fixture.control(), fixture.org_id).await {
+        Some(flags) => {
+            assert!(flags.is_empty(), "Ordinary reuse was flagged as theft: {:?}", flags);
+            println!("✓ No theft flags in the audit log");
+        },
+        None => println!("  Control has no audit log; flags not checked"),
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
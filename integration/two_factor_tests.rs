@@ -0,0 +1,280 @@
+// Two-Factor Authentication Tests
+//
+// TOTP enrollment and login through Control, with the suite computing codes as an authenticator
+// app would (`totp`). Enrollment stays pending until confirmed with a valid code, and only then
+// hands out recovery codes. Once enrolled, a password alone yields a challenge instead of a
+// session; the challenge is completed with a TOTP code or a recovery code, each recovery code
+// working once. Two-factor authentication guards interactive login only: sessions that already
+// exist keep managing the organization, and Engine client JWTs are unaffected. Skipped if Control
+// does not support TOTP.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// A confirmed TOTP enrollment of the fixture's user
+struct Enrollment {
+    email: String,
+    secret: String,
+    /// Last code sent, which the server may refuse to accept again
+    last_code: String,
+    recovery_codes: Vec<String>,
+}
+
+/// A six-digit code that is not `code`, nor the code of a neighbouring step
+fn wrong_code(code: &str) -> String {
+    let value: u32 = code.parse().expect("TOTP code should be numeric");
+    format!("{:06}", (value + 500_000) % 1_000_000)
+}
+
+#[track_caller]
+fn assert_rejected(result: ApiResult<impl std::fmt::Debug>, what: &str) {
+    match result {
+        Ok(response) => panic!("{} should be rejected, got {:?}", what, response),
+        Err(e) => assert!(
+            matches!(
+                e.status(),
+                Some(
+                    StatusCode::BAD_REQUEST
+                        | StatusCode::UNAUTHORIZED
+                        | StatusCode::FORBIDDEN
+                        | StatusCode::UNPROCESSABLE_ENTITY
+                )
+            ),
+            "{} should be rejected with a 4xx, got {}",
+            what,
+            e
+        ),
+    }
+}
+
+async fn session_valid(ctx: &TestContext, session_id: i64) -> bool {
+    ControlClient::with_session(ctx.clone(), session_id).list_organizations().await.is_ok()
+}
+
+/// Enroll and confirm TOTP for the fixture's user; `None` (after recording a skip) if Control
+/// does not support it
+async fn enroll(fixture: &TestFixture) -> Option<Enrollment> {
+    let control = fixture.control();
+    let email = control.get_current_user().await.expect("Failed to get current user").email;
+    let enrollment = match control.enroll_totp().await {
+        Ok(enrollment) => enrollment,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping two-factor test - TOTP not available: {}", e));
+            return None;
+        },
+        Err(e) => panic!("Failed to enroll TOTP: {}", e),
+    };
+    let code = totp::code(&enrollment.secret).expect("Failed to compute TOTP code");
+    let confirmed = control
+        .confirm_totp(&TotpCodeRequest { code: code.clone() })
+        .await
+        .expect("Failed to confirm TOTP enrollment");
+    Some(Enrollment {
+        email,
+        secret: enrollment.secret,
+        last_code: code,
+        recovery_codes: confirmed.recovery_codes,
+    })
+}
+
+/// Start a login with the fixture's password, which must be challenged
+async fn challenge(fixture: &TestFixture, email: &str) -> String {
+    let req = LoginRequest { email: email.to_string(), password: FIXTURE_PASSWORD.to_string() };
+    ControlClient::new(fixture.ctx.clone())
+        .login_challenge(&req)
+        .await
+        .expect("Password login should yield a two-factor challenge")
+        .mfa_token
+}
+
+#[tokio::test]
+async fn test_totp_enrollment_requires_valid_code() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let email = control.get_current_user().await.expect("Failed to get current user").email;
+
+    let enrollment = match control.enroll_totp().await {
+        Ok(enrollment) => enrollment,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping two-factor test - TOTP not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to enroll TOTP: {}", e),
+    };
+    assert!(!enrollment.secret.is_empty(), "Enrollment should carry a secret");
+    if let Some(url) = &enrollment.otpauth_url {
+        assert!(url.starts_with("otpauth://totp/"), "Unexpected provisioning URL {}", url);
+        assert!(url.contains(&enrollment.secret), "Provisioning URL should embed the secret");
+    }
+    println!("✓ Enrollment started");
+
+    let code = totp::code(&enrollment.secret).expect("Failed to compute TOTP code");
+    assert_rejected(
+        control.confirm_totp(&TotpCodeRequest { code: wrong_code(&code) }).await,
+        "Confirmation with a wrong code",
+    );
+    let req = LoginRequest { email: email.clone(), password: FIXTURE_PASSWORD.to_string() };
+    ControlClient::new(fixture.ctx.clone())
+        .login(&req)
+        .await
+        .expect("An unconfirmed enrollment must not change how the user logs in");
+    println!("✓ Wrong code rejected; enrollment still pending");
+
+    let confirmed = control
+        .confirm_totp(&TotpCodeRequest { code })
+        .await
+        .expect("Failed to confirm TOTP enrollment");
+    assert!(!confirmed.recovery_codes.is_empty(), "Confirmation should hand out recovery codes");
+    let unique: std::collections::BTreeSet<_> = confirmed.recovery_codes.iter().collect();
+    assert_eq!(unique.len(), confirmed.recovery_codes.len(), "Recovery codes should be distinct");
+    println!("✓ Enrollment confirmed with {} recovery codes", confirmed.recovery_codes.len());
+
+    assert!(
+        ControlClient::new(fixture.ctx.clone()).login(&req).await.is_err(),
+        "Password alone must not yield a session once TOTP is confirmed"
+    );
+    println!("✓ Password alone no longer logs in");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_login_requires_totp_code() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(enrollment) = enroll(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    let anonymous = ControlClient::new(fixture.ctx.clone());
+
+    let mfa_token = challenge(&fixture, &enrollment.email).await;
+    let code = totp::next_code(&enrollment.secret, &enrollment.last_code)
+        .await
+        .expect("Failed to compute TOTP code");
+    assert_rejected(
+        anonymous
+            .login_totp(&TotpLoginRequest { mfa_token: mfa_token.clone(), code: wrong_code(&code) })
+            .await
+            .map(|login| login.session_id),
+        "Login with a wrong TOTP code",
+    );
+    println!("✓ Wrong TOTP code rejected");
+
+    let login = anonymous
+        .login_totp(&TotpLoginRequest { mfa_token: mfa_token.clone(), code: code.clone() })
+        .await
+        .expect("Login with a valid TOTP code should succeed");
+    assert_eq!(login.user_id, fixture.user_id, "Login should be for the enrolled user");
+    assert!(session_valid(&fixture.ctx, login.session_id).await, "New session should be usable");
+    println!("✓ Valid TOTP code completes the login");
+
+    assert_rejected(
+        anonymous
+            .login_totp(&TotpLoginRequest { mfa_token, code })
+            .await
+            .map(|login| login.session_id),
+        "A completed challenge used again",
+    );
+    println!("✓ Challenge cannot be completed twice");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_recovery_codes_work_once() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some(enrollment) = enroll(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    assert!(enrollment.recovery_codes.len() >= 2, "Expected at least two recovery codes");
+    let anonymous = ControlClient::new(fixture.ctx.clone());
+    let recover = |code: &str, mfa_token: String| {
+        let req = RecoveryLoginRequest { mfa_token, recovery_code: code.to_string() };
+        let anonymous = anonymous.clone();
+        async move { anonymous.login_recovery(&req).await }
+    };
+
+    let first = &enrollment.recovery_codes[0];
+    let login = recover(first, challenge(&fixture, &enrollment.email).await)
+        .await
+        .expect("Login with a recovery code should succeed");
+    assert!(session_valid(&fixture.ctx, login.session_id).await, "New session should be usable");
+    println!("✓ Recovery code completes the login");
+
+    assert_rejected(
+        recover(first, challenge(&fixture, &enrollment.email).await)
+            .await
+            .map(|login| login.session_id),
+        "A used recovery code",
+    );
+    println!("✓ Used recovery code rejected");
+
+    assert_rejected(
+        recover("not-a-recovery-code", challenge(&fixture, &enrollment.email).await)
+            .await
+            .map(|login| login.session_id),
+        "An unknown recovery code",
+    );
+    recover(&enrollment.recovery_codes[1], challenge(&fixture, &enrollment.email).await)
+        .await
+        .expect("An unused recovery code should still work");
+    println!("✓ Remaining recovery codes still work");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_api_flows_unaffected_by_two_factor() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let evaluate = || async {
+        let jwt = fixture.jwt().encode().expect("Failed to encode JWT");
+        fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server")
+            .status()
+    };
+    assert_ne!(evaluate().await, StatusCode::UNAUTHORIZED, "JWT should work before enrollment");
+
+    let Some(enrollment) = enroll(&fixture).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+    println!("✓ TOTP enrolled");
+
+    // The fixture's session predates enrollment and keeps full access
+    control.list_vaults(fixture.org_id).await.expect("Existing session should list vaults");
+    let req = CreateClientRequest { name: naming::entity_name("Two Factor Client") };
+    let client = control
+        .create_client(fixture.org_id, &req)
+        .await
+        .expect("Existing session should create clients");
+    control
+        .delete_client(fixture.org_id, client.client.id)
+        .await
+        .expect("Existing session should delete clients");
+    println!("✓ Existing session unaffected");
+
+    let status = evaluate().await;
+    assert_ne!(status, StatusCode::UNAUTHORIZED, "Client JWTs must not depend on the user's 2FA");
+    println!("✓ Client JWTs unaffected");
+
+    // Disabling needs a current code; afterwards the password alone logs in again
+    let code = totp::next_code(&enrollment.secret, &enrollment.last_code)
+        .await
+        .expect("Failed to compute TOTP code");
+    control.disable_totp(&TotpCodeRequest { code }).await.expect("Failed to disable TOTP");
+    let login = LoginRequest { email: enrollment.email, password: FIXTURE_PASSWORD.to_string() };
+    ControlClient::new(fixture.ctx.clone())
+        .login(&login)
+        .await
+        .expect("Password alone should log in once TOTP is disabled");
+    assert_ne!(evaluate().await, StatusCode::UNAUTHORIZED, "JWT should work after disabling");
+    println!("✓ TOTP disabled; password login and client JWTs work");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,214 @@
+// User Account Tests
+//
+// Profile updates and password changes through Control's `/users/me` endpoints. A profile update
+// must be visible to every session of the user and leave the email alone. A password change must
+// require the current password, switch which password logs in, and treat the user's other sessions
+// as the environment expects (`EnvProfile::password_change_revokes_sessions`). Engine client JWTs
+// are signed with client certificates, not the user's credentials, so they must keep working
+// whatever happens to the sessions. Skipped if Control does not expose the endpoints.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Whether `control`'s session is still accepted
+async fn session_valid(control: &ControlClient) -> bool {
+    match control.list_organizations().await {
+        Ok(_) => true,
+        Err(e) if e.status() == Some(StatusCode::UNAUTHORIZED) => false,
+        Err(e) => panic!("Unexpected error checking session: {}", e),
+    }
+}
+
+/// A second session for the fixture's user
+async fn login(fixture: &TestFixture, email: &str, password: &str) -> ApiResult<LoginResponse> {
+    let req = LoginRequest { email: email.to_string(), password: password.to_string() };
+    ControlClient::new(fixture.ctx.clone()).login(&req).await
+}
+
+#[tokio::test]
+async fn test_profile_update_visible_to_all_sessions() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+
+    let before = match control.get_current_user().await {
+        Ok(user) => user,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping profile test - endpoint not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to get current user: {}", e),
+    };
+    assert_eq!(before.id, fixture.user_id, "/users/me should return the session's user");
+
+    let name = naming::entity_name("Renamed User");
+    let req = UpdateProfileRequest { name: Some(name.clone()) };
+    let updated = match control.update_profile(&req).await {
+        Ok(user) => user,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping profile test - updates not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to update profile: {}", e),
+    };
+    assert_eq!(updated.name, name, "Update response should carry the new name");
+    assert_eq!(updated.email, before.email, "A name change must not touch the email");
+    println!("✓ Name updated");
+
+    let other = login(&fixture, &before.email, FIXTURE_PASSWORD).await.expect("Failed to login");
+    let seen = ControlClient::with_session(fixture.ctx.clone(), other.session_id)
+        .get_current_user()
+        .await
+        .expect("Failed to get current user");
+    assert_eq!(seen.name, name, "Another session should see the new name");
+    println!("✓ New name visible to another session");
+
+    let blank = UpdateProfileRequest { name: Some(String::new()) };
+    match control.update_profile(&blank).await {
+        Ok(user) => panic!("Blank name should be rejected, profile is now {:?}", user),
+        Err(e) => assert!(
+            matches!(e.status(), Some(StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY)),
+            "Blank name should be rejected with 400 or 422, got {}",
+            e
+        ),
+    }
+    let current = control.get_current_user().await.expect("Failed to get current user");
+    assert_eq!(current.name, name, "A rejected update must leave the profile unchanged");
+    println!("✓ Blank name rejected");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_password_change_requires_current_password() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let email = match control.get_current_user().await {
+        Ok(user) => user.email,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping password change test - endpoint not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to get current user: {}", e),
+    };
+
+    let req = ChangePasswordRequest {
+        current_password: "WrongPassword123!".to_string(),
+        new_password: "ChangedPassword456!".to_string(),
+    };
+    match control.change_password(&req).await {
+        Ok(()) => panic!("Password change with the wrong current password should be rejected"),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping password change test - endpoint not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => assert!(
+            matches!(
+                e.status(),
+                Some(StatusCode::BAD_REQUEST | StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+            ),
+            "Wrong current password should be rejected with 400, 401, or 403, got {}",
+            e
+        ),
+    }
+    println!("✓ Wrong current password rejected");
+
+    login(&fixture, &email, FIXTURE_PASSWORD).await.expect("Original password should still work");
+    assert!(
+        login(&fixture, &email, &req.new_password).await.is_err(),
+        "Rejected new password must not log in"
+    );
+    assert!(session_valid(&control).await, "A rejected change must not end the session");
+    println!("✓ Password and session unchanged");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_password_change_sessions_and_engine_tokens() {
+    let mut fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let control = fixture.control();
+    let email = match control.get_current_user().await {
+        Ok(user) => user.email,
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping password change test - endpoint not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to get current user: {}", e),
+    };
+    let revokes = EnvProfile::current().password_change_revokes_sessions;
+
+    let other = login(&fixture, &email, FIXTURE_PASSWORD).await.expect("Failed to login");
+    let other = ControlClient::with_session(fixture.ctx.clone(), other.session_id);
+    let jwt = fixture.jwt().encode().expect("Failed to encode JWT");
+    let response = fixture
+        .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+        .await
+        .expect("Failed to call server");
+    assert_ne!(response.status(), StatusCode::UNAUTHORIZED, "JWT should work before the change");
+
+    let new_password = "ChangedPassword456!".to_string();
+    let req = ChangePasswordRequest {
+        current_password: FIXTURE_PASSWORD.to_string(),
+        new_password: new_password.clone(),
+    };
+    match control.change_password(&req).await {
+        Ok(()) => {},
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping password change test - endpoint not available: {}", e));
+            fixture.cleanup().await.expect("Failed to cleanup");
+            return;
+        },
+        Err(e) => panic!("Failed to change password: {}", e),
+    }
+    println!("✓ Password changed");
+
+    let old = login(&fixture, &email, FIXTURE_PASSWORD).await;
+    assert!(
+        matches!(&old, Err(e) if e.status() == Some(StatusCode::UNAUTHORIZED)),
+        "Old password should be refused with 401, got {:?}",
+        old.map(|resp| resp.session_id)
+    );
+    let fresh = login(&fixture, &email, &new_password).await.expect("New password should log in");
+    println!("✓ Only the new password logs in");
+
+    let other_valid = session_valid(&other).await;
+    if revokes {
+        assert!(!other_valid, "Password change should end the user's other sessions");
+        println!("✓ Other session ended");
+    } else {
+        assert!(other_valid, "Password change should leave the user's other sessions alone");
+        println!("✓ Other session still valid");
+    }
+    // Whether the session that made the change survives is left to Control
+    println!(
+        "  session that changed the password: {}",
+        if session_valid(&control).await { "still valid" } else { "ended" }
+    );
+
+    // Engine authenticates the client certificate, so the same JWT and a new one keep working
+    for (label, jwt) in
+        [("existing", jwt), ("new", fixture.jwt().encode().expect("Failed to encode JWT"))]
+    {
+        let response = fixture
+            .call_server_evaluate(&jwt, "document:1", "viewer", "user:alice")
+            .await
+            .expect("Failed to call server");
+        assert_ne!(
+            response.status(),
+            StatusCode::UNAUTHORIZED,
+            "The {} client JWT should be unaffected by the password change",
+            label
+        );
+    }
+    println!("✓ Client JWTs unaffected");
+
+    // Clean up through a session that is certain to be valid
+    fixture.session_id = fresh.session_id;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
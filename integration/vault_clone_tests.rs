@@ -0,0 +1,245 @@
+// Vault Clone Tests
+//
+// Clones a vault through Control (`TestFixture::clone_vault`) and checks the copy: a full clone
+// carries the schema and every relationship, a template clone carries the schema alone, and either
+// way the clone is an independent vault. Tokens minted for the original never reach the clone,
+// and writes on one side never show up on the other. Skipped if Control cannot clone vaults.
+
+use std::time::Instant;
+
+use reqwest::{Method, StatusCode};
+
+use super::*;
+
+/// Longest a clone may take to hold the original's relationships
+const CLONE_SLO: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// The original vault's tuples, all valid under `DOCUMENT_SCHEMA`
+fn original_tuples() -> Vec<Relationship> {
+    let mut tuples = vec![
+        Relationship::new("document:roadmap", "owner", "user:alice"),
+        Relationship::new("document:roadmap", "editor", "user:bob"),
+        Relationship::new("document:roadmap", "viewer", "user:carol"),
+        Relationship::new("document:budget", "viewer", "user:bob"),
+    ];
+    tuples.sort();
+    tuples
+}
+
+/// Deploy `DOCUMENT_SCHEMA`, write `original_tuples`, and clone the vault
+///
+/// Returns the engine for the original vault and the clone's ID, or `None` (after logging) if
+/// schemas or cloning are unsupported.
+async fn cloned(fixture: &TestFixture, include_relationships: bool) -> Option<(EngineClient, i64)> {
+    if !schema_validation_tests::deploy_schema(fixture, DOCUMENT_SCHEMA).await {
+        return None;
+    }
+    let jwt = fixture
+        .generate_jwt(None, &["inferadb.check", "inferadb.write", "inferadb.list-relationships"])
+        .expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+    engine.write_relationships(&original_tuples()).await.expect("Failed to write relationships");
+
+    match fixture.clone_vault(include_relationships).await {
+        Ok(clone_id) => Some((engine, clone_id)),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping vault clone test - clone endpoint not available: {}",
+                e
+            ));
+            None
+        },
+        Err(e) => panic!("Failed to clone vault: {}", e),
+    }
+}
+
+/// Engine client for the clone
+fn clone_engine(fixture: &TestFixture, clone_id: i64) -> EngineClient {
+    let jwt = fixture
+        .generate_jwt(
+            Some(clone_id),
+            &["inferadb.check", "inferadb.write", "inferadb.list-relationships"],
+        )
+        .expect("Failed to generate JWT for clone");
+    fixture.engine(&jwt)
+}
+
+async fn sorted_relationships(engine: &EngineClient) -> Vec<Relationship> {
+    let mut listed = engine
+        .list_all_relationships(&RelationshipFilter::new())
+        .await
+        .expect("Failed to list relationships");
+    listed.sort();
+    listed
+}
+
+async fn cleanup(fixture: TestFixture, clone_id: Option<i64>) {
+    if let Some(clone_id) = clone_id {
+        let _ = fixture.control().delete_vault(fixture.org_id, clone_id).await;
+    }
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_clone_copies_schema_and_relationships() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((_, clone_id)) = cloned(&fixture, true).await else {
+        cleanup(fixture, None).await;
+        return;
+    };
+    let clone = clone_engine(&fixture, clone_id);
+
+    let start = Instant::now();
+    let mut listed = sorted_relationships(&clone).await;
+    while listed != original_tuples() && start.elapsed() < CLONE_SLO {
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+        listed = sorted_relationships(&clone).await;
+    }
+    assert_eq!(listed, original_tuples(), "Clone's relationships differ from the original's");
+    println!("✓ Clone holds all {} relationships after {:?}", listed.len(), start.elapsed());
+
+    clone
+        .evaluate("document:roadmap", "edit", "user:bob")
+        .await
+        .expect("Failed to evaluate on clone")
+        .assert_allowed("editor on the clone (derived permission)");
+    clone
+        .evaluate("document:roadmap", "view", "user:alice")
+        .await
+        .expect("Failed to evaluate on clone")
+        .assert_allowed("owner on the clone (derived permission)");
+    println!("✓ Clone evaluates the schema's derived permissions");
+
+    let err = clone
+        .write_relationships(&[Relationship::new("document:roadmap", "approver", "user:dave")])
+        .await
+        .expect_err("Clone should enforce the original's schema");
+    assert!(
+        err.status().is_some_and(|status| status.is_client_error()),
+        "Expected a 4xx for an undeclared relation on the clone, got {}",
+        err
+    );
+    println!("✓ Clone rejects relations the schema does not declare");
+
+    cleanup(fixture, Some(clone_id)).await;
+}
+
+#[tokio::test]
+async fn test_template_clone_copies_schema_only() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((_, clone_id)) = cloned(&fixture, false).await else {
+        cleanup(fixture, None).await;
+        return;
+    };
+    let clone = clone_engine(&fixture, clone_id);
+
+    assert!(
+        sorted_relationships(&clone).await.is_empty(),
+        "Template clone should start without relationships"
+    );
+    println!("✓ Template clone starts empty");
+
+    clone
+        .write_relationships(&[Relationship::new("document:roadmap", "editor", "user:dave")])
+        .await
+        .expect("Declared relation should be writable on the template clone");
+    clone
+        .evaluate("document:roadmap", "view", "user:dave")
+        .await
+        .expect("Failed to evaluate on clone")
+        .assert_allowed("editor on the template clone (derived permission)");
+    let err = clone
+        .write_relationships(&[Relationship::new("document:roadmap", "approver", "user:dave")])
+        .await
+        .expect_err("Template clone should enforce the original's schema");
+    assert!(
+        err.status().is_some_and(|status| status.is_client_error()),
+        "Expected a 4xx for an undeclared relation on the template clone, got {}",
+        err
+    );
+    println!("✓ Template clone carries the schema");
+
+    cleanup(fixture, Some(clone_id)).await;
+}
+
+#[tokio::test]
+async fn test_clone_has_independent_access_control() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((original, clone_id)) = cloned(&fixture, true).await else {
+        cleanup(fixture, None).await;
+        return;
+    };
+    let clone = clone_engine(&fixture, clone_id);
+
+    // Original's token, naming the clone explicitly: rejected, or answered from the original
+    let only_on_clone = Relationship::new("document:clone-only", "viewer", "user:erin");
+    clone
+        .write_relationships(std::slice::from_ref(&only_on_clone))
+        .await
+        .expect("Failed to write to clone");
+    let body = serde_json::json!({
+        "evaluations": [{
+            "resource": only_on_clone.resource,
+            "permission": only_on_clone.relation,
+            "subject": only_on_clone.subject,
+        }]
+    });
+    let response = original
+        .request(Method::POST, "/evaluate")
+        .header("X-Vault-Id", clone_id.to_string())
+        .json(&body)
+        .send_recorded()
+        .await
+        .expect("Failed to call server");
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    assert_eq!(
+        status,
+        StatusCode::FORBIDDEN,
+        "Original vault's token should be refused on the clone: {}",
+        text
+    );
+    println!("✓ Original vault's token is refused on the clone (403)");
+
+    // Writes stay on their own side
+    assert!(
+        !original
+            .check(&only_on_clone.resource, &only_on_clone.relation, &only_on_clone.subject)
+            .await
+            .expect("Failed to check original"),
+        "Tuple written to the clone appeared in the original"
+    );
+    let only_on_original = Relationship::new("document:original-only", "viewer", "user:frank");
+    original
+        .write_relationships(std::slice::from_ref(&only_on_original))
+        .await
+        .expect("Failed to write to original");
+    assert!(
+        !clone
+            .check(
+                &only_on_original.resource,
+                &only_on_original.relation,
+                &only_on_original.subject
+            )
+            .await
+            .expect("Failed to check clone"),
+        "Tuple written to the original after cloning appeared in the clone"
+    );
+    println!("✓ Writes after cloning stay in their own vault");
+
+    // Revoking on the clone leaves the original's grant in place
+    let bob = Relationship::new("document:roadmap", "editor", "user:bob");
+    clone.delete_relationships(std::slice::from_ref(&bob)).await.expect("Failed to delete");
+    assert!(
+        !clone.check("document:roadmap", "edit", "user:bob").await.expect("Failed to check clone"),
+        "Revocation on the clone did not take effect"
+    );
+    original
+        .evaluate("document:roadmap", "edit", "user:bob")
+        .await
+        .expect("Failed to evaluate original")
+        .assert_allowed("editor on the original after revocation on the clone");
+    println!("✓ Revocation on the clone leaves the original untouched");
+
+    cleanup(fixture, Some(clone_id)).await;
+}
@@ -0,0 +1,135 @@
+// Vault Manage Scope Tests
+//
+// Tests for `inferadb.vault.manage`: it unlocks vault management on Engine (schema writes and
+// purges) but grants no data write access by itself, and REST and gRPC enforce the derived
+// `manage` role the same way.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Authorization outcome of a call, comparable across REST and gRPC
+#[derive(Debug, PartialEq, Eq)]
+enum AuthOutcome {
+    /// Passed authorization (the call may still fail validation)
+    Allowed,
+    Forbidden,
+    Unauthenticated,
+    Other(String),
+}
+
+impl AuthOutcome {
+    fn from_rest(result: &ApiResult<()>) -> Self {
+        match result {
+            Ok(()) => Self::Allowed,
+            Err(e) => match e.status() {
+                Some(StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY) => Self::Allowed,
+                Some(StatusCode::FORBIDDEN) => Self::Forbidden,
+                Some(StatusCode::UNAUTHORIZED) => Self::Unauthenticated,
+                _ => Self::Other(e.to_string()),
+            },
+        }
+    }
+
+    fn from_grpc(code: i32) -> Self {
+        match code {
+            grpc_status::OK | grpc_status::INVALID_ARGUMENT => Self::Allowed,
+            grpc_status::PERMISSION_DENIED => Self::Forbidden,
+            grpc_status::UNAUTHENTICATED => Self::Unauthenticated,
+            other => Self::Other(format!("grpc-status {}", other)),
+        }
+    }
+}
+
+fn manage_jwt(fixture: &TestFixture) -> String {
+    fixture.generate_jwt(None, &["inferadb.vault.manage"]).expect("Failed to generate JWT")
+}
+
+#[tokio::test]
+async fn test_vault_manage_unlocks_schema_write() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let engine = fixture.engine(&manage_jwt(&fixture));
+
+    let req = DeploySchemaRequest { definition: DOCUMENT_SCHEMA.to_string() };
+    match engine.write_schema(&req).await {
+        Ok(()) => println!("✓ Schema write allowed with inferadb.vault.manage"),
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!(
+                "Skipping vault.manage schema test - endpoint not available: {}",
+                e
+            ));
+        },
+        Err(e) => panic!("Schema write should be allowed with inferadb.vault.manage: {}", e),
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_vault_manage_does_not_grant_data_write() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let engine = fixture.engine(&manage_jwt(&fixture));
+
+    let err = engine
+        .write_relationships(&[Relationship::new("document:managed", "viewer", "user:alice")])
+        .await
+        .expect_err("inferadb.vault.manage alone must not allow relationship writes");
+
+    assert_eq!(
+        err.status(),
+        Some(StatusCode::FORBIDDEN),
+        "Relationship write with only inferadb.vault.manage should be forbidden, got {}",
+        err
+    );
+
+    // Nothing was written
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let allowed = fixture
+        .engine(&jwt)
+        .check("document:managed", "viewer", "user:alice")
+        .await
+        .expect("Failed to check");
+    assert!(!allowed, "Refused write must not be visible");
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_vault_manage_role_consistent_across_rest_and_grpc() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let grpc = GrpcProbe::new(&fixture.ctx);
+
+    let tokens = [
+        ("inferadb.vault.manage", manage_jwt(&fixture)),
+        (
+            "inferadb.write",
+            fixture.generate_jwt(None, &["inferadb.write"]).expect("Failed to generate JWT"),
+        ),
+    ];
+
+    for (label, jwt) in &tokens {
+        let rest = fixture
+            .engine(jwt)
+            .write_relationships(&[Relationship::new("document:parity", "viewer", "user:alice")])
+            .await;
+        let rest_outcome = AuthOutcome::from_rest(&rest);
+
+        let grpc_outcome = match grpc.call("WriteRelationships", jwt).await {
+            None | Some(grpc_status::UNIMPLEMENTED) => {
+                outcome::skip("Skipping REST/gRPC parity test - gRPC endpoint not reachable");
+                fixture.cleanup().await.expect("Failed to cleanup");
+                return;
+            },
+            Some(code) => AuthOutcome::from_grpc(code),
+        };
+
+        assert_eq!(
+            rest_outcome, grpc_outcome,
+            "REST and gRPC disagree on relationship writes with {}",
+            label
+        );
+        println!("✓ {}: REST and gRPC both {:?}", label, rest_outcome);
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
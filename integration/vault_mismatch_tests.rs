@@ -0,0 +1,209 @@
+// Vault Claim Mismatch Tests
+//
+// The JWT's `vault_id` claim is the only source of truth for which vault a request touches. These
+// tests send a valid vault A token while naming vault B in the request itself (body field, query
+// parameter, or header) and assert the claim always wins: the mismatch is rejected with 403, and
+// vault B's data is never read or written.
+
+use reqwest::{Method, StatusCode};
+use serde_json::{Value, json};
+
+use super::*;
+
+/// Places an explicit vault reference can appear in an Engine request
+#[derive(Debug, Clone, Copy)]
+enum VaultReference {
+    BodyVault,
+    BodyVaultId,
+    Query,
+    Header,
+}
+
+const REFERENCES: [VaultReference; 4] = [
+    VaultReference::BodyVault,
+    VaultReference::BodyVaultId,
+    VaultReference::Query,
+    VaultReference::Header,
+];
+
+impl VaultReference {
+    /// POST `body` to `path`, naming `vault_id` in this position
+    async fn send(
+        self,
+        engine: &EngineClient,
+        path: &str,
+        mut body: Value,
+        vault_id: i64,
+    ) -> reqwest::Response {
+        let vault = vault_id.to_string();
+        let mut request = match self {
+            Self::Query => engine.request(Method::POST, &format!("{}?vault={}", path, vault)),
+            _ => engine.request(Method::POST, path),
+        };
+        match self {
+            Self::BodyVault => body["vault"] = json!(vault),
+            Self::BodyVaultId => body["vault_id"] = json!(vault),
+            Self::Header => request = request.header("X-Vault-Id", &vault),
+            Self::Query => {},
+        }
+        request.json(&body).send_recorded().await.expect("Failed to call server")
+    }
+}
+
+/// Two vaults in one organization; vault B holds a tuple vault A lacks
+struct TwoVaults {
+    fixture: TestFixture,
+    vault_b_id: i64,
+}
+
+impl TwoVaults {
+    async fn create() -> Self {
+        let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+        let vault_req = CreateVaultRequest {
+            name: naming::entity_name("Mismatch Vault B"),
+            organization_id: fixture.org_id,
+        };
+        let vault_b_id = fixture
+            .control()
+            .create_vault(fixture.org_id, &vault_req)
+            .await
+            .expect("Failed to create vault B")
+            .vault
+            .id;
+
+        let jwt_b = fixture
+            .generate_jwt(Some(vault_b_id), &["inferadb.write"])
+            .expect("Failed to generate JWT for vault B");
+        fixture
+            .engine(&jwt_b)
+            .write_relationships(&[Relationship::new("document:vault-b", "viewer", "user:bob")])
+            .await
+            .expect("Failed to seed vault B");
+
+        Self { fixture, vault_b_id }
+    }
+
+    async fn cleanup(self) {
+        let _ = self.fixture.control().delete_vault(self.fixture.org_id, self.vault_b_id).await;
+        self.fixture.cleanup().await.expect("Failed to cleanup");
+    }
+}
+
+fn evaluate_body(resource: &str) -> Value {
+    json!({
+        "evaluations": [{
+            "subject": "user:bob",
+            "resource": resource,
+            "permission": "viewer",
+            "trace": false
+        }]
+    })
+}
+
+#[tokio::test]
+async fn test_evaluate_with_mismatched_vault_rejected() {
+    let vaults = TwoVaults::create().await;
+    let jwt_a = vaults
+        .fixture
+        .generate_jwt(None, &["inferadb.check"])
+        .expect("Failed to generate JWT for vault A");
+    let engine = vaults.fixture.engine(&jwt_a);
+
+    let mut failures = Vec::new();
+    for reference in REFERENCES {
+        let response = reference
+            .send(&engine, "/evaluate", evaluate_body("document:vault-b"), vaults.vault_b_id)
+            .await;
+        let status = response.status();
+
+        if status == StatusCode::FORBIDDEN {
+            println!("✓ {:?} naming vault B: 403", reference);
+            continue;
+        }
+
+        // Anything other than a rejection must still have been answered from vault A
+        let body = response.text().await.unwrap_or_default();
+        let leaked = status.is_success() && body.contains("ALLOW");
+        if leaked {
+            failures.push(format!(
+                "{:?}: vault B's tuple was visible ({}): {}",
+                reference, status, body
+            ));
+        } else {
+            failures.push(format!(
+                "{:?}: expected 403 for mismatched vault, got {}",
+                reference, status
+            ));
+        }
+    }
+
+    vaults.cleanup().await;
+    assert!(failures.is_empty(), "Vault claim did not win:\n  {}", failures.join("\n  "));
+}
+
+#[tokio::test]
+async fn test_write_with_mismatched_vault_rejected() {
+    let vaults = TwoVaults::create().await;
+    let jwt_a = vaults
+        .fixture
+        .generate_jwt(None, &["inferadb.write"])
+        .expect("Failed to generate JWT for vault A");
+    let engine = vaults.fixture.engine(&jwt_a);
+
+    let mut failures = Vec::new();
+    for reference in REFERENCES {
+        let resource = format!("document:smuggled-{:?}", reference).to_lowercase();
+        let body = json!({ "relationships": [Relationship::new(&resource, "viewer", "user:bob")] });
+        let status =
+            reference.send(&engine, "/relationships/write", body, vaults.vault_b_id).await.status();
+        if status != StatusCode::FORBIDDEN {
+            failures.push(format!(
+                "{:?}: expected 403 for mismatched vault, got {}",
+                reference, status
+            ));
+        }
+
+        // Whatever the status, the tuple must not have reached vault B
+        let jwt_b = vaults
+            .fixture
+            .generate_jwt(Some(vaults.vault_b_id), &["inferadb.check"])
+            .expect("Failed to generate JWT for vault B");
+        let in_vault_b = vaults
+            .fixture
+            .engine(&jwt_b)
+            .check(&resource, "viewer", "user:bob")
+            .await
+            .expect("Failed to evaluate in vault B");
+        if in_vault_b {
+            failures.push(format!("{:?}: write landed in vault B", reference));
+        } else {
+            println!("✓ {:?} naming vault B: {}, vault B untouched", reference, status);
+        }
+    }
+
+    vaults.cleanup().await;
+    assert!(failures.is_empty(), "Vault claim did not win:\n  {}", failures.join("\n  "));
+}
+
+#[tokio::test]
+async fn test_matching_vault_reference_accepted() {
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let jwt = fixture.generate_jwt(None, &["inferadb.check"]).expect("Failed to generate JWT");
+    let engine = fixture.engine(&jwt);
+
+    // Naming the claimed vault is redundant, not a mismatch; 403s above must come from the mismatch
+    for reference in REFERENCES {
+        let status = reference
+            .send(&engine, "/evaluate", evaluate_body("document:1"), fixture.vault_id)
+            .await
+            .status();
+        assert!(
+            status == StatusCode::OK || status == StatusCode::NOT_FOUND,
+            "{:?} naming the claimed vault should be accepted, got {}",
+            reference,
+            status
+        );
+    }
+
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,168 @@
+// Webhook Tests
+//
+// Subscribes a `WebhookEndpoint` on the suite's own receiver to an organization's cache
+// invalidation and audit webhooks, then changes the organization's vault so Control has something
+// to deliver. Every delivery must carry a signature that verifies under the key Control returned
+// for the subscription (`webhooks::verify`), and the same body with one byte changed must not. A
+// delivery the receiver rejects with 401 must be retried, with the gap between attempts growing,
+// until it is accepted. Skipped unless INFERADB_WEBHOOK_URL is set and Control supports webhooks.
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Events the tests subscribe to
+const EVENTS: &[&str] = &["cache.invalidation", "audit"];
+
+/// Longest to wait for a first delivery
+pub(super) const DELIVERY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// Longest to wait for a rejected delivery to be retried through to acceptance
+const RETRY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(180);
+
+/// 401s the receiver answers before accepting in the retry test
+const REJECTIONS: usize = 3;
+
+/// Subscribe `endpoint` to `EVENTS` for the fixture's organization, returning the webhook ID and
+/// the key its deliveries are signed with, or `None` (after logging) if webhooks are unsupported
+pub(super) async fn subscribe(
+    fixture: &TestFixture,
+    endpoint: &WebhookEndpoint,
+) -> Option<(i64, WebhookKey)> {
+    let req = CreateWebhookRequest {
+        url: endpoint.url().to_string(),
+        events: EVENTS.iter().map(|event| event.to_string()).collect(),
+    };
+    match fixture.control().create_webhook(fixture.org_id, &req).await {
+        Ok(response) => {
+            let key = response.key().expect("Webhook response should carry its signing key");
+            Some((response.webhook.id, key))
+        },
+        Err(e) if e.is_unsupported() => {
+            outcome::skip(format!("Skipping webhook test - webhook endpoint not available: {}", e));
+            None
+        },
+        Err(e) => panic!("Failed to create webhook: {}", e),
+    }
+}
+
+/// Rename the fixture's vault, which Control audits and invalidates Engine's caches for
+pub(super) async fn trigger(fixture: &TestFixture) {
+    let update = serde_json::json!({ "name": naming::entity_name("Webhook Vault") });
+    fixture
+        .control()
+        .update_vault(fixture.org_id, fixture.vault_id, &update)
+        .await
+        .expect("Failed to update vault");
+}
+
+#[tokio::test]
+async fn test_control_signs_webhook_deliveries() {
+    let Some(endpoint) = WebhookEndpoint::create() else { return };
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((webhook_id, key)) = subscribe(&fixture, &endpoint).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    trigger(&fixture).await;
+    let deliveries = endpoint.wait_until(DELIVERY_TIMEOUT, |d| !d.is_empty()).await;
+    assert!(!deliveries.is_empty(), "No webhook delivered within {:?}", DELIVERY_TIMEOUT);
+
+    for delivery in &deliveries {
+        let event = delivery.json().and_then(|body| body.get("type").cloned()).unwrap_or_default();
+        webhooks::verify(
+            &key,
+            &delivery.headers,
+            &delivery.body,
+            webhooks::DEFAULT_TOLERANCE_SECONDS,
+        )
+        .unwrap_or_else(|e| panic!("Delivery {} failed verification: {:#}", event, e));
+        println!("✓ Delivery {} verifies", event);
+
+        let mut tampered = delivery.body.clone();
+        if let Some(byte) = tampered.last_mut() {
+            *byte ^= 0x01;
+        }
+        assert!(
+            webhooks::verify(&key, &delivery.headers, &tampered, i64::MAX).is_err(),
+            "Signature still verified after the body was changed"
+        );
+    }
+    println!("✓ {} deliveries signed; tampered bodies rejected", deliveries.len());
+
+    let _ = fixture.control().delete_webhook(fixture.org_id, webhook_id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
+
+#[tokio::test]
+async fn test_rejected_delivery_retried_with_backoff() {
+    let Some(endpoint) = WebhookEndpoint::create() else { return };
+    let fixture = TestFixture::create().await.expect("Failed to create test fixture");
+    let Some((webhook_id, key)) = subscribe(&fixture, &endpoint).await else {
+        fixture.cleanup().await.expect("Failed to cleanup");
+        return;
+    };
+
+    endpoint.respond_with(std::iter::repeat_n(StatusCode::UNAUTHORIZED, REJECTIONS));
+    trigger(&fixture).await;
+
+    // Attempts of the first delivery, identified by Control's delivery ID
+    let attempts_of_first = |deliveries: &[Delivery]| -> Vec<Delivery> {
+        let Some(id) = deliveries.first().and_then(|d| d.id()) else { return Vec::new() };
+        deliveries.iter().filter(|d| d.id() == Some(id)).cloned().collect()
+    };
+    let deliveries = endpoint
+        .wait_until(RETRY_TIMEOUT, |d| attempts_of_first(d).iter().any(Delivery::accepted))
+        .await;
+    assert!(
+        deliveries.first().is_some_and(|d| d.id().is_some()),
+        "Deliveries should carry a {} header",
+        webhook_receiver::DELIVERY_ID_HEADER
+    );
+    let attempts = attempts_of_first(&deliveries);
+    let statuses: Vec<_> = attempts
+        .iter()
+        .filter_map(|attempt| attempt.response.status())
+        .map(|s| s.as_u16())
+        .collect();
+    assert_eq!(
+        statuses.last(),
+        Some(&200),
+        "Delivery was not retried through to acceptance within {:?}: {:?}",
+        RETRY_TIMEOUT,
+        statuses
+    );
+    assert_eq!(attempts.len(), REJECTIONS + 1, "Unexpected attempts: {:?}", statuses);
+
+    for attempt in &attempts {
+        webhooks::verify(
+            &key,
+            &attempt.headers,
+            &attempt.body,
+            webhooks::DEFAULT_TOLERANCE_SECONDS,
+        )
+        .unwrap_or_else(|e| panic!("Retried delivery failed verification: {:#}", e));
+    }
+
+    let gaps: Vec<_> =
+        attempts.windows(2).map(|pair| pair[1].received_at - pair[0].received_at).collect();
+    println!("  attempts {:?}, gaps {:?}", statuses, gaps);
+    for pair in gaps.windows(2) {
+        // Allow for scheduling jitter, but never a markedly shorter wait than the last one
+        assert!(
+            pair[1].as_secs_f64() >= pair[0].as_secs_f64() * 0.8,
+            "Retry gaps shrank instead of backing off: {:?}",
+            gaps
+        );
+    }
+    assert!(
+        gaps.last() > gaps.first(),
+        "Retry gaps never grew, so retries do not back off: {:?}",
+        gaps
+    );
+    println!("✓ 401s retried with backoff until accepted, every attempt signed");
+
+    let _ = fixture.control().delete_webhook(fixture.org_id, webhook_id).await;
+    fixture.cleanup().await.expect("Failed to cleanup");
+}
@@ -0,0 +1,105 @@
+// Shared plumbing for the typed Control and Engine clients
+//
+// Both clients send through these helpers so a non-success response always becomes an
+// `ApiError::Status` carrying the service name, HTTP status, and response body.
+
+use std::fmt;
+
+use reqwest::{RequestBuilder, StatusCode};
+use serde::de::DeserializeOwned;
+
+/// Error returned by typed API client calls
+#[derive(Debug)]
+pub enum ApiError {
+    /// The service answered with a non-success status
+    Status { service: &'static str, status: StatusCode, body: String },
+    /// The request could not be sent, or the response body could not be decoded
+    Transport(reqwest::Error),
+}
+
+impl ApiError {
+    /// HTTP status returned by the service, if the request reached it
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Status { status, .. } => Some(*status),
+            Self::Transport(e) => e.status(),
+        }
+    }
+
+    /// Response body returned with a non-success status
+    pub fn body(&self) -> Option<&str> {
+        match self {
+            Self::Status { body, .. } => Some(body),
+            Self::Transport(_) => None,
+        }
+    }
+
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// Whether the service does not implement the endpoint (404, 405, or 501)
+    pub fn is_unsupported(&self) -> bool {
+        matches!(
+            self.status(),
+            Some(
+                StatusCode::NOT_FOUND
+                    | StatusCode::METHOD_NOT_ALLOWED
+                    | StatusCode::NOT_IMPLEMENTED
+            )
+        )
+    }
+}
+
+impl fmt::Display for ApiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Status { service, status, body } => {
+                write!(f, "{} returned {}: {}", service, status, body)
+            },
+            Self::Transport(e) => write!(f, "Request failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ApiError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Status { .. } => None,
+            Self::Transport(e) => Some(e),
+        }
+    }
+}
+
+impl From<reqwest::Error> for ApiError {
+    fn from(e: reqwest::Error) -> Self {
+        Self::Transport(e)
+    }
+}
+
+pub type ApiResult<T> = std::result::Result<T, ApiError>;
+
+/// Send a request and map non-success statuses to `ApiError::Status`
+pub async fn send(service: &'static str, builder: RequestBuilder) -> ApiResult<reqwest::Response> {
+    let response = crate::recorder::send(builder).await?;
+    let status = response.status();
+    if status.is_success() {
+        Ok(response)
+    } else {
+        let body = response.text().await.unwrap_or_else(|_| "<unreadable body>".to_string());
+        Err(ApiError::Status { service, status, body })
+    }
+}
+
+/// Send a request and decode the JSON response body
+pub async fn send_json<T: DeserializeOwned>(
+    service: &'static str,
+    builder: RequestBuilder,
+) -> ApiResult<T> {
+    Ok(send(service, builder).await?.json().await?)
+}
+
+/// Send a request, discarding any response body
+pub async fn send_empty(service: &'static str, builder: RequestBuilder) -> ApiResult<()> {
+    send(service, builder).await.map(|_| ())
+}
@@ -0,0 +1,237 @@
+// API Surface Coverage Map
+//
+// Records every (method, path template) the suite sends and diffs it against the Engine and
+// Control OpenAPI specs, so endpoints the integration suite never touches are visible.
+//
+// Enabled with the `api-coverage` feature:
+//   INFERADB_OPENAPI_SPECS=control=../control/openapi.json,access=../engine/openapi.json \
+//     cargo test --features integration-tests,api-coverage -- --test-threads=1
+//
+// Each spec is keyed by the ingress prefix its routes are served under (`control` or `access`).
+// The report is rewritten whenever a new route is exercised, so it is complete when the run
+// ends. It goes to `INFERADB_COVERAGE_REPORT` (default: `target/api-coverage.json`).
+
+use std::collections::BTreeSet;
+#[cfg(feature = "api-coverage")]
+use std::sync::Mutex;
+
+use reqwest::Method;
+#[cfg(feature = "api-coverage")]
+use serde::Serialize;
+
+#[cfg(feature = "api-coverage")]
+use super::*;
+
+/// Placeholder that replaces identifiers in recorded paths and OpenAPI path parameters
+const ID_PLACEHOLDER: &str = "{id}";
+
+/// A route in normalized form, e.g. `POST /control/v1/organizations/{id}/vaults`
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "api-coverage", derive(Serialize))]
+pub struct Route {
+    pub method: String,
+    pub path: String,
+}
+
+/// Convert a request URL into a path template
+///
+/// Numeric segments (Snowflake IDs), UUIDs, and OpenAPI `{param}` segments all become `{id}`;
+/// the query string is dropped.
+pub fn path_template(url: &str) -> String {
+    let without_scheme = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let path = without_scheme.find('/').map_or("/", |i| &without_scheme[i..]);
+    let path = path.split(['?', '#']).next().unwrap_or(path);
+
+    path.split('/')
+        .map(|segment| {
+            let is_id = !segment.is_empty() && segment.chars().all(|c| c.is_ascii_digit());
+            let is_param = segment.starts_with('{') && segment.ends_with('}');
+            if is_id || is_param || uuid::Uuid::parse_str(segment).is_ok() {
+                ID_PLACEHOLDER
+            } else {
+                segment
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Extract all routes from an OpenAPI document, mounted under `/{prefix}`
+pub fn spec_routes(prefix: &str, spec: &serde_json::Value) -> BTreeSet<Route> {
+    const METHODS: &[&str] = &["get", "put", "post", "delete", "patch", "head", "options"];
+
+    let mount = format!("/{}", prefix.trim_matches('/'));
+    let Some(paths) = spec.get("paths").and_then(|p| p.as_object()) else {
+        return BTreeSet::new();
+    };
+
+    paths
+        .iter()
+        .flat_map(|(path, item)| {
+            let full_path =
+                if path.starts_with(&mount) { path.clone() } else { format!("{}{}", mount, path) };
+            METHODS
+                .iter()
+                .filter(|m| item.get(**m).is_some())
+                .map(move |m| Route { method: m.to_uppercase(), path: path_template(&full_path) })
+        })
+        .collect()
+}
+
+#[cfg(feature = "api-coverage")]
+#[derive(Serialize)]
+struct UntestedRoute {
+    service: String,
+    #[serde(flatten)]
+    route: Route,
+}
+
+#[cfg(feature = "api-coverage")]
+#[derive(Serialize)]
+struct CoverageReport<'a> {
+    api_base_url: String,
+    exercised: &'a BTreeSet<Route>,
+    untested: Vec<UntestedRoute>,
+    /// Exercised routes missing from every spec (typos or undocumented endpoints)
+    undocumented: Vec<&'a Route>,
+    coverage_percent: f64,
+}
+
+#[cfg(feature = "api-coverage")]
+struct CoverageState {
+    exercised: BTreeSet<Route>,
+    /// (service prefix, routes) for every configured spec
+    specs: Vec<(String, BTreeSet<Route>)>,
+}
+
+#[cfg(feature = "api-coverage")]
+static COVERAGE: Mutex<Option<CoverageState>> = Mutex::new(None);
+
+/// Load the specs listed in `INFERADB_OPENAPI_SPECS` (`prefix=path,prefix=path`)
+#[cfg(feature = "api-coverage")]
+fn load_specs() -> Vec<(String, BTreeSet<Route>)> {
+    let Ok(config) = std::env::var("INFERADB_OPENAPI_SPECS") else {
+        eprintln!("Warning: INFERADB_OPENAPI_SPECS not set - coverage report lists hits only");
+        return Vec::new();
+    };
+
+    config
+        .split(',')
+        .filter_map(|entry| entry.split_once('='))
+        .filter_map(|(prefix, path)| {
+            let spec = std::fs::read(path.trim())
+                .ok()
+                .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok());
+            match spec {
+                Some(spec) => Some((prefix.trim().to_string(), spec_routes(prefix.trim(), &spec))),
+                None => {
+                    eprintln!("Warning: Could not load OpenAPI spec {}", path);
+                    None
+                },
+            }
+        })
+        .collect()
+}
+
+#[cfg(feature = "api-coverage")]
+fn write_report(state: &CoverageState) -> Result<()> {
+    let untested: Vec<UntestedRoute> = state
+        .specs
+        .iter()
+        .flat_map(|(service, routes)| {
+            routes
+                .difference(&state.exercised)
+                .map(|route| UntestedRoute { service: service.clone(), route: route.clone() })
+        })
+        .collect();
+    let undocumented = state
+        .exercised
+        .iter()
+        .filter(|route| !state.specs.iter().any(|(_, routes)| routes.contains(route)))
+        .collect();
+
+    let total: usize = state.specs.iter().map(|(_, routes)| routes.len()).sum();
+    let coverage_percent =
+        if total == 0 { 0.0 } else { (total - untested.len()) as f64 / total as f64 * 100.0 };
+
+    let report = CoverageReport {
+        api_base_url: api_base_url(),
+        exercised: &state.exercised,
+        untested,
+        undocumented,
+        coverage_percent,
+    };
+
+    let path = std::env::var("INFERADB_COVERAGE_REPORT")
+        .unwrap_or_else(|_| "target/api-coverage.json".to_string());
+    if let Some(parent) = std::path::Path::new(&path).parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(&report)?)
+        .with_context(|| format!("Failed to write coverage report {}", path))
+}
+
+/// Record a request; a no-op unless the `api-coverage` feature is enabled
+#[cfg(feature = "api-coverage")]
+pub fn record(method: &Method, url: &str) {
+    let route = Route { method: method.to_string(), path: path_template(url) };
+
+    let mut guard = COVERAGE.lock().unwrap_or_else(|e| e.into_inner());
+    let state = guard
+        .get_or_insert_with(|| CoverageState { exercised: BTreeSet::new(), specs: load_specs() });
+
+    if state.exercised.insert(route)
+        && let Err(e) = write_report(state)
+    {
+        eprintln!("Warning: {:#}", e);
+    }
+}
+
+/// Record a request; a no-op unless the `api-coverage` feature is enabled
+#[cfg(not(feature = "api-coverage"))]
+pub fn record(_method: &Method, _url: &str) {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coverage_path_template_normalizes_identifiers() {
+        assert_eq!(
+            path_template(
+                "https://api.example.com/control/v1/organizations/123/vaults/456?limit=10"
+            ),
+            "/control/v1/organizations/{id}/vaults/{id}"
+        );
+        assert_eq!(
+            path_template(
+                "https://api.example.com/access/v1/vaults/7c9e6679-7425-40de-944b-e07fc1f90ae7#top"
+            ),
+            "/access/v1/vaults/{id}"
+        );
+        assert_eq!(path_template("/control/v1/clients/{client}"), "/control/v1/clients/{id}");
+    }
+
+    #[test]
+    fn test_coverage_spec_routes_are_mounted_under_prefix() {
+        let spec = serde_json::json!({
+            "paths": {
+                "/v1/organizations/{org}/vaults": { "get": {}, "post": {} },
+                "/v1/evaluate": { "post": {} }
+            }
+        });
+
+        let routes = spec_routes("control", &spec);
+        let rendered: Vec<String> =
+            routes.iter().map(|r| format!("{} {}", r.method, r.path)).collect();
+
+        assert_eq!(
+            rendered,
+            vec![
+                "GET /control/v1/organizations/{id}/vaults",
+                "POST /control/v1/evaluate",
+                "POST /control/v1/organizations/{id}/vaults",
+            ]
+        );
+    }
+}
@@ -0,0 +1,386 @@
+// Benchmarks
+//
+// A benchmark times one or more operations across a set of cases (e.g. graph fan-out sizes) and
+// summarizes each (case, operation) pair as a `BenchResult`. The `bench` binary collects them into
+// a `BenchReport`, the JSON artifact kept per run so scaling can be tracked across releases.
+//
+//     let mut samples = Samples::default();
+//     for _ in 0..iterations {
+//         samples.time(engine.check("document:1", "viewer", "user:alice")).await;
+//     }
+//     results.push(samples.summarize("fanout", "100", "evaluate"));
+
+use std::{future::Future, time::Instant};
+
+use super::{scenario::percentile, *};
+
+/// Latencies and failures collected for one (case, operation) pair
+#[derive(Debug, Default)]
+pub struct Samples {
+    latencies_ms: Vec<f64>,
+    failures: usize,
+    errors: Vec<String>,
+    elapsed_ms: f64,
+}
+
+impl Samples {
+    /// Await `future`, recording its latency on success and its error on failure
+    pub async fn time<T, E: std::fmt::Display>(
+        &mut self,
+        future: impl Future<Output = std::result::Result<T, E>>,
+    ) -> Option<T> {
+        let start = Instant::now();
+        let result = future.await;
+        let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+        self.elapsed_ms += elapsed_ms;
+        match result {
+            Ok(value) => {
+                self.latencies_ms.push(elapsed_ms);
+                Some(value)
+            },
+            Err(e) => {
+                self.record_failure(e.to_string());
+                None
+            },
+        }
+    }
+
+    /// Record a latency measured elsewhere (e.g. inside a spawned task)
+    pub fn record(&mut self, latency_ms: f64) {
+        self.latencies_ms.push(latency_ms);
+    }
+
+    /// Record a failed operation
+    pub fn record_failure(&mut self, error: String) {
+        self.failures += 1;
+        if !self.errors.contains(&error) {
+            self.errors.push(error);
+        }
+    }
+
+    /// Wall-clock duration the operations ran over, for throughput; defaults to their summed
+    /// latency, which is only right when they ran one at a time
+    pub fn set_elapsed_ms(&mut self, elapsed_ms: f64) {
+        self.elapsed_ms = elapsed_ms;
+    }
+
+    pub fn summarize(&self, benchmark: &str, case: &str, operation: &str) -> BenchResult {
+        let mut sorted = self.latencies_ms.clone();
+        sorted.sort_by(f64::total_cmp);
+
+        BenchResult {
+            benchmark: benchmark.to_string(),
+            case: case.to_string(),
+            operation: operation.to_string(),
+            samples: sorted.len(),
+            failures: self.failures,
+            p50_ms: percentile(&sorted, 0.50),
+            p95_ms: percentile(&sorted, 0.95),
+            p99_ms: percentile(&sorted, 0.99),
+            max_ms: sorted.last().copied(),
+            throughput_per_sec: (self.elapsed_ms > 0.0 && !sorted.is_empty())
+                .then(|| sorted.len() as f64 / (self.elapsed_ms / 1000.0)),
+            errors: self.errors.clone(),
+            notes: Vec::new(),
+        }
+    }
+}
+
+/// Latency summary for one operation in one benchmark case
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub benchmark: String,
+    /// Parameter value this result was measured at, e.g. the fan-out size
+    pub case: String,
+    pub operation: String,
+    pub samples: usize,
+    pub failures: usize,
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    /// Successful operations per second of wall-clock time
+    pub throughput_per_sec: Option<f64>,
+    /// Distinct error messages, in order of first occurrence
+    #[serde(default)]
+    pub errors: Vec<String>,
+    /// Benchmark-specific observations, e.g. correctness checks made alongside the timing
+    #[serde(default)]
+    pub notes: Vec<String>,
+}
+
+impl BenchResult {
+    /// `benchmark/case/operation`, the key results are matched on across reports
+    pub fn key(&self) -> String {
+        format!("{}/{}/{}", self.benchmark, self.case, self.operation)
+    }
+}
+
+/// The JSON benchmark artifact covering a whole invocation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub api_base_url: String,
+    /// Engine and Control versions the run was made against
+    #[serde(default)]
+    pub build: BuildInfo,
+    pub started_at: String,
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchReport {
+    /// Write the report as pretty-printed JSON, creating parent directories
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write benchmark report {}", path.display()))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read benchmark report {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("Invalid benchmark report {}", path.display()))
+    }
+}
+
+/// One comparable measurement from a benchmark or scenario report
+#[derive(Debug, Clone, PartialEq)]
+pub struct Measurement {
+    /// `benchmark/case/operation` or `scenario/step`
+    pub key: String,
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub p99_ms: Option<f64>,
+    pub throughput_per_sec: Option<f64>,
+    /// Failed fraction of all attempts
+    pub failure_rate: f64,
+}
+
+impl Measurement {
+    fn failure_rate(failures: usize, attempts: usize) -> f64 {
+        if attempts == 0 { 0.0 } else { failures as f64 / attempts as f64 }
+    }
+
+    /// Latency at `percentile` (50, 95, or 99)
+    pub fn latency_ms(&self, percentile: u8) -> Option<f64> {
+        match percentile {
+            50 => self.p50_ms,
+            99 => self.p99_ms,
+            _ => self.p95_ms,
+        }
+    }
+}
+
+impl From<&BenchResult> for Measurement {
+    fn from(result: &BenchResult) -> Self {
+        Self {
+            key: result.key(),
+            p50_ms: result.p50_ms,
+            p95_ms: result.p95_ms,
+            p99_ms: result.p99_ms,
+            throughput_per_sec: result.throughput_per_sec,
+            failure_rate: Self::failure_rate(result.failures, result.samples + result.failures),
+        }
+    }
+}
+
+/// Load the measurements in a benchmark report or a scenario report
+pub fn load_measurements(path: &Path) -> Result<Vec<Measurement>> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("Failed to read report {}", path.display()))?;
+    let report: serde_json::Value = serde_json::from_slice(&bytes)
+        .with_context(|| format!("Invalid report {}", path.display()))?;
+
+    if report.get("results").is_some() {
+        let report: BenchReport = serde_json::from_value(report)
+            .with_context(|| format!("Invalid benchmark report {}", path.display()))?;
+        return Ok(report.results.iter().map(Measurement::from).collect());
+    }
+
+    let scenarios: Vec<scenario::ScenarioReport> =
+        serde_json::from_value(report.get("scenarios").cloned().with_context(|| {
+            format!("{} is neither a benchmark nor a scenario report", path.display())
+        })?)
+        .with_context(|| format!("Invalid scenario report {}", path.display()))?;
+    Ok(scenarios
+        .iter()
+        .flat_map(|scenario| {
+            scenario.steps.iter().map(|step| Measurement {
+                key: format!("{}/{}", scenario.scenario, step.step),
+                p50_ms: step.p50_ms,
+                p95_ms: step.p95_ms,
+                p99_ms: None,
+                throughput_per_sec: None,
+                failure_rate: Measurement::failure_rate(step.failures, step.runs),
+            })
+        })
+        .collect())
+}
+
+/// How much worse a candidate may be than its baseline before it counts as a regression
+#[derive(Debug, Clone)]
+pub struct Thresholds {
+    /// Latency percentile compared (50, 95, or 99)
+    pub percentile: u8,
+    /// Allowed relative latency increase, e.g. 0.10 for 10%
+    pub max_latency_increase: f64,
+    /// Latency increases below this many milliseconds are noise, whatever their ratio
+    pub min_latency_delta_ms: f64,
+    /// Allowed relative throughput drop, e.g. 0.10 for 10%
+    pub max_throughput_drop: f64,
+    /// Allowed absolute failure-rate increase, e.g. 0.01 for one point
+    pub max_failure_rate_increase: f64,
+    /// Whether baseline keys missing from the candidate are let through instead of failing
+    pub allow_missing: bool,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            percentile: 95,
+            max_latency_increase: 0.10,
+            min_latency_delta_ms: 1.0,
+            max_throughput_drop: 0.10,
+            max_failure_rate_increase: 0.0,
+            allow_missing: false,
+        }
+    }
+}
+
+/// One metric of one measurement, compared between reports
+#[derive(Debug, Clone)]
+pub struct Change {
+    pub key: String,
+    /// `p95`, `throughput`, `failure rate`, or `presence` (1 if measured, 0 if missing)
+    pub metric: String,
+    pub baseline: f64,
+    pub candidate: f64,
+    pub regression: bool,
+}
+
+/// Compare every measurement of the baseline with the candidate's
+///
+/// A baseline key the candidate lacks is a `presence` regression unless `allow_missing` is set, so
+/// a benchmark that stopped running can't pass the gate; keys only the candidate has are ignored.
+pub fn compare(
+    baseline: &[Measurement],
+    candidate: &[Measurement],
+    thresholds: &Thresholds,
+) -> Vec<Change> {
+    let mut changes = Vec::new();
+    for base in baseline {
+        let Some(cand) = candidate.iter().find(|m| m.key == base.key) else {
+            changes.push(Change {
+                key: base.key.clone(),
+                metric: "presence".to_string(),
+                baseline: 1.0,
+                candidate: 0.0,
+                regression: !thresholds.allow_missing,
+            });
+            continue;
+        };
+        let change = |metric: String, baseline: f64, candidate: f64, regression: bool| Change {
+            key: base.key.clone(),
+            metric,
+            baseline,
+            candidate,
+            regression,
+        };
+
+        let percentile = thresholds.percentile;
+        if let (Some(b), Some(c)) = (base.latency_ms(percentile), cand.latency_ms(percentile)) {
+            let regression = c - b > thresholds.min_latency_delta_ms
+                && c > b * (1.0 + thresholds.max_latency_increase);
+            changes.push(change(format!("p{}", percentile), b, c, regression));
+        }
+        if let (Some(b), Some(c)) = (base.throughput_per_sec, cand.throughput_per_sec) {
+            let regression = c < b * (1.0 - thresholds.max_throughput_drop);
+            changes.push(change("throughput".to_string(), b, c, regression));
+        }
+        let regression =
+            cand.failure_rate - base.failure_rate > thresholds.max_failure_rate_increase;
+        changes.push(change(
+            "failure rate".to_string(),
+            base.failure_rate,
+            cand.failure_rate,
+            regression,
+        ));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_samples_summarize_latency_and_throughput() {
+        let mut samples = Samples::default();
+        for latency_ms in 1..=100 {
+            samples.record(latency_ms as f64);
+        }
+        samples.record_failure("timeout".to_string());
+        samples.record_failure("timeout".to_string());
+        samples.set_elapsed_ms(2000.0);
+
+        let result = samples.summarize("fanout", "100", "evaluate-allow");
+        assert_eq!(result.key(), "fanout/100/evaluate-allow");
+        assert_eq!((result.samples, result.failures), (100, 2));
+        assert_eq!(
+            (result.p50_ms, result.p95_ms, result.p99_ms),
+            (Some(50.0), Some(95.0), Some(99.0))
+        );
+        assert_eq!(result.throughput_per_sec, Some(50.0));
+        assert_eq!(result.errors, vec!["timeout"], "Errors should be deduplicated");
+    }
+
+    #[test]
+    fn test_bench_compare_flags_regressions_beyond_thresholds() {
+        let measurement =
+            |key: &str, p95_ms: f64, throughput: f64, failure_rate: f64| Measurement {
+                key: key.to_string(),
+                p50_ms: None,
+                p95_ms: Some(p95_ms),
+                p99_ms: None,
+                throughput_per_sec: Some(throughput),
+                failure_rate,
+            };
+        let baseline = [
+            measurement("fanout/100/evaluate-allow", 10.0, 100.0, 0.0),
+            measurement("fanout/1000/evaluate-allow", 0.5, 100.0, 0.0),
+            measurement("auth/cache-hit/auth-only", 2.0, 100.0, 0.0),
+        ];
+        let candidate = [
+            measurement("fanout/100/evaluate-allow", 12.0, 95.0, 0.01),
+            // Doubled, but by less than the minimum delta
+            measurement("fanout/1000/evaluate-allow", 1.0, 100.0, 0.0),
+        ];
+
+        let changes = compare(&baseline, &candidate, &Thresholds::default());
+        let regressed: Vec<(&str, &str)> = changes
+            .iter()
+            .filter(|change| change.regression)
+            .map(|change| (change.key.as_str(), change.metric.as_str()))
+            .collect();
+
+        assert_eq!(
+            regressed,
+            vec![
+                ("fanout/100/evaluate-allow", "p95"),
+                ("fanout/100/evaluate-allow", "failure rate"),
+                ("auth/cache-hit/auth-only", "presence"),
+            ]
+        );
+        assert_eq!(changes.len(), 7);
+
+        let thresholds = Thresholds { allow_missing: true, ..Default::default() };
+        let changes = compare(&baseline, &candidate, &thresholds);
+        let missing = changes.iter().find(|change| change.metric == "presence");
+        assert!(
+            missing.is_some_and(|change| !change.regression),
+            "allow_missing should let it through"
+        );
+    }
+}
@@ -0,0 +1,414 @@
+#![deny(unsafe_code)]
+
+// Benchmark runner
+//
+// Runs named benchmarks (`inferadb_integration_tests::bench`) against an arbitrary environment,
+// each against its own fixture, and writes every latency summary to a JSON artifact that can be
+// kept per release to track how Engine scales.
+//
+// Run with:
+//   cargo run --release --bin bench -- --benchmark fanout --iterations 50
+//
+// Flags:
+//   --benchmark NAME   Benchmark to run; repeatable (default: all)
+//   --iterations N     Timed operations per case, or per writer (default: 20)
+//   --fanout N         Fan-out sizes for `fanout`; repeatable (default: 1, 10, 100, 1000)
+//   --writers N        Writers for `write-contention`; repeatable (default: 1, 10, 50)
+//   --report PATH      Report destination (default: target/bench-report.json)
+//   --api-url URL      API base URL (default: INFERADB_API_URL or Tailscale discovery)
+//   --list             List benchmarks and exit
+
+use std::{
+    collections::BTreeSet, future::Future, path::PathBuf, pin::Pin, process::ExitCode,
+    time::Instant,
+};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use inferadb_integration_tests::{bench::*, *};
+use reqwest::{Method, StatusCode};
+
+/// Relationships written per request when seeding
+const SEED_BATCH_SIZE: usize = 100;
+
+/// Engine paths that may authenticate a token without evaluating anything, tried in order
+const AUTH_ONLY_PATHS: &[&str] = &["/whoami", "/auth/whoami", "/token/introspect"];
+
+type BenchFuture<'a> = Pin<Box<dyn Future<Output = Result<Vec<BenchResult>>> + Send + 'a>>;
+
+/// A named benchmark: an async function from the options to its results
+struct Benchmark {
+    name: &'static str,
+    description: &'static str,
+    run: for<'a> fn(&'a Options, &'a TestFixture) -> BenchFuture<'a>,
+}
+
+/// Write `relationships` in batches of `SEED_BATCH_SIZE`
+async fn seed(engine: &EngineClient, relationships: &[Relationship]) -> Result<()> {
+    for batch in relationships.chunks(SEED_BATCH_SIZE) {
+        engine.write_relationships(batch).await.context("Failed to seed relationships")?;
+    }
+    Ok(())
+}
+
+/// Evaluate and list-subjects latency on resources with 1, 10, 100, ... direct viewers
+fn fanout<'a>(options: &'a Options, fixture: &'a TestFixture) -> BenchFuture<'a> {
+    Box::pin(async move {
+        let jwt = fixture.generate_jwt(None, &["inferadb.check", "inferadb.write"])?;
+        let engine = fixture.engine(&jwt);
+        let mut results = Vec::new();
+
+        for &size in &options.fanouts {
+            let resource = format!("document:fanout-{}", size);
+            let viewers: Vec<Relationship> = (0..size)
+                .map(|i| Relationship::new(&resource, "viewer", format!("user:fanout-{}", i)))
+                .collect();
+            seed(&engine, &viewers).await?;
+            let case = size.to_string();
+            println!("  fan-out {}: seeded", size);
+
+            // Every evaluation names a subject of its own, walking back from the last edge written
+            // for allows and one never written for denials, and asks for full consistency, so no
+            // decision can come from a cache
+            let mut allow = Samples::default();
+            let mut deny = Samples::default();
+            let mut list = Samples::default();
+            let mut listed = None;
+            let _ = engine.check(&resource, "viewer", "user:warmup").await;
+            for i in 0..options.iterations {
+                let viewer = format!("user:fanout-{}", size - 1 - i % size);
+                let outsider = format!("user:outsider-{}", i);
+                allow
+                    .time(engine.evaluate_with(&resource, "viewer", &viewer, &Consistency::Full))
+                    .await;
+                deny.time(engine.evaluate_with(&resource, "viewer", &outsider, &Consistency::Full))
+                    .await;
+                if let Some(response) =
+                    list.time(engine.list_subjects(&resource, "viewer", "user")).await
+                {
+                    listed = Some(response.subjects.len());
+                }
+            }
+
+            let uncached =
+                |subjects: usize| format!("uncached: full consistency, {} subjects", subjects);
+            let mut allow = allow.summarize("fanout", &case, "evaluate-allow");
+            allow.notes.push(uncached(options.iterations.min(size)));
+            results.push(allow);
+            let mut deny = deny.summarize("fanout", &case, "evaluate-deny");
+            deny.notes.push(uncached(options.iterations));
+            results.push(deny);
+            let mut list = list.summarize("fanout", &case, "list-subjects");
+            list.notes.push("cacheable: the same listing every iteration".to_string());
+            if let Some(listed) = listed {
+                list.notes.push(format!("first page listed {} of {} subjects", listed, size));
+            }
+            results.push(list);
+
+            engine.delete_relationships(&viewers).await.context("Failed to remove seed")?;
+        }
+        Ok(results)
+    })
+}
+
+/// Concurrent writers adding viewers to one hot resource; every acknowledged write must survive
+fn write_contention<'a>(options: &'a Options, fixture: &'a TestFixture) -> BenchFuture<'a> {
+    Box::pin(async move {
+        let jwt = fixture.generate_jwt(None, &["inferadb.check", "inferadb.write"])?;
+        let engine = fixture.engine(&jwt);
+        let mut results = Vec::new();
+
+        for &writers in &options.writers {
+            let resource = format!("document:hot-{}", writers);
+            let start = Instant::now();
+            let handles: Vec<_> = (0..writers)
+                .map(|writer| {
+                    let engine = engine.clone();
+                    let resource = resource.clone();
+                    let writes = options.iterations;
+                    tokio::spawn(async move {
+                        let mut outcomes = Vec::with_capacity(writes);
+                        for i in 0..writes {
+                            let subject = format!("user:writer-{}-{}", writer, i);
+                            let relationship = Relationship::new(&resource, "viewer", &subject);
+                            let write_start = Instant::now();
+                            let result = engine.write_relationships(&[relationship]).await;
+                            let latency_ms = write_start.elapsed().as_secs_f64() * 1000.0;
+                            outcomes.push((subject, result.map(|()| latency_ms)));
+                        }
+                        outcomes
+                    })
+                })
+                .collect();
+
+            let mut samples = Samples::default();
+            let mut acknowledged = BTreeSet::new();
+            for handle in handles {
+                for (subject, outcome) in handle.await.context("Writer task failed")? {
+                    match outcome {
+                        Ok(latency_ms) => {
+                            samples.record(latency_ms);
+                            acknowledged.insert(subject);
+                        },
+                        Err(e) => samples.record_failure(e.to_string()),
+                    }
+                }
+            }
+            samples.set_elapsed_ms(start.elapsed().as_secs_f64() * 1000.0);
+
+            // Lost updates: acknowledged but missing from the final state
+            let filter = RelationshipFilter::new().resource(&resource).relation("viewer");
+            let stored: BTreeSet<String> = engine
+                .list_all_relationships(&filter)
+                .await
+                .context("Failed to list the hot resource")?
+                .into_iter()
+                .map(|relationship| relationship.subject)
+                .collect();
+            let lost = acknowledged.difference(&stored).count();
+            for _ in 0..lost {
+                samples.record_failure("acknowledged write missing from final state".to_string());
+            }
+
+            let mut result = samples.summarize("write-contention", &writers.to_string(), "write");
+            result.notes.push(format!(
+                "{} acknowledged, {} stored, {} lost",
+                acknowledged.len(),
+                stored.len(),
+                lost
+            ));
+            println!("  {} writers: {}", writers, result.notes[0]);
+            results.push(result);
+
+            let written: Vec<Relationship> = stored
+                .iter()
+                .map(|subject| Relationship::new(&resource, "viewer", subject))
+                .collect();
+            for batch in written.chunks(SEED_BATCH_SIZE) {
+                engine.delete_relationships(batch).await.context("Failed to remove writes")?;
+            }
+        }
+        Ok(results)
+    })
+}
+
+/// The first `AUTH_ONLY_PATHS` entry that answers a valid token and refuses a missing one
+async fn find_auth_only_path(fixture: &TestFixture, engine: &EngineClient) -> Option<&'static str> {
+    for &path in AUTH_ONLY_PATHS {
+        let authenticated = engine.request(Method::GET, path).send().await;
+        if !authenticated.is_ok_and(|response| response.status().is_success()) {
+            continue;
+        }
+        let anonymous = fixture.ctx.client.get(fixture.ctx.engine_url(path)).send().await;
+        if anonymous.is_ok_and(|response| response.status() == StatusCode::UNAUTHORIZED) {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Token validation alone (warm cache) next to a full evaluate with the same token
+fn auth<'a>(options: &'a Options, fixture: &'a TestFixture) -> BenchFuture<'a> {
+    Box::pin(async move {
+        let jwt = fixture.generate_jwt(None, &["inferadb.check"])?;
+        let engine = fixture.engine(&jwt);
+        let Some(path) = find_auth_only_path(fixture, &engine).await else {
+            println!("  - skipped: Engine exposes none of {:?}", AUTH_ONLY_PATHS);
+            return Ok(Vec::new());
+        };
+        println!("  auth-only endpoint: {}", path);
+
+        // Warm the certificate cache so both operations take the cache-hit path
+        let _ = engine.check("document:bench", "viewer", "user:alice").await;
+
+        let mut auth_only = Samples::default();
+        let mut evaluate = Samples::default();
+        for _ in 0..options.iterations {
+            auth_only
+                .time(async { engine.request(Method::GET, path).send().await?.error_for_status() })
+                .await;
+            evaluate.time(engine.check("document:bench", "viewer", "user:alice")).await;
+        }
+
+        let auth_only = auth_only.summarize("auth", "cache-hit", "auth-only");
+        let mut evaluate = evaluate.summarize("auth", "cache-hit", "evaluate");
+        if let (Some(auth_p50), Some(evaluate_p50)) = (auth_only.p50_ms, evaluate.p50_ms) {
+            evaluate.notes.push(format!(
+                "p50 spent beyond authentication: {:.1}ms (auth-only via {})",
+                evaluate_p50 - auth_p50,
+                path
+            ));
+        }
+        Ok(vec![auth_only, evaluate])
+    })
+}
+
+fn catalog() -> Vec<Benchmark> {
+    vec![
+        Benchmark {
+            name: "fanout",
+            description: "Evaluate and list-subjects latency as direct viewers grow",
+            run: fanout,
+        },
+        Benchmark {
+            name: "write-contention",
+            description: "Concurrent writers on one resource: throughput and lost updates",
+            run: write_contention,
+        },
+        Benchmark {
+            name: "auth",
+            description: "Token validation overhead, separated from evaluation work",
+            run: auth,
+        },
+    ]
+}
+
+/// Parsed command line
+struct Options {
+    benchmarks: Vec<String>,
+    iterations: usize,
+    fanouts: Vec<usize>,
+    writers: Vec<usize>,
+    report: PathBuf,
+    api_url: Option<String>,
+    list: bool,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut options = Self {
+            benchmarks: Vec::new(),
+            iterations: 20,
+            fanouts: Vec::new(),
+            writers: Vec::new(),
+            report: PathBuf::from("target/bench-report.json"),
+            api_url: None,
+            list: false,
+        };
+
+        while let Some(flag) = args.next() {
+            if flag == "--list" {
+                options.list = true;
+                continue;
+            }
+            let value = args.next().with_context(|| format!("{} requires a value", flag))?;
+            let invalid = || format!("Invalid value for {}: {}", flag, value);
+            match flag.as_str() {
+                "--benchmark" => options.benchmarks.push(value.clone()),
+                "--iterations" => options.iterations = value.parse().with_context(invalid)?,
+                "--fanout" => options.fanouts.push(value.parse().with_context(invalid)?),
+                "--writers" => options.writers.push(value.parse().with_context(invalid)?),
+                "--report" => options.report = PathBuf::from(&value),
+                "--api-url" => options.api_url = Some(value.clone()),
+                _ => anyhow::bail!("Unknown flag: {}", flag),
+            }
+        }
+        if options.fanouts.is_empty() {
+            options.fanouts = vec![1, 10, 100, 1000];
+        }
+        anyhow::ensure!(!options.fanouts.contains(&0), "--fanout must be at least 1");
+        if options.writers.is_empty() {
+            options.writers = vec![1, 10, 50];
+        }
+        Ok(options)
+    }
+}
+
+async fn run(options: Options) -> Result<bool> {
+    let catalog = catalog();
+    if options.list {
+        for benchmark in &catalog {
+            println!("{:<20} {}", benchmark.name, benchmark.description);
+        }
+        return Ok(true);
+    }
+
+    for name in &options.benchmarks {
+        anyhow::ensure!(
+            catalog.iter().any(|benchmark| benchmark.name == name),
+            "Unknown benchmark '{}' (see --list)",
+            name
+        );
+    }
+    if let Some(url) = &options.api_url {
+        set_api_base_url(url)?;
+    }
+
+    let build = BuildInfo::fetch(&TestContext::new()).await;
+    println!("Engine {}, Control {}", build.engine, build.control);
+    build.require_configured_minimums()?;
+
+    let mut report = BenchReport {
+        api_base_url: api_base_url(),
+        build,
+        started_at: Utc::now().to_rfc3339(),
+        results: Vec::new(),
+    };
+    let mut passed = true;
+
+    let selected = catalog.iter().filter(|benchmark| {
+        options.benchmarks.is_empty() || options.benchmarks.contains(&benchmark.name.to_string())
+    });
+    for benchmark in selected {
+        println!("{} ({} iterations per case)", benchmark.name, options.iterations);
+        let fixture = TestFixture::create().await.context("Failed to provision fixture")?;
+        let outcome = (benchmark.run)(&options, &fixture).await;
+        if let Err(e) = fixture.cleanup().await {
+            eprintln!("  ⚠ cleanup failed: {:#}", e);
+        }
+
+        match outcome {
+            Ok(results) => {
+                for result in &results {
+                    println!(
+                        "  {:<8} {:<16} p50 {:>8} p95 {:>8} p99 {:>8}{}",
+                        result.case,
+                        result.operation,
+                        format_ms(result.p50_ms),
+                        format_ms(result.p95_ms),
+                        format_ms(result.p99_ms),
+                        if result.failures > 0 {
+                            format!("  ({} failed)", result.failures)
+                        } else {
+                            String::new()
+                        }
+                    );
+                    passed &= result.failures == 0;
+                }
+                report.results.extend(results);
+            },
+            Err(e) => {
+                println!("  ✗ {}: {:#}", benchmark.name, e);
+                passed = false;
+            },
+        }
+    }
+
+    report.write(&options.report)?;
+    println!("Report written to {}", options.report.display());
+    Ok(passed)
+}
+
+fn format_ms(ms: Option<f64>) -> String {
+    ms.map_or_else(|| "-".to_string(), |ms| format!("{:.1}ms", ms))
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => run(options).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("Benchmark run FAILED: some operations failed");
+            ExitCode::FAILURE
+        },
+        Err(e) => {
+            eprintln!("Benchmark run FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,128 @@
+#![deny(unsafe_code)]
+
+// Report comparison
+//
+// Compares two JSON artifacts written by `bench` or `scenarios` (e.g. staging against a
+// production candidate) measurement by measurement, prints every change, and exits non-zero if the
+// candidate is slower, has less throughput, fails more often than the baseline allows, or lacks a
+// measurement the baseline has.
+//
+// Run with:
+//   cargo run --bin compare-reports -- baseline.json candidate.json --max-latency-increase 15
+//
+// Flags:
+//   --percentile N                 Latency percentile compared: 50, 95, or 99 (default: 95)
+//   --max-latency-increase PCT     Allowed latency increase in percent (default: 10)
+//   --min-latency-delta-ms MS      Ignore latency increases smaller than this (default: 1)
+//   --max-throughput-drop PCT      Allowed throughput drop in percent (default: 10)
+//   --max-failure-increase PCT     Allowed failure-rate increase in points (default: 0)
+//   --allow-missing                Don't fail on baseline measurements the candidate lacks
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::{Context, Result};
+use inferadb_integration_tests::bench::*;
+
+/// Parsed command line
+struct Options {
+    baseline: PathBuf,
+    candidate: PathBuf,
+    thresholds: Thresholds,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut thresholds = Thresholds::default();
+        let mut reports = Vec::new();
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                reports.push(PathBuf::from(arg));
+                continue;
+            }
+            if arg == "--allow-missing" {
+                thresholds.allow_missing = true;
+                continue;
+            }
+            let value = args.next().with_context(|| format!("{} requires a value", arg))?;
+            let invalid = || format!("Invalid value for {}: {}", arg, value);
+            let percent = || value.parse::<f64>().map(|pct| pct / 100.0).with_context(invalid);
+            match arg.as_str() {
+                "--percentile" => {
+                    thresholds.percentile = value.parse().with_context(invalid)?;
+                    anyhow::ensure!(
+                        [50, 95, 99].contains(&thresholds.percentile),
+                        "--percentile must be 50, 95, or 99"
+                    );
+                },
+                "--max-latency-increase" => thresholds.max_latency_increase = percent()?,
+                "--min-latency-delta-ms" => {
+                    thresholds.min_latency_delta_ms = value.parse().with_context(invalid)?
+                },
+                "--max-throughput-drop" => thresholds.max_throughput_drop = percent()?,
+                "--max-failure-increase" => thresholds.max_failure_rate_increase = percent()?,
+                _ => anyhow::bail!("Unknown flag: {}", arg),
+            }
+        }
+
+        let [baseline, candidate] = <[PathBuf; 2]>::try_from(reports)
+            .map_err(|_| anyhow::anyhow!("Expected two reports: BASELINE CANDIDATE"))?;
+        Ok(Self { baseline, candidate, thresholds })
+    }
+}
+
+fn format_value(metric: &str, value: f64) -> String {
+    match metric {
+        "throughput" => format!("{:.1}/s", value),
+        "failure rate" => format!("{:.2}%", value * 100.0),
+        "presence" => if value > 0.0 { "measured" } else { "missing" }.to_string(),
+        _ => format!("{:.1}ms", value),
+    }
+}
+
+fn run(options: Options) -> Result<bool> {
+    let baseline = load_measurements(&options.baseline)?;
+    let candidate = load_measurements(&options.candidate)?;
+    println!("Baseline:  {} ({} measurements)", options.baseline.display(), baseline.len());
+    println!("Candidate: {} ({} measurements)", options.candidate.display(), candidate.len());
+
+    for added in candidate.iter().filter(|c| baseline.iter().all(|b| b.key != c.key)) {
+        println!("  - {} not in baseline", added.key);
+    }
+
+    let changes = compare(&baseline, &candidate, &options.thresholds);
+    for change in &changes {
+        let delta = if change.baseline == 0.0 || change.metric == "presence" {
+            String::new()
+        } else {
+            format!(" ({:+.1}%)", (change.candidate / change.baseline - 1.0) * 100.0)
+        };
+        println!(
+            "  {} {:<50} {:<12} {} → {}{}",
+            if change.regression { "✗" } else { "✓" },
+            change.key,
+            change.metric,
+            format_value(&change.metric, change.baseline),
+            format_value(&change.metric, change.candidate),
+            delta
+        );
+    }
+
+    let regressions = changes.iter().filter(|change| change.regression).count();
+    println!("{} comparisons, {} regressions", changes.len(), regressions);
+    Ok(regressions == 0)
+}
+
+fn main() -> ExitCode {
+    match Options::parse(std::env::args().skip(1)).and_then(run) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("Comparison FAILED: candidate regressed against baseline");
+            ExitCode::FAILURE
+        },
+        Err(e) => {
+            eprintln!("Comparison FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,141 @@
+#![deny(unsafe_code)]
+
+// Dataset provisioning
+//
+// Populates an environment with a seeded multi-tenant dataset (`inferadb_integration_tests::
+// datasets`) for demos, manual exploration, or scale runs, and writes a manifest of everything it
+// created. The manifest carries each organization's session and signing key, so the data can be
+// queried afterwards, and `--cleanup` deletes the dataset it describes.
+//
+// Run with:
+//   cargo run --bin dataset -- --orgs 3 --vaults 4 --relationships 10000 --seed 7
+//   cargo run --bin dataset -- --cleanup --manifest target/dataset.json
+//
+// Flags:
+//   --orgs N           Organizations to create (default: 2)
+//   --vaults N         Vaults per organization (default: 2)
+//   --relationships N  Relationships per vault (default: 1000)
+//   --documents N      Documents per vault tuples are drawn from (default: 1000)
+//   --users N          Users per vault tuples are drawn from (default: 500)
+//   --zipf S           Zipf exponent of document and user popularity (default: 1.1)
+//   --seed N           Seed for the drawn tuples (default: 1)
+//   --manifest PATH    Manifest to write, or to read with --cleanup (default: target/dataset.json)
+//   --cleanup          Delete the dataset in the manifest instead of creating one
+//   --api-url URL      API base URL (default: INFERADB_API_URL or Tailscale discovery)
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::{Context, Result};
+use inferadb_integration_tests::*;
+
+/// Parsed command line
+struct Options {
+    spec: DatasetSpec,
+    manifest: PathBuf,
+    cleanup: bool,
+    api_url: Option<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut spec = DatasetSpec::new(2, 2, 1000);
+        let mut manifest = PathBuf::from("target/dataset.json");
+        let mut cleanup = false;
+        let mut api_url = None;
+
+        while let Some(flag) = args.next() {
+            if flag == "--cleanup" {
+                cleanup = true;
+                continue;
+            }
+            let value = args.next().with_context(|| format!("{} requires a value", flag))?;
+            let invalid = || format!("Invalid value for {}: {}", flag, value);
+            match flag.as_str() {
+                "--orgs" => spec.orgs = value.parse().with_context(invalid)?,
+                "--vaults" => spec.vaults_per_org = value.parse().with_context(invalid)?,
+                "--relationships" => {
+                    spec.relationships_per_vault = value.parse().with_context(invalid)?
+                },
+                "--documents" => spec.documents = value.parse().with_context(invalid)?,
+                "--users" => spec.users = value.parse().with_context(invalid)?,
+                "--zipf" => spec.zipf_exponent = value.parse().with_context(invalid)?,
+                "--seed" => spec.seed = value.parse().with_context(invalid)?,
+                "--manifest" => manifest = PathBuf::from(&value),
+                "--api-url" => api_url = Some(value.clone()),
+                _ => anyhow::bail!("Unknown flag: {}", flag),
+            }
+        }
+
+        anyhow::ensure!(spec.vaults_per_org >= 1, "--vaults must be at least 1");
+        anyhow::ensure!(spec.documents >= 1 && spec.users >= 1, "Populations must not be empty");
+        Ok(Self { spec, manifest, cleanup, api_url })
+    }
+}
+
+async fn run(options: Options) -> Result<()> {
+    if let Some(url) = &options.api_url {
+        set_api_base_url(url)?;
+    }
+
+    if options.cleanup {
+        let mut manifest = DatasetManifest::read(&options.manifest)?;
+        println!(
+            "Deleting dataset {} ({} organizations) from {}",
+            options.manifest.display(),
+            manifest.tenants.len(),
+            api_base_url()
+        );
+        if let Err(e) = manifest.cleanup().await {
+            manifest.write(&options.manifest)?;
+            return Err(e.context(format!(
+                "{} now lists the {} organizations left; rerun --cleanup to retry them",
+                options.manifest.display(),
+                manifest.tenants.len()
+            )));
+        }
+        std::fs::remove_file(&options.manifest)
+            .with_context(|| format!("Failed to remove {}", options.manifest.display()))?;
+        println!("✓ Dataset deleted");
+        return Ok(());
+    }
+
+    anyhow::ensure!(
+        !options.manifest.exists(),
+        "{} already describes a dataset; delete it with --cleanup or pass another --manifest",
+        options.manifest.display()
+    );
+    let spec = &options.spec;
+    println!(
+        "Provisioning {} orgs × {} vaults × {} relationships (seed {}) against {}",
+        spec.orgs,
+        spec.vaults_per_org,
+        spec.relationships_per_vault,
+        spec.seed,
+        api_base_url()
+    );
+    let manifest = DatasetManifest::provision(spec).await?;
+    manifest.write(&options.manifest)?;
+    println!(
+        "✓ {} relationships across {} vaults; manifest written to {}",
+        manifest.relationship_count(),
+        manifest.tenants.iter().map(|tenant| tenant.vaults.len()).sum::<usize>(),
+        options.manifest.display()
+    );
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => run(options).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Dataset FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,124 @@
+#![deny(unsafe_code)]
+
+// Shadow-traffic replay
+//
+// Replays the Engine traffic in a capture file (written by the recorder when
+// `INFERADB_CAPTURE_FILE` is set) against an arbitrary environment, using a fresh fixture there,
+// and exits non-zero if any request's status or evaluate decisions differ from the capture.
+//
+// Run with:
+//   cargo run --bin replay -- target/capture.jsonl --speed 4 --api-url https://staging.example.com
+//
+// Flags:
+//   --speed N          Pacing relative to the capture; 0 sends back-to-back (default: 1)
+//   --api-url URL      API base URL (default: INFERADB_API_URL or Tailscale discovery)
+
+use std::{path::PathBuf, process::ExitCode};
+
+use anyhow::{Context, Result};
+use inferadb_integration_tests::*;
+
+/// Scopes the replay JWT carries, enough for any captured Engine call
+const REPLAY_SCOPES: &[&str] = &[
+    "inferadb.check",
+    "inferadb.read",
+    "inferadb.write",
+    "inferadb.expand",
+    "inferadb.list",
+    "inferadb.list-relationships",
+    "inferadb.list-subjects",
+    "inferadb.list-resources",
+];
+
+/// Parsed command line
+struct Options {
+    capture: PathBuf,
+    speed: f64,
+    api_url: Option<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut capture = None;
+        let mut speed = 1.0;
+        let mut api_url = None;
+
+        while let Some(arg) = args.next() {
+            if !arg.starts_with("--") {
+                anyhow::ensure!(capture.is_none(), "Expected one capture file");
+                capture = Some(PathBuf::from(arg));
+                continue;
+            }
+            let value = args.next().with_context(|| format!("{} requires a value", arg))?;
+            let invalid = || format!("Invalid value for {}: {}", arg, value);
+            match arg.as_str() {
+                "--speed" => {
+                    speed = value.parse().with_context(invalid)?;
+                    anyhow::ensure!(speed >= 0.0, "--speed must not be negative");
+                },
+                "--api-url" => api_url = Some(value.clone()),
+                _ => anyhow::bail!("Unknown flag: {}", arg),
+            }
+        }
+
+        let capture = capture.context("Expected a capture file")?;
+        Ok(Self { capture, speed, api_url })
+    }
+}
+
+async fn run(options: Options) -> Result<bool> {
+    let exchanges = recorder::read_capture(&options.capture)?;
+    if let Some(url) = &options.api_url {
+        set_api_base_url(url)?;
+    }
+    println!(
+        "Replaying {} ({} requests) against {}",
+        options.capture.display(),
+        exchanges.len(),
+        api_base_url()
+    );
+
+    let fixture = TestFixture::create().await.context("Failed to provision fixture")?;
+    let outcome = match fixture.generate_jwt(None, REPLAY_SCOPES) {
+        Ok(jwt) => Ok(Replayer::new(fixture.ctx.clone(), jwt)
+            .speed(options.speed)
+            .replay(&exchanges)
+            .await),
+        Err(e) => Err(e),
+    };
+    if let Err(e) = fixture.cleanup().await {
+        eprintln!("  ⚠ cleanup failed: {:#}", e);
+    }
+    let report = outcome?;
+
+    for divergence in &report.divergences {
+        println!("  ✗ {}", divergence);
+    }
+    println!(
+        "{} replayed, {} skipped, {} diverged",
+        report.replayed,
+        report.skipped,
+        report.divergences.len()
+    );
+    Ok(report.divergences.is_empty())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => run(options).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("Replay FAILED: target answered differently from the capture");
+            ExitCode::FAILURE
+        },
+        Err(e) => {
+            eprintln!("Replay FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,321 @@
+#![deny(unsafe_code)]
+
+// Scenario runner
+//
+// Runs named workflows built on the scenario DSL (`inferadb_integration_tests::scenario`) against
+// an arbitrary environment, holds each to the given SLOs, and writes a JSON report.
+//
+// Run with:
+//   cargo run --bin scenarios -- --scenario token-lifecycle --iterations 20 --p95-ms 250
+//
+// Flags:
+//   --scenario NAME          Scenario to run; repeatable (default: all)
+//   --iterations N           Iterations per scenario (default: 5)
+//   --p95-ms MS              Maximum p95 latency for any step
+//   --max-failure-rate F     Fraction of iterations allowed to fail (default: 0)
+//   --report PATH            Report destination (default: target/scenario-report.json)
+//   --api-url URL            API base URL (default: INFERADB_API_URL or Tailscale discovery)
+//   --audience AUD           Audience Engine requires
+//   --issuer URL             Issuer Engine accepts; repeatable (default: the API base URL)
+//   --clock-leeway SECONDS   Leeway Engine applies to `exp`/`nbf`
+//   --record                 Write a curl reproduction script for every failed iteration
+//   --list                   List scenarios and exit
+
+use std::{path::PathBuf, process::ExitCode, time::Duration};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use inferadb_integration_tests::*;
+use reqwest::StatusCode;
+
+/// Poll an evaluate call with `jwt` until Engine answers `expected`, for up to 10 seconds
+async fn await_status(run: &ScenarioRun, jwt: &str, expected: StatusCode) -> Result<()> {
+    let mut status = None;
+    for _ in 0..20 {
+        let response = run
+            .fixture
+            .call_server_evaluate(jwt, "document:scenario", "viewer", "user:alice")
+            .await?;
+        if response.status() == expected {
+            return Ok(());
+        }
+        status = Some(response.status());
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    anyhow::bail!("Expected {} within 10s, last status was {:?}", expected, status)
+}
+
+/// Evaluate with `jwt` once and require that authentication passed
+async fn expect_accepted(run: &ScenarioRun, jwt: &str) -> Result<()> {
+    let response =
+        run.fixture.call_server_evaluate(jwt, "document:scenario", "viewer", "user:alice").await?;
+    let status = response.status();
+    anyhow::ensure!(
+        status == StatusCode::OK || status == StatusCode::NOT_FOUND,
+        "Expected 200 or 404, got {}",
+        status
+    );
+    Ok(())
+}
+
+fn token_lifecycle() -> Scenario {
+    Scenario::new("token-lifecycle", "Valid tokens pass; expired and revoked ones are refused")
+        .step("valid token accepted", |run| {
+            Box::pin(async move {
+                let jwt = run.fixture.generate_jwt(None, &["inferadb.check"])?;
+                expect_accepted(run, &jwt).await
+            })
+        })
+        .step("expired token rejected", |run| {
+            Box::pin(async move {
+                let jwt = run.fixture.jwt().expired().encode()?;
+                await_status(run, &jwt, StatusCode::UNAUTHORIZED).await
+            })
+        })
+        .step("revoke certificate", |run| {
+            Box::pin(async move {
+                let fixture = &run.fixture;
+                fixture
+                    .control()
+                    .revoke_certificate(fixture.org_id, fixture.client_id, fixture.cert_id)
+                    .await?;
+                Ok(())
+            })
+        })
+        .step("revoked key rejected", |run| {
+            Box::pin(async move {
+                let jwt = run.fixture.generate_jwt(None, &["inferadb.check"])?;
+                await_status(run, &jwt, StatusCode::UNAUTHORIZED).await
+            })
+        })
+}
+
+fn isolation() -> Scenario {
+    Scenario::new("isolation", "A second vault cannot read the first vault's relationships")
+        .step("seed vault A", |run| {
+            Box::pin(async move {
+                let jwt = run.fixture.generate_jwt(None, &["inferadb.write"])?;
+                run.fixture
+                    .engine(&jwt)
+                    .write_relationships(&[Relationship::new(
+                        "document:scenario",
+                        "viewer",
+                        "user:alice",
+                    )])
+                    .await?;
+                Ok(())
+            })
+        })
+        .step("create vault B", |run| {
+            Box::pin(async move {
+                let fixture = &run.fixture;
+                let req = CreateVaultRequest {
+                    name: naming::entity_name("Scenario Vault B"),
+                    organization_id: fixture.org_id,
+                };
+                let vault = fixture.control().create_vault(fixture.org_id, &req).await?.vault;
+                run.ids.insert("vault_b", vault.id);
+                Ok(())
+            })
+        })
+        .step("vault B denied", |run| {
+            Box::pin(async move {
+                let jwt =
+                    run.fixture.generate_jwt(Some(run.id("vault_b")?), &["inferadb.check"])?;
+                let allowed = run
+                    .fixture
+                    .engine(&jwt)
+                    .check("document:scenario", "viewer", "user:alice")
+                    .await?;
+                anyhow::ensure!(!allowed, "Vault B was allowed by vault A's relationship");
+                Ok(())
+            })
+        })
+        .teardown("delete vault B", |run| {
+            Box::pin(async move {
+                if let Ok(vault_b) = run.id("vault_b") {
+                    run.fixture.control().delete_vault(run.fixture.org_id, vault_b).await?;
+                }
+                Ok(())
+            })
+        })
+}
+
+fn cache_invalidation() -> Scenario {
+    Scenario::new("cache-invalidation", "Deleting a vault evicts it from Engine's cache")
+        .step("warm vault cache", |run| {
+            Box::pin(async move {
+                let jwt = run.fixture.generate_jwt(None, &["inferadb.check"])?;
+                expect_accepted(run, &jwt).await
+            })
+        })
+        .step("delete vault", |run| {
+            Box::pin(async move {
+                let fixture = &run.fixture;
+                fixture.control().delete_vault(fixture.org_id, fixture.vault_id).await?;
+                Ok(())
+            })
+        })
+        .step("deleted vault rejected", |run| {
+            Box::pin(async move {
+                let jwt = run.fixture.generate_jwt(None, &["inferadb.check"])?;
+                await_status(run, &jwt, StatusCode::FORBIDDEN).await
+            })
+        })
+}
+
+fn catalog() -> Vec<Scenario> {
+    vec![token_lifecycle(), isolation(), cache_invalidation()]
+}
+
+/// Parsed command line
+struct Options {
+    scenarios: Vec<String>,
+    iterations: usize,
+    slo: Slo,
+    report: PathBuf,
+    api_url: Option<String>,
+    audience: Option<String>,
+    issuers: Vec<String>,
+    clock_leeway_seconds: Option<i64>,
+    record: bool,
+    list: bool,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut options = Self {
+            scenarios: Vec::new(),
+            iterations: 5,
+            slo: Slo::default(),
+            report: PathBuf::from("target/scenario-report.json"),
+            api_url: None,
+            audience: None,
+            issuers: Vec::new(),
+            clock_leeway_seconds: None,
+            record: false,
+            list: false,
+        };
+
+        while let Some(flag) = args.next() {
+            if flag == "--list" {
+                options.list = true;
+                continue;
+            }
+            if flag == "--record" {
+                options.record = true;
+                continue;
+            }
+            let value = args.next().with_context(|| format!("{} requires a value", flag))?;
+            let invalid = || format!("Invalid value for {}: {}", flag, value);
+            match flag.as_str() {
+                "--scenario" => options.scenarios.push(value.clone()),
+                "--iterations" => options.iterations = value.parse().with_context(invalid)?,
+                "--p95-ms" => options.slo.p95_ms = Some(value.parse().with_context(invalid)?),
+                "--max-failure-rate" => {
+                    options.slo.max_failure_rate = value.parse().with_context(invalid)?
+                },
+                "--report" => options.report = PathBuf::from(&value),
+                "--api-url" => options.api_url = Some(value.clone()),
+                "--audience" => options.audience = Some(value.clone()),
+                "--issuer" => options.issuers.push(value.clone()),
+                "--clock-leeway" => {
+                    options.clock_leeway_seconds = Some(value.parse().with_context(invalid)?)
+                },
+                _ => anyhow::bail!("Unknown flag: {}", flag),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Pin the target environment before anything resolves it
+    fn install_environment(&self) -> Result<()> {
+        if let Some(url) = &self.api_url {
+            set_api_base_url(url)?;
+        }
+
+        let mut profile = EnvProfile::from_env();
+        if let Some(audience) = &self.audience {
+            profile.audience = audience.clone();
+        }
+        if !self.issuers.is_empty() {
+            profile.issuer_allow_list = self.issuers.clone();
+        }
+        if let Some(leeway) = self.clock_leeway_seconds {
+            profile.clock_leeway_seconds = leeway;
+        }
+        profile.install()
+    }
+}
+
+async fn run(options: Options) -> Result<bool> {
+    let catalog = catalog();
+    if options.list {
+        for scenario in &catalog {
+            println!("{:<20} {}", scenario.name, scenario.description);
+        }
+        return Ok(true);
+    }
+
+    for name in &options.scenarios {
+        anyhow::ensure!(
+            catalog.iter().any(|scenario| scenario.name == name),
+            "Unknown scenario '{}' (see --list)",
+            name
+        );
+    }
+    options.install_environment()?;
+    if options.record {
+        recorder::enable();
+    }
+
+    let build = BuildInfo::fetch(&TestContext::new()).await;
+    println!("Engine {}, Control {}", build.engine, build.control);
+    build.require_configured_minimums()?;
+
+    let mut report = RunReport {
+        api_base_url: api_base_url(),
+        build,
+        profile: EnvProfile::current().clone(),
+        slo: options.slo.clone(),
+        started_at: Utc::now().to_rfc3339(),
+        scenarios: Vec::new(),
+    };
+
+    let selected = catalog.iter().filter(|scenario| {
+        options.scenarios.is_empty() || options.scenarios.contains(&scenario.name.to_string())
+    });
+    for scenario in selected {
+        println!("{} ({} iterations)", scenario.name, options.iterations);
+        let mut result = scenario.run(options.iterations).await;
+        result.check(&options.slo);
+        for violation in &result.violations {
+            println!("  ✗ SLO: {}", violation);
+        }
+        report.scenarios.push(result);
+    }
+
+    report.write(&options.report)?;
+    println!("Report written to {}", options.report.display());
+    Ok(report.passed())
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => run(options).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("Scenario run FAILED: SLOs not met");
+            ExitCode::FAILURE
+        },
+        Err(e) => {
+            eprintln!("Scenario run FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,201 @@
+#![deny(unsafe_code)]
+
+// Deployment smoke test
+//
+// Runs the minimal end-to-end path (register → vault → client → certificate → JWT → evaluate)
+// against a deployed environment, printing each step, and exits non-zero on the first failure.
+// Whatever was provisioned is deleted on every path, including a failure halfway through
+// provisioning; a failed cleanup is reported on its own and does not change the smoke outcome.
+// Intended for deployment pipelines that cannot run the full integration suite.
+//
+// Run with:
+//   INFERADB_API_URL=https://inferadb-api.example.com cargo run --bin smoke
+
+use std::{future::Future, process::ExitCode, time::Instant};
+
+use anyhow::{Context, Result};
+use inferadb_integration_tests::*;
+
+/// Run one step, printing its outcome and duration
+async fn step<T>(name: &str, future: impl Future<Output = Result<T>>) -> Result<T> {
+    let start = Instant::now();
+    let result = future.await;
+    match &result {
+        Ok(_) => println!("  ✓ {} ({}ms)", name, start.elapsed().as_millis()),
+        Err(e) => println!("  ✗ {}: {:#}", name, e),
+    }
+    result.with_context(|| format!("Smoke step '{}' failed", name))
+}
+
+/// What provisioning has created so far, so cleanup can undo a partial tenant
+#[derive(Default)]
+struct Tenant {
+    user_id: Option<i64>,
+    session_id: Option<i64>,
+    org_id: Option<i64>,
+    vault_id: Option<i64>,
+    client_id: Option<i64>,
+}
+
+impl Tenant {
+    /// Delete everything recorded, most dependent first, continuing past failures
+    async fn clean_up(&self, ctx: &TestContext) -> Result<()> {
+        // Registration yields the user and its session together; without them nothing exists
+        let Some(session_id) = self.session_id else { return Ok(()) };
+        let control = ControlClient::with_session(ctx.clone(), session_id);
+        let mut errors = Vec::new();
+        if let (Some(org_id), Some(vault_id)) = (self.org_id, self.vault_id)
+            && let Err(e) = control.delete_vault(org_id, vault_id).await
+        {
+            errors.push(format!("vault {}: {}", vault_id, e));
+        }
+        if let (Some(org_id), Some(client_id)) = (self.org_id, self.client_id)
+            && let Err(e) = control.delete_client(org_id, client_id).await
+        {
+            errors.push(format!("client {}: {}", client_id, e));
+        }
+        if let Some(org_id) = self.org_id
+            && let Err(e) = control.delete_organization(org_id).await
+        {
+            errors.push(format!("organization {}: {}", org_id, e));
+        }
+        if let Some(user_id) = self.user_id
+            && let Err(e) = control.delete_user(user_id).await
+        {
+            errors.push(format!("user {}: {}", user_id, e));
+        }
+        anyhow::ensure!(errors.is_empty(), "Failed to delete {}", errors.join("; "));
+        Ok(())
+    }
+}
+
+/// Provision a throwaway tenant and return it as a fixture, recording each resource in `tenant`
+async fn provision(ctx: &TestContext, tenant: &mut Tenant) -> Result<TestFixture> {
+    let email = naming::email("smoke");
+    let password = "SecurePassword123!".to_string();
+
+    let register_resp = step("register user", async {
+        let req = RegisterRequest {
+            name: naming::entity_name("Smoke Test User"),
+            email: email.clone(),
+            password: password.clone(),
+            accept_tos: true,
+        };
+        Ok(ControlClient::new(ctx.clone()).register(&req).await?)
+    })
+    .await?;
+    tenant.user_id = Some(register_resp.user_id);
+    tenant.session_id = Some(register_resp.session_id);
+
+    let login_resp = step("log in", async {
+        let req = LoginRequest { email: email.clone(), password: password.clone() };
+        Ok(ControlClient::new(ctx.clone()).login(&req).await?)
+    })
+    .await?;
+    tenant.session_id = Some(login_resp.session_id);
+    let control = ControlClient::with_session(ctx.clone(), login_resp.session_id);
+
+    let org_id = step("find default organization", async {
+        let orgs = control.list_organizations().await?;
+        Ok(orgs.organizations.first().context("No default organization found")?.id)
+    })
+    .await?;
+    tenant.org_id = Some(org_id);
+
+    let vault_id = step("create vault", async {
+        let req = CreateVaultRequest {
+            name: naming::entity_name("Smoke Vault"),
+            organization_id: org_id,
+        };
+        Ok(control.create_vault(org_id, &req).await?.vault.id)
+    })
+    .await?;
+    tenant.vault_id = Some(vault_id);
+
+    let client_id = step("create client", async {
+        let req = CreateClientRequest { name: naming::entity_name("Smoke Client") };
+        Ok(control.create_client(org_id, &req).await?.client.id)
+    })
+    .await?;
+    tenant.client_id = Some(client_id);
+
+    let cert = step("create certificate", async {
+        let req = CreateCertificateRequest::new(naming::entity_name("Smoke Certificate"));
+        Ok(control.create_certificate(org_id, client_id, &req).await?)
+    })
+    .await?;
+
+    let signing_key = keys::from_base64(&cert.private_key)?;
+
+    Ok(TestFixture {
+        ctx: ctx.clone(),
+        user_id: register_resp.user_id,
+        session_id: login_resp.session_id,
+        org_id,
+        vault_id,
+        client_id,
+        cert_id: cert.certificate.id,
+        cert_kid: cert.certificate.kid,
+        verifying_key: signing_key.verifying_key(),
+        signing_key,
+        // Deleted through `Tenant::clean_up`, which reports failures instead of ignoring them
+        persistent: true,
+        snapshot_backed: false,
+    })
+}
+
+/// Write a relationship with a fresh JWT and read it back through evaluate
+async fn authorize(fixture: &TestFixture) -> Result<()> {
+    let jwt = step("issue JWT", async {
+        fixture.generate_jwt(None, &["inferadb.check", "inferadb.write"])
+    })
+    .await?;
+    let engine = fixture.engine(&jwt);
+
+    step("write relationship", async {
+        let relationship = Relationship::new("document:smoke", "viewer", "user:smoke");
+        Ok(engine.write_relationships(&[relationship]).await?)
+    })
+    .await?;
+
+    step("evaluate", async {
+        let allowed = engine.check("document:smoke", "viewer", "user:smoke").await?;
+        anyhow::ensure!(allowed, "Expected ALLOW for the relationship just written");
+        Ok(())
+    })
+    .await
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let start = Instant::now();
+    let ctx = TestContext::new();
+    println!("InferaDB smoke test against {}", ctx.api_base_url);
+
+    let mut tenant = Tenant::default();
+    let result = async {
+        step("health check", validate_environment()).await?;
+        let fixture = provision(&ctx, &mut tenant).await?;
+        authorize(&fixture).await
+    }
+    .await;
+    let cleanup = step("clean up", tenant.clean_up(&ctx)).await;
+
+    if let Err(e) = &cleanup {
+        eprintln!(
+            "Warning: smoke tenant left behind ({:#}); run `sweep --run-id {}`",
+            e,
+            naming::run_id()
+        );
+    }
+    match result {
+        Ok(()) => {
+            println!("Smoke test passed in {:.1}s", start.elapsed().as_secs_f64());
+            ExitCode::SUCCESS
+        },
+        Err(e) => {
+            eprintln!("Smoke test FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
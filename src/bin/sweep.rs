@@ -0,0 +1,262 @@
+#![deny(unsafe_code)]
+
+// Orphaned resource sweeper
+//
+// Crashed or interrupted runs leave their fixtures behind, since cleanup only happens when a test
+// finishes. This deletes Control resources that follow the suite's naming conventions and are
+// older than a threshold, so shared environments do not fill up with garbage. It logs in as an
+// operator account that can see every organization and user, and walks every page of each list
+// before deleting from it, so cursors are not invalidated mid-walk.
+//
+// Run with:
+//   INFERADB_SWEEP_EMAIL=ops@example.org INFERADB_SWEEP_PASSWORD=... \
+//     cargo run --bin sweep -- --older-than-hours 24 --dry-run
+//
+// Flags:
+//   --older-than-hours N   Only sweep resources created at least N hours ago (default: 24)
+//   --run-id ID            Only sweep resources created by one run, e.g. run-1a2b3c4d
+//   --dry-run              Print what would be deleted without deleting anything
+//   --api-url URL          API base URL (default: INFERADB_API_URL or Tailscale discovery)
+//   --email EMAIL          Operator account (default: INFERADB_SWEEP_EMAIL)
+//   --password PASSWORD    Operator password (default: INFERADB_SWEEP_PASSWORD)
+
+use std::{collections::HashSet, future::Future, process::ExitCode};
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use inferadb_integration_tests::*;
+
+/// Parsed command line
+struct Options {
+    older_than: Duration,
+    run_id: Option<String>,
+    dry_run: bool,
+    api_url: Option<String>,
+    email: Option<String>,
+    password: Option<String>,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut options = Self {
+            older_than: Duration::hours(24),
+            run_id: None,
+            dry_run: false,
+            api_url: None,
+            email: std::env::var("INFERADB_SWEEP_EMAIL").ok(),
+            password: std::env::var("INFERADB_SWEEP_PASSWORD").ok(),
+        };
+
+        while let Some(flag) = args.next() {
+            if flag == "--dry-run" {
+                options.dry_run = true;
+                continue;
+            }
+            let value = args.next().with_context(|| format!("{} requires a value", flag))?;
+            let invalid = || format!("Invalid value for {}: {}", flag, value);
+            match flag.as_str() {
+                "--older-than-hours" => {
+                    options.older_than = Duration::hours(value.parse().with_context(invalid)?)
+                },
+                "--run-id" => {
+                    let id = value.strip_prefix("run-").unwrap_or(&value);
+                    options.run_id = Some(format!("run-{}", id))
+                },
+                "--api-url" => options.api_url = Some(value.clone()),
+                "--email" => options.email = Some(value.clone()),
+                "--password" => options.password = Some(value.clone()),
+                _ => anyhow::bail!("Unknown flag: {}", flag),
+            }
+        }
+        Ok(options)
+    }
+
+    /// Whether a vault, client, or organization name marks it as sweepable
+    fn matches_name(&self, name: &str) -> bool {
+        match &self.run_id {
+            Some(run_id) => naming::run_id_of(name) == Some(run_id.as_str()),
+            None => naming::is_test_name(name),
+        }
+    }
+
+    /// Whether a user's email marks it as sweepable
+    fn matches_email(&self, email: &str) -> bool {
+        match &self.run_id {
+            Some(run_id) => {
+                naming::is_test_email(email) && naming::run_id_of(email) == Some(run_id.as_str())
+            },
+            None => naming::is_test_email(email),
+        }
+    }
+}
+
+/// Tally of what a sweep did
+#[derive(Default)]
+struct Sweep {
+    dry_run: bool,
+    deleted: usize,
+    failed: usize,
+}
+
+impl Sweep {
+    /// Delete one resource, or only report it in a dry run
+    async fn delete(&mut self, what: String, future: impl Future<Output = ApiResult<()>>) {
+        if self.dry_run {
+            println!("  would delete {}", what);
+            self.deleted += 1;
+            return;
+        }
+        match future.await {
+            Ok(()) => {
+                println!("  ✓ deleted {}", what);
+                self.deleted += 1;
+            },
+            Err(e) if e.is_not_found() => println!("  - {} already gone", what),
+            Err(e) => {
+                println!("  ✗ {}: {}", what, e);
+                self.failed += 1;
+            },
+        }
+    }
+}
+
+/// Give up after this many pages; a cursor that never ends is a Control bug
+const MAX_PAGES: usize = 10_000;
+
+/// Every item of a paginated list, following `next_cursor` from the first page to the last
+///
+/// Fails if a cursor comes back a second time or the list runs past `MAX_PAGES`, rather than
+/// sweeping a list that was only partly read or read twice.
+async fn all_pages<T, F, Fut>(mut fetch: F) -> Result<Vec<T>>
+where
+    F: FnMut(PageRequest) -> Fut,
+    Fut: Future<Output = ApiResult<(Vec<T>, Option<serde_json::Value>)>>,
+{
+    let mut items = Vec::new();
+    let mut page = PageRequest::default();
+    let mut seen = HashSet::new();
+    for _ in 0..MAX_PAGES {
+        let (batch, pagination) = fetch(page.clone()).await?;
+        items.extend(batch);
+        let Some(cursor) = next_cursor(pagination.as_ref()) else {
+            return Ok(items);
+        };
+        if !seen.insert(cursor.clone()) {
+            anyhow::bail!("Pagination cursor '{}' repeated after {} pages", cursor, seen.len());
+        }
+        page = page.cursor(cursor);
+    }
+    anyhow::bail!("Pagination did not end after {} pages", MAX_PAGES)
+}
+
+async fn run(options: Options) -> Result<bool> {
+    if let Some(url) = &options.api_url {
+        set_api_base_url(url)?;
+    }
+    let email = options.email.clone().context("Operator email required (--email)")?;
+    let password = options.password.clone().context("Operator password required (--password)")?;
+
+    let ctx = TestContext::new();
+    let session = ControlClient::new(ctx.clone())
+        .login(&LoginRequest { email, password })
+        .await
+        .context("Failed to log in as operator")?;
+    let control = ControlClient::with_session(ctx, session.session_id);
+
+    let cutoff = Utc::now() - options.older_than;
+    println!(
+        "Sweeping {} for {} created before {}{}",
+        api_base_url(),
+        options
+            .run_id
+            .as_deref()
+            .map_or("test resources".to_string(), |id| format!("{}'s resources", id)),
+        cutoff.to_rfc3339(),
+        if options.dry_run { " (dry run)" } else { "" }
+    );
+    let mut sweep = Sweep { dry_run: options.dry_run, ..Sweep::default() };
+
+    let control = &control;
+    let organizations = all_pages(|page| async move {
+        control.list_organizations_page(&page).await.map(|r| (r.organizations, r.pagination))
+    })
+    .await
+    .context("Failed to list organizations")?;
+    for org in &organizations {
+        let vaults = all_pages(|page| async move {
+            control.list_vaults_page(org.id, &page).await.map(|r| (r.vaults, r.pagination))
+        })
+        .await
+        .with_context(|| format!("Failed to list vaults of organization {}", org.id))?;
+        for vault in vaults {
+            if options.matches_name(&vault.name) && vault.created_at < cutoff {
+                let what = format!("vault {} ({:?})", vault.id, vault.name);
+                sweep.delete(what, control.delete_vault(org.id, vault.id)).await;
+            }
+        }
+
+        let clients = all_pages(|page| async move {
+            control.list_clients_page(org.id, &page).await.map(|r| (r.clients, r.pagination))
+        })
+        .await
+        .with_context(|| format!("Failed to list clients of organization {}", org.id))?;
+        for client in clients {
+            if options.matches_name(&client.name) && client.created_at < cutoff {
+                let what = format!("client {} ({:?})", client.id, client.name);
+                sweep.delete(what, control.delete_client(org.id, client.id)).await;
+            }
+        }
+
+        if options.matches_name(&org.name) && org.created_at < cutoff {
+            let what = format!("organization {} ({:?})", org.id, org.name);
+            sweep.delete(what, control.delete_organization(org.id)).await;
+        }
+    }
+
+    let users = all_pages(|page| async move {
+        control.list_users_page(&page).await.map(|r| (r.users, r.pagination))
+    })
+    .await;
+    match users {
+        Ok(users) => {
+            for user in users {
+                if options.matches_email(&user.email) && user.created_at < cutoff {
+                    let what = format!("user {} ({})", user.id, user.email);
+                    sweep.delete(what, control.delete_user(user.id)).await;
+                }
+            }
+        },
+        Err(e) if e.downcast_ref::<ApiError>().is_some_and(ApiError::is_unsupported) => {
+            println!("  - skipping users: Control does not list them ({})", e);
+        },
+        Err(e) => return Err(e).context("Failed to list users"),
+    }
+
+    println!(
+        "{} {} resources, {} failures",
+        if options.dry_run { "Would delete" } else { "Deleted" },
+        sweep.deleted,
+        sweep.failed
+    );
+    Ok(sweep.failed == 0)
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let result = match Options::parse(std::env::args().skip(1)) {
+        Ok(options) => run(options).await,
+        Err(e) => Err(e),
+    };
+
+    match result {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("Sweep FAILED: some resources could not be deleted");
+            ExitCode::FAILURE
+        },
+        Err(e) => {
+            eprintln!("Sweep FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
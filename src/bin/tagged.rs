@@ -0,0 +1,237 @@
+#![deny(unsafe_code)]
+
+// Tagged test runner
+//
+// Runs the integration suite restricted to a tag selection (`inferadb_integration_tests::tags`),
+// turning it into libtest filters for `cargo test`. Without flags the selection comes from
+// INFERADB_TAGS and INFERADB_SKIP_TAGS, so CI jobs can pick their subset through the environment.
+//
+// Run with:
+//   cargo run --bin tagged -- --tag smoke
+//   cargo run --bin tagged -- --tag chaos --features k8s -- --test-threads=1
+//
+// Flags:
+//   --tag NAMES        Run modules carrying any of these tags; comma-separated, repeatable
+//   --skip NAMES       Leave out modules carrying any of these tags (default: chaos, perf, slow,
+//                      and destructive, unless selected with --tag)
+//   --features LIST    Extra cargo features, e.g. k8s or api-coverage
+//   --forbid-server-errors
+//                      Capture the run and fail it on any Engine or Control 500/502, even in
+//                      passing tests (`inferadb_integration_tests::invariants`); also enabled by
+//                      INFERADB_FORBID_SERVER_ERRORS=1
+//   --print            Print the cargo command instead of running it
+//   --list             List modules with their tags and exit
+//
+// Arguments after `--` go to the test harness. After the run, tests that skipped or passed with
+// warnings (`inferadb_integration_tests::outcome`) are listed with their reasons; STRICT_MODE=1 is
+// passed through, failing tests on warnings. The run gets one INFERADB_RUN_ID (random unless set),
+// and the shared fixtures its test processes registered are deleted once they exit
+// (`inferadb_integration_tests::shared_fixture`).
+
+use std::{
+    path::{Path, PathBuf},
+    process::{Command, ExitCode},
+};
+
+use anyhow::{Context, Result};
+use inferadb_integration_tests::{invariants, naming, outcome, recorder, shared_fixture, tags::*};
+use uuid::Uuid;
+
+/// Capture file for `--forbid-server-errors` when INFERADB_CAPTURE_FILE is unset
+const DEFAULT_CAPTURE: &str = "target/tagged-capture.jsonl";
+
+/// Outcome report of the run when INFERADB_OUTCOME_REPORT is unset
+const DEFAULT_OUTCOME_REPORT: &str = "target/tagged-outcomes.json";
+
+/// Parsed command line
+struct Options {
+    include: Vec<String>,
+    exclude: Option<Vec<String>>,
+    features: Vec<String>,
+    harness_args: Vec<String>,
+    forbid_server_errors: bool,
+    print: bool,
+    list: bool,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut options = Self {
+            include: Vec::new(),
+            exclude: None,
+            features: vec!["integration-tests".to_string()],
+            harness_args: Vec::new(),
+            forbid_server_errors: std::env::var("INFERADB_FORBID_SERVER_ERRORS")
+                .is_ok_and(|value| value == "1" || value == "true"),
+            print: false,
+            list: false,
+        };
+        let mut tagged = false;
+
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--" => {
+                    options.harness_args.extend(args.by_ref());
+                    continue;
+                },
+                "--print" => {
+                    options.print = true;
+                    continue;
+                },
+                "--list" => {
+                    options.list = true;
+                    continue;
+                },
+                "--forbid-server-errors" => {
+                    options.forbid_server_errors = true;
+                    continue;
+                },
+                _ => {},
+            }
+            let value = args.next().with_context(|| format!("{} requires a value", flag))?;
+            match flag.as_str() {
+                "--tag" => {
+                    tagged = true;
+                    options.include.extend(parse_tags(&value));
+                },
+                "--skip" => options.exclude.get_or_insert_with(Vec::new).extend(parse_tags(&value)),
+                "--features" => options.features.extend(
+                    value.split(',').map(str::trim).filter(|f| !f.is_empty()).map(str::to_string),
+                ),
+                _ => anyhow::bail!("Unknown flag: {}", flag),
+            }
+        }
+
+        if !tagged && options.exclude.is_none() {
+            let from_env = TagSelection::from_env()?;
+            options.include = from_env.include;
+            options.exclude = (!from_env.default_exclude).then_some(from_env.exclude);
+        }
+        Ok(options)
+    }
+}
+
+fn run(options: Options) -> Result<bool> {
+    if options.list {
+        for (module, tags) in MODULE_TAGS {
+            println!("{:<34} {}", module, tags.join(", "));
+        }
+        return Ok(true);
+    }
+
+    let selection = TagSelection::new(options.include, options.exclude)?;
+    let modules = selection.modules();
+    anyhow::ensure!(!modules.is_empty(), "No test modules match {:?}", selection);
+
+    let mut args = vec![
+        "test".to_string(),
+        "--features".to_string(),
+        options.features.join(","),
+        "--test".to_string(),
+        "integration".to_string(),
+        "--".to_string(),
+    ];
+    args.extend(selection.test_args());
+    args.extend(options.harness_args);
+
+    if options.print {
+        println!("cargo {}", args.join(" "));
+        return Ok(true);
+    }
+
+    println!(
+        "Running {} of {} modules (tags: {}; skipping: {})",
+        modules.len(),
+        MODULE_TAGS.len(),
+        if selection.include.is_empty() { "any".to_string() } else { selection.include.join(", ") },
+        if selection.exclude.is_empty() {
+            "none".to_string()
+        } else {
+            selection.exclude.join(", ")
+        }
+    );
+    let cargo = std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let mut command = Command::new(cargo);
+    command.args(&args);
+
+    let outcomes = std::env::var("INFERADB_OUTCOME_REPORT")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_OUTCOME_REPORT));
+    let _ = std::fs::remove_file(&outcomes);
+    command.env("INFERADB_OUTCOME_REPORT", &outcomes);
+
+    let run_suffix = std::env::var("INFERADB_RUN_ID")
+        .ok()
+        .filter(|suffix| naming::run_id_for(suffix).is_some())
+        .unwrap_or_else(|| Uuid::new_v4().simple().to_string()[..8].to_string());
+    command.env("INFERADB_RUN_ID", &run_suffix);
+
+    // Exchanges already in a capture file belong to earlier runs
+    let capture = options.forbid_server_errors.then(|| {
+        let path = std::env::var("INFERADB_CAPTURE_FILE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| PathBuf::from(DEFAULT_CAPTURE));
+        let earlier = recorder::read_capture(&path).map(|e| e.len()).unwrap_or(0);
+        command.env("INFERADB_CAPTURE_FILE", &path);
+        (path, earlier)
+    });
+
+    let status = command.status().context("Failed to run cargo test")?;
+    print_outcomes(&outcomes);
+    if let Some(run_id) = naming::run_id_for(&run_suffix) {
+        cleanup_shared_fixtures(&run_id);
+    }
+    let Some((path, earlier)) = capture else { return Ok(status.success()) };
+
+    let exchanges: Vec<_> = recorder::read_capture(&path)?.into_iter().skip(earlier).collect();
+    let errors = invariants::server_errors(&exchanges);
+    if errors.is_empty() {
+        println!("✓ No 500/502 responses in {} captured exchanges", exchanges.len());
+        return Ok(status.success());
+    }
+    eprintln!(
+        "Server errors in {} of {} captured exchanges:\n{}",
+        errors.len(),
+        exchanges.len(),
+        invariants::summarize(&errors)
+    );
+    Ok(false)
+}
+
+/// Delete the shared fixtures the run's test processes left behind
+fn cleanup_shared_fixtures(run_id: &str) {
+    let removed = tokio::runtime::Runtime::new()
+        .context("Failed to start runtime")
+        .and_then(|runtime| runtime.block_on(shared_fixture::cleanup_registered(run_id)));
+    match removed {
+        Ok(0) => {},
+        Ok(removed) => println!("✓ Removed {} shared fixtures of {}", removed, run_id),
+        Err(e) => eprintln!(
+            "Warning: shared fixtures of {} not removed ({:#}); run `sweep --run-id {}`",
+            run_id, e, run_id
+        ),
+    }
+}
+
+/// Print the skips and warnings the run recorded, if it recorded any
+fn print_outcomes(path: &Path) {
+    let Ok(json) = std::fs::read(path) else { return };
+    match serde_json::from_slice::<outcome::OutcomeReport>(&json) {
+        Ok(report) => print!("{}", outcome::summarize(&report)),
+        Err(e) => eprintln!("Warning: Invalid outcome report {}: {}", path.display(), e),
+    }
+}
+
+fn main() -> ExitCode {
+    match Options::parse(std::env::args().skip(1)).and_then(run) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => {
+            eprintln!("Tagged run FAILED: some tests failed or servers answered 500/502");
+            ExitCode::FAILURE
+        },
+        Err(e) => {
+            eprintln!("Tagged run FAILED: {:#}", e);
+            ExitCode::FAILURE
+        },
+    }
+}
@@ -0,0 +1,236 @@
+// Build Metadata
+//
+// Reads the version and commit Engine and Control report, so a run can refuse builds older than
+// the features it tests need and record what it ran against in its JSON report. Each service is
+// asked at its own `/version` endpoint (a JSON object with `version` and, optionally, `commit`, or
+// a bare version string); a service without one falls back to the labels of its
+// `{service}_build_info` metric.
+//
+// Only dotted numeric versions (`1.4.2`, `v2.0.0-rc1`) count as reported; anything else a service
+// answers with, such as an error page's single word, is treated as no version at all.
+//
+// Minimum versions for the run come from INFERADB_MIN_ENGINE_VERSION and
+// INFERADB_MIN_CONTROL_VERSION; tests covering a newer feature can also call `require` directly.
+// A minimum fails against a service that reports no version, since it cannot be checked.
+
+use std::{cmp::Ordering, fmt};
+
+use tokio::sync::OnceCell;
+
+use super::*;
+
+static SUITE_BUILD_INFO: OnceCell<BuildInfo> = OnceCell::const_new();
+
+/// Labels of a `*_build_info` series that may carry the version
+const VERSION_LABELS: &[&str] = &["version", "build_version"];
+
+/// Labels of a `*_build_info` series that may carry the commit
+const COMMIT_LABELS: &[&str] = &["commit", "revision", "git_sha", "git_commit"];
+
+/// The version one service reports
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceVersion {
+    pub version: Option<String>,
+    pub commit: Option<String>,
+    /// Where the version was read from: an endpoint URL or a metric name
+    pub source: Option<String>,
+}
+
+impl fmt::Display for ServiceVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.version, &self.commit) {
+            (Some(version), Some(commit)) => write!(f, "{} ({})", version, commit),
+            (Some(version), None) => write!(f, "{}", version),
+            (None, Some(commit)) => write!(f, "unknown version ({})", commit),
+            (None, None) => write!(f, "unknown"),
+        }
+    }
+}
+
+/// Versions of the services a run was made against
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BuildInfo {
+    pub engine: ServiceVersion,
+    pub control: ServiceVersion,
+}
+
+impl BuildInfo {
+    /// Ask both services for their version
+    pub async fn fetch(ctx: &TestContext) -> Self {
+        let metrics = MetricsSnapshot::fetch(ctx).await.unwrap_or_default();
+        Self {
+            engine: service_version(ctx, &ctx.engine_url("/version"), "engine", &metrics).await,
+            control: service_version(ctx, &ctx.control_url("/version"), "control", &metrics).await,
+        }
+    }
+
+    /// The reported version of `service` (`engine` or `control`)
+    pub fn service(&self, service: &str) -> Option<&ServiceVersion> {
+        match service {
+            "engine" => Some(&self.engine),
+            "control" => Some(&self.control),
+            _ => None,
+        }
+    }
+
+    /// Fail unless `service` reports a version at least `minimum`
+    pub fn require(&self, service: &str, minimum: &str) -> Result<()> {
+        let reported =
+            self.service(service).with_context(|| format!("Unknown service '{}'", service))?;
+        let Some(version) = &reported.version else {
+            anyhow::bail!("{} reports no version to check against {}", service, minimum);
+        };
+        match compare_versions(version, minimum) {
+            Some(Ordering::Less) => {
+                anyhow::bail!("{} {} is older than the required {}", service, version, minimum)
+            },
+            Some(_) => Ok(()),
+            None => anyhow::bail!("Minimum {:?} for {} is not a version", minimum, service),
+        }
+    }
+
+    /// Apply INFERADB_MIN_ENGINE_VERSION and INFERADB_MIN_CONTROL_VERSION, where set
+    pub fn require_configured_minimums(&self) -> Result<()> {
+        for (service, variable, minimum) in configured_minimums() {
+            self.require(service, &minimum).with_context(|| format!("{} not met", variable))?;
+        }
+        Ok(())
+    }
+}
+
+/// The minimums set in the environment, as (service, variable, minimum)
+pub fn configured_minimums() -> Vec<(&'static str, String, String)> {
+    ["engine", "control"]
+        .into_iter()
+        .filter_map(|service| {
+            let variable = format!("INFERADB_MIN_{}_VERSION", service.to_uppercase());
+            let minimum = std::env::var(&variable).ok()?;
+            Some((service, variable, minimum))
+        })
+        .collect()
+}
+
+/// Build information for the suite's environment, fetched on first use
+pub async fn suite_build_info() -> &'static BuildInfo {
+    SUITE_BUILD_INFO.get_or_init(|| async { BuildInfo::fetch(&TestContext::new()).await }).await
+}
+
+async fn service_version(
+    ctx: &TestContext,
+    url: &str,
+    service: &str,
+    metrics: &MetricsSnapshot,
+) -> ServiceVersion {
+    if let Ok(response) = ctx.client.get(url).send().await
+        && response.status().is_success()
+        && let Ok(body) = response.text().await
+        && let Some(mut version) = parse_version_body(&body)
+    {
+        version.source = Some(url.to_string());
+        return version;
+    }
+
+    let metric = format!("{}_build_info", service);
+    let Some(sample) = metrics.samples.iter().find(|sample| sample.name == metric) else {
+        return ServiceVersion::default();
+    };
+    let label = |names: &[&str]| names.iter().find_map(|name| sample.labels.get(*name).cloned());
+    ServiceVersion {
+        version: label(VERSION_LABELS).filter(|version| is_version(version)),
+        commit: label(COMMIT_LABELS),
+        source: Some(metric),
+    }
+}
+
+/// Read a `/version` body: a JSON object with `version` (and optionally a commit field) or a
+/// bare version, quoted or not; `None` unless it carries a dotted numeric version or a commit
+pub fn parse_version_body(body: &str) -> Option<ServiceVersion> {
+    let body = body.trim();
+    match serde_json::from_str::<serde_json::Value>(body) {
+        Ok(serde_json::Value::Object(fields)) => {
+            let field = |names: &[&str]| {
+                names.iter().find_map(|name| fields.get(*name)?.as_str().map(str::to_string))
+            };
+            let version = field(VERSION_LABELS).filter(|version| is_version(version));
+            let commit = field(COMMIT_LABELS);
+            (version.is_some() || commit.is_some()).then_some(ServiceVersion {
+                version,
+                commit,
+                source: None,
+            })
+        },
+        Ok(serde_json::Value::String(version)) if is_version(&version) => {
+            Some(ServiceVersion { version: Some(version), ..ServiceVersion::default() })
+        },
+        _ if is_version(body) => {
+            Some(ServiceVersion { version: Some(body.to_string()), ..ServiceVersion::default() })
+        },
+        _ => None,
+    }
+}
+
+/// The numeric parts of a dotted version (`v1.10.0-rc1` is `[1, 10, 0]`), ignoring a leading `v`
+/// and any pre-release or build suffix; `None` unless every part is a number and there are two
+/// or more
+fn version_parts(version: &str) -> Option<Vec<u64>> {
+    let core = version.trim().strip_prefix('v').unwrap_or(version.trim());
+    let core = core.split(['-', '+']).next()?;
+    let parts = core.split('.').map(|part| part.parse().ok()).collect::<Option<Vec<u64>>>()?;
+    (parts.len() >= 2).then_some(parts)
+}
+
+/// Whether `value` is a dotted numeric version
+pub fn is_version(value: &str) -> bool {
+    version_parts(value).is_some()
+}
+
+/// Compare dotted versions numerically (`v1.10.0` > `1.9.2`); `None` if either is not a version
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    let (mut a, mut b) = (version_parts(a)?, version_parts(b)?);
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    Some(a.cmp(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_info_version_parsing_and_minimums() {
+        use std::cmp::Ordering;
+
+        assert_eq!(compare_versions("v1.10.0", "1.9.2"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("1.4", "1.4.0"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("1.4.0-rc1", "1.4.0"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("main", "1.0.0"), None);
+
+        let parsed = parse_version_body(r#"{"version":"1.4.2","git_sha":"abc123"}"#)
+            .expect("JSON version body should parse");
+        assert_eq!(parsed.version.as_deref(), Some("1.4.2"));
+        assert_eq!(parsed.commit.as_deref(), Some("abc123"));
+        assert_eq!(
+            parse_version_body("v2.0.0\n").and_then(|v| v.version).as_deref(),
+            Some("v2.0.0")
+        );
+        assert_eq!(parse_version_body("<html><body>Not here</body></html>"), None);
+        for rejected in [r#""ok""#, "ok", "Forbidden", "1", "v", "1.x.3", r#"{"version":"latest"}"#]
+        {
+            assert_eq!(parse_version_body(rejected), None, "{:?} is not a version", rejected);
+        }
+        assert_eq!(
+            parse_version_body(r#""1.4.0-rc1""#).and_then(|v| v.version).as_deref(),
+            Some("1.4.0-rc1")
+        );
+
+        let build = BuildInfo {
+            engine: ServiceVersion { version: Some("1.4.2".to_string()), ..Default::default() },
+            control: ServiceVersion::default(),
+        };
+        assert!(build.require("engine", "1.4.0").is_ok());
+        assert!(build.require("engine", "1.5.0").is_err());
+        assert!(build.require("control", "1.0.0").is_err(), "A minimum fails without a version");
+        assert!(build.require("engine", "soon").is_err(), "A minimum must be a version");
+    }
+}
@@ -0,0 +1,358 @@
+// Chaos hooks
+//
+// Fault injection is environment-specific (Docker network disconnects, iptables rules, scaling a
+// Kubernetes deployment to zero), so the suite never hard-codes it. Each fault is a named pair of
+// shell commands supplied through the environment:
+//   INFERADB_CHAOS_{NAME}_DISRUPT   Command that introduces the fault
+//   INFERADB_CHAOS_{NAME}_RESTORE   Command that removes it
+//
+// Tests that need a fault skip when its commands are not configured, so chaos tests are inert in
+// ordinary runs. For example, with the Docker Compose stack:
+//   INFERADB_CHAOS_LEDGER_DISRUPT="docker network disconnect e2e_default e2e-ledger-1"
+//   INFERADB_CHAOS_LEDGER_RESTORE="docker network connect e2e_default e2e-ledger-1"
+//
+// A `ChaosSchedule` interleaves several faults at fixed offsets while a steady-state probe runs,
+// and returns a `ChaosTimeline`: fault events alongside per-bucket probe outcomes, so error spikes
+// can be lined up with the fault that caused them.
+//
+//     let timeline = ChaosSchedule::new(Duration::from_secs(60))
+//         .fault(control, Duration::from_secs(10), Duration::from_secs(15))
+//         .min_availability(0.95)
+//         .run(|| async { Ok(engine.check("document:1", "viewer", "user:alice").await.map(drop)?)
+// })         .await;
+
+use std::{future::Future, time::Instant};
+
+use super::*;
+
+/// A configured fault that can be introduced and removed
+#[derive(Debug, Clone)]
+pub struct ChaosHook {
+    pub name: String,
+    disrupt: String,
+    restore: String,
+}
+
+impl ChaosHook {
+    /// Load the hook for `name` (e.g. `LEDGER`), or `None` if either command is unset
+    pub fn from_env(name: &str) -> Option<Self> {
+        let var = |suffix: &str| {
+            std::env::var(format!("INFERADB_CHAOS_{}_{}", name, suffix))
+                .ok()
+                .filter(|command| !command.trim().is_empty())
+        };
+        Some(Self { name: name.to_string(), disrupt: var("DISRUPT")?, restore: var("RESTORE")? })
+    }
+
+    /// Introduce the fault; it is removed when the returned guard is restored or dropped
+    pub fn disrupt(&self) -> Result<ChaosGuard<'_>> {
+        run_shell(&self.disrupt).with_context(|| format!("Failed to disrupt {}", self.name))?;
+        Ok(ChaosGuard { hook: self, restored: false })
+    }
+
+    fn restore(&self) -> Result<()> {
+        run_shell(&self.restore).with_context(|| format!("Failed to restore {}", self.name))
+    }
+}
+
+/// An active fault; restores on drop so a failing test never leaves the environment broken
+pub struct ChaosGuard<'a> {
+    hook: &'a ChaosHook,
+    restored: bool,
+}
+
+impl ChaosGuard<'_> {
+    /// Remove the fault now, reporting failure
+    pub fn restore(mut self) -> Result<()> {
+        self.restored = true;
+        self.hook.restore()
+    }
+}
+
+impl Drop for ChaosGuard<'_> {
+    fn drop(&mut self) {
+        if !self.restored
+            && let Err(e) = self.hook.restore()
+        {
+            eprintln!("Warning: {:#}", e);
+        }
+    }
+}
+
+fn run_shell(command: &str) -> Result<()> {
+    let status = Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .status()
+        .with_context(|| format!("Failed to run `{}`", command))?;
+    if !status.success() {
+        anyhow::bail!("`{}` exited with {}", command, status);
+    }
+    Ok(())
+}
+
+/// A fault introduced `at` into a schedule and removed `duration` later
+///
+/// A zero duration suits one-shot faults such as restarts, whose restore command only waits for
+/// the service to come back.
+#[derive(Debug, Clone)]
+pub struct ScheduledFault {
+    pub hook: ChaosHook,
+    pub at: std::time::Duration,
+    pub duration: std::time::Duration,
+}
+
+/// One disrupt or restore command run by a schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaultEvent {
+    pub at_ms: u64,
+    pub fault: String,
+    /// `disrupt` or `restore`
+    pub action: String,
+    pub error: Option<String>,
+}
+
+/// Probe outcomes within one slice of the schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimelineBucket {
+    pub start_ms: u64,
+    pub probes: usize,
+    pub errors: usize,
+    /// Faults active at any point during the bucket
+    pub active_faults: Vec<String>,
+    /// Distinct probe errors, at most three
+    pub sample_errors: Vec<String>,
+}
+
+/// What happened while a schedule ran; written as the run's JSON artifact
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChaosTimeline {
+    pub started_at: String,
+    pub duration_ms: u64,
+    pub events: Vec<FaultEvent>,
+    pub buckets: Vec<TimelineBucket>,
+    pub probes: usize,
+    pub errors: usize,
+    /// Fraction of probes that succeeded
+    pub availability: f64,
+    pub min_availability: f64,
+}
+
+impl ChaosTimeline {
+    /// Availability met the schedule's threshold and every fault command succeeded
+    pub fn passed(&self) -> bool {
+        self.availability >= self.min_availability
+            && self.events.iter().all(|event| event.error.is_none())
+    }
+
+    /// Buckets whose error rate is above the run's overall rate, i.e. the spikes
+    pub fn spikes(&self) -> Vec<&TimelineBucket> {
+        let overall = 1.0 - self.availability;
+        self.buckets
+            .iter()
+            .filter(|bucket| {
+                bucket.errors > 0 && bucket.errors as f64 / bucket.probes as f64 > overall
+            })
+            .collect()
+    }
+
+    /// Write the timeline as pretty-printed JSON, creating parent directories
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write chaos timeline {}", path.display()))
+    }
+}
+
+/// Faults interleaved at fixed offsets while a steady-state probe runs
+#[derive(Debug, Clone)]
+pub struct ChaosSchedule {
+    duration: std::time::Duration,
+    faults: Vec<ScheduledFault>,
+    min_availability: f64,
+    probe_interval: std::time::Duration,
+    bucket: std::time::Duration,
+}
+
+/// Faults still active when a schedule is dropped mid-run; restored so a panicking test never
+/// leaves the environment broken
+struct ActiveFaults(Vec<ChaosHook>);
+
+impl Drop for ActiveFaults {
+    fn drop(&mut self) {
+        for hook in self.0.drain(..) {
+            if let Err(e) = hook.restore() {
+                eprintln!("Warning: {:#}", e);
+            }
+        }
+    }
+}
+
+impl ChaosSchedule {
+    /// A schedule probing for `duration`, requiring 99% availability by default
+    pub fn new(duration: std::time::Duration) -> Self {
+        Self {
+            duration,
+            faults: Vec::new(),
+            min_availability: 0.99,
+            probe_interval: std::time::Duration::from_millis(100),
+            bucket: std::time::Duration::from_secs(1),
+        }
+    }
+
+    /// Introduce `hook` at `at`, removing it `duration` later
+    pub fn fault(
+        mut self,
+        hook: ChaosHook,
+        at: std::time::Duration,
+        duration: std::time::Duration,
+    ) -> Self {
+        self.faults.push(ScheduledFault { hook, at, duration });
+        self
+    }
+
+    /// Fraction of probes that must succeed across the whole run
+    pub fn min_availability(mut self, min_availability: f64) -> Self {
+        self.min_availability = min_availability;
+        self
+    }
+
+    /// Pause between probes
+    pub fn probe_interval(mut self, interval: std::time::Duration) -> Self {
+        self.probe_interval = interval;
+        self
+    }
+
+    /// Width of the timeline's buckets
+    pub fn bucket(mut self, bucket: std::time::Duration) -> Self {
+        self.bucket = bucket;
+        self
+    }
+
+    pub fn faults(&self) -> &[ScheduledFault] {
+        &self.faults
+    }
+
+    /// Disrupt and restore commands in the order they run
+    fn actions(&self) -> Vec<(std::time::Duration, bool, &ChaosHook)> {
+        let mut actions: Vec<_> = self
+            .faults
+            .iter()
+            .flat_map(|fault| {
+                [(fault.at, true, &fault.hook), (fault.at + fault.duration, false, &fault.hook)]
+            })
+            .collect();
+        // At equal offsets, restores run before disrupts
+        actions.sort_by_key(|(at, disrupt, _)| (*at, *disrupt));
+        actions
+    }
+
+    /// Run `probe` every probe interval for the schedule's duration while injecting its faults
+    ///
+    /// Every fault is restored before this returns, even ones scheduled past the end.
+    pub async fn run<F, Fut>(&self, mut probe: F) -> ChaosTimeline
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let started_at = Utc::now().to_rfc3339();
+        let start = Instant::now();
+        let elapsed_ms = || start.elapsed().as_millis() as u64;
+
+        let inject = async {
+            let mut events = Vec::new();
+            let mut active = ActiveFaults(Vec::new());
+            for (at, disrupt, hook) in self.actions() {
+                tokio::time::sleep_until((start + at).into()).await;
+                let command = if disrupt { hook.disrupt.clone() } else { hook.restore.clone() };
+                let result = tokio::task::spawn_blocking(move || run_shell(&command))
+                    .await
+                    .unwrap_or_else(|e| Err(anyhow::anyhow!("Fault command panicked: {}", e)));
+                if disrupt {
+                    active.0.push(hook.clone());
+                } else {
+                    active.0.retain(|active| active.name != hook.name);
+                }
+                events.push(FaultEvent {
+                    at_ms: elapsed_ms(),
+                    fault: hook.name.clone(),
+                    action: if disrupt { "disrupt" } else { "restore" }.to_string(),
+                    error: result.err().map(|e| format!("{:#}", e)),
+                });
+            }
+            events
+        };
+
+        let steady = async {
+            let mut samples = Vec::new();
+            while start.elapsed() < self.duration {
+                let at_ms = elapsed_ms();
+                samples.push((at_ms, probe().await.err().map(|e| format!("{:#}", e))));
+                tokio::time::sleep(self.probe_interval).await;
+            }
+            samples
+        };
+
+        let (events, samples) = tokio::join!(inject, steady);
+        self.timeline(started_at, events, &samples)
+    }
+
+    fn timeline(
+        &self,
+        started_at: String,
+        events: Vec<FaultEvent>,
+        samples: &[(u64, Option<String>)],
+    ) -> ChaosTimeline {
+        let bucket_ms = (self.bucket.as_millis() as u64).max(1);
+        let duration_ms = self.duration.as_millis() as u64;
+        let mut buckets: Vec<TimelineBucket> = (0..duration_ms.div_ceil(bucket_ms))
+            .map(|i| {
+                let (from, to) = (i * bucket_ms, (i + 1) * bucket_ms);
+                TimelineBucket {
+                    start_ms: from,
+                    probes: 0,
+                    errors: 0,
+                    active_faults: self
+                        .faults
+                        .iter()
+                        .filter(|fault| {
+                            let at = fault.at.as_millis() as u64;
+                            let end = at + fault.duration.as_millis() as u64;
+                            at < to && end >= from
+                        })
+                        .map(|fault| fault.hook.name.clone())
+                        .collect(),
+                    sample_errors: Vec::new(),
+                }
+            })
+            .collect();
+
+        for (at_ms, error) in samples {
+            let Some(bucket) = buckets.get_mut((at_ms / bucket_ms) as usize) else {
+                continue;
+            };
+            bucket.probes += 1;
+            if let Some(error) = error {
+                bucket.errors += 1;
+                if bucket.sample_errors.len() < 3 && !bucket.sample_errors.contains(error) {
+                    bucket.sample_errors.push(error.clone());
+                }
+            }
+        }
+
+        let probes = samples.len();
+        let errors = samples.iter().filter(|(_, error)| error.is_some()).count();
+        ChaosTimeline {
+            started_at,
+            duration_ms,
+            events,
+            buckets,
+            probes,
+            errors,
+            availability: if probes == 0 { 0.0 } else { (probes - errors) as f64 / probes as f64 },
+            min_availability: self.min_availability,
+        }
+    }
+}
@@ -0,0 +1,257 @@
+// Relationship Churn
+//
+// Keeps a vault's dataset moving while other assertions run: a background task writes and deletes
+// `viewer` tuples between `document:churn-*` resources and `user:churn-*` subjects at a steady
+// rate, keeping roughly `pool / 2` of them live. Correctness checks on other resources must hold
+// regardless, and every `propagation_every`-th write is polled through `check` until it becomes
+// visible, so propagation latency is measured under the same churn:
+//
+//     let churn = fixture.start_churn(ChurnConfig::new(20.0));
+//     // ... writes, checks, and lists on the fixture's own resources ...
+//     let stats = churn.stop().await;
+//     println!("p99 propagation {:?} ms", stats.propagation_ms(0.99));
+//
+// A propagation poll holds up the churn loop, so the achieved rate falls below the configured one
+// while a write is slow to become visible.
+//
+// Dropping a `RunningChurn` without `stop` (a test that panics midway) aborts the task, so churn
+// never runs on into the next test.
+
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Instant,
+};
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use super::{scenario::percentile, *};
+
+/// Distinct error messages kept per run
+const ERROR_SAMPLE_LIMIT: usize = 5;
+
+/// Interval between `check` polls while waiting for a write to become visible
+const PROPAGATION_POLL: std::time::Duration = std::time::Duration::from_millis(20);
+
+/// How often the churn task re-mints its token, within the five-minute token lifetime
+const TOKEN_REFRESH: std::time::Duration = std::time::Duration::from_secs(240);
+
+/// Resource prefix of every churned tuple
+pub const CHURN_RESOURCE_PREFIX: &str = "document:churn-";
+
+/// How a churn task writes and deletes
+#[derive(Debug, Clone)]
+pub struct ChurnConfig {
+    /// Writes plus deletes per second
+    pub rate: f64,
+    /// Distinct tuples churned through
+    pub pool: usize,
+    /// Measure propagation on every Nth write; 0 disables measuring
+    pub propagation_every: usize,
+    /// Give up on a write becoming visible after this long
+    pub propagation_timeout: std::time::Duration,
+}
+
+impl ChurnConfig {
+    /// `rate` operations per second over 200 tuples, measuring every 10th write for up to 5s
+    pub fn new(rate: f64) -> Self {
+        Self {
+            rate,
+            pool: 200,
+            propagation_every: 10,
+            propagation_timeout: std::time::Duration::from_secs(5),
+        }
+    }
+
+    pub fn pool(mut self, pool: usize) -> Self {
+        self.pool = pool.max(2);
+        self
+    }
+
+    pub fn propagation_every(mut self, every: usize) -> Self {
+        self.propagation_every = every;
+        self
+    }
+
+    pub fn propagation_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.propagation_timeout = timeout;
+        self
+    }
+}
+
+/// What a churn task did
+#[derive(Debug, Clone, Default)]
+pub struct ChurnStats {
+    pub writes: usize,
+    pub deletes: usize,
+    /// Failed writes, deletes, and propagation checks
+    pub errors: usize,
+    /// First few distinct failure messages
+    pub sample_errors: Vec<String>,
+    /// Milliseconds from a write returning to `check` seeing it, per measured write
+    pub propagation_latencies_ms: Vec<f64>,
+    /// Measured writes never seen within the propagation timeout
+    pub propagation_timeouts: usize,
+    /// How long the churn ran
+    pub elapsed: std::time::Duration,
+}
+
+impl ChurnStats {
+    fn record_error(&mut self, message: String) {
+        self.errors += 1;
+        if self.sample_errors.len() < ERROR_SAMPLE_LIMIT && !self.sample_errors.contains(&message) {
+            self.sample_errors.push(message);
+        }
+    }
+
+    /// Nearest-rank propagation latency at `quantile`, `None` without measurements
+    pub fn propagation_ms(&self, quantile: f64) -> Option<f64> {
+        let mut sorted = self.propagation_latencies_ms.clone();
+        sorted.sort_by(f64::total_cmp);
+        percentile(&sorted, quantile)
+    }
+
+    /// Writes and deletes per second actually achieved
+    pub fn achieved_rate(&self) -> f64 {
+        let seconds = self.elapsed.as_secs_f64();
+        if seconds > 0.0 { (self.writes + self.deletes) as f64 / seconds } else { 0.0 }
+    }
+}
+
+/// Churn in progress, started by `TestFixture::start_churn`
+pub struct RunningChurn {
+    stop: Arc<AtomicBool>,
+    task: tokio::task::JoinHandle<ChurnStats>,
+}
+
+impl RunningChurn {
+    /// Churn the vault `token` is issued for until stopped, re-minting the token as it ages
+    pub fn start(ctx: TestContext, token: JwtBuilder, config: ChurnConfig) -> Self {
+        let stop = Arc::new(AtomicBool::new(false));
+        let task = tokio::spawn(churn(ctx, token, config, Arc::clone(&stop)));
+        Self { stop, task }
+    }
+
+    /// Stop after the current operation and report what was done
+    ///
+    /// Tuples still live are left in the vault; they are removed with it.
+    pub async fn stop(mut self) -> ChurnStats {
+        self.stop.store(true, Ordering::SeqCst);
+        (&mut self.task).await.unwrap_or_else(|e| {
+            let mut stats = ChurnStats::default();
+            stats.record_error(format!("Churn task panicked: {}", e));
+            stats
+        })
+    }
+}
+
+impl Drop for RunningChurn {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        self.task.abort();
+    }
+}
+
+/// An Engine client with a freshly minted token
+fn engine_client(ctx: &TestContext, token: &JwtBuilder) -> Result<EngineClient> {
+    let now = Utc::now();
+    let jwt = token
+        .clone()
+        .claim("iat", now.timestamp())
+        .claim("exp", (now + Duration::minutes(5)).timestamp())
+        .claim("jti", Uuid::new_v4().to_string())
+        .encode()?;
+    Ok(EngineClient::new(ctx.clone(), jwt))
+}
+
+async fn churn(
+    ctx: TestContext,
+    token: JwtBuilder,
+    config: ChurnConfig,
+    stop: Arc<AtomicBool>,
+) -> ChurnStats {
+    let mut stats = ChurnStats::default();
+    let mut minted = Instant::now();
+    let mut engine = match engine_client(&ctx, &token) {
+        Ok(engine) => engine,
+        Err(e) => {
+            stats.record_error(format!("{:#}", e));
+            return stats;
+        },
+    };
+    let mut rng = StdRng::seed_from_u64(rand::rng().random());
+    // Live tuples, keyed by pool slot
+    let mut live: BTreeMap<usize, Relationship> = BTreeMap::new();
+    let mut interval =
+        tokio::time::interval(std::time::Duration::from_secs_f64(1.0 / config.rate.max(0.001)));
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    let started = Instant::now();
+
+    while !stop.load(Ordering::SeqCst) {
+        interval.tick().await;
+        if minted.elapsed() >= TOKEN_REFRESH {
+            match engine_client(&ctx, &token) {
+                Ok(fresh) => engine = fresh,
+                Err(e) => stats.record_error(format!("{:#}", e)),
+            }
+            minted = Instant::now();
+        }
+        let slot = rng.random_range(0..config.pool);
+
+        if let Some(tuple) = live.remove(&slot) {
+            match engine.delete_relationships(std::slice::from_ref(&tuple)).await {
+                Ok(()) => stats.deletes += 1,
+                Err(e) => {
+                    live.insert(slot, tuple);
+                    stats.record_error(e.to_string());
+                },
+            }
+            continue;
+        }
+
+        let tuple = Relationship::new(
+            format!("{}{}", CHURN_RESOURCE_PREFIX, slot),
+            "viewer",
+            format!("user:churn-{}", rng.random_range(0..config.pool)),
+        );
+        if let Err(e) = engine.write_relationships(std::slice::from_ref(&tuple)).await {
+            stats.record_error(e.to_string());
+            continue;
+        }
+        stats.writes += 1;
+        if config.propagation_every > 0 && stats.writes % config.propagation_every == 0 {
+            measure_propagation(&engine, &tuple, &config, &mut stats).await;
+        }
+        live.insert(slot, tuple);
+    }
+
+    stats.elapsed = started.elapsed();
+    stats
+}
+
+async fn measure_propagation(
+    engine: &EngineClient,
+    tuple: &Relationship,
+    config: &ChurnConfig,
+    stats: &mut ChurnStats,
+) {
+    let written = Instant::now();
+    while written.elapsed() < config.propagation_timeout {
+        match engine.check(&tuple.resource, &tuple.relation, &tuple.subject).await {
+            Ok(true) => {
+                stats.propagation_latencies_ms.push(written.elapsed().as_secs_f64() * 1000.0);
+                return;
+            },
+            Ok(false) => {},
+            Err(e) => {
+                stats.record_error(e.to_string());
+                return;
+            },
+        }
+        tokio::time::sleep(PROPAGATION_POLL).await;
+    }
+    stats.propagation_timeouts += 1;
+}
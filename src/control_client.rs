@@ -0,0 +1,467 @@
+// Typed Control API client
+//
+// Every management call the suite makes (registration, login, organizations, vaults, schemas,
+// clients, certificates, users) goes through `ControlClient`, so URL formatting and session
+// headers live in one place and failures surface as an `ApiError` carrying the HTTP status.
+
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+
+use super::*;
+
+const SERVICE: &str = "Control";
+
+/// Client for the Control (management) API
+///
+/// Requests carry the session as a bearer token once one is attached with `with_session`.
+#[derive(Clone)]
+pub struct ControlClient {
+    ctx: TestContext,
+    session_id: Option<i64>,
+}
+
+impl ControlClient {
+    /// Create an unauthenticated client (for registration and login)
+    pub fn new(ctx: TestContext) -> Self {
+        Self { ctx, session_id: None }
+    }
+
+    /// Create a client authenticated with an existing session
+    pub fn with_session(ctx: TestContext, session_id: i64) -> Self {
+        Self { ctx, session_id: Some(session_id) }
+    }
+
+    /// Build a request to a Control path, attaching the session if present
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let builder = self.ctx.client.request(method, self.ctx.control_url(path));
+        match self.session_id {
+            Some(session_id) => builder.header("Authorization", format!("Bearer {}", session_id)),
+            None => builder,
+        }
+    }
+
+    async fn send_json<T: DeserializeOwned>(&self, builder: RequestBuilder) -> ApiResult<T> {
+        api_client::send_json(SERVICE, builder).await
+    }
+
+    async fn send_empty(&self, builder: RequestBuilder) -> ApiResult<()> {
+        api_client::send_empty(SERVICE, builder).await
+    }
+
+    // -------------------------------------------------------------------------
+    // Authentication
+    // -------------------------------------------------------------------------
+
+    pub async fn register(&self, req: &RegisterRequest) -> ApiResult<RegisterResponse> {
+        self.send_json(self.request(Method::POST, "/auth/register").json(req)).await
+    }
+
+    pub async fn login(&self, req: &LoginRequest) -> ApiResult<LoginResponse> {
+        self.send_json(self.request(Method::POST, "/auth/login/password").json(req)).await
+    }
+
+    /// Password login for a user with two-factor authentication, which yields a challenge
+    pub async fn login_challenge(&self, req: &LoginRequest) -> ApiResult<MfaChallengeResponse> {
+        self.send_json(self.request(Method::POST, "/auth/login/password").json(req)).await
+    }
+
+    pub async fn login_totp(&self, req: &TotpLoginRequest) -> ApiResult<LoginResponse> {
+        self.send_json(self.request(Method::POST, "/auth/login/totp").json(req)).await
+    }
+
+    pub async fn login_recovery(&self, req: &RecoveryLoginRequest) -> ApiResult<LoginResponse> {
+        self.send_json(self.request(Method::POST, "/auth/login/recovery").json(req)).await
+    }
+
+    // -------------------------------------------------------------------------
+    // Organizations
+    // -------------------------------------------------------------------------
+
+    pub async fn create_organization(
+        &self,
+        req: &CreateOrganizationRequest,
+    ) -> ApiResult<CreateOrganizationResponse> {
+        self.send_json(self.request(Method::POST, "/organizations").json(req)).await
+    }
+
+    pub async fn list_organizations(&self) -> ApiResult<ListOrganizationsResponse> {
+        self.send_json(self.request(Method::GET, "/organizations")).await
+    }
+
+    pub async fn list_organizations_page(
+        &self,
+        page: &PageRequest,
+    ) -> ApiResult<ListOrganizationsResponse> {
+        self.send_json(self.request(Method::GET, &page.apply("/organizations"))).await
+    }
+
+    pub async fn suspend_organization(&self, org_id: i64) -> ApiResult<()> {
+        self.send_empty(self.request(Method::POST, &format!("/organizations/{}/suspend", org_id)))
+            .await
+    }
+
+    pub async fn list_audit_events(&self, org_id: i64) -> ApiResult<ListAuditEventsResponse> {
+        self.send_json(self.request(Method::GET, &format!("/organizations/{}/audit-logs", org_id)))
+            .await
+    }
+
+    pub async fn create_invitation(
+        &self,
+        org_id: i64,
+        req: &CreateInvitationRequest,
+    ) -> ApiResult<CreateInvitationResponse> {
+        self.send_json(
+            self.request(Method::POST, &format!("/organizations/{}/invitations", org_id)).json(req),
+        )
+        .await
+    }
+
+    /// Join the invitation's organization as the session's user
+    pub async fn accept_invitation(&self, req: &AcceptInvitationRequest) -> ApiResult<()> {
+        self.send_empty(self.request(Method::POST, "/invitations/accept").json(req)).await
+    }
+
+    pub async fn list_members(&self, org_id: i64) -> ApiResult<ListMembersResponse> {
+        self.send_json(self.request(Method::GET, &format!("/organizations/{}/members", org_id)))
+            .await
+    }
+
+    pub async fn add_member(
+        &self,
+        org_id: i64,
+        req: &AddMemberRequest,
+    ) -> ApiResult<MemberResponse> {
+        self.send_json(
+            self.request(Method::POST, &format!("/organizations/{}/members", org_id)).json(req),
+        )
+        .await
+    }
+
+    /// Make another member the owner; only the current owner may
+    pub async fn transfer_ownership(
+        &self,
+        org_id: i64,
+        req: &TransferOwnershipRequest,
+    ) -> ApiResult<()> {
+        self.send_empty(
+            self.request(Method::POST, &format!("/organizations/{}/transfer-ownership", org_id))
+                .json(req),
+        )
+        .await
+    }
+
+    /// Billing details; restricted to the owner
+    pub async fn get_billing(&self, org_id: i64) -> ApiResult<serde_json::Value> {
+        self.send_json(self.request(Method::GET, &format!("/organizations/{}/billing", org_id)))
+            .await
+    }
+
+    pub async fn delete_organization(&self, org_id: i64) -> ApiResult<()> {
+        self.send_empty(self.request(Method::DELETE, &format!("/organizations/{}", org_id))).await
+    }
+
+    // -------------------------------------------------------------------------
+    // Vaults
+    // -------------------------------------------------------------------------
+
+    pub async fn create_vault(
+        &self,
+        org_id: i64,
+        req: &CreateVaultRequest,
+    ) -> ApiResult<CreateVaultResponse> {
+        self.send_json(
+            self.request(Method::POST, &format!("/organizations/{}/vaults", org_id)).json(req),
+        )
+        .await
+    }
+
+    pub async fn list_vaults(&self, org_id: i64) -> ApiResult<ListVaultsResponse> {
+        self.send_json(self.request(Method::GET, &format!("/organizations/{}/vaults", org_id)))
+            .await
+    }
+
+    pub async fn list_vaults_page(
+        &self,
+        org_id: i64,
+        page: &PageRequest,
+    ) -> ApiResult<ListVaultsResponse> {
+        self.send_json(
+            self.request(Method::GET, &page.apply(&format!("/organizations/{}/vaults", org_id))),
+        )
+        .await
+    }
+
+    pub async fn get_vault(&self, org_id: i64, vault_id: i64) -> ApiResult<VaultResponse> {
+        self.send_json(
+            self.request(Method::GET, &format!("/organizations/{}/vaults/{}", org_id, vault_id)),
+        )
+        .await
+    }
+
+    pub async fn update_vault(
+        &self,
+        org_id: i64,
+        vault_id: i64,
+        update: &serde_json::Value,
+    ) -> ApiResult<()> {
+        self.send_empty(
+            self.request(Method::PATCH, &format!("/organizations/{}/vaults/{}", org_id, vault_id))
+                .json(update),
+        )
+        .await
+    }
+
+    pub async fn delete_vault(&self, org_id: i64, vault_id: i64) -> ApiResult<()> {
+        self.send_empty(
+            self.request(Method::DELETE, &format!("/organizations/{}/vaults/{}", org_id, vault_id)),
+        )
+        .await
+    }
+
+    /// Create a vault from an existing one's schema and, if requested, its relationships
+    pub async fn clone_vault(
+        &self,
+        org_id: i64,
+        vault_id: i64,
+        req: &CloneVaultRequest,
+    ) -> ApiResult<CreateVaultResponse> {
+        self.send_json(
+            self.request(
+                Method::POST,
+                &format!("/organizations/{}/vaults/{}/clone", org_id, vault_id),
+            )
+            .json(req),
+        )
+        .await
+    }
+
+    /// Deploy a schema to a vault, replacing the active one
+    pub async fn deploy_schema(
+        &self,
+        org_id: i64,
+        vault_id: i64,
+        req: &DeploySchemaRequest,
+    ) -> ApiResult<()> {
+        self.send_empty(
+            self.request(
+                Method::POST,
+                &format!("/organizations/{}/vaults/{}/schemas", org_id, vault_id),
+            )
+            .json(req),
+        )
+        .await
+    }
+
+    /// Issue an end-user-scoped token bound to one subject on a vault
+    pub async fn issue_user_token(
+        &self,
+        org_id: i64,
+        vault_id: i64,
+        req: &IssueUserTokenRequest,
+    ) -> ApiResult<UserTokenResponse> {
+        self.send_json(
+            self.request(
+                Method::POST,
+                &format!("/organizations/{}/vaults/{}/user-tokens", org_id, vault_id),
+            )
+            .json(req),
+        )
+        .await
+    }
+
+    // -------------------------------------------------------------------------
+    // Webhooks
+    // -------------------------------------------------------------------------
+
+    /// Subscribe a URL to an organization's webhook events
+    pub async fn create_webhook(
+        &self,
+        org_id: i64,
+        req: &CreateWebhookRequest,
+    ) -> ApiResult<CreateWebhookResponse> {
+        self.send_json(
+            self.request(Method::POST, &format!("/organizations/{}/webhooks", org_id)).json(req),
+        )
+        .await
+    }
+
+    pub async fn delete_webhook(&self, org_id: i64, webhook_id: i64) -> ApiResult<()> {
+        self.send_empty(
+            self.request(
+                Method::DELETE,
+                &format!("/organizations/{}/webhooks/{}", org_id, webhook_id),
+            ),
+        )
+        .await
+    }
+
+    /// Deliveries Control has made or is still making for a webhook, dead-lettered ones included
+    pub async fn list_webhook_deliveries(
+        &self,
+        org_id: i64,
+        webhook_id: i64,
+    ) -> ApiResult<ListWebhookDeliveriesResponse> {
+        self.send_json(self.request(
+            Method::GET,
+            &format!("/organizations/{}/webhooks/{}/deliveries", org_id, webhook_id),
+        ))
+        .await
+    }
+
+    // -------------------------------------------------------------------------
+    // Clients
+    // -------------------------------------------------------------------------
+
+    pub async fn create_client(
+        &self,
+        org_id: i64,
+        req: &CreateClientRequest,
+    ) -> ApiResult<CreateClientResponse> {
+        self.send_json(
+            self.request(Method::POST, &format!("/organizations/{}/clients", org_id)).json(req),
+        )
+        .await
+    }
+
+    pub async fn list_clients(&self, org_id: i64) -> ApiResult<ListClientsResponse> {
+        self.send_json(self.request(Method::GET, &format!("/organizations/{}/clients", org_id)))
+            .await
+    }
+
+    pub async fn list_clients_page(
+        &self,
+        org_id: i64,
+        page: &PageRequest,
+    ) -> ApiResult<ListClientsResponse> {
+        self.send_json(
+            self.request(Method::GET, &page.apply(&format!("/organizations/{}/clients", org_id))),
+        )
+        .await
+    }
+
+    pub async fn deactivate_client(&self, org_id: i64, client_id: i64) -> ApiResult<()> {
+        self.send_empty(self.request(
+            Method::POST,
+            &format!("/organizations/{}/clients/{}/deactivate", org_id, client_id),
+        ))
+        .await
+    }
+
+    pub async fn delete_client(&self, org_id: i64, client_id: i64) -> ApiResult<()> {
+        self.send_empty(
+            self.request(
+                Method::DELETE,
+                &format!("/organizations/{}/clients/{}", org_id, client_id),
+            ),
+        )
+        .await
+    }
+
+    // -------------------------------------------------------------------------
+    // Certificates
+    // -------------------------------------------------------------------------
+
+    pub async fn create_certificate(
+        &self,
+        org_id: i64,
+        client_id: i64,
+        req: &CreateCertificateRequest,
+    ) -> ApiResult<CertificateResponse> {
+        self.send_json(
+            self.request(
+                Method::POST,
+                &format!("/organizations/{}/clients/{}/certificates", org_id, client_id),
+            )
+            .json(req),
+        )
+        .await
+    }
+
+    pub async fn list_certificates(
+        &self,
+        org_id: i64,
+        client_id: i64,
+    ) -> ApiResult<ListCertificatesResponse> {
+        self.send_json(self.request(
+            Method::GET,
+            &format!("/organizations/{}/clients/{}/certificates", org_id, client_id),
+        ))
+        .await
+    }
+
+    pub async fn rotate_certificate(
+        &self,
+        org_id: i64,
+        client_id: i64,
+        cert_id: i64,
+        req: &RotateCertificateRequest,
+    ) -> ApiResult<RotateCertificateResponse> {
+        self.send_json(
+            self.request(
+                Method::POST,
+                &format!(
+                    "/organizations/{}/clients/{}/certificates/{}/rotate",
+                    org_id, client_id, cert_id
+                ),
+            )
+            .json(req),
+        )
+        .await
+    }
+
+    pub async fn revoke_certificate(
+        &self,
+        org_id: i64,
+        client_id: i64,
+        cert_id: i64,
+    ) -> ApiResult<()> {
+        self.send_empty(self.request(
+            Method::DELETE,
+            &format!("/organizations/{}/clients/{}/certificates/{}", org_id, client_id, cert_id),
+        ))
+        .await
+    }
+
+    // -------------------------------------------------------------------------
+    // Users
+    // -------------------------------------------------------------------------
+
+    /// List every user; requires an operator session
+    pub async fn list_users(&self) -> ApiResult<ListUsersResponse> {
+        self.send_json(self.request(Method::GET, "/users")).await
+    }
+
+    pub async fn list_users_page(&self, page: &PageRequest) -> ApiResult<ListUsersResponse> {
+        self.send_json(self.request(Method::GET, &page.apply("/users"))).await
+    }
+
+    /// The session's own user
+    pub async fn get_current_user(&self) -> ApiResult<UserResponse> {
+        self.send_json(self.request(Method::GET, "/users/me")).await
+    }
+
+    pub async fn update_profile(&self, req: &UpdateProfileRequest) -> ApiResult<UserResponse> {
+        self.send_json(self.request(Method::PATCH, "/users/me").json(req)).await
+    }
+
+    pub async fn change_password(&self, req: &ChangePasswordRequest) -> ApiResult<()> {
+        self.send_empty(self.request(Method::POST, "/users/me/password").json(req)).await
+    }
+
+    /// Start TOTP enrollment for the session's own user
+    pub async fn enroll_totp(&self) -> ApiResult<TotpEnrollmentResponse> {
+        self.send_json(self.request(Method::POST, "/users/me/mfa/totp")).await
+    }
+
+    pub async fn confirm_totp(&self, req: &TotpCodeRequest) -> ApiResult<TotpConfirmResponse> {
+        self.send_json(self.request(Method::POST, "/users/me/mfa/totp/confirm").json(req)).await
+    }
+
+    pub async fn disable_totp(&self, req: &TotpCodeRequest) -> ApiResult<()> {
+        self.send_empty(self.request(Method::DELETE, "/users/me/mfa/totp").json(req)).await
+    }
+
+    pub async fn delete_user(&self, user_id: i64) -> ApiResult<()> {
+        self.send_empty(self.request(Method::DELETE, &format!("/users/{}", user_id))).await
+    }
+}
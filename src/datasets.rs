@@ -0,0 +1,313 @@
+// Seeded Datasets
+//
+// Provisions a realistic multi-tenant dataset: `orgs` organizations, each with `vaults_per_org`
+// vaults of `relationships_per_vault` document-sharing tuples under `DOCUMENT_SCHEMA`. Documents
+// and users are drawn from a Zipfian distribution, so a few hot documents and heavy users carry
+// most tuples as in real tenants, and the same seed always produces the same tuples. Scale tests
+// provision one directly; developers who want a populated environment use the `dataset` binary.
+//
+// Every organization is a fixture of its own (a registered user's default organization), and
+// everything created is recorded in a `DatasetManifest`: the fixture snapshots (session and
+// signing key included, so the data can be queried) and vault IDs. The manifest is all
+// `DatasetManifest::cleanup` needs to delete the dataset again, from any process.
+
+use std::collections::BTreeSet;
+
+use rand::{Rng, SeedableRng, rngs::StdRng};
+
+use super::*;
+
+/// Relationships per write request
+const WRITE_BATCH: usize = 500;
+
+/// Relations assigned to drawn tuples, with their relative weights
+const RELATION_WEIGHTS: &[(&str, u32)] = &[("viewer", 70), ("editor", 25), ("owner", 5)];
+
+/// Shape of a dataset
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DatasetSpec {
+    pub seed: u64,
+    pub orgs: usize,
+    pub vaults_per_org: usize,
+    pub relationships_per_vault: usize,
+    /// Distinct documents per vault that tuples are drawn from
+    pub documents: usize,
+    /// Distinct users per vault that tuples are drawn from
+    pub users: usize,
+    /// Zipf exponent; larger values concentrate tuples on fewer documents and users
+    pub zipf_exponent: f64,
+}
+
+impl DatasetSpec {
+    /// `orgs` × `vaults_per_org` × `relationships_per_vault`, drawn from 1,000 documents and 500
+    /// users with exponent 1.1 and seed 1
+    pub fn new(orgs: usize, vaults_per_org: usize, relationships_per_vault: usize) -> Self {
+        Self {
+            seed: 1,
+            orgs,
+            vaults_per_org: vaults_per_org.max(1),
+            relationships_per_vault,
+            documents: 1000,
+            users: 500,
+            zipf_exponent: 1.1,
+        }
+    }
+
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Documents and users tuples are drawn from
+    pub fn population(mut self, documents: usize, users: usize) -> Self {
+        self.documents = documents.max(1);
+        self.users = users.max(1);
+        self
+    }
+
+    pub fn zipf_exponent(mut self, exponent: f64) -> Self {
+        self.zipf_exponent = exponent;
+        self
+    }
+
+    /// The tuples of vault `vault` in organization `org` (both zero-based)
+    ///
+    /// Deterministic for a given spec; distinct tuples only, so a small population may yield
+    /// fewer than `relationships_per_vault`.
+    pub fn relationships(&self, org: usize, vault: usize) -> Vec<Relationship> {
+        let stream = (org as u64) << 32 | vault as u64;
+        let mut rng = StdRng::seed_from_u64(self.seed ^ stream.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+        let documents = Zipf::new(self.documents, self.zipf_exponent);
+        let users = Zipf::new(self.users, self.zipf_exponent);
+        let total_weight: u32 = RELATION_WEIGHTS.iter().map(|(_, weight)| weight).sum();
+
+        let mut drawn = BTreeSet::new();
+        let mut attempts = self.relationships_per_vault * 20;
+        while drawn.len() < self.relationships_per_vault && attempts > 0 {
+            attempts -= 1;
+            let mut pick = rng.random_range(0..total_weight);
+            let relation = RELATION_WEIGHTS
+                .iter()
+                .find(|(_, weight)| {
+                    let hit = pick < *weight;
+                    pick = pick.saturating_sub(*weight);
+                    hit
+                })
+                .map_or("viewer", |(relation, _)| relation);
+            drawn.insert(Relationship::new(
+                format!("document:doc-{}", documents.sample(&mut rng)),
+                relation,
+                format!("user:user-{}", users.sample(&mut rng)),
+            ));
+        }
+        drawn.into_iter().collect()
+    }
+}
+
+/// Zipfian distribution over ranks `0..n`: rank `k` is drawn with weight `1 / (k + 1)^s`
+#[derive(Debug, Clone)]
+pub struct Zipf {
+    cumulative: Vec<f64>,
+}
+
+impl Zipf {
+    pub fn new(n: usize, exponent: f64) -> Self {
+        let mut total = 0.0;
+        let cumulative = (1..=n.max(1))
+            .map(|rank| {
+                total += 1.0 / (rank as f64).powf(exponent);
+                total
+            })
+            .collect();
+        Self { cumulative }
+    }
+
+    /// Draw a rank; 0 is the most frequent
+    pub fn sample(&self, rng: &mut impl Rng) -> usize {
+        let target = rng.random::<f64>() * self.cumulative[self.cumulative.len() - 1];
+        self.cumulative.partition_point(|&sum| sum <= target).min(self.cumulative.len() - 1)
+    }
+}
+
+/// One vault of a provisioned dataset
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetVault {
+    pub vault_id: i64,
+    pub relationships: usize,
+}
+
+/// One organization of a provisioned dataset, with the fixture that owns it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetTenant {
+    pub fixture: FixtureSnapshot,
+    /// The fixture's own vault first, then the ones created for the dataset
+    pub vaults: Vec<DatasetVault>,
+}
+
+/// What a dataset provisioning created, as written to the manifest file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatasetManifest {
+    pub run_id: String,
+    pub created_at: String,
+    pub spec: DatasetSpec,
+    pub tenants: Vec<DatasetTenant>,
+}
+
+impl DatasetManifest {
+    /// Provision `spec`, deleting whatever was created if any step fails
+    pub async fn provision(spec: &DatasetSpec) -> Result<Self> {
+        let mut manifest = Self {
+            run_id: naming::run_id().to_string(),
+            created_at: Utc::now().to_rfc3339(),
+            spec: spec.clone(),
+            tenants: Vec::new(),
+        };
+        for org in 0..spec.orgs {
+            if let Err(e) = manifest.provision_tenant(org).await {
+                if let Err(cleanup) = manifest.cleanup().await {
+                    eprintln!("Warning: dataset cleanup after failure incomplete: {:#}", cleanup);
+                }
+                return Err(e.context(format!("Failed to provision dataset organization {}", org)));
+            }
+        }
+        Ok(manifest)
+    }
+
+    async fn provision_tenant(&mut self, org: usize) -> Result<()> {
+        let mut fixture = TestFixture::create().await?;
+        fixture.persistent = true;
+        self.tenants.push(DatasetTenant {
+            fixture: fixture.snapshot(),
+            vaults: vec![DatasetVault { vault_id: fixture.vault_id, relationships: 0 }],
+        });
+        let tenant = self.tenants.last_mut().expect("Tenant was just pushed");
+
+        let control = fixture.control();
+        for _ in 1..self.spec.vaults_per_org {
+            let req = CreateVaultRequest {
+                name: naming::entity_name("Dataset Vault"),
+                organization_id: fixture.org_id,
+            };
+            let vault = control.create_vault(fixture.org_id, &req).await?.vault;
+            tenant.vaults.push(DatasetVault { vault_id: vault.id, relationships: 0 });
+        }
+
+        for (index, vault) in tenant.vaults.iter_mut().enumerate() {
+            let req = DeploySchemaRequest { definition: DOCUMENT_SCHEMA.to_string() };
+            match control.deploy_schema(fixture.org_id, vault.vault_id, &req).await {
+                Ok(()) => {},
+                Err(e) if e.is_unsupported() => {},
+                Err(e) => return Err(e).context("Failed to deploy dataset schema"),
+            }
+
+            let jwt = fixture.generate_jwt(Some(vault.vault_id), &["inferadb.write"])?;
+            let engine = fixture.engine(&jwt);
+            let relationships = self.spec.relationships(org, index);
+            for batch in relationships.chunks(WRITE_BATCH) {
+                engine.write_relationships(batch).await?;
+                vault.relationships += batch.len();
+            }
+        }
+        Ok(())
+    }
+
+    /// Total relationships written across every vault
+    pub fn relationship_count(&self) -> usize {
+        self.tenants.iter().flat_map(|tenant| &tenant.vaults).map(|vault| vault.relationships).sum()
+    }
+
+    /// Fixture owning each organization, with its session and signing key
+    pub fn fixtures(&self) -> Result<Vec<TestFixture>> {
+        self.tenants
+            .iter()
+            .map(|tenant| TestFixture::from_snapshot(tenant.fixture.clone()))
+            .collect()
+    }
+
+    /// Delete every vault, client, organization, and user the dataset created
+    ///
+    /// A tenant that fails to delete does not stop the others. The manifest keeps just the tenants
+    /// that failed, so writing it back lets a later cleanup finish the job.
+    pub async fn cleanup(&mut self) -> Result<()> {
+        let mut errors = Vec::new();
+        for tenant in std::mem::take(&mut self.tenants) {
+            if let Err(e) = Self::delete_tenant(&tenant).await {
+                errors.push(format!("organization {}: {:#}", tenant.fixture.org_id, e));
+                self.tenants.push(tenant);
+            }
+        }
+        anyhow::ensure!(
+            errors.is_empty(),
+            "Failed to delete {} dataset organizations:\n  {}",
+            errors.len(),
+            errors.join("\n  ")
+        );
+        Ok(())
+    }
+
+    /// Delete one tenant's vaults, client, organization, and user; ones already gone count as
+    /// deleted, so a tenant left over from a failed cleanup can be retried
+    async fn delete_tenant(tenant: &DatasetTenant) -> Result<()> {
+        let fixture = TestFixture::from_snapshot(tenant.fixture.clone())?;
+        usage::finish(&fixture).await;
+        let gone = |result: ApiResult<()>| match result {
+            Err(e) if !e.is_not_found() => Err(e),
+            _ => Ok(()),
+        };
+
+        let control = fixture.control();
+        for vault in &tenant.vaults {
+            gone(control.delete_vault(fixture.org_id, vault.vault_id).await)
+                .with_context(|| format!("Failed to delete vault {}", vault.vault_id))?;
+        }
+        gone(control.delete_client(fixture.org_id, fixture.client_id).await)
+            .context("Failed to delete client")?;
+        gone(control.delete_organization(fixture.org_id).await)
+            .context("Failed to delete organization")?;
+        gone(control.delete_user(fixture.user_id).await).context("Failed to delete user")?;
+        Ok(())
+    }
+
+    /// Write the manifest as pretty-printed JSON, owner-only (`write_private`): it holds every
+    /// tenant's session and private key
+    pub fn write(&self, path: &Path) -> Result<()> {
+        write_private(path, &serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write dataset manifest {}", path.display()))
+    }
+
+    pub fn read(path: &Path) -> Result<Self> {
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read dataset manifest {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .with_context(|| format!("Invalid dataset manifest {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dataset_relationships_are_seeded_and_skewed() {
+        let spec = DatasetSpec::new(3, 2, 2000).population(200, 100);
+        let tuples = spec.relationships(0, 0);
+        assert_eq!(tuples.len(), 2000, "Large populations yield every requested tuple");
+        assert_eq!(tuples, spec.relationships(0, 0), "Same spec and vault, same tuples");
+        assert_ne!(tuples, spec.relationships(0, 1), "Vaults draw independently");
+        assert_ne!(tuples, spec.relationships(1, 0), "Organizations draw independently");
+        assert_ne!(tuples, spec.clone().seed(2).relationships(0, 0), "Seeds change the draw");
+
+        // Zipfian: the most popular tenth of documents carries far more than a tenth of the tuples
+        let mut per_document = std::collections::HashMap::new();
+        for tuple in &tuples {
+            *per_document.entry(tuple.resource.as_str()).or_insert(0usize) += 1;
+        }
+        let mut counts: Vec<usize> = per_document.into_values().collect();
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+        let head: usize = counts.iter().take(20).sum();
+        assert!(head * 100 / tuples.len() >= 35, "Top 20 documents hold only {} tuples", head);
+
+        let tiny = DatasetSpec::new(1, 1, 500).population(3, 2);
+        assert!(tiny.relationships(0, 0).len() <= 3 * 2 * 3, "Only distinct tuples are drawn");
+    }
+}
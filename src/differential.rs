@@ -0,0 +1,176 @@
+// Differential Testing
+//
+// Validates an Engine upgrade by sending identical read traffic to two Engine builds that share
+// one Control and Ledger (so the same JWT and the same relationships are valid on both) and
+// reporting every query whose answer differs. Answers are normalized before comparison: subject
+// lists and expand trees are order-insensitive, and errors compare by status code.
+//
+// The candidate build is reached at `INFERADB_CANDIDATE_ENGINE_URL`; the baseline is the API base
+// URL. Both are API base URLs, with Engine served under `/access/{version}`.
+
+use std::{collections::BTreeSet, fmt};
+
+use super::*;
+
+/// A read query replayed against both builds
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Query {
+    Evaluate { resource: String, permission: String, subject: String },
+    Expand { resource: String, relation: String },
+    ListSubjects { resource: String, permission: String, subject_type: String },
+}
+
+impl Query {
+    pub fn evaluate(resource: &str, permission: &str, subject: &str) -> Self {
+        Self::Evaluate {
+            resource: resource.to_string(),
+            permission: permission.to_string(),
+            subject: subject.to_string(),
+        }
+    }
+
+    pub fn expand(resource: &str, relation: &str) -> Self {
+        Self::Expand { resource: resource.to_string(), relation: relation.to_string() }
+    }
+
+    pub fn list_subjects(resource: &str, permission: &str, subject_type: &str) -> Self {
+        Self::ListSubjects {
+            resource: resource.to_string(),
+            permission: permission.to_string(),
+            subject_type: subject_type.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for Query {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Evaluate { resource, permission, subject } => {
+                write!(f, "evaluate {} {} {}", resource, permission, subject)
+            },
+            Self::Expand { resource, relation } => write!(f, "expand {} {}", resource, relation),
+            Self::ListSubjects { resource, permission, subject_type } => {
+                write!(f, "list-subjects {} {} {}", resource, permission, subject_type)
+            },
+        }
+    }
+}
+
+/// A build's normalized answer to a query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Decision(bool),
+    Subjects(BTreeSet<String>),
+    /// Expand tree with every array sorted
+    Tree(serde_json::Value),
+    /// The build answered with a non-success status
+    Status(u16),
+    /// The request never got an answer
+    Unreachable(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Decision(allowed) => write!(f, "{}", if *allowed { "allow" } else { "deny" }),
+            Self::Subjects(subjects) => write!(f, "{:?}", subjects),
+            Self::Tree(tree) => write!(f, "{}", tree),
+            Self::Status(status) => write!(f, "HTTP {}", status),
+            Self::Unreachable(error) => write!(f, "unreachable ({})", error),
+        }
+    }
+}
+
+/// Sort every array in an expand tree so child order does not count as a difference
+fn normalize_tree(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Array(items) => {
+            items.iter_mut().for_each(normalize_tree);
+            items.sort_by_key(|item| item.to_string());
+        },
+        serde_json::Value::Object(map) => map.values_mut().for_each(normalize_tree),
+        _ => {},
+    }
+}
+
+fn outcome_of<T>(result: ApiResult<T>, answer: impl FnOnce(T) -> Outcome) -> Outcome {
+    match result {
+        Ok(value) => answer(value),
+        Err(e) => match e.status() {
+            Some(status) => Outcome::Status(status.as_u16()),
+            None => Outcome::Unreachable(e.to_string()),
+        },
+    }
+}
+
+/// A query the two builds answered differently
+#[derive(Debug, Clone)]
+pub struct Mismatch {
+    pub query: Query,
+    pub baseline: Outcome,
+    pub candidate: Outcome,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: baseline {}, candidate {}", self.query, self.baseline, self.candidate)
+    }
+}
+
+/// Replays queries against a baseline and a candidate Engine build
+pub struct DifferentialRunner {
+    baseline: TestContext,
+    candidate: TestContext,
+}
+
+impl DifferentialRunner {
+    pub fn new(baseline: TestContext, candidate: TestContext) -> Self {
+        Self { baseline, candidate }
+    }
+
+    /// Baseline `ctx` against the build at `INFERADB_CANDIDATE_ENGINE_URL`, if one is configured
+    pub fn from_env(ctx: &TestContext) -> Option<Self> {
+        let candidate = std::env::var("INFERADB_CANDIDATE_ENGINE_URL").ok()?;
+        Some(Self::new(ctx.clone(), TestContext::with_base_url(candidate)))
+    }
+
+    pub fn candidate(&self) -> &TestContext {
+        &self.candidate
+    }
+
+    /// Answer `query` on one build with `jwt`
+    pub async fn ask(ctx: &TestContext, jwt: &str, query: &Query) -> Outcome {
+        let engine = EngineClient::new(ctx.clone(), jwt);
+        match query {
+            Query::Evaluate { resource, permission, subject } => {
+                outcome_of(engine.check(resource, permission, subject).await, Outcome::Decision)
+            },
+            Query::Expand { resource, relation } => {
+                outcome_of(engine.expand(resource, relation).await, |mut tree| {
+                    normalize_tree(&mut tree);
+                    Outcome::Tree(tree)
+                })
+            },
+            Query::ListSubjects { resource, permission, subject_type } => outcome_of(
+                engine.list_subjects(resource, permission, subject_type).await,
+                |response| Outcome::Subjects(response.subjects.into_iter().collect()),
+            ),
+        }
+    }
+
+    /// Send every query to both builds, returning the ones they disagree on
+    pub async fn run(&self, jwt: &str, queries: &[Query]) -> Vec<Mismatch> {
+        let mut mismatches = Vec::new();
+        for query in queries {
+            let (baseline, candidate) = tokio::join!(
+                Self::ask(&self.baseline, jwt, query),
+                Self::ask(&self.candidate, jwt, query)
+            );
+            if baseline != candidate {
+                mismatches.push(Mismatch { query: query.clone(), baseline, candidate });
+            }
+        }
+        mismatches
+    }
+}
@@ -0,0 +1,669 @@
+// Typed Engine API client
+//
+// Wraps the Engine (Access) endpoints the suite exercises beyond a single evaluate call. Every
+// request carries the client JWT it was created with, so the token decides which vault and
+// scopes apply.
+
+use std::time::{Duration, Instant};
+
+use chrono::DateTime;
+use reqwest::{Method, RequestBuilder, header::HeaderMap};
+use serde::de::DeserializeOwned;
+
+use super::*;
+
+const SERVICE: &str = "Engine";
+
+/// A relationship tuple as read and written by Engine
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct Relationship {
+    pub resource: String,
+    pub relation: String,
+    pub subject: String,
+}
+
+impl Relationship {
+    pub fn new(
+        resource: impl Into<String>,
+        relation: impl Into<String>,
+        subject: impl Into<String>,
+    ) -> Self {
+        Self { resource: resource.into(), relation: relation.into(), subject: subject.into() }
+    }
+}
+
+/// A relationship as stored by Engine, with the bookkeeping it records per tuple
+///
+/// Used where timestamps or metadata matter; `Relationship` stays a plain tuple so listed and
+/// written relationships compare equal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StoredRelationship {
+    #[serde(flatten)]
+    pub relationship: Relationship,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Filter for listing relationships; unset fields match everything
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RelationshipFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resource: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    /// Continuation token from the previous page
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursor: Option<String>,
+}
+
+impl RelationshipFilter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn resource(mut self, resource: impl Into<String>) -> Self {
+        self.resource = Some(resource.into());
+        self
+    }
+
+    pub fn relation(mut self, relation: impl Into<String>) -> Self {
+        self.relation = Some(relation.into());
+        self
+    }
+
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+}
+
+/// Response from the relationship list endpoint
+#[derive(Debug, Deserialize)]
+pub struct ListRelationshipsResponse {
+    #[serde(default)]
+    pub relationships: Vec<Relationship>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Relationship list or export response, with per-tuple timestamps and metadata
+#[derive(Debug, Deserialize)]
+pub struct StoredRelationshipsResponse {
+    #[serde(default)]
+    pub relationships: Vec<StoredRelationship>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Response from the subject list endpoint
+#[derive(Debug, Deserialize)]
+pub struct ListSubjectsResponse {
+    #[serde(default)]
+    pub subjects: Vec<String>,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Why a permission check came out as it did, from the explain endpoint
+///
+/// For a deny, `missing_relationships` names the absent edges: writing any one of them would grant
+/// the permission. Fields the suite does not model (the evaluation trace) are kept in `extra`.
+#[derive(Debug, Deserialize)]
+pub struct ExplainResponse {
+    pub decision: String,
+    #[serde(default)]
+    pub missing_relationships: Vec<Relationship>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ExplainResponse {
+    /// Whether the explained check was allowed
+    pub fn allowed(&self) -> bool {
+        self.decision == "ALLOW"
+    }
+}
+
+/// Statistics of the token's vault
+///
+/// Fields the suite does not model (storage usage and the like) are kept in `extra`.
+#[derive(Debug, Deserialize)]
+pub struct VaultStats {
+    pub relationship_count: u64,
+    /// When the vault was last written, RFC 3339; absent for a vault never written
+    #[serde(default)]
+    pub last_write_at: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl VaultStats {
+    /// The vault the statistics describe, as a string whether Engine sends it as one or not
+    pub fn vault_id(&self) -> Option<String> {
+        match self.extra.get("vault_id")? {
+            serde_json::Value::String(vault_id) => Some(vault_id.clone()),
+            serde_json::Value::Null => None,
+            other => Some(other.to_string()),
+        }
+    }
+
+    /// `last_write_at`, parsed
+    pub fn last_write(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(self.last_write_at.as_deref()?)
+            .ok()
+            .map(|at| at.with_timezone(&Utc))
+    }
+}
+
+/// How fresh the data behind an evaluation must be, sent as the request's `consistency` field
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "requirement", content = "revision", rename_all = "snake_case")]
+pub enum Consistency {
+    /// The latest committed data
+    Full,
+    /// Data at least as new as a revision returned by a write
+    AtLeastAsFresh(String),
+    /// Whatever the nearest cache or replica holds, possibly stale
+    MinimizeLatency,
+    /// Exactly the data as of a revision, ignoring later writes
+    AtExactSnapshot(String),
+}
+
+impl Consistency {
+    /// Name of the requirement on the wire
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Full => "full",
+            Self::AtLeastAsFresh(_) => "at_least_as_fresh",
+            Self::MinimizeLatency => "minimize_latency",
+            Self::AtExactSnapshot(_) => "at_exact_snapshot",
+        }
+    }
+}
+
+/// A single evaluation result
+///
+/// Fields the suite does not model are kept in `extra` so failure messages show the whole result.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvaluationResult {
+    /// Empty when the item failed and only `error` is set
+    #[serde(default)]
+    pub decision: String,
+    /// Per-item failure in a batch that otherwise succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<serde_json::Value>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Response from the evaluate endpoint
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct EvaluateResponse {
+    #[serde(default)]
+    pub results: Vec<EvaluationResult>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl EvaluateResponse {
+    /// Whether the first evaluation was allowed
+    pub fn allowed(&self) -> bool {
+        self.results.first().is_some_and(|r| r.decision == "ALLOW")
+    }
+
+    /// Decisions in request order
+    pub fn decisions(&self) -> Vec<&str> {
+        self.results.iter().map(|r| r.decision.as_str()).collect()
+    }
+
+    /// Assert every evaluation was allowed, printing the full response otherwise
+    #[track_caller]
+    pub fn assert_allowed(&self, context: &str) {
+        let decisions = self.decisions();
+        assert!(
+            !decisions.is_empty() && decisions.iter().all(|d| *d == "ALLOW"),
+            "{}: expected ALLOW, got {:?}\nResponse: {}",
+            context,
+            decisions,
+            self.body()
+        );
+    }
+
+    /// Assert every evaluation was denied, printing the full response otherwise
+    ///
+    /// An empty result list counts as denied, matching Engine's 404 for "no relationships".
+    #[track_caller]
+    pub fn assert_denied(&self, context: &str) {
+        let decisions = self.decisions();
+        assert!(
+            decisions.iter().all(|d| *d == "DENY"),
+            "{}: expected DENY, got {:?}\nResponse: {}",
+            context,
+            decisions,
+            self.body()
+        );
+    }
+
+    /// The response as pretty-printed JSON, including fields the suite does not model
+    fn body(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_else(|e| format!("<unprintable: {}>", e))
+    }
+}
+
+/// Server-side latency breakdown reported by Engine
+///
+/// Parsed from standard `Server-Timing` entries (`auth;dur=0.8, evaluate;dur=2.1`) and from
+/// `X-InferaDB-{Phase}-Ms` headers; durations are in milliseconds.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ServerTiming {
+    pub metrics: Vec<(String, f64)>,
+}
+
+impl ServerTiming {
+    /// Token validation, including key lookup
+    pub const AUTH: &str = "auth";
+    /// Permission evaluation
+    pub const EVALUATE: &str = "evaluate";
+    /// Time spent waiting on Ledger
+    pub const LEDGER: &str = "ledger";
+
+    pub fn from_headers(headers: &HeaderMap) -> Self {
+        let mut metrics = Vec::new();
+
+        for value in headers.get_all("server-timing") {
+            let Ok(value) = value.to_str() else { continue };
+            for entry in value.split(',') {
+                let mut params = entry.split(';').map(str::trim);
+                let Some(name) = params.next().filter(|name| !name.is_empty()) else { continue };
+                let duration = params
+                    .filter_map(|param| param.strip_prefix("dur="))
+                    .find_map(|dur| dur.trim_matches('"').parse().ok());
+                if let Some(duration) = duration {
+                    metrics.push((name.to_lowercase(), duration));
+                }
+            }
+        }
+
+        for (header, value) in headers {
+            let phase = header
+                .as_str()
+                .strip_prefix("x-inferadb-")
+                .and_then(|rest| rest.strip_suffix("-ms"));
+            if let (Some(phase), Some(duration)) =
+                (phase, value.to_str().ok().and_then(|v| v.trim().parse().ok()))
+            {
+                metrics.push((phase.to_string(), duration));
+            }
+        }
+
+        Self { metrics }
+    }
+
+    /// Duration of a phase, summed if Engine reported it more than once
+    pub fn get(&self, name: &str) -> Option<f64> {
+        let matching: Vec<f64> =
+            self.metrics.iter().filter(|(metric, _)| metric == name).map(|(_, d)| *d).collect();
+        (!matching.is_empty()).then(|| matching.iter().sum())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.metrics.is_empty()
+    }
+}
+
+/// A response value with Engine's timing breakdown and the client-observed round trip
+#[derive(Debug)]
+pub struct Timed<T> {
+    pub value: T,
+    pub server_timing: ServerTiming,
+    pub elapsed: Duration,
+}
+
+/// Client for the Engine (Access) API, authenticated with a client JWT
+#[derive(Clone)]
+pub struct EngineClient {
+    ctx: TestContext,
+    jwt: String,
+    dpop: Option<DpopKey>,
+    vault: Option<i64>,
+}
+
+impl EngineClient {
+    pub fn new(ctx: TestContext, jwt: impl Into<String>) -> Self {
+        Self { ctx, jwt: jwt.into(), dpop: None, vault: None }
+    }
+
+    /// Present the JWT as a DPoP-bound token, with a fresh proof from `key` on every request
+    pub fn with_dpop(mut self, key: DpopKey) -> Self {
+        self.dpop = Some(key);
+        self
+    }
+
+    /// Name the vault every request targets (`X-Vault-Id`), for tokens that claim more than one
+    pub fn for_vault(mut self, vault_id: i64) -> Self {
+        self.vault = Some(vault_id);
+        self
+    }
+
+    /// Build a request to an Engine path, authenticated with the client JWT
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let url = self.ctx.engine_url(path);
+        let mut builder = self.ctx.client.request(method.clone(), &url);
+        if let Some(vault_id) = self.vault {
+            builder = builder.header("X-Vault-Id", vault_id.to_string());
+        }
+        match &self.dpop {
+            Some(key) => builder
+                .header("Authorization", format!("DPoP {}", self.jwt))
+                .header("DPoP", key.proof(&method, &url, &self.jwt)),
+            None => builder.header("Authorization", format!("Bearer {}", self.jwt)),
+        }
+    }
+
+    async fn send_json<T: DeserializeOwned>(&self, builder: RequestBuilder) -> ApiResult<T> {
+        api_client::send_json(SERVICE, builder).await
+    }
+
+    async fn send_empty(&self, builder: RequestBuilder) -> ApiResult<()> {
+        api_client::send_empty(SERVICE, builder).await
+    }
+
+    fn evaluate_request(
+        &self,
+        resource: &str,
+        permission: &str,
+        subject: &str,
+        consistency: Option<&Consistency>,
+    ) -> RequestBuilder {
+        let mut body = serde_json::json!({
+            "evaluations": [{
+                "subject": subject,
+                "resource": resource,
+                "permission": permission,
+                "trace": false
+            }]
+        });
+        if let Some(consistency) = consistency {
+            body["consistency"] = serde_json::json!(consistency);
+        }
+
+        self.request(Method::POST, "/evaluate").json(&body)
+    }
+
+    pub async fn evaluate(
+        &self,
+        resource: &str,
+        permission: &str,
+        subject: &str,
+    ) -> ApiResult<EvaluateResponse> {
+        self.send_json(self.evaluate_request(resource, permission, subject, None)).await
+    }
+
+    /// Evaluate with an explicit consistency requirement
+    pub async fn evaluate_with(
+        &self,
+        resource: &str,
+        permission: &str,
+        subject: &str,
+        consistency: &Consistency,
+    ) -> ApiResult<EvaluateResponse> {
+        self.send_json(self.evaluate_request(resource, permission, subject, Some(consistency)))
+            .await
+    }
+
+    /// Evaluate, capturing Engine's latency headers alongside the decision
+    ///
+    /// Engine's 404 for "no relationships" is returned as an empty response so its timing is kept.
+    pub async fn evaluate_timed(
+        &self,
+        resource: &str,
+        permission: &str,
+        subject: &str,
+    ) -> ApiResult<Timed<EvaluateResponse>> {
+        let start = Instant::now();
+        let response =
+            self.evaluate_request(resource, permission, subject, None).send_recorded().await?;
+        let elapsed = start.elapsed();
+        let server_timing = ServerTiming::from_headers(response.headers());
+
+        let status = response.status();
+        let value = if status == reqwest::StatusCode::NOT_FOUND {
+            EvaluateResponse::default()
+        } else if status.is_success() {
+            response.json().await?
+        } else {
+            let body = response.text().await.unwrap_or_else(|_| "<unreadable body>".to_string());
+            return Err(ApiError::Status { service: SERVICE, status, body });
+        };
+
+        Ok(Timed { value, server_timing, elapsed })
+    }
+
+    /// Evaluate a single permission; Engine's 404 for "no relationships" counts as a deny
+    pub async fn check(&self, resource: &str, permission: &str, subject: &str) -> ApiResult<bool> {
+        match self.evaluate(resource, permission, subject).await {
+            Ok(response) => Ok(response.allowed()),
+            Err(e) if e.is_not_found() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Write relationships, returning the revision Engine committed them at, if it reports one
+    pub async fn write_relationships_at(
+        &self,
+        relationships: &[Relationship],
+    ) -> ApiResult<Option<String>> {
+        let body = serde_json::json!({ "relationships": relationships });
+        let response = api_client::send(
+            SERVICE,
+            self.request(Method::POST, "/relationships/write").json(&body),
+        )
+        .await?;
+        Ok(revision(&response.text().await?))
+    }
+
+    /// Delete relationships, returning the revision Engine committed the delete at, if reported
+    pub async fn delete_relationships_at(
+        &self,
+        relationships: &[Relationship],
+    ) -> ApiResult<Option<String>> {
+        let body = serde_json::json!({ "relationships": relationships });
+        let response = api_client::send(
+            SERVICE,
+            self.request(Method::POST, "/relationships/delete").json(&body),
+        )
+        .await?;
+        Ok(revision(&response.text().await?))
+    }
+
+    pub async fn write_relationships(&self, relationships: &[Relationship]) -> ApiResult<()> {
+        let body = serde_json::json!({ "relationships": relationships });
+        self.send_empty(self.request(Method::POST, "/relationships/write").json(&body)).await
+    }
+
+    pub async fn delete_relationships(&self, relationships: &[Relationship]) -> ApiResult<()> {
+        let body = serde_json::json!({ "relationships": relationships });
+        self.send_empty(self.request(Method::POST, "/relationships/delete").json(&body)).await
+    }
+
+    pub async fn list_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> ApiResult<ListRelationshipsResponse> {
+        self.send_json(self.request(Method::POST, "/relationships/list").json(filter)).await
+    }
+
+    /// List every relationship matching `filter`, following cursors across pages
+    pub async fn list_all_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> ApiResult<Vec<Relationship>> {
+        let mut filter = filter.clone();
+        let mut relationships = Vec::new();
+        loop {
+            let page = self.list_relationships(&filter).await?;
+            relationships.extend(page.relationships);
+            match page.cursor.filter(|cursor| !cursor.is_empty()) {
+                Some(cursor) => filter.cursor = Some(cursor),
+                None => return Ok(relationships),
+            }
+        }
+    }
+
+    /// List relationships including their `created_at` and metadata
+    pub async fn list_stored_relationships(
+        &self,
+        filter: &RelationshipFilter,
+    ) -> ApiResult<StoredRelationshipsResponse> {
+        self.send_json(self.request(Method::POST, "/relationships/list").json(filter)).await
+    }
+
+    /// Export every relationship in the token's vault, bookkeeping included, following cursors
+    /// across pages
+    pub async fn export_relationships(&self) -> ApiResult<Vec<StoredRelationship>> {
+        let mut request = self.request(Method::POST, "/relationships/export");
+        let mut relationships = Vec::new();
+        loop {
+            let page: StoredRelationshipsResponse = self.send_json(request).await?;
+            relationships.extend(page.relationships);
+            match page.cursor.filter(|cursor| !cursor.is_empty()) {
+                Some(cursor) => {
+                    request = self
+                        .request(Method::POST, "/relationships/export")
+                        .json(&serde_json::json!({ "cursor": cursor }))
+                },
+                None => return Ok(relationships),
+            }
+        }
+    }
+
+    /// Import exported relationships, preserving their timestamps and metadata
+    pub async fn import_relationships(
+        &self,
+        relationships: &[StoredRelationship],
+    ) -> ApiResult<()> {
+        let body = serde_json::json!({ "relationships": relationships });
+        self.send_empty(self.request(Method::POST, "/relationships/import").json(&body)).await
+    }
+
+    /// Expand the userset tree behind a relation or permission on a resource
+    pub async fn expand(&self, resource: &str, relation: &str) -> ApiResult<serde_json::Value> {
+        let body = serde_json::json!({ "resource": resource, "relation": relation });
+        self.send_json(self.request(Method::POST, "/expand").json(&body)).await
+    }
+
+    /// List subjects of a type that hold a permission on a resource
+    pub async fn list_subjects(
+        &self,
+        resource: &str,
+        permission: &str,
+        subject_type: &str,
+    ) -> ApiResult<ListSubjectsResponse> {
+        let body = serde_json::json!({
+            "resource": resource,
+            "permission": permission,
+            "subject_type": subject_type
+        });
+        self.send_json(self.request(Method::POST, "/subjects/list").json(&body)).await
+    }
+
+    /// Replace the schema of the token's vault
+    pub async fn write_schema(&self, req: &DeploySchemaRequest) -> ApiResult<()> {
+        self.send_empty(self.request(Method::POST, "/schemas").json(req)).await
+    }
+
+    /// Explain a permission check, naming the relationships a deny is missing
+    pub async fn explain(
+        &self,
+        resource: &str,
+        permission: &str,
+        subject: &str,
+    ) -> ApiResult<ExplainResponse> {
+        let body = serde_json::json!({
+            "resource": resource,
+            "permission": permission,
+            "subject": subject
+        });
+        self.send_json(self.request(Method::POST, "/evaluate/explain").json(&body)).await
+    }
+
+    /// Fetch vault statistics (relationship counts, last write, storage usage)
+    pub async fn stats(&self) -> ApiResult<VaultStats> {
+        self.send_json(self.request(Method::GET, "/stats")).await
+    }
+
+    /// Delete every relationship in the token's vault
+    pub async fn purge_relationships(&self) -> ApiResult<()> {
+        self.send_empty(self.request(Method::POST, "/relationships/purge")).await
+    }
+}
+
+/// The `revision` a write response reports, as a string whether Engine sends it as one or not
+fn revision(body: &str) -> Option<String> {
+    let value: serde_json::Value = serde_json::from_str(body).ok()?;
+    match value.get("revision")? {
+        serde_json::Value::String(revision) => Some(revision.clone()),
+        serde_json::Value::Null => None,
+        other => Some(other.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_consistency_wire_format() {
+        let cases = [
+            (Consistency::Full, serde_json::json!({ "requirement": "full" })),
+            (
+                Consistency::MinimizeLatency,
+                serde_json::json!({ "requirement": "minimize_latency" }),
+            ),
+            (
+                Consistency::AtLeastAsFresh("42".to_string()),
+                serde_json::json!({ "requirement": "at_least_as_fresh", "revision": "42" }),
+            ),
+            (
+                Consistency::AtExactSnapshot("7".to_string()),
+                serde_json::json!({ "requirement": "at_exact_snapshot", "revision": "7" }),
+            ),
+        ];
+        for (consistency, expected) in cases {
+            assert_eq!(serde_json::json!(consistency), expected, "{}", consistency.name());
+            assert_eq!(expected["requirement"], consistency.name());
+        }
+    }
+
+    #[test]
+    fn test_vault_stats_parsing() {
+        let stats: VaultStats = serde_json::from_str(
+            r#"{"relationship_count": 20, "last_write_at": "2026-01-02T03:04:05+01:00",
+                "vault_id": 42, "storage_bytes": 1024}"#,
+        )
+        .expect("Stats deserialize");
+        assert_eq!(stats.relationship_count, 20);
+        assert_eq!(stats.vault_id().as_deref(), Some("42"), "Numeric vault IDs read as strings");
+        assert_eq!(
+            stats.last_write().map(|at| at.to_rfc3339()).as_deref(),
+            Some("2026-01-02T02:04:05+00:00")
+        );
+        assert_eq!(stats.extra["storage_bytes"], 1024, "Unmodelled fields are kept");
+
+        let empty: VaultStats =
+            serde_json::from_str(r#"{"relationship_count": 0, "vault_id": "v-1"}"#).expect("Valid");
+        assert_eq!(empty.vault_id().as_deref(), Some("v-1"));
+        assert_eq!(empty.last_write(), None, "A vault never written has no last write");
+    }
+}
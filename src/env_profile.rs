@@ -0,0 +1,257 @@
+// Environment Profile
+//
+// Token validation rules (accepted audiences and issuers, clock leeway) are Engine configuration,
+// so they differ between deployments. `EnvProfile` records what the target environment is expected
+// to accept, letting tests pin that behavior instead of hard-coding one deployment's settings.
+//
+// The defaults describe the `inferadb dev` cluster; individual values can be overridden:
+//   INFERADB_AUDIENCE                   Audience Engine requires (default: REQUIRED_AUDIENCE)
+//   INFERADB_AUDIENCE_LIST_ACCEPTED     Whether an `aud` array containing it is accepted (true)
+//   INFERADB_ISSUER_ALLOW_LIST          Comma-separated issuers Engine accepts (API base URL)
+//   INFERADB_CLOCK_LEEWAY_SECONDS       Leeway Engine applies to `exp`/`nbf` (60)
+//   INFERADB_FORWARDED_HEADERS_TRUSTED  Whether client IPs come from forwarding headers (false)
+//   INFERADB_VAULT_LIMIT                Vaults a new organization may hold (unset: unknown)
+//   INFERADB_RELATIONSHIP_LIMIT         Relationships a new organization's vault may hold (unset)
+//   INFERADB_PASSWORD_CHANGE_REVOKES_SESSIONS
+//                                       Whether a password change ends other sessions (true)
+//   INFERADB_TOKEN_REUSE_DETECTION      Whether a token replayed elsewhere is caught (false)
+//   INFERADB_AUTH_CACHE_PREWARMED       Whether Engine fills its auth cache at startup (false)
+//   INFERADB_NUMERIC_ID_CLAIMS_ACCEPTED
+//                                       Whether ID claims may be JSON numbers (true)
+//   INFERADB_WARM_AUTH_BUDGET_MS        Ceiling on Engine's warm-cache p95 auth phase (5.0)
+//   INFERADB_EXPERIMENTAL_FEATURES      Comma-separated experimental features turned on (none)
+//
+// Endpoints still rolling out sit behind per-deployment feature flags. Their tests can land before
+// the rollout: each starts with `ExperimentalFeatures::require`, which records a skip naming the
+// flag wherever it is off.
+
+use std::collections::BTreeSet;
+
+use super::*;
+
+static ENV_PROFILE: OnceLock<EnvProfile> = OnceLock::new();
+
+/// Token validation settings expected of the target environment
+#[derive(Debug, Clone, Serialize)]
+pub struct EnvProfile {
+    /// Audience Engine requires; matched exactly
+    pub audience: String,
+    /// Whether Engine accepts an `aud` array that contains the required audience
+    pub audience_list_accepted: bool,
+    /// Issuers Engine accepts; matched exactly
+    pub issuer_allow_list: Vec<String>,
+    /// Seconds past `exp` (or before `nbf`) that Engine still accepts a token
+    pub clock_leeway_seconds: i64,
+    /// Whether services sit behind a trusted proxy and take client IPs from forwarding headers
+    pub forwarded_headers_trusted: bool,
+    /// Vaults a newly registered organization's tier allows, if known
+    pub vault_limit: Option<usize>,
+    /// Relationships a vault in a newly registered organization may hold, if known
+    pub relationship_limit: Option<usize>,
+    /// Whether changing a password ends the user's other Control sessions
+    pub password_change_revokes_sessions: bool,
+    /// Whether Engine flags or blocks a token replayed from a second client
+    pub token_reuse_detection: bool,
+    /// Whether Engine fills its auth cache at startup, before serving
+    pub auth_cache_prewarmed: bool,
+    /// Whether Engine takes `vault_id`/`org_id` claims as JSON numbers as well as decimal strings
+    pub numeric_id_claims_accepted: bool,
+    /// Ceiling on the p95 auth phase Engine reports with warm vault and key caches, in
+    /// milliseconds
+    pub warm_auth_budget_ms: f64,
+    /// Experimental endpoints turned on in this deployment
+    pub experimental: ExperimentalFeatures,
+}
+
+/// An endpoint behind a feature flag until its rollout completes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Feature {
+    /// Deleting every relationship matching a filter in one call
+    BatchDelete,
+    /// Streaming relationship changes as they are written
+    Watch,
+    /// Relationships that hold only when a condition on the request context is met
+    Caveats,
+    /// Client tokens whose `vault_id` claim lists several vaults, or `*` for the whole organization
+    MultiVaultTokens,
+}
+
+impl Feature {
+    pub const ALL: [Feature; 4] =
+        [Feature::BatchDelete, Feature::Watch, Feature::Caveats, Feature::MultiVaultTokens];
+
+    /// Name used in INFERADB_EXPERIMENTAL_FEATURES
+    pub fn name(self) -> &'static str {
+        match self {
+            Feature::BatchDelete => "batch-delete",
+            Feature::Watch => "watch",
+            Feature::Caveats => "caveats",
+            Feature::MultiVaultTokens => "multi-vault-tokens",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|feature| feature.name().eq_ignore_ascii_case(name.trim()))
+    }
+}
+
+/// Experimental features a deployment has turned on; all off by default
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ExperimentalFeatures {
+    pub enabled: BTreeSet<Feature>,
+}
+
+impl ExperimentalFeatures {
+    /// Parse a comma-separated list of feature names, ignoring (and logging) unknown ones
+    pub fn parse(list: &str) -> Self {
+        let mut enabled = BTreeSet::new();
+        for name in list.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            match Feature::parse(name) {
+                Some(feature) => {
+                    enabled.insert(feature);
+                },
+                None => eprintln!("Ignoring unknown experimental feature {:?}", name),
+            }
+        }
+        Self { enabled }
+    }
+
+    pub fn is_enabled(&self, feature: Feature) -> bool {
+        self.enabled.contains(&feature)
+    }
+
+    /// Whether `feature` is on in the active profile, recording a skip for the current test if not
+    pub fn require(feature: Feature) -> bool {
+        if EnvProfile::current().experimental.is_enabled(feature) {
+            return true;
+        }
+        outcome::skip(format!(
+            "Skipping {} test - experimental feature off in this deployment (enable with \
+             INFERADB_EXPERIMENTAL_FEATURES={})",
+            feature.name(),
+            feature.name()
+        ));
+        false
+    }
+}
+
+impl EnvProfile {
+    /// Profile of the local `inferadb dev` cluster
+    pub fn dev() -> Self {
+        Self {
+            audience: REQUIRED_AUDIENCE.to_string(),
+            audience_list_accepted: true,
+            issuer_allow_list: vec![api_base_url()],
+            // jsonwebtoken's default validation leeway
+            clock_leeway_seconds: 60,
+            forwarded_headers_trusted: false,
+            vault_limit: None,
+            relationship_limit: None,
+            password_change_revokes_sessions: true,
+            token_reuse_detection: false,
+            auth_cache_prewarmed: false,
+            numeric_id_claims_accepted: true,
+            warm_auth_budget_ms: 5.0,
+            experimental: ExperimentalFeatures::default(),
+        }
+    }
+
+    /// `dev`, with any overrides from the environment applied
+    pub fn from_env() -> Self {
+        let mut profile = Self::dev();
+        if let Ok(audience) = std::env::var("INFERADB_AUDIENCE") {
+            profile.audience = audience;
+        }
+        if let Ok(accepted) = std::env::var("INFERADB_AUDIENCE_LIST_ACCEPTED") {
+            profile.audience_list_accepted = accepted != "false" && accepted != "0";
+        }
+        if let Ok(issuers) = std::env::var("INFERADB_ISSUER_ALLOW_LIST") {
+            profile.issuer_allow_list = issuers
+                .split(',')
+                .map(|issuer| issuer.trim().to_string())
+                .filter(|issuer| !issuer.is_empty())
+                .collect();
+        }
+        if let Some(leeway) = std::env::var("INFERADB_CLOCK_LEEWAY_SECONDS")
+            .ok()
+            .and_then(|leeway| leeway.parse().ok())
+        {
+            profile.clock_leeway_seconds = leeway;
+        }
+        if let Ok(trusted) = std::env::var("INFERADB_FORWARDED_HEADERS_TRUSTED") {
+            profile.forwarded_headers_trusted = trusted == "true" || trusted == "1";
+        }
+        if let Some(limit) =
+            std::env::var("INFERADB_VAULT_LIMIT").ok().and_then(|limit| limit.parse().ok())
+        {
+            profile.vault_limit = Some(limit);
+        }
+        if let Some(limit) =
+            std::env::var("INFERADB_RELATIONSHIP_LIMIT").ok().and_then(|limit| limit.parse().ok())
+        {
+            profile.relationship_limit = Some(limit);
+        }
+        if let Ok(revokes) = std::env::var("INFERADB_PASSWORD_CHANGE_REVOKES_SESSIONS") {
+            profile.password_change_revokes_sessions = revokes != "false" && revokes != "0";
+        }
+        if let Ok(detection) = std::env::var("INFERADB_TOKEN_REUSE_DETECTION") {
+            profile.token_reuse_detection = detection == "true" || detection == "1";
+        }
+        if let Ok(prewarmed) = std::env::var("INFERADB_AUTH_CACHE_PREWARMED") {
+            profile.auth_cache_prewarmed = prewarmed == "true" || prewarmed == "1";
+        }
+        if let Ok(accepted) = std::env::var("INFERADB_NUMERIC_ID_CLAIMS_ACCEPTED") {
+            profile.numeric_id_claims_accepted = accepted != "false" && accepted != "0";
+        }
+        if let Some(budget) = std::env::var("INFERADB_WARM_AUTH_BUDGET_MS")
+            .ok()
+            .and_then(|budget| budget.parse().ok())
+        {
+            profile.warm_auth_budget_ms = budget;
+        }
+        if let Ok(features) = std::env::var("INFERADB_EXPERIMENTAL_FEATURES") {
+            profile.experimental = ExperimentalFeatures::parse(&features);
+        }
+        profile
+    }
+
+    /// The active profile: the installed one, or `from_env`
+    pub fn current() -> &'static EnvProfile {
+        ENV_PROFILE.get_or_init(Self::from_env)
+    }
+
+    /// Make this the active profile; fails once `current` has been resolved
+    pub fn install(self) -> Result<()> {
+        ENV_PROFILE.set(self).map_err(|_| anyhow::anyhow!("Environment profile already resolved"))
+    }
+
+    /// Whether Engine is expected to accept a token issued by `issuer`
+    pub fn accepts_issuer(&self, issuer: &str) -> bool {
+        self.issuer_allow_list.iter().any(|allowed| allowed == issuer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experimental_features_parsing() {
+        let features = ExperimentalFeatures::parse(" watch, Batch-Delete ,,teleport");
+        assert!(features.is_enabled(Feature::Watch));
+        assert!(features.is_enabled(Feature::BatchDelete), "Names match case-insensitively");
+        assert!(!features.is_enabled(Feature::Caveats));
+        assert_eq!(features.enabled.len(), 2, "Unknown names are ignored");
+        assert_eq!(
+            serde_json::to_value(&features).expect("Features serialize"),
+            serde_json::json!({ "enabled": ["batch-delete", "watch"] }),
+            "Reports list features by their flag names"
+        );
+
+        for feature in Feature::ALL {
+            assert_eq!(Feature::parse(feature.name()), Some(feature));
+        }
+        assert_eq!(ExperimentalFeatures::parse(""), ExperimentalFeatures::default());
+    }
+}
@@ -0,0 +1,103 @@
+// Minimal gRPC probe
+//
+// The suite has no generated gRPC stubs. Authorization checks only need the status Engine assigns
+// to a call, so `GrpcProbe` sends an empty unary message over HTTP/2 and reads `grpc-status`.
+//
+// Engine's gRPC listener is reached at `INFERADB_GRPC_URL` (default: the API base URL) and its
+// service name is `INFERADB_GRPC_SERVICE` (default: `inferadb.v1.AuthorizationService`).
+// `GrpcProbe::open` keeps a call's response open instead, so tests can hold streams on one
+// connection.
+
+use reqwest::{Client, StatusCode};
+
+use super::*;
+
+/// gRPC status codes the suite distinguishes
+pub mod grpc_status {
+    pub const OK: i32 = 0;
+    pub const INVALID_ARGUMENT: i32 = 3;
+    pub const PERMISSION_DENIED: i32 = 7;
+    pub const RESOURCE_EXHAUSTED: i32 = 8;
+    pub const UNIMPLEMENTED: i32 = 12;
+    pub const UNAUTHENTICATED: i32 = 16;
+}
+
+/// Sends empty unary gRPC calls and reports the resulting status code
+///
+/// Clones share one HTTP/2 connection; create a new probe for another.
+#[derive(Clone)]
+pub struct GrpcProbe {
+    client: Client,
+    base_url: String,
+    service: String,
+}
+
+/// A gRPC call whose response is still open; dropping it cancels the stream
+pub struct GrpcStream {
+    /// Status from the response headers; `OK` while a streaming response is still running
+    pub status: i32,
+    _response: reqwest::Response,
+}
+
+impl GrpcProbe {
+    pub fn new(ctx: &TestContext) -> Self {
+        Self {
+            client: Client::builder()
+                .http2_prior_knowledge()
+                .timeout(std::time::Duration::from_secs(10))
+                .danger_accept_invalid_certs(true) // For dev self-signed certs
+                .build()
+                .expect("Failed to create gRPC client"),
+            base_url: std::env::var("INFERADB_GRPC_URL")
+                .unwrap_or_else(|_| ctx.api_base_url.clone()),
+            service: std::env::var("INFERADB_GRPC_SERVICE")
+                .unwrap_or_else(|_| "inferadb.v1.AuthorizationService".to_string()),
+        }
+    }
+
+    /// Call `method` with an empty request message
+    ///
+    /// Returns `None` if the endpoint did not answer with gRPC at all (no listener, or routed to
+    /// a plain HTTP service). A `200` gRPC response without a `grpc-status` header carries its
+    /// status in trailers, which only successful calls do, so it is reported as `OK`.
+    pub async fn call(&self, method: &str, jwt: &str) -> Option<i32> {
+        self.open(method, jwt).await.map(|stream| stream.status)
+    }
+
+    /// Call `method` as `call` does, but keep the response open
+    ///
+    /// For a streaming method the HTTP/2 stream stays open, holding one of the connection's
+    /// concurrent streams, until the returned `GrpcStream` is dropped.
+    pub async fn open(&self, method: &str, jwt: &str) -> Option<GrpcStream> {
+        let url = format!("{}/{}/{}", self.base_url, self.service, method);
+
+        // Length-prefixed message: uncompressed flag, then a zero length (empty protobuf)
+        let frame = vec![0u8; 5];
+
+        let response = self
+            .client
+            .post(&url)
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .header("Authorization", format!("Bearer {}", jwt))
+            .body(frame)
+            .send_recorded()
+            .await
+            .ok()?;
+
+        let is_grpc = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/grpc"));
+        if !is_grpc || response.status() != StatusCode::OK {
+            return None;
+        }
+
+        let status = match response.headers().get("grpc-status").and_then(|v| v.to_str().ok()) {
+            Some(status) => status.parse().ok()?,
+            None => grpc_status::OK,
+        };
+        Some(GrpcStream { status, _response: response })
+    }
+}
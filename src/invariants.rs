@@ -0,0 +1,112 @@
+// Suite invariants
+//
+// Checks that hold across a whole run rather than inside one test. Many tests only assert what a
+// response is not ("anything but 401"), so a 500 or 502 from Engine or Control can pass unnoticed.
+// The recorder's capture (`INFERADB_CAPTURE_FILE`) holds every exchange of the run with the test
+// that sent it, and `server_errors` finds the forbidden responses in it.
+//
+// The `tagged` runner enforces this with `--forbid-server-errors` (or
+// `INFERADB_FORBID_SERVER_ERRORS=1`): it captures the run and fails it if the capture holds any
+// forbidden response, listed by test, even when every test passed. Modules tagged `chaos` are
+// exempt, since the faults they inject make gateway errors a legitimate answer.
+
+use std::{collections::BTreeMap, fmt::Write as _};
+
+use super::{recorder::Exchange, *};
+
+/// Statuses no Engine or Control response may carry
+pub const FORBIDDEN_STATUSES: &[u16] = &[500, 502];
+
+/// The service a request URL addresses (`engine` or `control`), by its ingress prefix
+pub fn service_of(url: &str) -> Option<&'static str> {
+    if url.contains("/access/") {
+        Some("engine")
+    } else if url.contains("/control/") {
+        Some("control")
+    } else {
+        None
+    }
+}
+
+/// Whether a test scope belongs to a module whose server errors are expected
+pub fn is_exempt(scope: &str) -> bool {
+    scope
+        .split("::")
+        .find_map(tags::module_tags)
+        .is_some_and(|module_tags| module_tags.contains(&tags::CHAOS))
+}
+
+/// Engine and Control responses with a forbidden status, outside exempt modules
+pub fn server_errors(exchanges: &[Exchange]) -> Vec<&Exchange> {
+    exchanges
+        .iter()
+        .filter(|e| e.status.is_some_and(|status| FORBIDDEN_STATUSES.contains(&status)))
+        .filter(|e| service_of(&e.url).is_some() && !is_exempt(&e.scope))
+        .collect()
+}
+
+/// Forbidden responses grouped by the test that got them, one line per response
+pub fn summarize(errors: &[&Exchange]) -> String {
+    let mut by_scope: BTreeMap<&str, Vec<&Exchange>> = BTreeMap::new();
+    for exchange in errors {
+        by_scope.entry(&exchange.scope).or_default().push(exchange);
+    }
+
+    let mut summary = String::new();
+    for (scope, exchanges) in by_scope {
+        let _ = writeln!(summary, "{}:", scope);
+        for e in exchanges {
+            let _ = writeln!(
+                summary,
+                "  {} {} {} {}",
+                e.status.unwrap_or_default(),
+                service_of(&e.url).unwrap_or("unknown"),
+                e.method,
+                e.url
+            );
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_server_error_invariant() {
+        let exchange = |scope: &str, url: &str, status: Option<u16>| recorder::Exchange {
+            scope: scope.to_string(),
+            started_at: String::new(),
+            elapsed_ms: 0.0,
+            method: "POST".to_string(),
+            url: url.to_string(),
+            headers: Vec::new(),
+            body: None,
+            status,
+            error: None,
+            response_body: None,
+        };
+        let exchanges = [
+            exchange("auth_jwt_tests::test_a", "https://api/access/v1/evaluate", Some(500)),
+            exchange("auth_jwt_tests::test_a", "https://api/access/v1/evaluate", Some(401)),
+            exchange("cache_tests::test_b", "https://api/control/v1/organizations", Some(502)),
+            exchange("cache_tests::test_b", "https://api/control/v1/organizations", Some(503)),
+            exchange("cache_tests::test_b", "https://api/metrics", Some(500)),
+            exchange("cache_tests::test_b", "https://api/access/v1/evaluate", None),
+            exchange("ledger_outage_tests::test_c", "https://api/access/v1/evaluate", Some(502)),
+        ];
+
+        let errors = server_errors(&exchanges);
+        let found: Vec<_> = errors.iter().map(|e| (e.scope.as_str(), e.status)).collect();
+        assert_eq!(
+            found,
+            [("auth_jwt_tests::test_a", Some(500)), ("cache_tests::test_b", Some(502))],
+            "Only Engine/Control 500/502 outside chaos modules are forbidden"
+        );
+
+        let summary = summarize(&errors);
+        assert!(summary.contains("auth_jwt_tests::test_a:\n  500 engine POST"), "{}", summary);
+        assert!(summary.contains("cache_tests::test_b:\n  502 control POST"), "{}", summary);
+    }
+}
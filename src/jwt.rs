@@ -0,0 +1,193 @@
+// JWT Builder
+//
+// Mints client JWTs with arbitrary claim values for tests that probe how Engine validates
+// individual claims. `TestFixture::generate_jwt` remains the shortcut for well-formed tokens.
+
+use serde_json::{Map, Value};
+
+use super::*;
+
+/// Scopes granted when a builder is not given any
+const DEFAULT_SCOPES: &str = "inferadb.check inferadb.read inferadb.expand inferadb.list inferadb.list-relationships inferadb.list-subjects inferadb.list-resources";
+
+/// Swap `https://` for `http://` (or vice versa) in a URL-valued claim
+pub fn swap_scheme(url: &str) -> String {
+    if let Some(rest) = url.strip_prefix("https://") {
+        format!("http://{}", rest)
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("https://{}", rest)
+    } else {
+        format!("https://{}", url)
+    }
+}
+
+/// Uppercase the host of a URL-valued claim, leaving scheme, port, and path untouched
+pub fn uppercase_host(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").map_or(("", url), |(s, r)| (s, r));
+    let host_end = rest.find([':', '/']).unwrap_or(rest.len());
+    let (host, tail) = rest.split_at(host_end);
+    let host = host.to_uppercase();
+
+    if scheme.is_empty() {
+        format!("{}{}", host, tail)
+    } else {
+        format!("{}://{}{}", scheme, host, tail)
+    }
+}
+
+/// Builder for client JWTs, starting from the claims `TestFixture::generate_jwt` produces
+#[derive(Clone)]
+pub struct JwtBuilder {
+    kid: Option<String>,
+    signing_key: SigningKey,
+    claims: Map<String, Value>,
+    /// Refuse to encode a token carrying a mutating scope (see `SharedFixture::jwt`)
+    read_only: bool,
+}
+
+impl JwtBuilder {
+    /// Start from a valid token for the fixture's client and default vault
+    pub fn new(fixture: &TestFixture) -> Self {
+        let now = Utc::now();
+        let Value::Object(claims) = serde_json::json!({
+            "iss": fixture.ctx.api_base_url,
+            "sub": format!("client:{}", fixture.client_id),
+            "aud": EnvProfile::current().audience,
+            "exp": (now + Duration::minutes(5)).timestamp(),
+            "iat": now.timestamp(),
+            "jti": Uuid::new_v4().to_string(),
+            "vault_id": fixture.vault_id.to_string(),
+            "org_id": fixture.org_id.to_string(),
+            "scope": DEFAULT_SCOPES,
+            "vault_role": "read",
+        }) else {
+            unreachable!("json! object literal is always an object")
+        };
+
+        Self {
+            kid: Some(fixture.cert_kid.clone()),
+            signing_key: fixture.signing_key.clone(),
+            claims,
+            read_only: false,
+        }
+    }
+
+    /// Fail `encode` if the token would carry a scope that mutates the vault
+    pub(crate) fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// A token that expired 10 minutes ago, well outside any clock leeway
+    pub fn expired(self) -> Self {
+        let now = Utc::now();
+        self.claim("iat", (now - Duration::minutes(15)).timestamp())
+            .claim("exp", (now - Duration::minutes(10)).timestamp())
+    }
+
+    /// A token whose `nbf` is 10 minutes in the future
+    pub fn not_yet_valid(self) -> Self {
+        let now = Utc::now();
+        self.claim("nbf", (now + Duration::minutes(10)).timestamp())
+            .claim("exp", (now + Duration::minutes(15)).timestamp())
+    }
+
+    /// A token that expires `seconds` from now (negative values are in the past)
+    pub fn expiring_in(self, seconds: i64) -> Self {
+        self.claim("exp", (Utc::now() + Duration::seconds(seconds)).timestamp())
+    }
+
+    /// Move every time claim (`iat`, `nbf`, `exp`) by `offset`, as if minted at another time
+    pub fn time_shift(mut self, offset: Duration) -> Self {
+        for name in ["iat", "nbf", "exp"] {
+            if let Some(timestamp) = self.claims.get(name).and_then(Value::as_i64) {
+                self.claims.insert(name.to_string(), (timestamp + offset.num_seconds()).into());
+            }
+        }
+        self
+    }
+
+    /// Set `scope` and the `vault_role` Control would derive from it
+    pub fn scopes(self, scopes: &[&str]) -> Self {
+        self.claim("scope", scopes.join(" ")).vault_role(vault_role_for_scopes(scopes))
+    }
+
+    /// Set `vault_role` independently of the scopes
+    pub fn vault_role(self, vault_role: &str) -> Self {
+        self.claim("vault_role", vault_role)
+    }
+
+    /// Claim several vaults at once: `vault_id` becomes an array of vault IDs
+    pub fn vaults(self, vault_ids: &[i64]) -> Self {
+        let vault_ids: Vec<Value> = vault_ids.iter().map(|id| id.to_string().into()).collect();
+        self.claim("vault_id", vault_ids)
+    }
+
+    /// Claim every vault in the token's organization: `vault_id` becomes `*`
+    pub fn all_vaults(self) -> Self {
+        self.claim("vault_id", "*")
+    }
+
+    /// Set `aud`; pass a string or an array of strings
+    pub fn audience(self, audience: impl Into<Value>) -> Self {
+        self.claim("aud", audience)
+    }
+
+    /// Set `iss`
+    pub fn issuer(self, issuer: impl Into<String>) -> Self {
+        self.claim("iss", issuer.into())
+    }
+
+    /// Set an arbitrary claim, replacing any existing value
+    pub fn claim(mut self, name: &str, value: impl Into<Value>) -> Self {
+        self.claims.insert(name.to_string(), value.into());
+        self
+    }
+
+    /// Remove a claim entirely
+    pub fn without_claim(mut self, name: &str) -> Self {
+        self.claims.remove(name);
+        self
+    }
+
+    /// Bind the token to a proof-of-possession key (`cnf.jkt`)
+    pub fn bound_to(self, key: &DpopKey) -> Self {
+        self.claim("cnf", serde_json::json!({ "jkt": key.thumbprint() }))
+    }
+
+    /// Sign with another certificate's key, keeping the claims
+    pub fn signed_by(mut self, kid: impl Into<String>, signing_key: &SigningKey) -> Self {
+        self.kid = Some(kid.into());
+        self.signing_key = signing_key.clone();
+        self
+    }
+
+    /// Name another certificate in the header (`None` leaves `kid` out), keeping the signing key
+    pub fn kid(mut self, kid: Option<String>) -> Self {
+        self.kid = kid;
+        self
+    }
+
+    /// Sign the token with EdDSA
+    pub fn encode(&self) -> Result<String> {
+        if self.read_only {
+            let scopes = self.claims.get("scope").and_then(Value::as_str).unwrap_or_default();
+            if let Some(scope) = scopes
+                .split_whitespace()
+                .find(|scope| shared_fixture::MUTATING_SCOPES.contains(scope))
+            {
+                anyhow::bail!(
+                    "Token is read-only: scope '{}' requires a private TestFixture",
+                    scope
+                );
+            }
+        }
+
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = self.kid.clone();
+
+        let encoding_key = keys::encoding_key(&self.signing_key)?;
+
+        encode(&header, &self.claims, &encoding_key).context("Failed to encode JWT")
+    }
+}
@@ -0,0 +1,288 @@
+// JWT Golden Corpus
+//
+// Signed token vectors for every failure class Engine's token validator must catch, each stored
+// with the verdict Engine must return. Tokens are minted from fixed Ed25519 keys, fixed JTIs, and
+// a given clock, so the same identity and time always produce byte-identical tokens: the corpus in
+// `integration/golden/jwt_corpus.json` is generated for a canonical identity and checked offline,
+// and the live replay re-signs the same vectors for a deployment's identity once the corpus key is
+// registered as a certificate.
+
+use std::fmt;
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use serde_json::{Map, Value};
+
+use super::*;
+
+/// Seed of the key that signs corpus tokens
+pub const CORPUS_KEY_SEED: [u8; 32] = *b"inferadb-golden-jwt-corpus-key-1";
+
+/// Seed of a key Engine never trusts, for signature failures
+pub const FOREIGN_KEY_SEED: [u8; 32] = *b"inferadb-golden-jwt-foreign-key1";
+
+/// Clock the committed corpus is generated at (2024-01-01T00:00:00Z)
+pub const GOLDEN_ISSUED_AT: i64 = 1_704_067_200;
+
+/// Every vector in corpus order, with the verdict Engine must return
+pub const VECTORS: &[(&str, Verdict)] = &[
+    ("valid", Verdict::Accepted),
+    ("expired", Verdict::Unauthorized),
+    ("not_yet_valid", Verdict::Unauthorized),
+    ("wrong_audience", Verdict::Unauthorized),
+    ("wrong_issuer", Verdict::Unauthorized),
+    ("missing_exp", Verdict::Unauthorized),
+    ("missing_sub", Verdict::Unauthorized),
+    ("missing_vault_id", Verdict::Unauthorized),
+    ("foreign_key", Verdict::Unauthorized),
+    ("unknown_kid", Verdict::Unauthorized),
+    ("alg_none", Verdict::Unauthorized),
+    ("hs256_with_public_key", Verdict::Unauthorized),
+    ("tampered_payload", Verdict::Unauthorized),
+    ("truncated_signature", Verdict::Unauthorized),
+    ("malformed", Verdict::Unauthorized),
+];
+
+/// What Engine must answer a vector with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// Authenticated: 200, or 404 when nothing grants the checked permission
+    Accepted,
+    Unauthorized,
+}
+
+impl Verdict {
+    /// Whether `status` is this verdict
+    pub fn matches(self, status: reqwest::StatusCode) -> bool {
+        match self {
+            Self::Accepted => {
+                status == reqwest::StatusCode::OK || status == reqwest::StatusCode::NOT_FOUND
+            },
+            Self::Unauthorized => status == reqwest::StatusCode::UNAUTHORIZED,
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Accepted => write!(f, "accepted"),
+            Self::Unauthorized => write!(f, "unauthorized"),
+        }
+    }
+}
+
+/// Who corpus tokens are issued to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CorpusIdentity {
+    pub issuer: String,
+    pub audience: String,
+    /// Certificate ID of the registered corpus key
+    pub kid: String,
+    pub org_id: i64,
+    pub vault_id: i64,
+    pub client_id: i64,
+}
+
+impl CorpusIdentity {
+    /// The identity the committed corpus is generated for
+    pub fn golden() -> Self {
+        Self {
+            issuer: "https://golden.inferadb.test".to_string(),
+            audience: REQUIRED_AUDIENCE.to_string(),
+            kid: "golden-corpus-kid".to_string(),
+            org_id: 1001,
+            vault_id: 2002,
+            client_id: 3003,
+        }
+    }
+
+    /// The fixture's client and vault, signing as the certificate `kid`
+    pub fn for_fixture(fixture: &TestFixture, kid: impl Into<String>) -> Self {
+        Self {
+            issuer: fixture.ctx.api_base_url.clone(),
+            audience: EnvProfile::current().audience.clone(),
+            kid: kid.into(),
+            org_id: fixture.org_id,
+            vault_id: fixture.vault_id,
+            client_id: fixture.client_id,
+        }
+    }
+}
+
+/// One signed token and the verdict it must get
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GoldenVector {
+    pub name: String,
+    pub expected: Verdict,
+    pub token: String,
+}
+
+/// Every vector, generated for one identity at one time
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JwtCorpus {
+    pub identity: CorpusIdentity,
+    pub issued_at: i64,
+    pub vectors: Vec<GoldenVector>,
+}
+
+impl JwtCorpus {
+    /// Mint every vector for `identity` with `issued_at` as the current time
+    pub fn generate(identity: &CorpusIdentity, issued_at: i64) -> Result<Self> {
+        let vectors = VECTORS
+            .iter()
+            .map(|&(name, expected)| {
+                let token = mint(name, identity, issued_at)
+                    .with_context(|| format!("Failed to mint vector '{}'", name))?;
+                Ok(GoldenVector { name: name.to_string(), expected, token })
+            })
+            .collect::<Result<_>>()?;
+        Ok(Self { identity: identity.clone(), issued_at, vectors })
+    }
+
+    /// Parse a corpus file
+    pub fn parse(json: &str) -> Result<Self> {
+        serde_json::from_str(json).context("Failed to parse JWT corpus")
+    }
+
+    /// Expected verdict of the vector `name`
+    pub fn expected(&self, name: &str) -> Option<Verdict> {
+        self.vectors.iter().find(|vector| vector.name == name).map(|vector| vector.expected)
+    }
+}
+
+/// Key that signs corpus tokens
+pub fn corpus_signing_key() -> SigningKey {
+    SigningKey::from_bytes(&CORPUS_KEY_SEED)
+}
+
+/// The corpus public key as Control's certificate endpoints encode keys (base64 of the raw bytes)
+pub fn corpus_public_key() -> String {
+    keys::public_key_base64(&corpus_signing_key().verifying_key())
+}
+
+fn claims(identity: &CorpusIdentity, issued_at: i64, name: &str) -> Map<String, Value> {
+    let Value::Object(claims) = serde_json::json!({
+        "iss": identity.issuer,
+        "sub": format!("client:{}", identity.client_id),
+        "aud": identity.audience,
+        "exp": issued_at + 300,
+        "iat": issued_at,
+        "jti": format!("golden-{}", name),
+        "vault_id": identity.vault_id.to_string(),
+        "org_id": identity.org_id.to_string(),
+        "scope": "inferadb.check inferadb.read",
+        "vault_role": "read",
+    }) else {
+        unreachable!("json! object literal is always an object")
+    };
+    claims
+}
+
+fn sign(kid: &str, key: &SigningKey, claims: &Map<String, Value>) -> Result<String> {
+    let mut header = Header::new(Algorithm::EdDSA);
+    header.kid = Some(kid.to_string());
+    encode(&header, claims, &keys::encoding_key(key)?).context("Failed to encode JWT")
+}
+
+fn segment(value: &Value) -> String {
+    URL_SAFE_NO_PAD.encode(value.to_string())
+}
+
+fn mint(name: &str, identity: &CorpusIdentity, issued_at: i64) -> Result<String> {
+    let key = corpus_signing_key();
+    let mut claims = claims(identity, issued_at, name);
+
+    match name {
+        "valid" => {},
+        "expired" => {
+            claims.insert("iat".into(), (issued_at - 900).into());
+            claims.insert("exp".into(), (issued_at - 600).into());
+        },
+        "not_yet_valid" => {
+            claims.insert("nbf".into(), (issued_at + 600).into());
+            claims.insert("exp".into(), (issued_at + 900).into());
+        },
+        "wrong_audience" => {
+            claims.insert("aud".into(), "https://audience.invalid".into());
+        },
+        "wrong_issuer" => {
+            claims.insert("iss".into(), "https://issuer.invalid".into());
+        },
+        "missing_exp" | "missing_sub" | "missing_vault_id" => {
+            claims.remove(name.trim_start_matches("missing_"));
+        },
+        "foreign_key" => {
+            return sign(&identity.kid, &SigningKey::from_bytes(&FOREIGN_KEY_SEED), &claims);
+        },
+        "unknown_kid" => return sign("golden-unknown-kid", &key, &claims),
+        "alg_none" => {
+            let header = serde_json::json!({ "alg": "none", "typ": "JWT", "kid": identity.kid });
+            return Ok(format!("{}.{}.", segment(&header), segment(&Value::Object(claims))));
+        },
+        "hs256_with_public_key" => {
+            let mut header = Header::new(Algorithm::HS256);
+            header.kid = Some(identity.kid.clone());
+            let secret = EncodingKey::from_secret(key.verifying_key().as_bytes());
+            return encode(&header, &claims, &secret).context("Failed to encode JWT");
+        },
+        "tampered_payload" => {
+            // A genuine signature over read scopes, presented with an admin payload
+            let signed = sign(&identity.kid, &key, &claims)?;
+            claims.insert(
+                "scope".into(),
+                "inferadb.check inferadb.read inferadb.write inferadb.admin".into(),
+            );
+            claims.insert("vault_role".into(), "admin".into());
+            let (header, rest) = signed.split_once('.').context("JWT without segments")?;
+            let (_, signature) = rest.split_once('.').context("JWT without signature")?;
+            return Ok(format!("{}.{}.{}", header, segment(&Value::Object(claims)), signature));
+        },
+        "truncated_signature" => {
+            let signed = sign(&identity.kid, &key, &claims)?;
+            return Ok(signed[..signed.len() - 8].to_string());
+        },
+        "malformed" => {
+            let signed = sign(&identity.kid, &key, &claims)?;
+            return Ok(signed.replacen('.', "", 1));
+        },
+        _ => anyhow::bail!("Unknown vector '{}'", name),
+    }
+    sign(&identity.kid, &key, &claims)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_jwt_corpus_matches_golden_file() {
+        let corpus = JwtCorpus::generate(&CorpusIdentity::golden(), GOLDEN_ISSUED_AT)
+            .expect("Failed to generate corpus");
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("integration/golden/jwt_corpus.json");
+        if std::env::var("INFERADB_UPDATE_GOLDEN").is_ok() {
+            let json = serde_json::to_string_pretty(&corpus).expect("Failed to serialize corpus");
+            std::fs::write(&path, json + "\n").expect("Failed to write golden corpus");
+        }
+
+        // Read from disk rather than `include_str!`, so a run that just rewrote the file compares
+        // against the new contents instead of the copy compiled into the test binary.
+        let stored = std::fs::read_to_string(&path).expect("Failed to read golden corpus");
+        let golden = JwtCorpus::parse(&stored).expect("Failed to parse golden corpus");
+        assert_eq!(golden.vectors.len(), VECTORS.len(), "Every vector class must be in the file");
+        for (generated, stored) in corpus.vectors.iter().zip(&golden.vectors) {
+            assert_eq!(
+                generated, stored,
+                "Vector '{}' changed; rerun with INFERADB_UPDATE_GOLDEN=1 if intended",
+                stored.name
+            );
+        }
+        assert_eq!(corpus, golden);
+
+        let mut tokens: Vec<_> = corpus.vectors.iter().map(|vector| &vector.token).collect();
+        tokens.sort();
+        tokens.dedup();
+        assert_eq!(tokens.len(), VECTORS.len(), "Every vector must be a distinct token");
+    }
+}
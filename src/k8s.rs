@@ -0,0 +1,280 @@
+// Kubernetes Operations
+//
+// Deletes Engine pods, scales and restarts the Engine deployment, and cordons nodes through the
+// Kubernetes API, for multi-replica availability and invalidation tests the shell-hook chaos layer
+// (`chaos`) cannot express. Enabled with the `k8s` feature:
+//   cargo test --features integration-tests,k8s -- --test-threads=1
+//
+// The cluster comes from the current kubeconfig context or the in-cluster service account. The
+// Engine deployment is looked up by:
+//   INFERADB_K8S_NAMESPACE          Namespace InferaDB runs in (default: inferadb)
+//   INFERADB_K8S_ENGINE_DEPLOYMENT  Engine deployment name (default: inferadb-engine)
+//   INFERADB_K8S_ENGINE_PORT        Engine HTTP port, for per-pod requests (default: 8080)
+//
+// Per-pod requests go through the API server's pod proxy, so they work wherever the kubeconfig
+// does, without reaching pod IPs directly. Requests carrying a JWT are the exception: the API
+// server keeps the `Authorization` header for itself, so they go to the pod IP
+// (`engine_pod_url`) and need a runner inside the cluster network.
+//
+// Tests that need the cluster skip when `K8sCluster::connect` finds no cluster or no deployment.
+
+use std::{collections::BTreeMap, time::Instant};
+
+use k8s_openapi::api::{
+    apps::v1::Deployment,
+    core::v1::{Node, Pod},
+};
+use kube::{
+    Api, Client,
+    api::{DeleteParams, ListParams, Patch, PatchParams},
+};
+
+use super::*;
+
+/// How often readiness is polled while waiting for a rollout
+const ROLLOUT_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Engine endpoint summarizing its effective configuration, where Engine serves one
+const CONFIG_FINGERPRINT_PATH: &str = "/config/fingerprint";
+
+/// An Engine pod as the tests see it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnginePod {
+    pub name: String,
+    pub node: Option<String>,
+    pub ip: Option<String>,
+    pub ready: bool,
+    /// Set once the pod has been asked to terminate
+    pub terminating: bool,
+}
+
+impl From<Pod> for EnginePod {
+    fn from(pod: Pod) -> Self {
+        let status = pod.status.unwrap_or_default();
+        let ready = status
+            .conditions
+            .unwrap_or_default()
+            .iter()
+            .any(|condition| condition.type_ == "Ready" && condition.status == "True");
+        Self {
+            name: pod.metadata.name.unwrap_or_default(),
+            node: pod.spec.and_then(|spec| spec.node_name),
+            ip: status.pod_ip,
+            ready,
+            terminating: pod.metadata.deletion_timestamp.is_some(),
+        }
+    }
+}
+
+/// The Engine deployment of one cluster
+pub struct K8sCluster {
+    client: Client,
+    namespace: String,
+    engine_deployment: String,
+    engine_port: u16,
+}
+
+impl K8sCluster {
+    /// Connect with the ambient kubeconfig, or `None` if there is no cluster or Engine deployment
+    pub async fn connect() -> Option<Self> {
+        let client = match Client::try_default().await {
+            Ok(client) => client,
+            Err(e) => {
+                eprintln!("No Kubernetes cluster available: {}", e);
+                return None;
+            },
+        };
+        let cluster = Self {
+            client,
+            namespace: std::env::var("INFERADB_K8S_NAMESPACE")
+                .unwrap_or_else(|_| "inferadb".to_string()),
+            engine_deployment: std::env::var("INFERADB_K8S_ENGINE_DEPLOYMENT")
+                .unwrap_or_else(|_| "inferadb-engine".to_string()),
+            engine_port: std::env::var("INFERADB_K8S_ENGINE_PORT")
+                .ok()
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(8080),
+        };
+        match cluster.deployments().get_opt(&cluster.engine_deployment).await {
+            Ok(Some(_)) => Some(cluster),
+            Ok(None) => {
+                eprintln!(
+                    "Deployment {}/{} not found",
+                    cluster.namespace, cluster.engine_deployment
+                );
+                None
+            },
+            Err(e) => {
+                eprintln!("Kubernetes API unavailable: {}", e);
+                None
+            },
+        }
+    }
+
+    fn deployments(&self) -> Api<Deployment> {
+        Api::namespaced(self.client.clone(), &self.namespace)
+    }
+
+    fn pods(&self) -> Api<Pod> {
+        Api::namespaced(self.client.clone(), &self.namespace)
+    }
+
+    fn nodes(&self) -> Api<Node> {
+        Api::all(self.client.clone())
+    }
+
+    async fn engine(&self) -> Result<Deployment> {
+        self.deployments().get(&self.engine_deployment).await.with_context(|| {
+            format!("Failed to read deployment {}/{}", self.namespace, self.engine_deployment)
+        })
+    }
+
+    /// Label selector matching the Engine deployment's pods
+    async fn engine_selector(&self) -> Result<String> {
+        let labels = self
+            .engine()
+            .await?
+            .spec
+            .and_then(|spec| spec.selector.match_labels)
+            .context("Engine deployment has no matchLabels selector")?;
+        Ok(labels
+            .iter()
+            .map(|(key, value)| format!("{}={}", key, value))
+            .collect::<Vec<_>>()
+            .join(","))
+    }
+
+    /// Every Engine pod, including ones still starting or terminating
+    pub async fn engine_pods(&self) -> Result<Vec<EnginePod>> {
+        let selector = self.engine_selector().await?;
+        let pods = self
+            .pods()
+            .list(&ListParams::default().labels(&selector))
+            .await
+            .context("Failed to list Engine pods")?;
+        Ok(pods.items.into_iter().map(EnginePod::from).collect())
+    }
+
+    /// Replicas the Engine deployment asks for
+    pub async fn engine_replicas(&self) -> Result<i32> {
+        Ok(self.engine().await?.spec.and_then(|spec| spec.replicas).unwrap_or(1))
+    }
+
+    /// Delete an Engine pod without a grace period, as a crash would
+    pub async fn kill_pod(&self, name: &str) -> Result<()> {
+        let params = DeleteParams { grace_period_seconds: Some(0), ..DeleteParams::default() };
+        self.pods()
+            .delete(name, &params)
+            .await
+            .with_context(|| format!("Failed to delete pod {}", name))?;
+        Ok(())
+    }
+
+    /// Kill one ready Engine pod, returning its name
+    pub async fn kill_engine_pod(&self) -> Result<String> {
+        let pod = self
+            .engine_pods()
+            .await?
+            .into_iter()
+            .find(|pod| pod.ready && !pod.terminating)
+            .context("No ready Engine pod to kill")?;
+        self.kill_pod(&pod.name).await?;
+        Ok(pod.name)
+    }
+
+    /// Set the Engine deployment's replica count, returning the previous one
+    pub async fn scale_engine(&self, replicas: i32) -> Result<i32> {
+        let previous = self.engine_replicas().await?;
+        let patch = serde_json::json!({ "spec": { "replicas": replicas } });
+        self.deployments()
+            .patch_scale(&self.engine_deployment, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| format!("Failed to scale Engine to {} replicas", replicas))?;
+        Ok(previous)
+    }
+
+    /// Start a rolling restart of the Engine deployment, as `kubectl rollout restart` does
+    pub async fn rolling_restart_engine(&self) -> Result<()> {
+        self.deployments()
+            .restart(&self.engine_deployment)
+            .await
+            .context("Failed to restart Engine deployment")?;
+        Ok(())
+    }
+
+    /// Wait until every Engine replica is updated, ready, and the only pods left
+    pub async fn wait_for_engine_rollout(&self, timeout: std::time::Duration) -> Result<()> {
+        let start = Instant::now();
+        loop {
+            let deployment = self.engine().await?;
+            let wanted = deployment.spec.as_ref().and_then(|spec| spec.replicas).unwrap_or(1);
+            let generation = deployment.metadata.generation;
+            let status = deployment.status.unwrap_or_default();
+            if status.observed_generation >= generation
+                && status.updated_replicas.unwrap_or(0) == wanted
+                && status.ready_replicas.unwrap_or(0) == wanted
+                && status.replicas.unwrap_or(0) == wanted
+            {
+                return Ok(());
+            }
+            if start.elapsed() > timeout {
+                anyhow::bail!(
+                    "Engine rollout incomplete after {:?}: {} wanted, {} updated, {} ready, {} total",
+                    timeout,
+                    wanted,
+                    status.updated_replicas.unwrap_or(0),
+                    status.ready_replicas.unwrap_or(0),
+                    status.replicas.unwrap_or(0)
+                );
+            }
+            tokio::time::sleep(ROLLOUT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// GET `path` from one Engine pod through the API server's pod proxy
+    pub async fn engine_pod_get(&self, pod: &str, path: &str) -> Result<String> {
+        let uri = format!(
+            "/api/v1/namespaces/{}/pods/{}:{}/proxy{}",
+            self.namespace, pod, self.engine_port, path
+        );
+        let request = http::Request::get(&uri).body(Vec::new())?;
+        self.client
+            .request_text(request)
+            .await
+            .with_context(|| format!("Failed to GET {} from pod {}", path, pod))
+    }
+
+    /// URL of `path` on one Engine pod's IP, or `None` before the pod has one
+    pub fn engine_pod_url(&self, pod: &EnginePod, path: &str) -> Option<String> {
+        pod.ip.as_ref().map(|ip| format!("http://{}:{}{}", ip, self.engine_port, path))
+    }
+
+    /// Build and configuration one Engine pod reports: its config fingerprint endpoint if it has
+    /// one, otherwise `MetricsSnapshot::fingerprint` of its `/metrics`
+    pub async fn engine_pod_fingerprint(&self, pod: &str) -> Result<BTreeMap<String, String>> {
+        if let Ok(body) = self.engine_pod_get(pod, CONFIG_FINGERPRINT_PATH).await
+            && !body.trim().is_empty()
+        {
+            return Ok(BTreeMap::from([(
+                CONFIG_FINGERPRINT_PATH.to_string(),
+                body.trim().to_string(),
+            )]));
+        }
+        let metrics = self.engine_pod_get(pod, "/metrics").await?;
+        Ok(MetricsSnapshot::parse(&metrics).fingerprint())
+    }
+
+    /// Mark a node unschedulable so replacement pods land elsewhere
+    pub async fn cordon(&self, node: &str) -> Result<()> {
+        self.nodes().cordon(node).await.with_context(|| format!("Failed to cordon {}", node))?;
+        Ok(())
+    }
+
+    pub async fn uncordon(&self, node: &str) -> Result<()> {
+        self.nodes()
+            .uncordon(node)
+            .await
+            .with_context(|| format!("Failed to uncordon {}", node))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,138 @@
+// Leak Scanner
+//
+// Tests that check a tenant's data never reaches another tenant plant canaries: unique values
+// shaped like secrets (an API key, a password, a cloud access key) written into whatever the test
+// wants to track, such as vault names or descriptions. `LeakScanner` remembers each canary and
+// reports every response text that contains one. A canary is matched by its random part alone and
+// case-insensitively, so a server that strips a prefix or changes case while echoing it is still
+// caught; base64 copies of the whole canary are matched too.
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+
+use super::*;
+
+/// Characters of context kept on either side of a match
+const EXCERPT_CONTEXT: usize = 40;
+
+/// A planted value and what it was planted in
+#[derive(Debug, Clone)]
+pub struct Canary {
+    pub label: String,
+    pub value: String,
+    /// The random part, lowercase; unique to this canary
+    token: String,
+}
+
+/// A canary found where it should not be
+#[derive(Debug, Clone, PartialEq)]
+pub struct Leak {
+    /// What the canary was planted in
+    pub label: String,
+    /// Where it turned up, as the scanning test described it
+    pub location: String,
+    /// The text around the match
+    pub excerpt: String,
+}
+
+/// The canaries of one test, and the scans of the responses it collects
+#[derive(Debug, Default)]
+pub struct LeakScanner {
+    canaries: Vec<Canary>,
+}
+
+impl LeakScanner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A fresh canary starting with `prefix` (e.g. `sk_live_`), recorded as planted in `label`
+    pub fn plant(&mut self, label: impl Into<String>, prefix: &str) -> String {
+        let token = Uuid::new_v4().simple().to_string();
+        let value = format!("{}{}", prefix, token);
+        self.canaries.push(Canary { label: label.into(), value: value.clone(), token });
+        value
+    }
+
+    pub fn canaries(&self) -> &[Canary] {
+        &self.canaries
+    }
+
+    /// Every canary in `text`, which was read from `location`
+    pub fn scan(&self, location: &str, text: &str) -> Vec<Leak> {
+        self.canaries
+            .iter()
+            .filter_map(|canary| {
+                let (at, len) = find_ignoring_case(text, &canary.token)
+                    .map(|at| (at, canary.token.len()))
+                    .or_else(|| {
+                        [STANDARD.encode(&canary.value), URL_SAFE_NO_PAD.encode(&canary.value)]
+                            .iter()
+                            .map(|encoded| encoded.trim_end_matches('='))
+                            .find_map(|encoded| Some((text.find(encoded)?, encoded.len())))
+                    })?;
+                Some(Leak {
+                    label: canary.label.clone(),
+                    location: location.to_string(),
+                    excerpt: excerpt(text, at, at + len),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Byte offset in `text` of ASCII `needle`, ignoring ASCII case
+///
+/// Searching `text` itself keeps the offset valid for it, which a search of `text.to_lowercase()`
+/// does not: lowercasing can change the byte length of characters before the match.
+fn find_ignoring_case(text: &str, needle: &str) -> Option<usize> {
+    text.as_bytes()
+        .windows(needle.len())
+        .position(|window| window.eq_ignore_ascii_case(needle.as_bytes()))
+}
+
+/// The match at bytes `start..end` of `text`, with up to `EXCERPT_CONTEXT` characters either side
+fn excerpt(text: &str, start: usize, end: usize) -> String {
+    let from = text[..start].char_indices().rev().nth(EXCERPT_CONTEXT - 1).map_or(0, |(i, _)| i);
+    let to = text[end..].char_indices().nth(EXCERPT_CONTEXT).map_or(text.len(), |(i, _)| end + i);
+    text[from..to].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_leak_scanner_finds_canaries() {
+        use base64::engine::general_purpose::STANDARD;
+
+        let mut scanner = LeakScanner::new();
+        let key = scanner.plant("vault name", "sk_live_");
+        let password = scanner.plant("vault description", "hunter2-");
+        assert!(key.starts_with("sk_live_") && key.len() == "sk_live_".len() + 32);
+        assert_eq!(scanner.canaries().len(), 2);
+
+        assert!(scanner.scan("clean", r#"{"error": "forbidden"}"#).is_empty());
+        let leaks =
+            scanner.scan("echo", &format!(r#"{{"error": "no access to vault '{}'"}}"#, key));
+        assert_eq!(leaks.len(), 1);
+        assert_eq!((leaks[0].label.as_str(), leaks[0].location.as_str()), ("vault name", "echo"));
+        assert!(leaks[0].excerpt.contains(&key), "Excerpt shows the match: {}", leaks[0].excerpt);
+
+        let padded = format!("{}{}{}", "İ".repeat(60), key.to_uppercase(), "é".repeat(60));
+        let excerpt = &scanner.scan("padded", &padded)[0].excerpt;
+        assert_eq!(
+            excerpt,
+            // 40 characters before the random part: 32 padding plus the 8 of the prefix
+            &format!("{}{}{}", "İ".repeat(32), key.to_uppercase(), "é".repeat(40)),
+            "Excerpts keep 40 characters of context, even after characters that lowercase longer"
+        );
+
+        let stripped = password.trim_start_matches("hunter2-").to_uppercase();
+        assert_eq!(scanner.scan("rewritten", &stripped).len(), 1, "Prefix and case do not hide it");
+        assert_eq!(
+            scanner.scan("encoded", &STANDARD.encode(&password)).len(),
+            1,
+            "Nor does base64"
+        );
+    }
+}
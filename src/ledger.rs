@@ -0,0 +1,206 @@
+// Ledger Block Stream
+//
+// Control and Engine persist through Ledger, which publishes every committed block on its
+// WatchBlocks stream. The suite has no generated Ledger stubs, so `LedgerClient` opens the stream
+// as a raw server-streaming gRPC call (an empty request: every block from now on) and hands back
+// each message undecoded, stamped with when it arrived. Tests match a block to the change that
+// produced it by a unique marker the change writes: protobuf carries strings verbatim, so the
+// marker shows up in the block's bytes. That holds only for uncompressed messages, so the stream
+// asks for identity encoding and fails on any compressed message rather than never matching it.
+//
+// Ledger is reached at INFERADB_LEDGER_URL; tests that need it skip when it is unset. The service
+// name is INFERADB_LEDGER_SERVICE (default: `inferadb.ledger.v1.LedgerService`).
+
+use std::time::Instant;
+
+use reqwest::{Client, StatusCode};
+use tokio::sync::mpsc;
+
+use super::*;
+
+/// Client for Ledger's gRPC API
+pub struct LedgerClient {
+    client: Client,
+    base_url: String,
+    service: String,
+}
+
+/// One message from the WatchBlocks stream
+#[derive(Debug, Clone)]
+pub struct Block {
+    /// When the runner read the block off the stream
+    pub received_at: Instant,
+    /// The encoded message, without its gRPC length prefix
+    pub bytes: Vec<u8>,
+}
+
+/// An open WatchBlocks stream; closed when dropped
+pub struct BlockStream {
+    blocks: mpsc::UnboundedReceiver<Result<Block>>,
+    reader: tokio::task::JoinHandle<()>,
+}
+
+impl LedgerClient {
+    /// Ledger's gRPC API, or `None` (after logging) when INFERADB_LEDGER_URL is unset
+    pub fn connect() -> Option<Self> {
+        let Some(base_url) = std::env::var("INFERADB_LEDGER_URL")
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+        else {
+            outcome::skip("Skipping Ledger test - INFERADB_LEDGER_URL not set");
+            return None;
+        };
+        Some(Self {
+            // No overall timeout: the stream stays open for the whole test
+            client: Client::builder()
+                .http2_prior_knowledge()
+                .connect_timeout(std::time::Duration::from_secs(10))
+                .danger_accept_invalid_certs(true) // For dev self-signed certs
+                .build()
+                .expect("Failed to create gRPC client"),
+            base_url,
+            service: std::env::var("INFERADB_LEDGER_SERVICE")
+                .unwrap_or_else(|_| "inferadb.ledger.v1.LedgerService".to_string()),
+        })
+    }
+
+    /// Open WatchBlocks; blocks committed from now on arrive on the returned stream
+    pub async fn watch_blocks(&self) -> Result<BlockStream> {
+        let url = format!("{}/{}/WatchBlocks", self.base_url, self.service);
+        let mut response = self
+            .client
+            .post(&url)
+            .header("content-type", "application/grpc")
+            .header("te", "trailers")
+            .header("grpc-accept-encoding", "identity")
+            // Length-prefixed empty request message
+            .body(vec![0u8; 5])
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach Ledger at {}", url))?;
+
+        let is_grpc = response
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|v| v.starts_with("application/grpc"));
+        anyhow::ensure!(
+            is_grpc && response.status() == StatusCode::OK,
+            "{} did not answer with gRPC (status {})",
+            url,
+            response.status()
+        );
+        // An immediate failure arrives as a trailers-only response, with the status in headers
+        if let Some(status) = response.headers().get("grpc-status").and_then(|v| v.to_str().ok())
+            && status != "0"
+        {
+            anyhow::bail!("WatchBlocks failed with grpc-status {}", status);
+        }
+
+        let (sender, blocks) = mpsc::unbounded_channel();
+        let reader = tokio::spawn(async move {
+            let mut buffer = Vec::new();
+            while let Ok(Some(chunk)) = response.chunk().await {
+                let received_at = Instant::now();
+                buffer.extend_from_slice(&chunk);
+                let frames = match split_frames(&mut buffer) {
+                    Ok(frames) => frames,
+                    Err(e) => {
+                        let _ = sender.send(Err(e));
+                        return;
+                    },
+                };
+                for bytes in frames {
+                    if sender.send(Ok(Block { received_at, bytes })).is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok(BlockStream { blocks, reader })
+    }
+}
+
+impl Block {
+    /// Whether `marker` appears anywhere in the block
+    pub fn contains(&self, marker: &str) -> bool {
+        let marker = marker.as_bytes();
+        !marker.is_empty() && self.bytes.windows(marker.len()).any(|window| window == marker)
+    }
+}
+
+impl BlockStream {
+    /// The next block containing `marker`, discarding blocks before it; `None` if none arrives
+    /// within `timeout` or the stream ends, an error if the stream sent a message it cannot read
+    pub async fn next_containing(
+        &mut self,
+        marker: &str,
+        timeout: std::time::Duration,
+    ) -> Result<Option<Block>> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            match tokio::time::timeout_at(deadline, self.blocks.recv()).await {
+                Ok(Some(Ok(block))) if block.contains(marker) => return Ok(Some(block)),
+                Ok(Some(Ok(_))) => continue,
+                Ok(Some(Err(e))) => return Err(e),
+                Ok(None) | Err(_) => return Ok(None),
+            }
+        }
+    }
+}
+
+impl Drop for BlockStream {
+    fn drop(&mut self) {
+        self.reader.abort();
+    }
+}
+
+/// Remove every complete length-prefixed gRPC message from the front of `buffer`
+///
+/// Each message is a compression flag byte and a big-endian `u32` length, then the message itself;
+/// a trailing partial message stays in the buffer until the rest arrives. A compressed message
+/// (flag other than 0) is an error: its bytes would never contain a marker.
+pub fn split_frames(buffer: &mut Vec<u8>) -> Result<Vec<Vec<u8>>> {
+    let mut frames = Vec::new();
+    let mut offset = 0;
+    while let Some(header) = buffer.get(offset..offset + 5) {
+        anyhow::ensure!(
+            header[0] == 0,
+            "Ledger sent a compressed gRPC message (flag {}) despite identity encoding",
+            header[0]
+        );
+        let len = u32::from_be_bytes([header[1], header[2], header[3], header[4]]) as usize;
+        let Some(frame) = buffer.get(offset + 5..offset + 5 + len) else { break };
+        frames.push(frame.to_vec());
+        offset += 5 + len;
+    }
+    buffer.drain(..offset);
+    Ok(frames)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grpc_frames_split_across_chunks() {
+        let mut buffer = vec![0, 0, 0, 0, 3, b'a', b'b', b'c', 0, 0, 0, 0, 0, 0, 0, 0, 0, 4, b'x'];
+        let frames = split_frames(&mut buffer).expect("Uncompressed frames");
+        assert_eq!(frames, [b"abc".to_vec(), Vec::new()], "Complete frames, empty ones included");
+        assert_eq!(buffer, [0, 0, 0, 0, 4, b'x'], "A partial frame waits for the rest");
+
+        buffer.extend_from_slice(b"yz!");
+        assert_eq!(split_frames(&mut buffer).expect("Uncompressed frame"), [b"xyz!".to_vec()]);
+        assert!(buffer.is_empty());
+
+        let mut compressed = vec![1, 0, 0, 0, 2, 0x1f, 0x8b];
+        assert!(split_frames(&mut compressed).is_err(), "Compressed frames are rejected");
+
+        let block =
+            Block { received_at: std::time::Instant::now(), bytes: b"\x12\x07marker1".to_vec() };
+        assert!(block.contains("marker1"));
+        assert!(!block.contains("marker2"));
+        assert!(!block.contains(""), "An empty marker matches nothing");
+    }
+}
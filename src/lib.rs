@@ -0,0 +1,1619 @@
+#![deny(unsafe_code)]
+
+// Test harness for InferaDB engine-control integration testing
+//
+// Fixtures, typed Control and Engine clients, and JWT helpers shared by the integration test
+// suite (`integration/`) and the standalone binaries in `src/bin/`.
+//
+// The harness automatically discovers the API URL from the local Tailscale CLI; set
+// INFERADB_API_URL to target another environment.
+
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    process::Command,
+    sync::{Mutex, OnceLock},
+};
+
+use anyhow::{Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Duration, Utc};
+use ed25519_dalek::{SigningKey, VerifyingKey};
+use jsonwebtoken::{Algorithm, EncodingKey, Header, encode};
+use rand::RngCore;
+use reqwest::{Client, Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+pub mod api_client;
+pub mod api_coverage;
+pub mod bench;
+pub mod build_info;
+pub mod chaos;
+pub mod churn;
+pub mod control_client;
+pub mod datasets;
+pub mod differential;
+pub mod dpop;
+pub mod engine_client;
+pub mod env_profile;
+pub mod grpc;
+pub mod invariants;
+pub mod jwt;
+pub mod jwt_corpus;
+#[cfg(feature = "k8s")]
+pub mod k8s;
+pub mod keys;
+pub mod leak_scan;
+pub mod ledger;
+pub mod load;
+pub mod mailbox;
+pub mod metrics;
+pub mod multi_client_fixture;
+pub mod naming;
+pub mod outcome;
+pub mod recorder;
+pub mod replay;
+pub mod request_signing;
+pub mod scenario;
+pub mod schema;
+pub mod shared_fixture;
+pub mod tags;
+pub mod timestamps;
+pub mod totp;
+pub mod tuple_import;
+pub mod usage;
+pub mod webhook_receiver;
+pub mod webhooks;
+
+pub use api_client::{ApiError, ApiResult};
+pub use build_info::{BuildInfo, suite_build_info};
+pub use chaos::{ChaosGuard, ChaosHook, ChaosSchedule, ChaosTimeline};
+pub use churn::{ChurnConfig, ChurnStats, RunningChurn};
+pub use control_client::ControlClient;
+pub use datasets::{DatasetManifest, DatasetSpec};
+pub use differential::DifferentialRunner;
+pub use dpop::DpopKey;
+pub use engine_client::{
+    Consistency, EngineClient, EvaluateResponse, ExplainResponse, ListRelationshipsResponse,
+    ListSubjectsResponse, Relationship, RelationshipFilter, ServerTiming, StoredRelationship,
+    StoredRelationshipsResponse, Timed, VaultStats,
+};
+pub use env_profile::{EnvProfile, ExperimentalFeatures, Feature};
+pub use grpc::{GrpcProbe, GrpcStream, grpc_status};
+pub use jwt::{JwtBuilder, swap_scheme, uppercase_host};
+pub use jwt_corpus::JwtCorpus;
+pub use leak_scan::{Leak, LeakScanner};
+pub use ledger::{Block, BlockStream, LedgerClient};
+pub use load::{LoadRunner, LoadStats};
+pub use mailbox::{MailMessage, Mailbox};
+pub use metrics::{MetricsSnapshot, TenantLabels};
+pub use multi_client_fixture::{MultiClientFixture, multi_client_fixture};
+pub use recorder::RecordedSend;
+pub use replay::Replayer;
+pub use request_signing::{RequestProof, RequestSigner};
+pub use scenario::{RunReport, Scenario, ScenarioReport, ScenarioRun, Slo, StepFuture};
+pub use schema::{
+    DOCUMENT_SCHEMA, DeploySchemaRequest, EXCLUSION_SCHEMA, GROUP_SCHEMA, HIERARCHY_SCHEMA,
+    INTERSECTION_SCHEMA, MIGRATED_DOCUMENT_SCHEMA,
+};
+pub use shared_fixture::SharedFixture;
+pub use tuple_import::{ImportProgress, TupleFile, TupleFormat, TupleImport};
+pub use webhook_receiver::{Delivery, WebhookEndpoint, WebhookResponse};
+pub use webhooks::WebhookKey;
+
+/// Required JWT audience for InferaDB Server API
+/// This MUST match the server's REQUIRED_AUDIENCE constant
+pub const REQUIRED_AUDIENCE: &str = "https://api.inferadb.com";
+
+/// Cached API base URL discovered from Tailscale
+static API_BASE_URL: OnceLock<String> = OnceLock::new();
+
+/// Discover the tailnet domain from the local Tailscale CLI
+fn discover_tailnet() -> Result<String> {
+    let output = Command::new("tailscale")
+        .args(["status", "--json"])
+        .output()
+        .context("Failed to run 'tailscale status --json'. Is Tailscale installed and running?")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Tailscale status failed: {}", stderr);
+    }
+
+    let status: serde_json::Value =
+        serde_json::from_slice(&output.stdout).context("Failed to parse Tailscale status JSON")?;
+
+    // Extract DNS name from Self.DNSName (e.g., "hostname.tail27bf77.ts.net.")
+    let dns_name = status
+        .get("Self")
+        .and_then(|s| s.get("DNSName"))
+        .and_then(|d| d.as_str())
+        .context("Could not find DNSName in Tailscale status")?;
+
+    // Extract tailnet domain (everything after first dot, removing trailing dot)
+    // e.g., "hostname.tail27bf77.ts.net." -> "tail27bf77.ts.net"
+    let tailnet = dns_name.trim_end_matches('.').split('.').skip(1).collect::<Vec<_>>().join(".");
+
+    if tailnet.is_empty() {
+        anyhow::bail!("Could not extract tailnet from DNSName: {}", dns_name);
+    }
+
+    Ok(tailnet)
+}
+
+/// Get the API base URL (discovers from Tailscale or uses environment override)
+pub fn api_base_url() -> String {
+    API_BASE_URL
+        .get_or_init(|| {
+            // Allow environment override for CI/testing
+            if let Ok(url) = std::env::var("INFERADB_API_URL") {
+                return url;
+            }
+
+            // Discover from Tailscale
+            match discover_tailnet() {
+                Ok(tailnet) => format!("https://inferadb-api.{}", tailnet),
+                Err(e) => {
+                    eprintln!("Warning: Could not discover Tailscale tailnet: {}", e);
+                    eprintln!("Falling back to localhost. Set INFERADB_API_URL to override.");
+                    "http://localhost:9090".to_string()
+                },
+            }
+        })
+        .clone()
+}
+
+/// Pin the API base URL before anything reads it
+///
+/// Lets binaries target an environment given on the command line; fails once `api_base_url` has
+/// been resolved.
+pub fn set_api_base_url(url: impl Into<String>) -> Result<()> {
+    API_BASE_URL
+        .set(url.into().trim_end_matches('/').to_string())
+        .map_err(|_| anyhow::anyhow!("API base URL already resolved"))
+}
+
+/// API version segment used in Control and Engine paths (`INFERADB_API_VERSION`, default `v1`)
+pub fn api_version() -> String {
+    std::env::var("INFERADB_API_VERSION").unwrap_or_else(|_| "v1".to_string())
+}
+
+/// Validate that the dev environment is running and accessible
+pub async fn validate_environment() -> Result<()> {
+    let base_url = api_base_url();
+    let client = Client::builder()
+        .timeout(std::time::Duration::from_secs(10))
+        .danger_accept_invalid_certs(true) // For dev self-signed certs
+        .build()?;
+
+    // Check health endpoint (routed to Control via ingress at /healthz)
+    let health_url = TestContext::with_base_url(&base_url).root_url("/healthz");
+    let response = client.get(&health_url).send().await.context(format!(
+        "Failed to connect to API at {}. Is the dev environment running? Run: inferadb dev start",
+        health_url
+    ))?;
+
+    if !response.status().is_success() {
+        anyhow::bail!(
+            "Health check failed with status {}. Is the dev environment healthy?",
+            response.status()
+        );
+    }
+
+    println!("Environment validated: {}", base_url);
+    Ok(())
+}
+
+/// HTTP client shared by the harness and tests
+///
+/// Mirrors the request-building methods of `reqwest::Client`, recording each request for the
+/// API coverage map (see `api_coverage`).
+#[derive(Clone)]
+pub struct HttpClient {
+    inner: Client,
+}
+
+impl HttpClient {
+    pub fn new(inner: Client) -> Self {
+        Self { inner }
+    }
+
+    pub fn request(&self, method: Method, url: impl AsRef<str>) -> RequestBuilder {
+        api_coverage::record(&method, url.as_ref());
+        self.inner.request(method, url.as_ref())
+    }
+
+    pub fn get(&self, url: impl AsRef<str>) -> RequestBuilder {
+        self.request(Method::GET, url)
+    }
+
+    pub fn post(&self, url: impl AsRef<str>) -> RequestBuilder {
+        self.request(Method::POST, url)
+    }
+
+    pub fn put(&self, url: impl AsRef<str>) -> RequestBuilder {
+        self.request(Method::PUT, url)
+    }
+
+    pub fn patch(&self, url: impl AsRef<str>) -> RequestBuilder {
+        self.request(Method::PATCH, url)
+    }
+
+    pub fn delete(&self, url: impl AsRef<str>) -> RequestBuilder {
+        self.request(Method::DELETE, url)
+    }
+}
+
+/// Test context containing all necessary state for integration tests
+#[derive(Clone)]
+pub struct TestContext {
+    pub client: HttpClient,
+    pub api_base_url: String,
+    pub api_version: String,
+}
+
+impl Default for TestContext {
+    fn default() -> Self {
+        Self::with_base_url(api_base_url())
+    }
+}
+
+impl TestContext {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a context targeting a specific API base URL
+    pub fn with_base_url(api_base_url: impl Into<String>) -> Self {
+        Self {
+            client: HttpClient::new(
+                Client::builder()
+                    .cookie_store(true)
+                    .timeout(std::time::Duration::from_secs(30))
+                    .danger_accept_invalid_certs(true) // For dev self-signed certs
+                    .build()
+                    .expect("Failed to create HTTP client"),
+            ),
+            api_base_url: api_base_url.into().trim_end_matches('/').to_string(),
+            api_version: api_version(),
+        }
+    }
+
+    /// Get Control API URL
+    pub fn control_url(&self, path: &str) -> String {
+        format!("{}/control/{}{}", self.api_base_url, self.api_version, path)
+    }
+
+    /// Get Engine (Access) API URL
+    pub fn engine_url(&self, path: &str) -> String {
+        format!("{}/access/{}{}", self.api_base_url, self.api_version, path)
+    }
+
+    /// Get an unversioned URL served at the ingress root (`/metrics`, `/healthz`)
+    pub fn root_url(&self, path: &str) -> String {
+        format!("{}{}", self.api_base_url, path)
+    }
+}
+
+/// User registration request
+#[derive(Debug, Serialize)]
+pub struct RegisterRequest {
+    pub name: String,
+    pub email: String,
+    pub password: String,
+    pub accept_tos: bool,
+}
+
+/// User registration response
+#[derive(Debug, Deserialize)]
+pub struct RegisterResponse {
+    pub user_id: i64,
+    pub name: String,
+    pub email: String,
+    pub session_id: i64,
+}
+
+/// Login request
+#[derive(Debug, Serialize)]
+pub struct LoginRequest {
+    pub email: String,
+    pub password: String,
+}
+
+/// Login response with session
+#[derive(Debug, Deserialize)]
+pub struct LoginResponse {
+    pub user_id: i64,
+    pub name: String,
+    pub session_id: i64,
+}
+
+/// Password login answer for a user with two-factor authentication: no session yet, only a token
+/// to complete the login with a TOTP or recovery code
+#[derive(Debug, Deserialize)]
+pub struct MfaChallengeResponse {
+    pub mfa_token: String,
+}
+
+/// Second login step with a code from the authenticator
+#[derive(Debug, Serialize)]
+pub struct TotpLoginRequest {
+    pub mfa_token: String,
+    pub code: String,
+}
+
+/// Second login step with a single-use recovery code
+#[derive(Debug, Serialize)]
+pub struct RecoveryLoginRequest {
+    pub mfa_token: String,
+    pub recovery_code: String,
+}
+
+/// TOTP enrollment; the secret is pending until confirmed with a code
+#[derive(Debug, Deserialize)]
+pub struct TotpEnrollmentResponse {
+    /// Base32 secret, as an authenticator app would scan it
+    pub secret: String,
+    pub otpauth_url: Option<String>,
+}
+
+/// TOTP code, to confirm an enrollment or disable two-factor authentication
+#[derive(Debug, Serialize)]
+pub struct TotpCodeRequest {
+    pub code: String,
+}
+
+/// Confirmed enrollment, with the recovery codes shown to the user once
+#[derive(Debug, Deserialize)]
+pub struct TotpConfirmResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// User summary (for operator listings)
+#[derive(Debug, Deserialize)]
+pub struct UserResponse {
+    pub id: i64,
+    pub name: String,
+    pub email: String,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Profile update request; fields left `None` are unchanged
+#[derive(Debug, Default, Serialize)]
+pub struct UpdateProfileRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+}
+
+/// Password change request for the session's own user
+#[derive(Debug, Serialize)]
+pub struct ChangePasswordRequest {
+    pub current_password: String,
+    pub new_password: String,
+}
+
+/// User list response
+#[derive(Debug, Deserialize)]
+pub struct ListUsersResponse {
+    pub users: Vec<UserResponse>,
+    pub pagination: Option<serde_json::Value>,
+}
+
+/// Organization creation request
+#[derive(Debug, Serialize)]
+pub struct CreateOrganizationRequest {
+    pub name: String,
+}
+
+/// Organization response
+#[derive(Debug, Deserialize)]
+pub struct OrganizationResponse {
+    pub id: i64,
+    pub name: String,
+    pub tier: String,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub created_at: DateTime<Utc>,
+    pub role: String,
+}
+
+/// Organization creation response
+#[derive(Debug, Deserialize)]
+pub struct CreateOrganizationResponse {
+    pub organization: OrganizationResponse,
+}
+
+/// Invitation of an email address to an organization, with the role it joins as
+#[derive(Debug, Serialize)]
+pub struct CreateInvitationRequest {
+    pub email: String,
+    pub role: String,
+}
+
+/// Invitation response
+#[derive(Debug, Deserialize)]
+pub struct InvitationResponse {
+    pub id: i64,
+    pub email: String,
+    pub role: String,
+    pub status: String,
+}
+
+/// Invitation creation response
+#[derive(Debug, Deserialize)]
+pub struct CreateInvitationResponse {
+    pub invitation: InvitationResponse,
+}
+
+/// Acceptance of an invitation by the session's user, with the token from the invite link
+#[derive(Debug, Serialize)]
+pub struct AcceptInvitationRequest {
+    pub token: String,
+}
+
+/// Organization member
+#[derive(Debug, Deserialize)]
+pub struct MemberResponse {
+    pub user_id: i64,
+    pub role: String,
+}
+
+/// Direct addition of an existing user to an organization
+#[derive(Debug, Serialize)]
+pub struct AddMemberRequest {
+    pub user_id: i64,
+    pub role: String,
+}
+
+/// Ownership transfer to another member of the organization
+#[derive(Debug, Serialize)]
+pub struct TransferOwnershipRequest {
+    pub user_id: i64,
+}
+
+/// Organization member list response
+#[derive(Debug, Deserialize)]
+pub struct ListMembersResponse {
+    pub members: Vec<MemberResponse>,
+    pub pagination: Option<serde_json::Value>,
+}
+
+/// List organizations response (paginated)
+#[derive(Debug, Deserialize)]
+pub struct ListOrganizationsResponse {
+    pub organizations: Vec<OrganizationResponse>,
+    pub pagination: Option<serde_json::Value>,
+}
+
+/// One page of a Control list endpoint, sent as query parameters
+#[derive(Debug, Clone, Default)]
+pub struct PageRequest {
+    pub limit: Option<u32>,
+    /// `next_cursor` of the previous page
+    pub cursor: Option<String>,
+    /// Name filter, for the list endpoints that support one
+    pub search: Option<String>,
+}
+
+impl PageRequest {
+    pub fn new(limit: u32) -> Self {
+        Self { limit: Some(limit), ..Default::default() }
+    }
+
+    pub fn cursor(mut self, cursor: impl Into<String>) -> Self {
+        self.cursor = Some(cursor.into());
+        self
+    }
+
+    /// A first page of everything whose name matches `query`
+    pub fn search(query: impl Into<String>) -> Self {
+        Self { search: Some(query.into()), ..Default::default() }
+    }
+
+    /// `path` with the page's query string appended
+    pub fn apply(&self, path: &str) -> String {
+        let mut params = Vec::new();
+        if let Some(limit) = self.limit {
+            params.push(format!("limit={}", limit));
+        }
+        if let Some(cursor) = &self.cursor {
+            params.push(format!("cursor={}", escape_query_value(cursor)));
+        }
+        if let Some(search) = &self.search {
+            params.push(format!("search={}", escape_query_value(search)));
+        }
+        if params.is_empty() { path.to_string() } else { format!("{}?{}", path, params.join("&")) }
+    }
+}
+
+/// Percent-encode everything outside the unreserved set, so cursors and arbitrary search text
+/// reach the server verbatim
+fn escape_query_value(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (byte as char).to_string()
+            },
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// The cursor of the page after this one, from a list response's `pagination`; `None` on the
+/// last page
+pub fn next_cursor(pagination: Option<&serde_json::Value>) -> Option<String> {
+    pagination?.get("next_cursor")?.as_str().filter(|cursor| !cursor.is_empty()).map(str::to_string)
+}
+
+/// Vault creation request
+#[derive(Debug, Serialize)]
+pub struct CreateVaultRequest {
+    pub name: String,
+    pub organization_id: i64,
+}
+
+/// Vault clone request; without relationships the clone is a template copy (schema only)
+#[derive(Debug, Serialize)]
+pub struct CloneVaultRequest {
+    pub name: String,
+    pub include_relationships: bool,
+}
+
+/// Vault info (inner structure)
+#[derive(Debug, Deserialize)]
+pub struct VaultInfo {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub organization_id: i64,
+    pub sync_status: String,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Vault creation response (wraps vault info)
+#[derive(Debug, Deserialize)]
+pub struct CreateVaultResponse {
+    pub vault: VaultInfo,
+}
+
+/// Vault response (for GET operations)
+#[derive(Debug, Deserialize)]
+pub struct VaultResponse {
+    pub id: i64,
+    pub name: String,
+    pub organization_id: i64,
+    pub sync_status: String,
+    pub sync_error: Option<String>,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub created_at: DateTime<Utc>,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub updated_at: DateTime<Utc>,
+    #[serde(default, deserialize_with = "timestamps::utc_option")]
+    pub deleted_at: Option<DateTime<Utc>>,
+}
+
+/// Vault list response
+#[derive(Debug, Deserialize)]
+pub struct ListVaultsResponse {
+    pub vaults: Vec<VaultResponse>,
+    pub pagination: Option<serde_json::Value>,
+}
+
+/// Client creation request
+#[derive(Debug, Serialize)]
+pub struct CreateClientRequest {
+    pub name: String,
+}
+
+/// Client info (inner structure)
+#[derive(Debug, Deserialize)]
+pub struct ClientInfo {
+    pub id: i64,
+    pub name: String,
+    pub description: String,
+    pub is_active: bool,
+    pub organization_id: i64,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Client creation response (wraps client info)
+#[derive(Debug, Deserialize)]
+pub struct CreateClientResponse {
+    pub client: ClientInfo,
+}
+
+/// Client response (for GET operations)
+#[derive(Debug, Deserialize)]
+pub struct ClientResponse {
+    pub id: i64,
+    pub name: String,
+    pub is_active: bool,
+    pub organization_id: i64,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub created_at: DateTime<Utc>,
+}
+
+/// Client list response
+#[derive(Debug, Deserialize)]
+pub struct ListClientsResponse {
+    pub clients: Vec<ClientResponse>,
+    pub pagination: Option<serde_json::Value>,
+}
+
+/// Certificate creation request
+#[derive(Debug, Serialize)]
+pub struct CreateCertificateRequest {
+    pub name: String,
+    /// Client-supplied public key; Control normally generates the keypair itself
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+impl CreateCertificateRequest {
+    /// Request a certificate with a server-generated keypair
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), public_key: None }
+    }
+}
+
+/// Certificate response
+#[derive(Debug, Deserialize)]
+pub struct CertificateResponse {
+    pub certificate: CertificateInfo,
+    pub private_key: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CertificateInfo {
+    pub id: i64,
+    pub kid: String,
+    pub name: String,
+    pub public_key: String,
+    pub is_active: bool,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub created_at: DateTime<Utc>,
+    /// Cutoff after which a rotated-out certificate stops authenticating, if Control sets one
+    #[serde(default, deserialize_with = "timestamps::utc_option")]
+    pub valid_until: Option<DateTime<Utc>>,
+    /// Lifecycle state, where Control reports one
+    #[serde(default)]
+    pub state: Option<CertificateState>,
+}
+
+/// Lifecycle state of a client certificate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CertificateState {
+    Active,
+    /// Replaced by a rotation but still inside its cutoff
+    Rotated,
+    Revoked,
+    /// Past its cutoff after rotation
+    Expired,
+}
+
+impl CertificateState {
+    /// Whether Engine should accept tokens signed by a certificate in this state
+    ///
+    /// A freshly rotated-in certificate is `Active` before its grace period ends, so callers
+    /// comparing against Engine must wait out the grace period first.
+    pub fn authenticates(self) -> bool {
+        matches!(self, Self::Active | Self::Rotated)
+    }
+}
+
+/// Certificate list response
+#[derive(Debug, Deserialize)]
+pub struct ListCertificatesResponse {
+    pub certificates: Vec<CertificateInfo>,
+    pub pagination: Option<serde_json::Value>,
+}
+
+/// JSON Web Key Set publishing client public keys
+#[derive(Debug, Deserialize)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+/// One public key in a JWKS (RFC 7517); Ed25519 keys are `kty: OKP`, `crv: Ed25519`
+#[derive(Debug, Deserialize)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(default)]
+    pub crv: Option<String>,
+    #[serde(default)]
+    pub kid: Option<String>,
+    /// Base64url-encoded public key bytes
+    #[serde(default)]
+    pub x: Option<String>,
+    /// Members beyond the ones above, e.g. a revocation marker or (wrongly) private material
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Jwk {
+    /// Whether the key is published but flagged as revoked (`status: revoked` or `revoked: true`)
+    pub fn is_marked_revoked(&self) -> bool {
+        self.extra.get("status").and_then(|v| v.as_str()) == Some("revoked")
+            || self.extra.get("revoked").and_then(|v| v.as_bool()) == Some(true)
+    }
+}
+
+/// Certificate rotation request
+#[derive(Debug, Serialize)]
+pub struct RotateCertificateRequest {
+    pub name: String,
+    /// Delay before the new certificate becomes valid
+    pub grace_period_seconds: i64,
+}
+
+/// Response from certificate rotation endpoint
+#[derive(Debug, Deserialize)]
+pub struct RotateCertificateResponse {
+    pub certificate: CertificateInfo,
+    #[serde(deserialize_with = "timestamps::utc")]
+    pub valid_from: DateTime<Utc>,
+    pub rotated_from: CertificateInfo,
+    pub private_key: String,
+}
+
+/// Request for an end-user-scoped token on a vault
+#[derive(Debug, Serialize)]
+pub struct IssueUserTokenRequest {
+    /// Subject the token is bound to (e.g. `user:alice`)
+    pub subject: String,
+    pub ttl_seconds: i64,
+}
+
+/// End-user-scoped token issued by Control
+#[derive(Debug, Deserialize)]
+pub struct UserTokenResponse {
+    pub token: String,
+    #[serde(default, deserialize_with = "timestamps::utc_option")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// One entry in an organization's audit log
+#[derive(Debug, Deserialize, Serialize)]
+pub struct AuditEvent {
+    pub action: String,
+    /// Client address Control recorded for the request
+    #[serde(default)]
+    pub ip_address: Option<String>,
+    #[serde(flatten)]
+    pub extra: serde_json::Map<String, serde_json::Value>,
+}
+
+/// Audit log response
+#[derive(Debug, Deserialize)]
+pub struct ListAuditEventsResponse {
+    pub events: Vec<AuditEvent>,
+    pub pagination: Option<serde_json::Value>,
+}
+
+/// Webhook subscription request
+#[derive(Debug, Serialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    /// Event types to deliver, e.g. `cache.invalidation`
+    pub events: Vec<String>,
+}
+
+/// A registered webhook
+#[derive(Debug, Deserialize)]
+pub struct WebhookInfo {
+    pub id: i64,
+    pub url: String,
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+/// Webhook creation response, with the key its deliveries are signed with
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookResponse {
+    pub webhook: WebhookInfo,
+    /// Shared HMAC secret, returned once, when the endpoint uses shared-secret signing
+    #[serde(default)]
+    pub secret: Option<String>,
+    /// Base64 raw Ed25519 public key, when deliveries are signed with Control's webhook key
+    #[serde(default)]
+    pub public_key: Option<String>,
+}
+
+impl CreateWebhookResponse {
+    /// The key a receiver verifies this webhook's deliveries with
+    pub fn key(&self) -> Result<WebhookKey> {
+        if let Some(secret) = &self.secret {
+            return Ok(WebhookKey::Hmac(secret.as_bytes().to_vec()));
+        }
+        let public_key = self.public_key.as_deref().context("Webhook has no signing key")?;
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(public_key.trim())
+            .context("Failed to decode webhook public key")?;
+        let bytes: [u8; 32] =
+            bytes.try_into().map_err(|_| anyhow::anyhow!("Invalid webhook public key length"))?;
+        Ok(WebhookKey::Ed25519(
+            VerifyingKey::from_bytes(&bytes).context("Invalid webhook public key")?,
+        ))
+    }
+}
+
+/// Control's record of one webhook delivery across its attempts
+#[derive(Debug, Clone, Deserialize)]
+pub struct WebhookDeliveryRecord {
+    /// Matches the receiver's `x-inferadb-webhook-id` header
+    pub id: String,
+    #[serde(default)]
+    pub event: Option<String>,
+    /// `pending`, `delivered`, or `dead_lettered`
+    pub status: String,
+    pub attempts: u32,
+    /// Status of the last attempt, absent when it got no answer
+    #[serde(default)]
+    pub last_response_status: Option<u16>,
+}
+
+impl WebhookDeliveryRecord {
+    pub fn is_dead_lettered(&self) -> bool {
+        self.status == "dead_lettered"
+    }
+}
+
+/// Webhook delivery listing
+#[derive(Debug, Deserialize)]
+pub struct ListWebhookDeliveriesResponse {
+    pub deliveries: Vec<WebhookDeliveryRecord>,
+}
+
+/// JWT claims for client authentication
+/// Matches the Control specification (see control/docs/Authentication.md)
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ClientClaims {
+    pub iss: String,
+    pub sub: String,
+    pub aud: String,
+    pub exp: i64,
+    pub iat: i64,
+    pub jti: String,
+    pub vault_id: String,
+    pub org_id: String,
+    pub scope: String,
+    pub vault_role: String,
+}
+
+/// Derive the `vault_role` claim from scopes (following control convention)
+pub fn vault_role_for_scopes(scopes: &[&str]) -> &'static str {
+    if scopes.contains(&"inferadb.admin") {
+        "admin"
+    } else if scopes.contains(&"inferadb.vault.manage") {
+        "manage"
+    } else if scopes.contains(&"inferadb.write") {
+        "write"
+    } else {
+        "read"
+    }
+}
+
+/// Test fixture for creating a complete test environment
+pub struct TestFixture {
+    pub ctx: TestContext,
+    pub user_id: i64,
+    pub session_id: i64,
+    pub org_id: i64,
+    pub vault_id: i64,
+    pub client_id: i64,
+    pub cert_id: i64,
+    pub cert_kid: String,
+    pub signing_key: SigningKey,
+    pub verifying_key: VerifyingKey,
+    /// Persistent fixtures are never deleted by `cleanup()` or drop
+    pub persistent: bool,
+    /// Backs a snapshot file: `cleanup()` removes what the test added to its vault and
+    /// organization, leaving the snapshotted resources for the next run
+    pub snapshot_backed: bool,
+}
+
+/// Serializable state of a provisioned fixture, used to skip provisioning between runs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureSnapshot {
+    pub api_base_url: String,
+    pub user_id: i64,
+    pub session_id: i64,
+    pub org_id: i64,
+    pub vault_id: i64,
+    pub client_id: i64,
+    pub cert_id: i64,
+    pub cert_kid: String,
+    /// Base64-encoded raw Ed25519 private key
+    pub private_key: String,
+}
+
+/// Fixtures created so far by each test, used to name snapshot files
+static FIXTURE_SEQUENCES: Mutex<BTreeMap<String, usize>> = Mutex::new(BTreeMap::new());
+
+/// Fixtures provisioned at once when `INFERADB_FIXTURE_CONCURRENCY` is unset
+const DEFAULT_FIXTURE_CONCURRENCY: usize = 4;
+
+/// Password every fixture's user registers with
+pub const FIXTURE_PASSWORD: &str = "SecurePassword123!";
+
+static FIXTURE_PERMITS: OnceLock<tokio::sync::Semaphore> = OnceLock::new();
+
+/// Time each Control call took while provisioning a fixture
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ProvisionTimings {
+    pub register: std::time::Duration,
+    /// Login and looking up the default organization
+    pub login: std::time::Duration,
+    pub vault: std::time::Duration,
+    pub client: std::time::Duration,
+    pub certificate: std::time::Duration,
+}
+
+/// Throttle shared by every fixture provisioned in this process
+///
+/// Provisioning registers a user and creates a vault, client, and certificate, and cargo runs
+/// tests in parallel; without a shared limit a full run bursts enough of these at Control to trip
+/// its rate limits. Override the limit with `INFERADB_FIXTURE_CONCURRENCY`.
+fn fixture_permits() -> &'static tokio::sync::Semaphore {
+    FIXTURE_PERMITS.get_or_init(|| {
+        let permits = std::env::var("INFERADB_FIXTURE_CONCURRENCY")
+            .ok()
+            .and_then(|value| value.parse().ok())
+            .filter(|&permits: &usize| permits > 0)
+            .unwrap_or(DEFAULT_FIXTURE_CONCURRENCY);
+        tokio::sync::Semaphore::new(permits)
+    })
+}
+
+/// Snapshot file for the current test's next fixture, if `INFERADB_FIXTURE_SNAPSHOT_DIR` is set
+///
+/// Files are named after the test (the recorder's scope) and numbered in the order it creates
+/// fixtures, so a test that creates two maps to `<test>-0.json` and `<test>-1.json` on every run,
+/// whichever other tests run alongside it.
+fn next_snapshot_path() -> Option<PathBuf> {
+    let dir = std::env::var("INFERADB_FIXTURE_SNAPSHOT_DIR").ok()?;
+    let test = recorder::current_scope();
+    let index = {
+        let mut sequences = FIXTURE_SEQUENCES.lock().unwrap_or_else(|e| e.into_inner());
+        let next = sequences.entry(test.clone()).or_insert(0);
+        *next += 1;
+        *next - 1
+    };
+    let file_name: String = test
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    Some(PathBuf::from(dir).join(format!("{}-{}.json", file_name, index)))
+}
+
+impl TestFixture {
+    /// Create a complete test fixture with user, org, vault, and client
+    ///
+    /// When `INFERADB_FIXTURE_SNAPSHOT_DIR` is set, a previously saved fixture is restored
+    /// instead (if it is still live), and newly provisioned fixtures are saved for the next run.
+    pub async fn create() -> Result<Self> {
+        Self::create_at(next_snapshot_path()).await
+    }
+
+    /// Create `count` fixtures concurrently, as fast as the provisioning throttle allows
+    ///
+    /// Results are in creation order, so snapshot files map to the same fixtures on every run.
+    pub async fn create_many(count: usize) -> Vec<Result<Self>> {
+        let handles: Vec<_> =
+            (0..count).map(|_| tokio::spawn(Self::create_at(next_snapshot_path()))).collect();
+
+        let mut fixtures = Vec::with_capacity(count);
+        for handle in handles {
+            fixtures.push(handle.await.context("Fixture creation task failed").and_then(|r| r));
+        }
+        fixtures
+    }
+
+    async fn create_at(path: Option<PathBuf>) -> Result<Self> {
+        let Some(path) = path else {
+            return Self::provision().await;
+        };
+
+        if path.exists() {
+            match Self::restore(&path).await {
+                Ok(fixture) => return Ok(fixture),
+                Err(e) => {
+                    eprintln!("Discarding stale fixture snapshot {}: {:#}", path.display(), e)
+                },
+            }
+        }
+
+        let mut fixture = Self::provision().await?;
+        fixture.save_snapshot(&path)?;
+        fixture.persistent = true;
+        fixture.snapshot_backed = true;
+        Ok(fixture)
+    }
+
+    /// Provision a fresh user, org, vault, client, and certificate via Control
+    async fn provision() -> Result<Self> {
+        let _permit = fixture_permits().acquire().await.context("Fixture throttle closed")?;
+        Ok(Self::provision_unthrottled().await?.0)
+    }
+
+    /// Provision a fixture without waiting for the throttle, timing each Control call
+    ///
+    /// For load tests measuring how Control copes with a provisioning burst; everything else should
+    /// use `create`, which the throttle keeps within Control's rate limits.
+    pub async fn provision_unthrottled() -> Result<(Self, ProvisionTimings)> {
+        let ctx = TestContext::new();
+        let mut timings = ProvisionTimings::default();
+
+        // Register user
+        let email = naming::email("test");
+        let register_req = RegisterRequest {
+            name: naming::entity_name("Test User"),
+            email: email.clone(),
+            password: FIXTURE_PASSWORD.to_string(),
+            accept_tos: true,
+        };
+
+        let started = std::time::Instant::now();
+        let register_resp = ControlClient::new(ctx.clone())
+            .register(&register_req)
+            .await
+            .context("Failed to register user")?;
+        timings.register = started.elapsed();
+
+        let user_id = register_resp.user_id;
+
+        // What exists so far, deleted again if a later step fails
+        let mut org = None;
+        let mut vault = None;
+        let mut client = None;
+        let result: Result<Self> = async {
+            // Login to get session
+            let login_req = LoginRequest { email, password: FIXTURE_PASSWORD.to_string() };
+
+            let started = std::time::Instant::now();
+            let login_resp = ControlClient::new(ctx.clone())
+                .login(&login_req)
+                .await
+                .context("Failed to login")?;
+
+            let session_id = login_resp.session_id;
+            let control = ControlClient::with_session(ctx.clone(), session_id);
+
+            // Get default organization (created during registration)
+            let orgs_response =
+                control.list_organizations().await.context("Failed to list organizations")?;
+
+            let org_id =
+                orgs_response.organizations.first().context("No default organization found")?.id;
+            org = Some(org_id);
+            timings.login = started.elapsed();
+
+            // Create vault
+            let vault_req = CreateVaultRequest {
+                name: naming::entity_name("Test Vault"),
+                organization_id: org_id,
+            };
+
+            let started = std::time::Instant::now();
+            let create_vault_resp =
+                control.create_vault(org_id, &vault_req).await.context("Failed to create vault")?;
+            timings.vault = started.elapsed();
+
+            let vault_id = create_vault_resp.vault.id;
+            vault = Some(vault_id);
+
+            // Create client
+            let client_req = CreateClientRequest { name: naming::entity_name("Test Client") };
+
+            let started = std::time::Instant::now();
+            let create_client_resp = control
+                .create_client(org_id, &client_req)
+                .await
+                .context("Failed to create client")?;
+            timings.client = started.elapsed();
+
+            let client_id = create_client_resp.client.id;
+            client = Some(client_id);
+
+            // Create certificate (server generates the keypair)
+            let cert_req = CreateCertificateRequest::new(naming::entity_name("Test Certificate"));
+
+            let started = std::time::Instant::now();
+            let cert_resp = control
+                .create_certificate(org_id, client_id, &cert_req)
+                .await
+                .context("Failed to create certificate")?;
+            timings.certificate = started.elapsed();
+
+            let cert_id = cert_resp.certificate.id;
+            let cert_kid = cert_resp.certificate.kid;
+
+            // Parse the server-generated private key (base64 encoded)
+            let signing_key = keys::from_base64(&cert_resp.private_key)?;
+            let verifying_key = signing_key.verifying_key();
+
+            Ok(Self {
+                ctx: ctx.clone(),
+                user_id,
+                session_id,
+                org_id,
+                vault_id,
+                client_id,
+                cert_id,
+                cert_kid,
+                signing_key,
+                verifying_key,
+                persistent: false,
+                snapshot_backed: false,
+            })
+        }
+        .await;
+
+        let fixture = match result {
+            Ok(fixture) => fixture,
+            Err(e) => {
+                // Login may be what failed, so clean up with the session registration returned
+                let control = ControlClient::with_session(ctx, register_resp.session_id);
+                if let Some(org_id) = org {
+                    if let Some(vault_id) = vault {
+                        let _ = control.delete_vault(org_id, vault_id).await;
+                    }
+                    if let Some(client_id) = client {
+                        let _ = control.delete_client(org_id, client_id).await;
+                    }
+                    let _ = control.delete_organization(org_id).await;
+                }
+                let _ = control.delete_user(user_id).await;
+                return Err(e);
+            },
+        };
+
+        usage::begin(fixture.org_id);
+        Ok((fixture, timings))
+    }
+
+    /// Control API client authenticated with the fixture's session
+    pub fn control(&self) -> ControlClient {
+        ControlClient::with_session(self.ctx.clone(), self.session_id)
+    }
+
+    /// Create another client with its own certificate in this fixture's organization
+    ///
+    /// The returned fixture shares this one's session, organization, and vault, and is marked
+    /// persistent so it never cleans them up; delete its client explicitly when done.
+    pub async fn add_client(&self) -> Result<TestFixture> {
+        let control = self.control();
+
+        let client_req = CreateClientRequest { name: naming::entity_name("Test Client") };
+        let client_id = control
+            .create_client(self.org_id, &client_req)
+            .await
+            .context("Failed to create additional client")?
+            .client
+            .id;
+
+        let cert_req = CreateCertificateRequest::new(naming::entity_name("Test Certificate"));
+        let cert_resp = control
+            .create_certificate(self.org_id, client_id, &cert_req)
+            .await
+            .context("Failed to create certificate for additional client")?;
+        let signing_key = keys::from_base64(&cert_resp.private_key)?;
+
+        Ok(TestFixture {
+            ctx: self.ctx.clone(),
+            user_id: self.user_id,
+            session_id: self.session_id,
+            org_id: self.org_id,
+            vault_id: self.vault_id,
+            client_id,
+            cert_id: cert_resp.certificate.id,
+            cert_kid: cert_resp.certificate.kid,
+            verifying_key: signing_key.verifying_key(),
+            signing_key,
+            persistent: true,
+            snapshot_backed: false,
+        })
+    }
+
+    /// Capture the fixture's identifiers, session, and signing key
+    pub fn snapshot(&self) -> FixtureSnapshot {
+        FixtureSnapshot {
+            api_base_url: self.ctx.api_base_url.clone(),
+            user_id: self.user_id,
+            session_id: self.session_id,
+            org_id: self.org_id,
+            vault_id: self.vault_id,
+            client_id: self.client_id,
+            cert_id: self.cert_id,
+            cert_kid: self.cert_kid.clone(),
+            private_key: base64::engine::general_purpose::STANDARD
+                .encode(self.signing_key.to_bytes()),
+        }
+    }
+
+    /// Write the fixture snapshot to `path`, creating parent directories as needed
+    ///
+    /// The snapshot holds the certificate's private key, so it is written with `write_private`.
+    pub fn save_snapshot(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_vec_pretty(&self.snapshot())
+            .context("Failed to serialize fixture snapshot")?;
+        write_private(path, &json)
+    }
+
+    /// Rehydrate a fixture from a snapshot file, verifying it is still live
+    ///
+    /// The restored fixture is persistent: `cleanup()` and drop leave its resources in place.
+    pub async fn restore(path: &Path) -> Result<Self> {
+        let json =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let snapshot: FixtureSnapshot =
+            serde_json::from_slice(&json).context("Failed to parse fixture snapshot")?;
+
+        let fixture = Self::from_snapshot(snapshot)?;
+        fixture.validate_live().await?;
+        Ok(fixture)
+    }
+
+    /// Rebuild a persistent fixture from a snapshot without checking it is still live
+    pub fn from_snapshot(snapshot: FixtureSnapshot) -> Result<Self> {
+        let ctx = TestContext::new();
+        if snapshot.api_base_url != ctx.api_base_url {
+            anyhow::bail!(
+                "Snapshot was taken against {}, current environment is {}",
+                snapshot.api_base_url,
+                ctx.api_base_url
+            );
+        }
+
+        let signing_key =
+            keys::from_base64(&snapshot.private_key).context("Invalid snapshot private key")?;
+        let verifying_key = signing_key.verifying_key();
+
+        Ok(Self {
+            ctx,
+            user_id: snapshot.user_id,
+            session_id: snapshot.session_id,
+            org_id: snapshot.org_id,
+            vault_id: snapshot.vault_id,
+            client_id: snapshot.client_id,
+            cert_id: snapshot.cert_id,
+            cert_kid: snapshot.cert_kid,
+            signing_key,
+            verifying_key,
+            persistent: true,
+            snapshot_backed: true,
+        })
+    }
+
+    /// Check that the session, vault, and certificate are all still usable
+    async fn validate_live(&self) -> Result<()> {
+        self.control()
+            .get_vault(self.org_id, self.vault_id)
+            .await
+            .context("Snapshot session or vault is no longer valid")?;
+
+        let jwt = self.generate_jwt(None, &["inferadb.check"])?;
+        let status = self
+            .call_server_evaluate(&jwt, "document:snapshot-probe", "viewer", "user:probe")
+            .await?
+            .status();
+        if !status.is_success() && status != reqwest::StatusCode::NOT_FOUND {
+            anyhow::bail!("Snapshot certificate rejected by Engine with status {}", status);
+        }
+
+        Ok(())
+    }
+
+    /// Generate a JWT token for the client with specified vault and scopes
+    pub fn generate_jwt(&self, vault_id: Option<i64>, scopes: &[&str]) -> Result<String> {
+        let now = Utc::now();
+
+        let vault_role = vault_role_for_scopes(scopes);
+
+        // Use scope format: space-separated inferadb.* scopes
+        let scope_str = if scopes.is_empty() {
+            // Default to read scope
+            "inferadb.check inferadb.read inferadb.expand inferadb.list inferadb.list-relationships inferadb.list-subjects inferadb.list-resources".to_string()
+        } else {
+            scopes.join(" ")
+        };
+
+        let claims = ClientClaims {
+            iss: self.ctx.api_base_url.clone(),
+            sub: format!("client:{}", self.client_id),
+            aud: EnvProfile::current().audience.clone(),
+            exp: (now + Duration::minutes(5)).timestamp(),
+            iat: now.timestamp(),
+            jti: Uuid::new_v4().to_string(),
+            vault_id: vault_id.unwrap_or(self.vault_id).to_string(),
+            org_id: self.org_id.to_string(),
+            scope: scope_str,
+            vault_role: vault_role.to_string(),
+        };
+
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(self.cert_kid.clone());
+
+        let encoding_key = keys::encoding_key(&self.signing_key)?;
+
+        encode(&header, &claims, &encoding_key).context("Failed to encode JWT")
+    }
+
+    /// Engine API client authenticated with the given JWT
+    pub fn engine(&self, jwt: &str) -> EngineClient {
+        EngineClient::new(self.ctx.clone(), jwt)
+    }
+
+    /// Start adding and removing `document:churn-*` relationships in the fixture's vault in the
+    /// background, until the returned churn is stopped
+    pub fn start_churn(&self, config: ChurnConfig) -> RunningChurn {
+        RunningChurn::start(
+            self.ctx.clone(),
+            self.jwt().scopes(&["inferadb.write", "inferadb.check"]),
+            config,
+        )
+    }
+
+    /// Clone the fixture's vault into a new vault in the same organization
+    ///
+    /// The clone has the vault's schema, and its relationships too when `include_relationships`
+    /// is set. It is not tracked by the fixture; delete it when done. Fails with an unsupported
+    /// `ApiError` on platforms that cannot clone vaults.
+    pub async fn clone_vault(&self, include_relationships: bool) -> ApiResult<i64> {
+        let req =
+            CloneVaultRequest { name: naming::entity_name("Cloned Vault"), include_relationships };
+        Ok(self.control().clone_vault(self.org_id, self.vault_id, &req).await?.vault.id)
+    }
+
+    /// Have Control issue a token bound to `subject` on the fixture's vault
+    ///
+    /// Fails with an unsupported `ApiError` on platforms without end-user tokens.
+    pub async fn user_token(&self, subject: &str) -> ApiResult<String> {
+        let req = IssueUserTokenRequest { subject: subject.to_string(), ttl_seconds: 300 };
+        Ok(self.control().issue_user_token(self.org_id, self.vault_id, &req).await?.token)
+    }
+
+    /// Resolve the metric labels the server uses for this fixture's org, client, and vault
+    ///
+    /// Scrapes `/metrics` once; `None` if metrics are unavailable.
+    pub async fn metric_labels(&self) -> Option<TenantLabels> {
+        let snapshot = MetricsSnapshot::fetch(&self.ctx).await?;
+        Some(TenantLabels::resolve(&snapshot, self.org_id, self.client_id, self.vault_id))
+    }
+
+    /// Start building a JWT for this fixture's client with custom claims
+    pub fn jwt(&self) -> JwtBuilder {
+        JwtBuilder::new(self)
+    }
+
+    /// Generate a JWT with a different signing key (for testing invalid signatures)
+    pub fn generate_invalid_jwt(&self) -> Result<String> {
+        let wrong_key = keys::generate();
+        let now = Utc::now();
+
+        let claims = ClientClaims {
+            iss: self.ctx.api_base_url.clone(),
+            sub: format!("client:{}", self.client_id),
+            aud: EnvProfile::current().audience.clone(),
+            exp: (now + Duration::minutes(5)).timestamp(),
+            iat: now.timestamp(),
+            jti: Uuid::new_v4().to_string(),
+            vault_id: self.vault_id.to_string(),
+            org_id: self.org_id.to_string(),
+            scope: "inferadb.check inferadb.read inferadb.write inferadb.expand inferadb.list inferadb.list-relationships inferadb.list-subjects inferadb.list-resources".to_string(),
+            vault_role: "write".to_string(),
+        };
+
+        let mut header = Header::new(Algorithm::EdDSA);
+        header.kid = Some(self.cert_kid.clone());
+
+        let encoding_key = keys::encoding_key(&wrong_key)
+            .context("Failed to create encoding key for invalid JWT")?;
+
+        encode(&header, &claims, &encoding_key).context("Failed to encode invalid JWT")
+    }
+
+    /// Call engine evaluate endpoint with JWT
+    pub async fn call_server_evaluate(
+        &self,
+        jwt: &str,
+        resource: &str,
+        permission: &str,
+        subject: &str,
+    ) -> Result<reqwest::Response> {
+        // Build evaluation request matching the server's expected format
+        let evaluation = serde_json::json!({
+            "subject": subject,
+            "resource": resource,
+            "permission": permission,
+            "trace": false
+        });
+
+        let body = serde_json::json!({
+            "evaluations": [evaluation]
+        });
+
+        let request = self
+            .ctx
+            .client
+            .post(self.ctx.engine_url("/evaluate"))
+            .header("Authorization", format!("Bearer {}", jwt))
+            .json(&body);
+        recorder::send(request).await.context("Failed to call server evaluate endpoint")
+    }
+
+    /// Cleanup test resources
+    pub async fn cleanup(&self) -> Result<()> {
+        if self.snapshot_backed {
+            return self.reset_to_snapshot().await;
+        }
+        if self.persistent {
+            return Ok(());
+        }
+        usage::finish(self).await;
+
+        let control = self.control();
+        let _ = control.delete_vault(self.org_id, self.vault_id).await;
+        let _ = control.delete_client(self.org_id, self.client_id).await;
+        let _ = control.delete_organization(self.org_id).await;
+        let _ = control.delete_user(self.user_id).await;
+
+        Ok(())
+    }
+
+    /// Remove what a test added to a snapshot-backed fixture: the vault's relationships, and any
+    /// other vaults and clients in its organization
+    async fn reset_to_snapshot(&self) -> Result<()> {
+        usage::finish(self).await;
+
+        let jwt = self.generate_jwt(None, &["inferadb.admin"])?;
+        match self.engine(&jwt).purge_relationships().await {
+            Ok(()) => {},
+            Err(e) if e.is_unsupported() => {
+                eprintln!("Warning: cannot purge snapshot fixture's vault: {}", e)
+            },
+            Err(e) => return Err(e).context("Failed to purge snapshot fixture's vault"),
+        }
+
+        let control = self.control();
+        let vaults = control.list_vaults(self.org_id).await.context("Failed to list vaults")?;
+        for vault in vaults.vaults.iter().filter(|vault| vault.id != self.vault_id) {
+            let _ = control.delete_vault(self.org_id, vault.id).await;
+        }
+        let clients = control.list_clients(self.org_id).await.context("Failed to list clients")?;
+        for client in clients.clients.iter().filter(|client| client.id != self.client_id) {
+            let _ = control.delete_client(self.org_id, client.id).await;
+        }
+        Ok(())
+    }
+}
+
+/// Write a file holding credentials, creating parent directories as needed
+///
+/// On Unix the file is readable by its owner only (0600) and directories created for it are
+/// owner-only too (0700).
+pub fn write_private(path: &Path, contents: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        let mut builder = std::fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        std::os::unix::fs::DirBuilderExt::mode(&mut builder, 0o700);
+        builder.create(parent).with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let mut file =
+        options.open(path).with_context(|| format!("Failed to write {}", path.display()))?;
+    // `mode` only applies on creation; tighten a file left by an older run too
+    #[cfg(unix)]
+    std::fs::set_permissions(path, std::os::unix::fs::PermissionsExt::from_mode(0o600))
+        .with_context(|| format!("Failed to restrict {}", path.display()))?;
+    std::io::Write::write_all(&mut file, contents)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+impl Drop for TestFixture {
+    fn drop(&mut self) {
+        if self.persistent {
+            return;
+        }
+
+        // Best-effort cleanup on drop
+        let control = self.control();
+        let vault_id = self.vault_id;
+        let org_id = self.org_id;
+        let client_id = self.client_id;
+        let user_id = self.user_id;
+
+        tokio::spawn(async move {
+            let _ = control.delete_vault(org_id, vault_id).await;
+            let _ = control.delete_client(org_id, client_id).await;
+            let _ = control.delete_organization(org_id).await;
+            let _ = control.delete_user(user_id).await;
+        });
+    }
+}
+
+// Legacy compatibility functions (deprecated - use TestContext methods instead)
+#[deprecated(note = "Use TestContext::control_url() instead")]
+pub fn control_url() -> String {
+    api_base_url()
+}
+
+#[deprecated(note = "Use TestContext::engine_url() instead")]
+pub fn engine_url() -> String {
+    api_base_url()
+}
+
+#[deprecated(note = "No longer needed with unified Tailscale endpoint")]
+pub fn engine_grpc_url() -> String {
+    api_base_url()
+}
+
+#[deprecated(note = "No longer needed with unified Tailscale endpoint")]
+pub fn engine_mesh_url() -> String {
+    api_base_url()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_context_urls_match_hand_formatted_urls() {
+        let mut ctx = TestContext::with_base_url("https://inferadb-api.example.ts.net/");
+        ctx.api_version = "v1".to_string();
+
+        assert_eq!(ctx.api_base_url, "https://inferadb-api.example.ts.net");
+        assert_eq!(
+            ctx.control_url("/organizations/1/vaults"),
+            "https://inferadb-api.example.ts.net/control/v1/organizations/1/vaults"
+        );
+        assert_eq!(
+            ctx.engine_url("/evaluate"),
+            "https://inferadb-api.example.ts.net/access/v1/evaluate"
+        );
+        assert_eq!(ctx.root_url("/metrics"), "https://inferadb-api.example.ts.net/metrics");
+    }
+
+    #[test]
+    fn test_page_request_query_string() {
+        assert_eq!(PageRequest::default().apply("/vaults"), "/vaults");
+        assert_eq!(
+            PageRequest::new(25).cursor("a+b/c=").apply("/vaults"),
+            "/vaults?limit=25&cursor=a%2Bb%2Fc%3D"
+        );
+        assert_eq!(
+            PageRequest::search("' OR 1=1 -- %_").apply("/clients"),
+            "/clients?search=%27%20OR%201%3D1%20--%20%25_",
+            "Search text is sent verbatim, not as query syntax"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_private_files_are_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!("inferadb-private-{}", Uuid::new_v4()));
+        let path = dir.join("nested").join("manifest.json");
+        write_private(&path, b"{}").expect("Failed to write private file");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))
+            .expect("Failed to loosen permissions");
+        write_private(&path, b"{\"rewritten\":true}").expect("Failed to rewrite private file");
+
+        let mode = |path: &Path| std::fs::metadata(path).expect("Missing").permissions().mode();
+        assert_eq!(mode(&path) & 0o777, 0o600, "Rewriting tightens a loosened file");
+        assert_eq!(mode(path.parent().expect("Has a parent")) & 0o777, 0o700);
+        assert_eq!(std::fs::read(&path).expect("Readable"), b"{\"rewritten\":true}");
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,154 @@
+// Load Runner
+//
+// Drives a request from a fixed number of concurrent workers until stopped, for tests that assert
+// on traffic while something else happens to the environment (a rolling restart, a pod kill).
+// Outcomes are classified so those tests can tell lost availability from authentication
+// regressions:
+//
+//     let load = LoadRunner::new(8).start(move || {
+//         let engine = engine.clone();
+//         async move { engine.evaluate("document:1", "viewer", "user:alice").await }
+//     });
+//     cluster.rolling_restart_engine().await?;
+//     let stats = load.stop().await;
+
+use std::{
+    collections::BTreeMap,
+    future::Future,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use reqwest::StatusCode;
+
+use super::*;
+
+/// Distinct error messages kept per run
+const ERROR_SAMPLE_LIMIT: usize = 5;
+
+/// Outcomes of every request a load run sent
+#[derive(Debug, Clone, Default)]
+pub struct LoadStats {
+    pub requests: usize,
+    pub successes: usize,
+    /// 401 and 403 responses
+    pub auth_failures: usize,
+    /// Every other failure: non-success statuses and requests that got no response
+    pub errors: usize,
+    /// Count per failing status; requests without a response are not counted here
+    pub failure_statuses: BTreeMap<u16, usize>,
+    /// First few distinct failure messages
+    pub sample_errors: Vec<String>,
+}
+
+impl LoadStats {
+    fn sample(&mut self, message: String) {
+        if self.sample_errors.len() < ERROR_SAMPLE_LIMIT && !self.sample_errors.contains(&message) {
+            self.sample_errors.push(message);
+        }
+    }
+
+    fn record<T>(&mut self, result: &ApiResult<T>) {
+        self.requests += 1;
+        let Err(e) = result else {
+            self.successes += 1;
+            return;
+        };
+        match e.status() {
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN) => self.auth_failures += 1,
+            _ => self.errors += 1,
+        }
+        if let Some(status) = e.status() {
+            *self.failure_statuses.entry(status.as_u16()).or_default() += 1;
+        }
+        self.sample(e.to_string());
+    }
+
+    fn merge(&mut self, other: LoadStats) {
+        self.requests += other.requests;
+        self.successes += other.successes;
+        self.auth_failures += other.auth_failures;
+        self.errors += other.errors;
+        for (status, count) in other.failure_statuses {
+            *self.failure_statuses.entry(status).or_default() += count;
+        }
+        other.sample_errors.into_iter().for_each(|message| self.sample(message));
+    }
+
+    /// Fraction of requests that failed for any reason
+    pub fn failure_rate(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            (self.requests - self.successes) as f64 / self.requests as f64
+        }
+    }
+}
+
+/// Concurrent workers sending one request in a loop
+#[derive(Debug, Clone)]
+pub struct LoadRunner {
+    workers: usize,
+    pause: std::time::Duration,
+}
+
+impl LoadRunner {
+    /// `workers` concurrent loops, each pausing 50ms between requests
+    pub fn new(workers: usize) -> Self {
+        Self { workers: workers.max(1), pause: std::time::Duration::from_millis(50) }
+    }
+
+    /// Pause each worker takes between requests
+    pub fn pause(mut self, pause: std::time::Duration) -> Self {
+        self.pause = pause;
+        self
+    }
+
+    /// Start sending `request` from every worker until the returned load is stopped
+    pub fn start<F, Fut, T>(&self, request: F) -> RunningLoad
+    where
+        F: Fn() -> Fut + Clone + Send + 'static,
+        Fut: Future<Output = ApiResult<T>> + Send,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let workers = (0..self.workers)
+            .map(|_| {
+                let stop = Arc::clone(&stop);
+                let request = request.clone();
+                let pause = self.pause;
+                tokio::spawn(async move {
+                    let mut stats = LoadStats::default();
+                    while !stop.load(Ordering::SeqCst) {
+                        stats.record(&request().await);
+                        tokio::time::sleep(pause).await;
+                    }
+                    stats
+                })
+            })
+            .collect();
+        RunningLoad { stop, workers }
+    }
+}
+
+/// Load in progress, started by `LoadRunner::start`
+pub struct RunningLoad {
+    stop: Arc<AtomicBool>,
+    workers: Vec<tokio::task::JoinHandle<LoadStats>>,
+}
+
+impl RunningLoad {
+    /// Stop every worker after its current request and combine their outcomes
+    pub async fn stop(self) -> LoadStats {
+        self.stop.store(true, Ordering::SeqCst);
+        let mut stats = LoadStats::default();
+        for worker in self.workers {
+            match worker.await {
+                Ok(worker_stats) => stats.merge(worker_stats),
+                Err(e) => eprintln!("Warning: load worker panicked: {}", e),
+            }
+        }
+        stats
+    }
+}
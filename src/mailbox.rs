@@ -0,0 +1,216 @@
+// Mailbox
+//
+// Control sends invitations and other mail over SMTP; test environments point it at a mail
+// catcher (Mailpit) instead of a real relay, and the suite reads the caught messages through its
+// HTTP API at INFERADB_MAILBOX_URL. Tests that need mail skip when it is unset. Messages are looked
+// up by recipient, and every test sends to its own `naming::email` address, so parallel tests
+// never see each other's mail. The catcher is read with a plain client rather than the suite's
+// recording one: its traffic is not part of the system under test.
+
+use std::time::Instant;
+
+use super::*;
+
+/// Gap between mailbox searches while waiting for a message
+const POLL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// Client for the mail catcher's API
+#[derive(Debug, Clone)]
+pub struct Mailbox {
+    client: reqwest::Client,
+    base_url: String,
+}
+
+/// One caught message
+#[derive(Debug, Clone)]
+pub struct MailMessage {
+    pub id: String,
+    pub subject: String,
+    pub to: Vec<String>,
+    pub text: String,
+    pub html: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    messages: Vec<MessageSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MessageSummary {
+    #[serde(rename = "ID")]
+    id: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MessageBody {
+    #[serde(rename = "ID")]
+    id: String,
+    subject: String,
+    to: Vec<Address>,
+    #[serde(default)]
+    text: String,
+    #[serde(rename = "HTML", default)]
+    html: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Address {
+    address: String,
+}
+
+impl Mailbox {
+    /// The mail catcher, or `None` (after logging) when INFERADB_MAILBOX_URL is unset
+    pub fn create() -> Option<Self> {
+        let Some(base_url) = std::env::var("INFERADB_MAILBOX_URL")
+            .ok()
+            .map(|url| url.trim().trim_end_matches('/').to_string())
+            .filter(|url| !url.is_empty())
+        else {
+            outcome::skip("Skipping mail test - INFERADB_MAILBOX_URL not set");
+            return None;
+        };
+        Some(Self { client: reqwest::Client::new(), base_url })
+    }
+
+    async fn get<T: serde::de::DeserializeOwned>(&self, path: &str) -> Result<T> {
+        let url = format!("{}{}", self.base_url, path);
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .with_context(|| format!("Failed to reach mailbox at {}", url))?
+            .error_for_status()
+            .with_context(|| format!("Mailbox refused {}", url))?;
+        response.json().await.with_context(|| format!("Unexpected mailbox response from {}", url))
+    }
+
+    /// Every caught message addressed to `address`, newest first
+    pub async fn messages_to(&self, address: &str) -> Result<Vec<MailMessage>> {
+        let query = escape_query_value(&format!("to:\"{}\"", address));
+        let search: SearchResponse = self.get(&format!("/api/v1/search?query={}", query)).await?;
+        let mut messages = Vec::with_capacity(search.messages.len());
+        for summary in search.messages {
+            let body: MessageBody = self.get(&format!("/api/v1/message/{}", summary.id)).await?;
+            messages.push(MailMessage {
+                id: body.id,
+                subject: body.subject,
+                to: body.to.into_iter().map(|to| to.address).collect(),
+                text: body.text,
+                html: body.html,
+            });
+        }
+        Ok(messages)
+    }
+
+    /// The newest message to `address`, waiting up to `timeout` for one to arrive
+    pub async fn wait_for(
+        &self,
+        address: &str,
+        timeout: std::time::Duration,
+    ) -> Result<MailMessage> {
+        let started = Instant::now();
+        loop {
+            if let Some(message) = self.messages_to(address).await?.into_iter().next() {
+                return Ok(message);
+            }
+            anyhow::ensure!(
+                started.elapsed() < timeout,
+                "No mail to {} within {:?}",
+                address,
+                timeout
+            );
+            tokio::time::sleep(POLL).await;
+        }
+    }
+}
+
+impl MailMessage {
+    /// Every http(s) link in the message, text part first, without duplicates
+    pub fn links(&self) -> Vec<String> {
+        let mut links: Vec<String> = Vec::new();
+        for body in [&self.text, &self.html] {
+            for (start, _) in body.match_indices("http") {
+                let link: String = body[start..]
+                    .chars()
+                    .take_while(|c| !c.is_whitespace() && !matches!(c, '"' | '\'' | '<' | '>'))
+                    .collect();
+                let link = link.replace("&amp;", "&");
+                if (link.starts_with("https://") || link.starts_with("http://"))
+                    && !links.contains(&link)
+                {
+                    links.push(link);
+                }
+            }
+        }
+        links
+    }
+}
+
+/// The value of query parameter `name` in `url`, percent-decoded
+pub fn query_param(url: &str, name: &str) -> Option<String> {
+    let query = url.split_once('?')?.1.split('#').next()?;
+    query.split('&').find_map(|pair| {
+        let (key, value) = pair.split_once('=')?;
+        (key == name).then(|| percent_decode(value))
+    })
+}
+
+fn percent_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| bytes.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| std::str::from_utf8(hex).ok())
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match (escaped, bytes[i]) {
+            (Some(byte), _) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            (None, b'+') => {
+                decoded.push(b' ');
+                i += 1;
+            },
+            (None, byte) => {
+                decoded.push(byte);
+                i += 1;
+            },
+        }
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mail_links_and_query_params() {
+        let message = MailMessage {
+            id: "1".into(),
+            subject: "Join Acme".into(),
+            to: vec!["invitee@example.com".into()],
+            text: "Accept: https://app.example.com/invite?token=a%2Bb+c&org=7\nThanks".into(),
+            html: r#"<a href="https://app.example.com/invite?token=a%2Bb+c&amp;org=7">Accept</a>
+                     <a href='http://example.com/help'>Help</a>"#
+                .into(),
+        };
+        let links = message.links();
+        assert_eq!(
+            links,
+            ["https://app.example.com/invite?token=a%2Bb+c&org=7", "http://example.com/help"],
+            "Links are collected once each, with HTML entities decoded"
+        );
+        assert_eq!(query_param(&links[0], "token").as_deref(), Some("a+b c"));
+        assert_eq!(query_param(&links[0], "org").as_deref(), Some("7"));
+        assert_eq!(query_param(&links[1], "token"), None);
+        assert_eq!(query_param("https://x.test/?t=%zz%4", "t").as_deref(), Some("%zz%4"));
+    }
+}
@@ -0,0 +1,241 @@
+// Prometheus metrics
+//
+// Parses Engine's `/metrics` exposition into labelled samples so tests can assert on one tenant's
+// series instead of summing every label set. Label naming is the server's choice (`vault_id="42"`,
+// `vault="vault:42"`, ...), so `TenantLabels::resolve` discovers which label and value the server
+// uses for a fixture's org, client, and vault from a single scrape. `fingerprint` reduces a scrape
+// to the build and configuration it reveals, so replicas can be compared with each other.
+
+use std::collections::BTreeMap;
+
+use super::*;
+
+/// One sample line: metric name, labels, and value
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    pub name: String,
+    pub labels: BTreeMap<String, String>,
+    pub value: f64,
+}
+
+/// A label name/value pair identifying one tenant resource in metric series
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Label {
+    pub name: String,
+    pub value: String,
+}
+
+/// Parsed `/metrics` output at one point in time
+#[derive(Debug, Clone, Default)]
+pub struct MetricsSnapshot {
+    pub samples: Vec<Sample>,
+}
+
+impl MetricsSnapshot {
+    /// Scrape `/metrics`, or `None` if the endpoint is unavailable
+    pub async fn fetch(ctx: &TestContext) -> Option<Self> {
+        let response = ctx.client.get(ctx.root_url("/metrics")).send_recorded().await.ok()?;
+        if !response.status().is_success() {
+            return None;
+        }
+        Some(Self::parse(&response.text().await.ok()?))
+    }
+
+    /// Parse Prometheus text exposition; comments and malformed lines are skipped
+    pub fn parse(text: &str) -> Self {
+        let samples = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(parse_sample)
+            .collect();
+        Self { samples }
+    }
+
+    /// Sum of `name` across series carrying every label in `labels`
+    pub fn sum(&self, name: &str, labels: &[&Label]) -> f64 {
+        self.samples
+            .iter()
+            .filter(|s| s.name == name)
+            .filter(|s| labels.iter().all(|l| s.labels.get(&l.name) == Some(&l.value)))
+            .map(|s| s.value)
+            .sum()
+    }
+
+    /// Find the label the server uses for resource `id` of `kind` (`org`, `client`, `vault`)
+    ///
+    /// A candidate label's name must mention `kind` and its value must be the ID itself or end in
+    /// it after a `:`, `-`, or `/` separator; exact values win over prefixed ones.
+    pub fn resolve_label(&self, kind: &str, id: i64) -> Option<Label> {
+        let id = id.to_string();
+        let mut prefixed = None;
+        for sample in &self.samples {
+            for (name, value) in &sample.labels {
+                if !name.to_lowercase().contains(kind) {
+                    continue;
+                }
+                if *value == id {
+                    return Some(Label { name: name.clone(), value: value.clone() });
+                }
+                let suffixed = value
+                    .strip_suffix(id.as_str())
+                    .is_some_and(|rest| rest.ends_with([':', '-', '/']));
+                if suffixed && prefixed.is_none() {
+                    prefixed = Some(Label { name: name.clone(), value: value.clone() });
+                }
+            }
+        }
+        prefixed
+    }
+}
+
+/// Info metrics whose labels describe a replica's build or configuration
+const FINGERPRINT_INFO_SUFFIXES: &[&str] = &["_build_info", "_config_info"];
+
+/// Metric name fragment of gauges reporting a configured TTL
+const FINGERPRINT_TTL_FRAGMENT: &str = "_ttl";
+
+impl MetricsSnapshot {
+    /// Build and configuration as reported by the scrape: every label of `*_build_info` and
+    /// `*_config_info` series, and the value of every TTL gauge, keyed by series
+    ///
+    /// Empty when the server exports neither.
+    pub fn fingerprint(&self) -> BTreeMap<String, String> {
+        let mut fingerprint = BTreeMap::new();
+        for sample in &self.samples {
+            if FINGERPRINT_INFO_SUFFIXES.iter().any(|suffix| sample.name.ends_with(suffix)) {
+                for (label, value) in &sample.labels {
+                    fingerprint.insert(format!("{}.{}", sample.name, label), value.clone());
+                }
+            } else if sample.name.contains(FINGERPRINT_TTL_FRAGMENT) {
+                let labels: Vec<String> = sample
+                    .labels
+                    .iter()
+                    .map(|(name, value)| format!("{}={}", name, value))
+                    .collect();
+                let key = format!("{}{{{}}}", sample.name, labels.join(","));
+                fingerprint.insert(key, sample.value.to_string());
+            }
+        }
+        fingerprint
+    }
+}
+
+/// Keys whose values differ between fingerprints (including keys some lack), with each value
+/// per fingerprint name
+pub fn fingerprint_drift(
+    fingerprints: &[(String, BTreeMap<String, String>)],
+) -> BTreeMap<String, Vec<(String, Option<String>)>> {
+    let keys: std::collections::BTreeSet<&String> =
+        fingerprints.iter().flat_map(|(_, fingerprint)| fingerprint.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let values: Vec<(String, Option<String>)> = fingerprints
+                .iter()
+                .map(|(name, fingerprint)| (name.clone(), fingerprint.get(key).cloned()))
+                .collect();
+            let first = &values.first()?.1;
+            values.iter().any(|(_, value)| value != first).then(|| (key.clone(), values))
+        })
+        .collect()
+}
+
+/// Labels identifying a fixture's resources, where the server emits them
+#[derive(Debug, Clone, Default)]
+pub struct TenantLabels {
+    pub org: Option<Label>,
+    pub client: Option<Label>,
+    pub vault: Option<Label>,
+}
+
+impl TenantLabels {
+    pub fn resolve(snapshot: &MetricsSnapshot, org_id: i64, client_id: i64, vault_id: i64) -> Self {
+        Self {
+            org: snapshot.resolve_label("org", org_id),
+            client: snapshot.resolve_label("client", client_id),
+            vault: snapshot.resolve_label("vault", vault_id),
+        }
+    }
+}
+
+fn parse_sample(line: &str) -> Option<Sample> {
+    let (series, rest) = match line.find('{') {
+        Some(open) => {
+            let close = open + line[open..].find('}')?;
+            (&line[..close + 1], &line[close + 1..])
+        },
+        None => line.split_once(char::is_whitespace)?,
+    };
+    let value = rest.split_whitespace().next()?.parse().ok()?;
+
+    let (name, labels) = match series.split_once('{') {
+        Some((name, labels)) => (name, parse_labels(labels.strip_suffix('}')?)?),
+        None => (series, BTreeMap::new()),
+    };
+    Some(Sample { name: name.to_string(), labels, value })
+}
+
+fn parse_labels(body: &str) -> Option<BTreeMap<String, String>> {
+    let mut labels = BTreeMap::new();
+    let mut rest = body.trim();
+    while !rest.is_empty() {
+        let (name, after) = rest.split_once('=')?;
+        let after = after.trim_start().strip_prefix('"')?;
+
+        // Values may contain escaped quotes
+        let mut value = String::new();
+        let mut chars = after.char_indices();
+        let end = loop {
+            match chars.next()? {
+                (i, '"') => break i,
+                (_, '\\') => value.push(chars.next()?.1),
+                (_, c) => value.push(c),
+            }
+        };
+        labels.insert(name.trim().to_string(), value);
+        rest = after[end + 1..].trim_start().trim_start_matches(',').trim_start();
+    }
+    Some(labels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_fingerprint_detects_replica_drift() {
+        let scrape = |version: &str, ttl: &str| {
+            MetricsSnapshot::parse(&format!(
+                "# HELP engine_build_info Build metadata\n\
+                 engine_build_info{{version=\"{}\",commit=\"abc123\"}} 1\n\
+                 engine_cache_ttl_seconds{{cache=\"keys\"}} {}\n\
+                 engine_requests_total{{vault=\"1\"}} 42\n",
+                version, ttl
+            ))
+            .fingerprint()
+        };
+
+        let fingerprint = scrape("1.4.0", "300");
+        assert_eq!(fingerprint.get("engine_build_info.version").map(String::as_str), Some("1.4.0"));
+        assert_eq!(
+            fingerprint.get("engine_cache_ttl_seconds{cache=keys}").map(String::as_str),
+            Some("300")
+        );
+        assert_eq!(fingerprint.len(), 3, "Counters should not be part of the fingerprint");
+
+        let same =
+            [("a".to_string(), scrape("1.4.0", "300")), ("b".to_string(), scrape("1.4.0", "300"))];
+        assert!(fingerprint_drift(&same).is_empty());
+
+        let mixed = [
+            ("a".to_string(), scrape("1.4.0", "300")),
+            ("b".to_string(), scrape("1.5.0", "300")),
+            ("c".to_string(), scrape("1.4.0", "60")),
+        ];
+        let drift = fingerprint_drift(&mixed);
+        assert_eq!(
+            drift.keys().map(String::as_str).collect::<Vec<_>>(),
+            vec!["engine_build_info.version", "engine_cache_ttl_seconds{cache=keys}"]
+        );
+    }
+}
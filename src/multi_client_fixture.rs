@@ -0,0 +1,38 @@
+// Multi-client fixture
+//
+// Two clients of one organization, each with its own certificate, both bound to the same vault.
+// Exercises the topology of a service fleet where several independently credentialed clients
+// share data: per-client attribution, revoking one identity without affecting the other, and
+// concurrent writes from distinct identities.
+
+use super::*;
+
+/// Two independently credentialed clients sharing one vault
+///
+/// `primary` owns the user, organization, and vault. `secondary` is a view over the same
+/// resources with its own client and certificate; it is marked persistent so that dropping it
+/// never tears down what `primary` owns. Call `cleanup` to remove both.
+pub struct MultiClientFixture {
+    pub primary: TestFixture,
+    pub secondary: TestFixture,
+}
+
+/// Provision a fixture with a second client on the same vault
+pub async fn multi_client_fixture() -> Result<MultiClientFixture> {
+    let primary = TestFixture::create().await?;
+    let secondary = primary.add_client().await?;
+    Ok(MultiClientFixture { primary, secondary })
+}
+
+impl MultiClientFixture {
+    /// Both fixtures, primary first
+    pub fn clients(&self) -> [&TestFixture; 2] {
+        [&self.primary, &self.secondary]
+    }
+
+    pub async fn cleanup(self) -> Result<()> {
+        let control = self.primary.control();
+        let _ = control.delete_client(self.primary.org_id, self.secondary.client_id).await;
+        self.primary.cleanup().await
+    }
+}
@@ -0,0 +1,100 @@
+// Test entity naming
+//
+// Every user, vault, client, and certificate the suite creates is named here, tagged with the ID
+// of the run that created it, so tooling can attribute a resource to one suite execution: the
+// `sweep` binary can clean up after a single crashed run, and logs can be filtered to one run's
+// tenants. Names look like `Test Vault run-1a2b3c4d <uuid>`, emails like
+// `test-run-1a2b3c4d-<uuid>@example.com`.
+//
+//   INFERADB_RUN_ID   Run ID suffix, e.g. a CI job number (default: random per process)
+
+use super::*;
+
+/// Domain of every email address the suite registers
+pub const EMAIL_DOMAIN: &str = "@example.com";
+
+/// Marks the start of the run ID inside a name or email
+const RUN_ID_PREFIX: &str = "run-";
+
+/// Name prefixes used before names carried a run ID, still recognized as test entities
+const LEGACY_NAME_PREFIXES: &[&str] = &["Test ", "Smoke ", "Scenario ", "journey-test-"];
+
+static RUN_ID: OnceLock<String> = OnceLock::new();
+
+/// The ID tagging every entity this process creates, e.g. `run-1a2b3c4d`
+pub fn run_id() -> &'static str {
+    RUN_ID.get_or_init(|| {
+        run_id_for(&std::env::var("INFERADB_RUN_ID").unwrap_or_default())
+            .unwrap_or_else(|| format!("{}{:08x}", RUN_ID_PREFIX, rand::rng().next_u32()))
+    })
+}
+
+/// The run ID a process given INFERADB_RUN_ID=`suffix` tags its entities with, if any
+pub fn run_id_for(suffix: &str) -> Option<String> {
+    let suffix: String = suffix.chars().filter(char::is_ascii_alphanumeric).collect();
+    (!suffix.is_empty()).then(|| format!("{}{}", RUN_ID_PREFIX, suffix))
+}
+
+/// Unique display name for an entity, e.g. `entity_name("Test Vault")`
+pub fn entity_name(label: &str) -> String {
+    format!("{} {} {}", label, run_id(), Uuid::new_v4())
+}
+
+/// Unique email address for a registered user, e.g. `email("test")`
+pub fn email(label: &str) -> String {
+    format!("{}-{}-{}{}", label, run_id(), Uuid::new_v4(), EMAIL_DOMAIN)
+}
+
+/// The run ID embedded in a name or email created by `entity_name` or `email`
+pub fn run_id_of(name: &str) -> Option<&str> {
+    let mut rest = name;
+    while let Some(start) = rest.find(RUN_ID_PREFIX) {
+        let preceded_by_boundary =
+            rest[..start].chars().next_back().is_none_or(|c| c == ' ' || c == '-');
+        let candidate = &rest[start..];
+        let end = RUN_ID_PREFIX.len()
+            + candidate[RUN_ID_PREFIX.len()..]
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(candidate.len() - RUN_ID_PREFIX.len());
+        if preceded_by_boundary && end > RUN_ID_PREFIX.len() {
+            return Some(&candidate[..end]);
+        }
+        rest = &rest[start + RUN_ID_PREFIX.len()..];
+    }
+    None
+}
+
+/// Whether a vault, client, or organization name follows the suite's conventions
+pub fn is_test_name(name: &str) -> bool {
+    run_id_of(name).is_some() || LEGACY_NAME_PREFIXES.iter().any(|prefix| name.starts_with(prefix))
+}
+
+/// Whether an email address is one the suite registers
+pub fn is_test_email(email: &str) -> bool {
+    email.to_lowercase().ends_with(EMAIL_DOMAIN)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_naming_tags_entities_with_run_id() {
+        let run_id = run_id();
+        assert!(run_id.starts_with("run-"), "Unexpected run ID format: {}", run_id);
+
+        let name = entity_name("Test Vault");
+        let email = email("test");
+        assert_eq!(run_id_of(&name), Some(run_id));
+        assert_eq!(run_id_of(&email), Some(run_id));
+        assert!(is_test_name(&name));
+        assert!(is_test_email(&email));
+        assert_ne!(name, entity_name("Test Vault"), "Names must be unique");
+
+        // Legacy names are still test entities, but belong to no run
+        assert!(is_test_name("Test Vault 7c9e6679-7425-40de-944b-e07fc1f90ae7"));
+        assert_eq!(run_id_of("Test Vault 7c9e6679-7425-40de-944b-e07fc1f90ae7"), None);
+        assert_eq!(run_id_of("Production rerun-42"), None);
+        assert!(!is_test_name("Production"));
+    }
+}
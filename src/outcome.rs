@@ -0,0 +1,197 @@
+// Test outcomes
+//
+// libtest only knows pass and fail, but many tests pass in a weaker sense: they skip because the
+// environment lacks a feature, or they pass with a warning ("may still be propagating") instead of
+// asserting. Such tests call `skip` or `warn`, which print the message as before and record it
+// against the current test (the recorder's scope), so the run can say what it did not verify.
+//
+// With `INFERADB_OUTCOME_REPORT` set, the recorded outcomes are written there as JSON, rewritten on
+// every record so the file is complete when the run ends; the `tagged` runner sets it and prints
+// the summary. Tests that record nothing passed outright and are not listed.
+//
+// `STRICT_MODE=1` turns every warning into a failure, for release-qualification runs where a
+// degraded pass is not good enough. Skips still skip: they mean the environment cannot run the
+// test, not that the test saw something wrong.
+
+use std::{collections::BTreeMap, fmt::Write as _, sync::Mutex};
+
+use super::*;
+
+static OUTCOMES: Mutex<BTreeMap<String, Outcome>> = Mutex::new(BTreeMap::new());
+
+/// How a test finished, beyond libtest's pass or fail
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "outcome", rename_all = "snake_case")]
+pub enum Outcome {
+    Passed,
+    PassedWithWarnings { warnings: Vec<String> },
+    Skipped { reason: String },
+}
+
+/// One test's outcome in the report
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestOutcome {
+    pub test: String,
+    #[serde(flatten)]
+    pub outcome: Outcome,
+}
+
+/// The JSON report artifact
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OutcomeReport {
+    pub run_id: String,
+    pub strict: bool,
+    /// Tests that skipped or passed with warnings, by name
+    pub tests: Vec<TestOutcome>,
+}
+
+/// Whether warnings fail the test (`STRICT_MODE=1`)
+pub fn is_strict() -> bool {
+    std::env::var("STRICT_MODE").is_ok_and(|value| value == "1" || value == "true")
+}
+
+/// Skip the rest of the current test for `reason`; the caller returns afterwards
+pub fn skip(reason: impl Into<String>) {
+    let reason = reason.into();
+    eprintln!("{}", reason);
+    record(Outcome::Skipped { reason });
+}
+
+/// Note something the current test could not verify, or failed softly
+///
+/// Panics instead under `STRICT_MODE`.
+#[track_caller]
+pub fn warn(message: impl Into<String>) {
+    let message = message.into();
+    if is_strict() {
+        panic!("STRICT_MODE: {}", message);
+    }
+    println!("⚠ {}", message);
+    record(Outcome::PassedWithWarnings { warnings: vec![message] });
+}
+
+/// The outcome recorded for `test`, `Passed` if nothing was
+pub fn of(test: &str) -> Outcome {
+    OUTCOMES.lock().unwrap_or_else(|e| e.into_inner()).get(test).cloned().unwrap_or(Outcome::Passed)
+}
+
+fn record(outcome: Outcome) {
+    // The report is written under the lock, so a slower writer can't overwrite a newer report
+    let mut outcomes = OUTCOMES.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = outcomes.entry(recorder::current_scope()).or_insert(Outcome::Passed);
+    *entry = merge(std::mem::replace(entry, Outcome::Passed), outcome);
+    let report = report(
+        outcomes
+            .iter()
+            .map(|(test, outcome)| TestOutcome { test: test.clone(), outcome: outcome.clone() })
+            .collect(),
+    );
+    if let Err(e) = write_report(&report) {
+        eprintln!("Warning: {:#}", e);
+    }
+}
+
+/// A skip outranks warnings; warnings accumulate
+pub fn merge(current: Outcome, new: Outcome) -> Outcome {
+    match (current, new) {
+        (Outcome::Skipped { reason }, _) | (_, Outcome::Skipped { reason }) => {
+            Outcome::Skipped { reason }
+        },
+        (
+            Outcome::PassedWithWarnings { mut warnings },
+            Outcome::PassedWithWarnings { warnings: more },
+        ) => {
+            warnings.extend(more);
+            Outcome::PassedWithWarnings { warnings }
+        },
+        (Outcome::Passed, outcome) | (outcome, Outcome::Passed) => outcome,
+    }
+}
+
+/// Report of `tests`, in name order
+pub fn report(mut tests: Vec<TestOutcome>) -> OutcomeReport {
+    tests.sort_by(|a, b| a.test.cmp(&b.test));
+    OutcomeReport { run_id: naming::run_id().to_string(), strict: is_strict(), tests }
+}
+
+/// Human-readable summary: counts, then every skip and warning by test
+pub fn summarize(report: &OutcomeReport) -> String {
+    let skipped: Vec<_> = report
+        .tests
+        .iter()
+        .filter_map(|t| match &t.outcome {
+            Outcome::Skipped { reason } => Some((&t.test, reason)),
+            _ => None,
+        })
+        .collect();
+    let warned: Vec<_> = report
+        .tests
+        .iter()
+        .filter_map(|t| match &t.outcome {
+            Outcome::PassedWithWarnings { warnings } => Some((&t.test, warnings)),
+            _ => None,
+        })
+        .collect();
+
+    let mut summary = format!("{} skipped, {} passed with warnings\n", skipped.len(), warned.len());
+    for (test, reason) in skipped {
+        let _ = writeln!(summary, "  SKIPPED {}: {}", test, reason);
+    }
+    for (test, warnings) in warned {
+        for warning in warnings {
+            let _ = writeln!(summary, "  WARNING {}: {}", test, warning);
+        }
+    }
+    summary
+}
+
+fn write_report(report: &OutcomeReport) -> Result<()> {
+    let Some(path) = std::env::var("INFERADB_OUTCOME_REPORT").ok().filter(|p| !p.trim().is_empty())
+    else {
+        return Ok(());
+    };
+    let path = PathBuf::from(path);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(report)?)
+        .with_context(|| format!("Failed to write outcome report {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outcomes_merge_and_summarize() {
+        let warned = |warning: &str| Outcome::PassedWithWarnings { warnings: vec![warning.into()] };
+        let skipped = |reason: &str| Outcome::Skipped { reason: reason.into() };
+
+        assert_eq!(merge(Outcome::Passed, warned("a")), warned("a"));
+        assert_eq!(
+            merge(warned("a"), warned("b")),
+            Outcome::PassedWithWarnings { warnings: vec!["a".into(), "b".into()] },
+            "Warnings accumulate"
+        );
+        assert_eq!(
+            merge(warned("a"), skipped("gone")),
+            skipped("gone"),
+            "A skip outranks warnings"
+        );
+        assert_eq!(merge(skipped("first"), skipped("second")), skipped("first"));
+
+        let report = report(vec![
+            TestOutcome { test: "b::test".into(), outcome: skipped("Skipping b - no endpoint") },
+            TestOutcome { test: "a::test".into(), outcome: warned("slow") },
+        ]);
+        assert_eq!(report.tests[0].test, "a::test", "Report is in name order");
+        let json = serde_json::to_value(&report.tests[1]).expect("Outcome serializes");
+        assert_eq!(json["outcome"], "skipped");
+        assert_eq!(json["reason"], "Skipping b - no endpoint");
+
+        let summary = summarize(&report);
+        assert!(summary.starts_with("1 skipped, 1 passed with warnings\n"), "{}", summary);
+        assert!(summary.contains("SKIPPED b::test: Skipping b - no endpoint"), "{}", summary);
+        assert!(summary.contains("WARNING a::test: slow"), "{}", summary);
+    }
+}
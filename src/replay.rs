@@ -0,0 +1,227 @@
+// Shadow-Traffic Replay
+//
+// Sends the Engine traffic in a capture file (see `recorder`) to a target environment again, in
+// capture order, and reports every request whose status or evaluate decisions differ from what
+// was captured. Production-shaped traffic captured once can then gate a new build.
+//
+// Requests are re-authenticated with the target's client JWT, so Engine resolves the target vault
+// from the token; the capture must include the relationship writes its reads depend on. Control
+// requests name the capture environment's organizations, vaults, and sessions and are skipped.
+// Pacing keeps the captured gaps between requests, divided by `speed`.
+
+use std::{fmt, time::Instant};
+
+use chrono::DateTime;
+use reqwest::Method;
+
+use super::{recorder::Exchange, *};
+
+/// Headers replayed as captured; credentials are replaced and the rest are recomputed by reqwest
+const REPLAYED_HEADERS: &[&str] = &["content-type", "accept"];
+
+/// The Engine path (after `/access/{version}`) of a captured URL, with its query string
+pub fn engine_path(url: &str) -> Option<String> {
+    let (_, rest) = url.split_once("/access/")?;
+    let (_version, path) = rest.split_once('/')?;
+    Some(format!("/{}", path))
+}
+
+/// Decisions in an evaluate response body, in request order
+fn decisions(body: &str) -> Option<Vec<String>> {
+    let response: EvaluateResponse = serde_json::from_str(body).ok()?;
+    Some(response.decisions().into_iter().map(str::to_string).collect())
+}
+
+/// A replayed request whose answer differs from the capture
+#[derive(Debug, Clone)]
+pub struct Divergence {
+    /// Position in the capture file
+    pub index: usize,
+    pub request: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "#{} {}: captured {}, replayed {}",
+            self.index, self.request, self.expected, self.actual
+        )
+    }
+}
+
+/// Compare a replayed answer with a captured one; decisions are compared for evaluate calls whose
+/// captured and replayed bodies both parse
+pub fn diverges(
+    index: usize,
+    captured: &Exchange,
+    status: Option<u16>,
+    body: Option<&str>,
+) -> Option<Divergence> {
+    let request = format!(
+        "{} {}",
+        captured.method,
+        engine_path(&captured.url).unwrap_or_else(|| captured.url.clone())
+    );
+    let divergence = |expected: String, actual: String| {
+        Some(Divergence { index, request: request.clone(), expected, actual })
+    };
+    let describe = |status: Option<u16>| {
+        status.map_or_else(|| "no response".to_string(), |status| format!("HTTP {}", status))
+    };
+
+    if captured.status != status {
+        return divergence(describe(captured.status), describe(status));
+    }
+    if request.ends_with("/evaluate")
+        && let (Some(expected), Some(actual)) =
+            (captured.response_body.as_deref().and_then(decisions), body.and_then(decisions))
+        && expected != actual
+    {
+        return divergence(format!("{:?}", expected), format!("{:?}", actual));
+    }
+    None
+}
+
+/// Outcome of replaying a capture
+#[derive(Debug, Clone, Default)]
+pub struct ReplayReport {
+    pub replayed: usize,
+    /// Control requests and requests that never got a response when captured
+    pub skipped: usize,
+    pub divergences: Vec<Divergence>,
+}
+
+/// Replays captured Engine traffic against a target environment
+pub struct Replayer {
+    target: TestContext,
+    jwt: String,
+    speed: Option<f64>,
+}
+
+impl Replayer {
+    /// Replay against `target` as fast as possible, authenticated with `jwt`
+    pub fn new(target: TestContext, jwt: impl Into<String>) -> Self {
+        Self { target, jwt: jwt.into(), speed: None }
+    }
+
+    /// Keep the captured gaps between requests, divided by `speed` (1.0 is original pacing)
+    pub fn speed(mut self, speed: f64) -> Self {
+        self.speed = (speed > 0.0).then_some(speed);
+        self
+    }
+
+    /// When `exchange` should be sent, relative to the start of the replay
+    fn offset(&self, first: Option<DateTime<Utc>>, exchange: &Exchange) -> std::time::Duration {
+        let (Some(speed), Some(first)) = (self.speed, first) else {
+            return std::time::Duration::ZERO;
+        };
+        let Ok(started_at) = DateTime::parse_from_rfc3339(&exchange.started_at) else {
+            return std::time::Duration::ZERO;
+        };
+        let gap = (started_at.with_timezone(&Utc) - first).to_std().unwrap_or_default();
+        gap.div_f64(speed)
+    }
+
+    /// Send one captured request to the target, returning its status and body
+    async fn send(&self, exchange: &Exchange, path: &str) -> (Option<u16>, Option<String>) {
+        let Ok(method) = Method::from_bytes(exchange.method.as_bytes()) else {
+            return (None, None);
+        };
+        let mut builder = self
+            .target
+            .client
+            .request(method, self.target.engine_url(path))
+            .header("Authorization", format!("Bearer {}", self.jwt));
+        for (name, value) in &exchange.headers {
+            if REPLAYED_HEADERS.contains(&name.to_ascii_lowercase().as_str()) {
+                builder = builder.header(name, value);
+            }
+        }
+        if let Some(body) = &exchange.body {
+            builder = builder.body(body.clone());
+        }
+
+        match builder.send().await {
+            Ok(response) => {
+                let status = response.status().as_u16();
+                (Some(status), response.text().await.ok())
+            },
+            Err(_) => (None, None),
+        }
+    }
+
+    /// Replay `exchanges` one at a time in capture order
+    pub async fn replay(&self, exchanges: &[Exchange]) -> ReplayReport {
+        let first = exchanges.iter().find_map(|exchange| {
+            DateTime::parse_from_rfc3339(&exchange.started_at)
+                .ok()
+                .map(|started_at| started_at.with_timezone(&Utc))
+        });
+        let start = Instant::now();
+        let mut report = ReplayReport::default();
+
+        for (index, exchange) in exchanges.iter().enumerate() {
+            let path = engine_path(&exchange.url);
+            let Some(path) = path.filter(|_| exchange.status.is_some()) else {
+                report.skipped += 1;
+                continue;
+            };
+
+            let due = self.offset(first, exchange);
+            if let Some(wait) = due.checked_sub(start.elapsed()) {
+                tokio::time::sleep(wait).await;
+            }
+
+            let (status, body) = self.send(exchange, &path).await;
+            report.replayed += 1;
+            if let Some(divergence) = diverges(index, exchange, status, body.as_deref()) {
+                report.divergences.push(divergence);
+            }
+        }
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_replay_compares_status_and_decisions() {
+        let captured = |status: u16, response_body: &str| recorder::Exchange {
+            scope: "example".to_string(),
+            started_at: "2024-01-01T00:00:00Z".to_string(),
+            elapsed_ms: 5.0,
+            method: "POST".to_string(),
+            url: "https://api.example.com/access/v1/evaluate".to_string(),
+            headers: Vec::new(),
+            body: None,
+            status: Some(status),
+            error: None,
+            response_body: Some(response_body.to_string()),
+        };
+        let allow = r#"{"results":[{"decision":"ALLOW"}]}"#;
+        let deny = r#"{"results":[{"decision":"DENY"}]}"#;
+
+        assert_eq!(
+            engine_path("https://api.example.com/access/v1/relationships?limit=10").as_deref(),
+            Some("/relationships?limit=10")
+        );
+        assert_eq!(engine_path("https://api.example.com/control/v1/organizations"), None);
+
+        assert!(diverges(0, &captured(200, allow), Some(200), Some(allow)).is_none());
+        let decision = diverges(1, &captured(200, allow), Some(200), Some(deny))
+            .expect("A flipped decision should diverge");
+        assert_eq!(decision.request, "POST /evaluate");
+        let status = diverges(2, &captured(200, allow), Some(403), Some("{}"))
+            .expect("A different status should diverge");
+        assert_eq!((status.expected.as_str(), status.actual.as_str()), ("HTTP 200", "HTTP 403"));
+        assert!(
+            diverges(3, &captured(200, "not json"), Some(200), Some(deny)).is_none(),
+            "Bodies that do not parse are compared by status only"
+        );
+    }
+}
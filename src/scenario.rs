@@ -0,0 +1,329 @@
+// Scenario DSL
+//
+// A scenario is a named, ordered list of steps run against a freshly provisioned fixture. Each
+// iteration provisions its own fixture, runs the steps until one fails, runs the teardown steps
+// regardless, and cleans up. Step latencies are aggregated across iterations into a
+// `ScenarioReport`, which is checked against an `Slo` and serialized as the JSON report artifact.
+// With the request recorder on, every failed iteration also leaves a curl reproduction script.
+//
+//     Scenario::new("isolation", "Vault B cannot read vault A's relationships")
+//         .step("seed vault A", seed_vault_a)
+//         .step("create vault B", create_vault_b)
+//         .step("vault B denied", vault_b_denied)
+//         .teardown("delete vault B", delete_vault_b);
+
+use std::{
+    collections::{BTreeSet, HashMap},
+    future::Future,
+    pin::Pin,
+    time::Instant,
+};
+
+use super::*;
+
+/// Future returned by a scenario step, borrowing the run it advances
+pub type StepFuture<'a> = Pin<Box<dyn Future<Output = Result<()>> + Send + 'a>>;
+
+/// A scenario step: an async function over the current run
+pub type StepFn = for<'a> fn(&'a mut ScenarioRun) -> StepFuture<'a>;
+
+/// State threaded through the steps of one iteration
+pub struct ScenarioRun {
+    pub fixture: TestFixture,
+    /// IDs created by earlier steps (e.g. a second vault) for later steps and teardown
+    pub ids: HashMap<&'static str, i64>,
+}
+
+impl ScenarioRun {
+    /// Look up an ID recorded by an earlier step
+    pub fn id(&self, name: &str) -> Result<i64> {
+        self.ids
+            .get(name)
+            .copied()
+            .with_context(|| format!("No '{}' recorded by earlier steps", name))
+    }
+}
+
+/// A named workflow of steps
+pub struct Scenario {
+    pub name: &'static str,
+    pub description: &'static str,
+    steps: Vec<(&'static str, StepFn)>,
+    teardown: Vec<(&'static str, StepFn)>,
+}
+
+impl Scenario {
+    pub fn new(name: &'static str, description: &'static str) -> Self {
+        Self { name, description, steps: Vec::new(), teardown: Vec::new() }
+    }
+
+    /// Append a timed step; a failing step ends the iteration
+    pub fn step(mut self, name: &'static str, step: StepFn) -> Self {
+        self.steps.push((name, step));
+        self
+    }
+
+    /// Append an untimed step that always runs after the iteration, even on failure
+    pub fn teardown(mut self, name: &'static str, step: StepFn) -> Self {
+        self.teardown.push((name, step));
+        self
+    }
+
+    /// Run `iterations` iterations, each against its own fixture
+    pub async fn run(&self, iterations: usize) -> ScenarioReport {
+        let mut samples: Vec<StepSamples> =
+            self.steps.iter().map(|(name, _)| StepSamples::new(name)).collect();
+        let mut passed_iterations = 0;
+        let mut reproductions = Vec::new();
+
+        for iteration in 1..=iterations {
+            let scope = format!("{}-iteration-{}", self.name, iteration);
+            if recorder::is_enabled() {
+                recorder::set_scope(Some(scope.clone()));
+            }
+            let fixture = match TestFixture::create().await {
+                Ok(fixture) => fixture,
+                Err(e) => {
+                    println!("  ✗ iteration {}: failed to provision fixture: {:#}", iteration, e);
+                    // The provisioning requests are the reproduction; none may leak into the next
+                    // iteration's scope
+                    if recorder::is_enabled() {
+                        let exchanges = recorder::take(&scope);
+                        write_reproduction(&scope, iteration, &exchanges, &mut reproductions);
+                        recorder::set_scope(None);
+                    }
+                    continue;
+                },
+            };
+            let mut run = ScenarioRun { fixture, ids: HashMap::new() };
+
+            let mut passed = true;
+            for ((name, step), samples) in self.steps.iter().zip(samples.iter_mut()) {
+                let start = Instant::now();
+                let result = step(&mut run).await;
+                let elapsed_ms = start.elapsed().as_secs_f64() * 1000.0;
+                match result {
+                    Ok(()) => samples.latencies_ms.push(elapsed_ms),
+                    Err(e) => {
+                        println!("  ✗ iteration {}: {}: {:#}", iteration, name, e);
+                        samples.failures.push(format!("{:#}", e));
+                        passed = false;
+                        break;
+                    },
+                }
+            }
+
+            // Only the provisioning and steps; teardown is noise in a reproduction
+            if recorder::is_enabled() {
+                let exchanges = recorder::take(&scope);
+                if !passed {
+                    write_reproduction(&scope, iteration, &exchanges, &mut reproductions);
+                }
+            }
+
+            for (name, step) in &self.teardown {
+                if let Err(e) = step(&mut run).await {
+                    eprintln!("  ⚠ iteration {}: teardown '{}' failed: {:#}", iteration, name, e);
+                }
+            }
+            if let Err(e) = run.fixture.cleanup().await {
+                eprintln!("  ⚠ iteration {}: cleanup failed: {:#}", iteration, e);
+            }
+
+            if recorder::is_enabled() {
+                recorder::take(&scope);
+                recorder::set_scope(None);
+            }
+
+            if passed {
+                passed_iterations += 1;
+                println!("  ✓ iteration {}", iteration);
+            }
+        }
+
+        ScenarioReport {
+            scenario: self.name.to_string(),
+            iterations,
+            passed_iterations,
+            steps: samples.iter().map(StepSamples::report).collect(),
+            violations: Vec::new(),
+            reproductions,
+        }
+    }
+}
+
+/// Raw per-step results collected across iterations
+struct StepSamples {
+    name: &'static str,
+    latencies_ms: Vec<f64>,
+    failures: Vec<String>,
+}
+
+impl StepSamples {
+    fn new(name: &'static str) -> Self {
+        Self { name, latencies_ms: Vec::new(), failures: Vec::new() }
+    }
+
+    fn report(&self) -> StepReport {
+        let mut sorted = self.latencies_ms.clone();
+        sorted.sort_by(f64::total_cmp);
+
+        let mut seen = BTreeSet::new();
+        let errors =
+            self.failures.iter().filter(|error| seen.insert(error.as_str())).cloned().collect();
+
+        StepReport {
+            step: self.name.to_string(),
+            runs: self.latencies_ms.len() + self.failures.len(),
+            failures: self.failures.len(),
+            p50_ms: percentile(&sorted, 0.50),
+            p95_ms: percentile(&sorted, 0.95),
+            max_ms: sorted.last().copied(),
+            errors,
+        }
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice
+pub fn percentile(sorted: &[f64], quantile: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (quantile * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+/// Latency and failure summary for one step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepReport {
+    pub step: String,
+    pub runs: usize,
+    pub failures: usize,
+    pub p50_ms: Option<f64>,
+    pub p95_ms: Option<f64>,
+    pub max_ms: Option<f64>,
+    /// Distinct error messages, in order of first occurrence
+    pub errors: Vec<String>,
+}
+
+/// Result of running one scenario
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioReport {
+    pub scenario: String,
+    pub iterations: usize,
+    pub passed_iterations: usize,
+    pub steps: Vec<StepReport>,
+    /// SLO breaches; empty when the scenario met its objectives
+    pub violations: Vec<String>,
+    /// Reproduction scripts written for failed iterations (see `recorder`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reproductions: Vec<String>,
+}
+
+impl ScenarioReport {
+    pub fn passed(&self) -> bool {
+        self.violations.is_empty()
+    }
+
+    /// Record every breach of `slo` in `violations`
+    pub fn check(&mut self, slo: &Slo) {
+        let failed = self.iterations - self.passed_iterations;
+        let failure_rate =
+            if self.iterations == 0 { 0.0 } else { failed as f64 / self.iterations as f64 };
+        if failure_rate > slo.max_failure_rate {
+            self.violations.push(format!(
+                "{}/{} iterations failed (max failure rate {})",
+                failed, self.iterations, slo.max_failure_rate
+            ));
+        }
+
+        if let Some(threshold) = slo.p95_ms {
+            for step in &self.steps {
+                if let Some(p95) = step.p95_ms
+                    && p95 > threshold
+                {
+                    self.violations.push(format!(
+                        "'{}' p95 {:.1}ms exceeds {:.1}ms",
+                        step.step, p95, threshold
+                    ));
+                }
+            }
+        }
+    }
+}
+
+/// Service-level objectives a scenario run is held to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Slo {
+    /// Maximum p95 latency for any single step
+    pub p95_ms: Option<f64>,
+    /// Fraction of iterations allowed to fail
+    pub max_failure_rate: f64,
+}
+
+impl Default for Slo {
+    fn default() -> Self {
+        Self { p95_ms: None, max_failure_rate: 0.0 }
+    }
+}
+
+/// The JSON report artifact covering a whole invocation
+#[derive(Debug, Clone, Serialize)]
+pub struct RunReport {
+    pub api_base_url: String,
+    /// Engine and Control versions the run was made against
+    pub build: BuildInfo,
+    pub profile: EnvProfile,
+    pub slo: Slo,
+    pub started_at: String,
+    pub scenarios: Vec<ScenarioReport>,
+}
+
+impl RunReport {
+    pub fn passed(&self) -> bool {
+        self.scenarios.iter().all(ScenarioReport::passed)
+    }
+
+    /// Write the report as pretty-printed JSON, creating parent directories
+    pub fn write(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_vec_pretty(self)?)
+            .with_context(|| format!("Failed to write scenario report {}", path.display()))
+    }
+}
+
+/// Write a failed iteration's exchanges as a repro script, noting its path
+fn write_reproduction(
+    scope: &str,
+    iteration: usize,
+    exchanges: &[recorder::Exchange],
+    reproductions: &mut Vec<String>,
+) {
+    match recorder::write_repro_script(scope, exchanges) {
+        Ok(path) => {
+            println!("    reproduction: {}", path.display());
+            reproductions.push(path.display().to_string());
+        },
+        Err(e) => eprintln!("  ⚠ iteration {}: {:#}", iteration, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_step_errors_are_distinct_in_order_of_first_occurrence() {
+        let mut samples = StepSamples::new("read");
+        for error in ["timeout", "403", "timeout", "403", "500"] {
+            samples.failures.push(error.to_string());
+        }
+        samples.latencies_ms.push(12.0);
+
+        let report = samples.report();
+        assert_eq!((report.runs, report.failures), (6, 5));
+        assert_eq!(report.errors, vec!["timeout", "403", "500"]);
+    }
+}
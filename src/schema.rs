@@ -0,0 +1,90 @@
+// Vault Schemas
+//
+// Schemas the suite deploys to vaults. Relationship writes are validated against the active
+// schema, so tests that exercise validation deploy one of these first through
+// `ControlClient::deploy_schema`.
+
+/// Document-sharing model: users hold `viewer`, `editor`, or `owner` on documents
+pub const DOCUMENT_SCHEMA: &str = r#"type user {}
+
+type document {
+    relation viewer: user
+    relation editor: user
+    relation owner: user
+
+    permission view = viewer + editor + owner
+    permission edit = editor + owner
+}
+"#;
+
+/// `DOCUMENT_SCHEMA` after a migration: adds a `commenter` relation and renames `view` to `read`
+pub const MIGRATED_DOCUMENT_SCHEMA: &str = r#"type user {}
+
+type document {
+    relation viewer: user
+    relation commenter: user
+    relation editor: user
+    relation owner: user
+
+    permission read = viewer + commenter + editor + owner
+    permission edit = editor + owner
+}
+"#;
+
+/// Role hierarchy: each permission builds on the one above it, so `owner` implies `edit` and
+/// `view`, and `editor` implies `view`
+pub const HIERARCHY_SCHEMA: &str = r#"type user {}
+
+type document {
+    relation viewer: user
+    relation editor: user
+    relation owner: user
+
+    permission own = owner
+    permission edit = editor + own
+    permission view = viewer + edit
+}
+"#;
+
+/// Group model: groups contain users and other groups' members, and documents grant `view`
+/// through either directly
+pub const GROUP_SCHEMA: &str = r#"type user {}
+
+type group {
+    relation member: user | group#member
+}
+
+type document {
+    relation viewer: user | group#member
+
+    permission view = viewer
+}
+"#;
+
+/// Exclusion model: `view` is granted to viewers unless they are also `banned`
+pub const EXCLUSION_SCHEMA: &str = r#"type user {}
+
+type document {
+    relation viewer: user
+    relation banned: user
+
+    permission view = viewer - banned
+}
+"#;
+
+/// Intersection model: `view` requires both a `viewer` grant and `cleared` status on the document
+pub const INTERSECTION_SCHEMA: &str = r#"type user {}
+
+type document {
+    relation viewer: user
+    relation cleared: user
+
+    permission view = viewer & cleared
+}
+"#;
+
+/// Schema deployment request
+#[derive(Debug, serde::Serialize)]
+pub struct DeploySchemaRequest {
+    pub definition: String,
+}
@@ -0,0 +1,220 @@
+// Suite-level shared fixture for read-only authentication checks
+//
+// Provisioning a fixture costs several Control round trips. Tests that only verify how Engine
+// treats a token (malformed JWTs, bad signatures, header handling) never change server state, so
+// they can share one fixture per test process. Tests that write data, revoke certificates, or
+// otherwise mutate state must keep using `TestFixture::create()`.
+//
+// A static is never dropped, so the fixture cannot delete itself when the process exits. Instead
+// it registers itself on creation, as `<run id>-<pid>.json` under INFERADB_SHARED_FIXTURE_DIR
+// (default: `target/shared-fixtures`), and `cleanup_registered` deletes a run's fixtures once its
+// test processes are done; the `tagged` runner calls it after every run. Outside `tagged`, the
+// fixture's resources carry the run ID like any other, so `sweep --run-id` removes them.
+
+use reqwest::{Method, RequestBuilder};
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+
+use super::*;
+
+/// Process-wide fixture, provisioned on first use
+static SHARED_FIXTURE: OnceCell<SharedFixture> = OnceCell::const_new();
+
+/// Scopes that would let a token mutate the shared vault
+pub(crate) const MUTATING_SCOPES: &[&str] =
+    &["inferadb.write", "inferadb.vault.manage", "inferadb.admin"];
+
+/// Engine endpoints that accept POST bodies but never change state
+const READ_ONLY_ENGINE_PATHS: &[&str] =
+    &["/evaluate", "/expand", "/relationships/list", "/resources/list", "/subjects/list"];
+
+/// Where shared fixtures are registered when INFERADB_SHARED_FIXTURE_DIR is unset
+const DEFAULT_REGISTRY_DIR: &str = "target/shared-fixtures";
+
+/// What `cleanup_registered` needs to delete a shared fixture; its session owns everything else
+#[derive(Debug, Serialize, Deserialize)]
+struct RegisteredFixture {
+    user_id: i64,
+    session_id: i64,
+    org_id: i64,
+    vault_id: i64,
+    client_id: i64,
+}
+
+fn registry_dir() -> PathBuf {
+    std::env::var("INFERADB_SHARED_FIXTURE_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_REGISTRY_DIR))
+}
+
+/// Record `fixture` for `cleanup_registered`; a fixture restored from a snapshot is kept instead
+fn register(fixture: &TestFixture) -> Result<()> {
+    if fixture.snapshot_backed {
+        return Ok(());
+    }
+    let dir = registry_dir();
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let record = RegisteredFixture {
+        user_id: fixture.user_id,
+        session_id: fixture.session_id,
+        org_id: fixture.org_id,
+        vault_id: fixture.vault_id,
+        client_id: fixture.client_id,
+    };
+    let path = dir.join(format!("{}-{}.json", naming::run_id(), std::process::id()));
+    std::fs::write(&path, serde_json::to_vec(&record)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Delete every shared fixture registered by processes of `run_id`, returning how many
+///
+/// Call only once those processes have exited: their fixtures are still in use until then.
+pub async fn cleanup_registered(run_id: &str) -> Result<usize> {
+    let dir = registry_dir();
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e).with_context(|| format!("Failed to read {}", dir.display())),
+    };
+    let prefix = format!("{}-", run_id);
+
+    let mut removed = 0;
+    for entry in entries {
+        let path = entry?.path();
+        let registered = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with(&prefix) && name.ends_with(".json"));
+        if !registered {
+            continue;
+        }
+        let record: RegisteredFixture = serde_json::from_slice(&std::fs::read(&path)?)
+            .with_context(|| format!("Invalid shared fixture record {}", path.display()))?;
+
+        let control = ControlClient::with_session(TestContext::new(), record.session_id);
+        let _ = control.delete_vault(record.org_id, record.vault_id).await;
+        let _ = control.delete_client(record.org_id, record.client_id).await;
+        let _ = control.delete_organization(record.org_id).await;
+        let _ = control.delete_user(record.user_id).await;
+        std::fs::remove_file(&path)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Read-only view over a fixture shared by every test in the process
+///
+/// Each `#[tokio::test]` runs on its own runtime, so requests go through a fresh `TestContext`
+/// rather than the provisioning runtime's connection pool. The underlying resources outlive the
+/// process until `cleanup_registered` runs.
+pub struct SharedFixture {
+    fixture: TestFixture,
+}
+
+impl SharedFixture {
+    /// Get the shared fixture, provisioning it on first call
+    pub async fn get() -> &'static SharedFixture {
+        SHARED_FIXTURE
+            .get_or_init(|| async {
+                let fixture =
+                    TestFixture::create().await.expect("Failed to create shared test fixture");
+                if let Err(e) = register(&fixture) {
+                    eprintln!("Warning: shared fixture not registered for cleanup: {:#}", e);
+                }
+                SharedFixture { fixture }
+            })
+            .await
+    }
+
+    pub fn api_base_url(&self) -> &str {
+        &self.fixture.ctx.api_base_url
+    }
+
+    pub fn org_id(&self) -> i64 {
+        self.fixture.org_id
+    }
+
+    pub fn vault_id(&self) -> i64 {
+        self.fixture.vault_id
+    }
+
+    pub fn client_id(&self) -> i64 {
+        self.fixture.client_id
+    }
+
+    pub fn cert_kid(&self) -> &str {
+        &self.fixture.cert_kid
+    }
+
+    /// Generate a JWT for the shared client
+    ///
+    /// Panics if any requested scope would allow mutating the shared vault.
+    pub fn generate_jwt(&self, vault_id: Option<i64>, scopes: &[&str]) -> Result<String> {
+        if let Some(scope) = scopes.iter().find(|s| MUTATING_SCOPES.contains(s)) {
+            panic!("SharedFixture is read-only: scope '{}' requires a private TestFixture", scope);
+        }
+        self.fixture.generate_jwt(vault_id, scopes)
+    }
+
+    /// Start building a JWT for the shared client with custom claims
+    ///
+    /// Builders start with read-only scopes, and `encode` fails if a mutating one is added.
+    pub fn jwt(&self) -> JwtBuilder {
+        JwtBuilder::new(&self.fixture).read_only()
+    }
+
+    /// Generate a JWT signed with a key that does not match the certificate
+    pub fn generate_invalid_jwt(&self) -> Result<String> {
+        self.fixture.generate_invalid_jwt()
+    }
+
+    /// Engine client for a token from `generate_jwt`, whose scopes keep it read-only
+    pub fn engine(&self, jwt: &str) -> EngineClient {
+        EngineClient::new(TestContext::new(), jwt)
+    }
+
+    /// Build a request against the shared environment
+    ///
+    /// Panics unless the request is a safe method or a POST to a read-only Engine endpoint.
+    pub fn request(&self, method: Method, url: &str) -> RequestBuilder {
+        let ctx = TestContext::new();
+        let read_only_post = method == Method::POST
+            && READ_ONLY_ENGINE_PATHS.iter().any(|path| url == ctx.engine_url(path));
+
+        if !(method == Method::GET
+            || method == Method::HEAD
+            || method == Method::OPTIONS
+            || read_only_post)
+        {
+            panic!("SharedFixture is read-only: {} {} requires a private TestFixture", method, url);
+        }
+
+        ctx.client.request(method, url)
+    }
+
+    /// Call engine evaluate endpoint with JWT
+    pub async fn call_server_evaluate(
+        &self,
+        jwt: &str,
+        resource: &str,
+        permission: &str,
+        subject: &str,
+    ) -> Result<reqwest::Response> {
+        let body = serde_json::json!({
+            "evaluations": [{
+                "subject": subject,
+                "resource": resource,
+                "permission": permission,
+                "trace": false
+            }]
+        });
+
+        let url = TestContext::new().engine_url("/evaluate");
+        self.request(Method::POST, &url)
+            .header("Authorization", format!("Bearer {}", jwt))
+            .json(&body)
+            .send_recorded()
+            .await
+            .context("Failed to call server evaluate endpoint")
+    }
+}
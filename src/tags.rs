@@ -0,0 +1,244 @@
+// Test Tags
+//
+// Groups integration test modules by what they exercise and what they cost, so a run can pick a
+// subset without every test checking for itself. Tags are assigned per module (every test path is
+// `{module}::{test}`), and a selection turns into the libtest filter arguments that run it:
+//   smoke        Fast end-to-end checks that the environment works at all
+//   auth         Token, key, and scope validation
+//   perf         Latency, throughput, and cache-efficiency measurements
+//   chaos        Fault injection through chaos hooks or the Kubernetes API
+//   slow         Takes a minute or more
+//   destructive  Disrupts the shared environment (outages, restarts), not just its own fixture
+//
+// Selections come from INFERADB_TAGS (run modules carrying any of these) and INFERADB_SKIP_TAGS
+// (leave out modules carrying any of these). Without INFERADB_SKIP_TAGS, chaos, perf, slow, and
+// destructive modules are left out unless INFERADB_TAGS asks for one of those tags, which also
+// accepts the others a module carries. The `tagged` binary applies a selection to `cargo test`.
+
+use super::*;
+
+pub const SMOKE: &str = "smoke";
+pub const AUTH: &str = "auth";
+pub const PERF: &str = "perf";
+pub const CHAOS: &str = "chaos";
+pub const SLOW: &str = "slow";
+pub const DESTRUCTIVE: &str = "destructive";
+
+/// Every tag, in display order
+pub const TAGS: &[&str] = &[SMOKE, AUTH, PERF, CHAOS, SLOW, DESTRUCTIVE];
+
+/// Tags left out of a run unless selected
+pub const DEFAULT_SKIP_TAGS: &[&str] = &[CHAOS, PERF, SLOW, DESTRUCTIVE];
+
+/// Tags of every integration test module; untagged modules run unless a selection includes tags
+pub const MODULE_TAGS: &[(&str, &[&str])] = &[
+    ("admin_scope_tests", &[AUTH]),
+    ("audience_tests", &[AUTH]),
+    ("auth_jwt_tests", &[SMOKE, AUTH]),
+    ("backpressure_tests", &[PERF, SLOW]),
+    ("batch_dedup_tests", &[]),
+    ("build_info_tests", &[SMOKE]),
+    ("cache_eviction_tests", &[PERF, SLOW]),
+    ("cache_tests", &[PERF]),
+    ("certificate_status_tests", &[AUTH]),
+    ("chaos_schedule_tests", &[CHAOS, SLOW, DESTRUCTIVE]),
+    ("churn_tests", &[PERF]),
+    ("clock_skew_tests", &[CHAOS, AUTH, DESTRUCTIVE]),
+    ("concurrency_tests", &[PERF]),
+    ("consistency_tests", &[]),
+    ("control_db_outage_tests", &[CHAOS, DESTRUCTIVE]),
+    ("control_integration_tests", &[SMOKE]),
+    ("dataset_tests", &[SLOW]),
+    ("decision_assertion_tests", &[]),
+    ("differential_tests", &[]),
+    ("dns_failure_tests", &[CHAOS, DESTRUCTIVE]),
+    ("dpop_tests", &[AUTH]),
+    ("e2e_workflows_tests", &[SMOKE]),
+    ("end_user_token_tests", &[AUTH]),
+    ("exclusion_tests", &[]),
+    ("explain_tests", &[]),
+    ("fixture_tests", &[]),
+    ("group_membership_tests", &[]),
+    ("grpc_stream_limit_tests", &[]),
+    ("header_robustness_tests", &[]),
+    ("id_format_tests", &[AUTH]),
+    ("intersection_tests", &[]),
+    ("invalidation_fallback_tests", &[CHAOS, SLOW, DESTRUCTIVE]),
+    ("invalidation_storm_tests", &[PERF, SLOW]),
+    ("invitation_tests", &[]),
+    ("issuer_tests", &[AUTH]),
+    ("jwks_tests", &[AUTH]),
+    ("jwt_corpus_tests", &[SMOKE, AUTH]),
+    ("k8s_tests", &[CHAOS, SLOW, DESTRUCTIVE]),
+    ("key_type_tests", &[AUTH]),
+    ("latency_attribution_tests", &[PERF]),
+    ("ledger_cache_invalidation_tests", &[AUTH, SLOW]),
+    ("ledger_latency_tests", &[PERF]),
+    ("ledger_outage_tests", &[CHAOS, DESTRUCTIVE]),
+    ("metric_label_tests", &[]),
+    ("multi_client_tests", &[AUTH]),
+    ("multi_vault_token_tests", &[AUTH]),
+    ("name_validation_tests", &[]),
+    ("ownership_transfer_tests", &[]),
+    ("pagination_tests", &[SLOW]),
+    ("partial_failure_tests", &[]),
+    ("permission_hierarchy_tests", &[]),
+    ("private_key_transport_tests", &[AUTH]),
+    ("provisioning_load_tests", &[PERF]),
+    ("proxy_header_tests", &[AUTH]),
+    ("purge_tests", &[]),
+    ("quota_tests", &[SLOW]),
+    ("relationship_filter_tests", &[]),
+    ("relationship_metadata_tests", &[]),
+    ("replay_tests", &[]),
+    ("request_signing_tests", &[AUTH]),
+    ("resilience_tests", &[]),
+    ("rotation_chain_tests", &[AUTH]),
+    ("rotation_cutoff_tests", &[AUTH]),
+    ("scenario_tests", &[]),
+    ("schema_migration_tests", &[]),
+    ("schema_validation_tests", &[]),
+    ("search_tests", &[]),
+    ("stats_tests", &[]),
+    ("subject_impersonation_tests", &[AUTH]),
+    ("timestamp_tests", &[]),
+    ("token_lifecycle_tests", &[AUTH]),
+    ("token_theft_tests", &[AUTH]),
+    ("two_factor_tests", &[AUTH]),
+    ("user_account_tests", &[AUTH]),
+    ("vault_clone_tests", &[]),
+    ("vault_isolation_tests", &[SMOKE, AUTH]),
+    ("vault_manage_scope_tests", &[AUTH]),
+    ("vault_metadata_leak_tests", &[AUTH]),
+    ("vault_mismatch_tests", &[AUTH]),
+    ("webhook_failure_tests", &[SLOW]),
+    ("webhook_tests", &[SLOW]),
+];
+
+/// Tags of `module`, or `None` if it is not registered
+pub fn module_tags(module: &str) -> Option<&'static [&'static str]> {
+    MODULE_TAGS.iter().find(|(name, _)| *name == module).map(|(_, tags)| *tags)
+}
+
+/// Which tags a run includes and leaves out
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TagSelection {
+    /// Run only modules carrying one of these; empty runs every module not excluded
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+    /// `exclude` is `DEFAULT_SKIP_TAGS`: a module carrying one of them still runs if it also
+    /// carries an included tag from that list (including `chaos` runs destructive chaos modules)
+    pub default_exclude: bool,
+}
+
+/// Parse a comma-separated tag list
+pub fn parse_tags(value: &str) -> Vec<String> {
+    value.split(',').map(|tag| tag.trim().to_lowercase()).filter(|tag| !tag.is_empty()).collect()
+}
+
+impl TagSelection {
+    /// Build a selection, skipping `DEFAULT_SKIP_TAGS` when `exclude` is `None`; fails on unknown
+    /// tags
+    pub fn new(include: Vec<String>, exclude: Option<Vec<String>>) -> Result<Self> {
+        let default_exclude = exclude.is_none();
+        let exclude = exclude
+            .unwrap_or_else(|| DEFAULT_SKIP_TAGS.iter().map(|tag| tag.to_string()).collect());
+        for tag in include.iter().chain(&exclude) {
+            anyhow::ensure!(
+                TAGS.contains(&tag.as_str()),
+                "Unknown tag '{}' (expected one of {})",
+                tag,
+                TAGS.join(", ")
+            );
+        }
+        Ok(Self { include, exclude, default_exclude })
+    }
+
+    /// The selection in INFERADB_TAGS and INFERADB_SKIP_TAGS
+    pub fn from_env() -> Result<Self> {
+        let include = std::env::var("INFERADB_TAGS").map(|v| parse_tags(&v)).unwrap_or_default();
+        let exclude = std::env::var("INFERADB_SKIP_TAGS").ok().map(|v| parse_tags(&v));
+        Self::new(include, exclude)
+    }
+
+    /// Whether a module carrying `tags` runs
+    pub fn selects(&self, tags: &[&str]) -> bool {
+        let has = |wanted: &[String]| tags.iter().any(|tag| wanted.iter().any(|w| w == tag));
+        if !self.include.is_empty() && !has(&self.include) {
+            return false;
+        }
+        if !has(&self.exclude) {
+            return true;
+        }
+        // Asking for one costly tag accepts the other costs that come with it
+        self.default_exclude
+            && tags.iter().any(|tag| {
+                self.include.iter().any(|included| included == tag)
+                    && self.exclude.iter().any(|excluded| excluded == tag)
+            })
+    }
+
+    /// Registered modules this selection runs
+    pub fn modules(&self) -> Vec<&'static str> {
+        MODULE_TAGS.iter().filter(|(_, tags)| self.selects(tags)).map(|(name, _)| *name).collect()
+    }
+
+    /// libtest arguments that run exactly this selection: module filters when tags are included,
+    /// `--skip` filters for excluded modules otherwise
+    pub fn test_args(&self) -> Vec<String> {
+        if self.include.is_empty() {
+            MODULE_TAGS
+                .iter()
+                .filter(|(_, tags)| !self.selects(tags))
+                .flat_map(|(name, _)| ["--skip".to_string(), format!("{}::", name)])
+                .collect()
+        } else {
+            self.modules().into_iter().map(|name| format!("{}::", name)).collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tags_cover_every_module_and_select_subsets() {
+        let declared: Vec<&str> = include_str!("../integration/mod.rs")
+            .lines()
+            .filter_map(|line| line.strip_prefix("mod ")?.strip_suffix(';'))
+            .collect();
+        for module in &declared {
+            assert!(module_tags(module).is_some(), "{} has no entry in tags::MODULE_TAGS", module);
+        }
+        for (module, tags) in MODULE_TAGS {
+            assert!(declared.contains(module), "tags::MODULE_TAGS lists unknown module {}", module);
+            assert!(tags.iter().all(|tag| TAGS.contains(tag)), "{} has an unknown tag", module);
+            // Filters are substrings of test paths, so no module filter may match another module
+            let filter = format!("{}::", module);
+            let clashes: Vec<_> = MODULE_TAGS
+                .iter()
+                .filter(|(other, _)| other != module && format!("{}::", other).contains(&filter))
+                .collect();
+            assert!(clashes.is_empty(), "Filter {} also matches {:?}", filter, clashes);
+        }
+
+        let default = TagSelection::new(Vec::new(), None).expect("Default selection");
+        assert!(default.modules().contains(&"auth_jwt_tests"));
+        assert!(!default.modules().contains(&"ledger_outage_tests"), "Chaos is skipped by default");
+        assert!(default.test_args().contains(&"ledger_outage_tests::".to_string()));
+
+        let chaos = TagSelection::new(vec!["chaos".to_string()], None).expect("Chaos selection");
+        assert!(chaos.modules().contains(&"ledger_outage_tests"));
+        assert!(chaos.modules().contains(&"chaos_schedule_tests"), "Included tags are not skipped");
+        assert!(
+            chaos.modules().iter().all(|module| module_tags(module).unwrap().contains(&"chaos"))
+        );
+
+        let auth = TagSelection::new(vec!["auth".to_string()], None).expect("Auth selection");
+        assert!(auth.modules().contains(&"jwks_tests"));
+        assert!(!auth.modules().contains(&"clock_skew_tests"), "Chaos auth tests need --tag chaos");
+
+        assert!(TagSelection::new(vec!["nightly".to_string()], None).is_err());
+    }
+}
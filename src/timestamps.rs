@@ -0,0 +1,87 @@
+// Timestamps
+//
+// Control reports `created_at`/`updated_at`, certificate validity bounds, and token expiry as RFC
+// 3339 strings in UTC. Response types deserialize them through `utc` (and `utc_option` for nullable
+// fields) rather than chrono's own impl, which takes any offset and converts silently: an offset
+// other than zero, a missing offset, or a locale-formatted date fails the response instead, so a
+// serialization change shows up as a test failure rather than as timestamps that quietly compare
+// wrong.
+
+use chrono::DateTime;
+use serde::{Deserializer, de::Error as _};
+
+use super::*;
+
+/// Parse an RFC 3339 timestamp whose offset is UTC (`Z` or `+00:00`)
+pub fn parse_utc(value: &str) -> Result<DateTime<Utc>> {
+    let parsed = DateTime::parse_from_rfc3339(value)
+        .with_context(|| format!("{:?} is not an RFC 3339 timestamp", value))?;
+    anyhow::ensure!(
+        parsed.offset().local_minus_utc() == 0,
+        "{:?} is not in UTC (offset {})",
+        value,
+        parsed.offset()
+    );
+    Ok(parsed.with_timezone(&Utc))
+}
+
+/// `deserialize_with` for a required UTC timestamp
+pub fn utc<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<DateTime<Utc>, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    parse_utc(&value).map_err(|e| D::Error::custom(format!("{:#}", e)))
+}
+
+/// `deserialize_with` for a nullable UTC timestamp; pair with `#[serde(default)]`
+pub fn utc_option<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Option<DateTime<Utc>>, D::Error> {
+    match Option::<String>::deserialize(deserializer)? {
+        Some(value) => {
+            parse_utc(&value).map(Some).map_err(|e| D::Error::custom(format!("{:#}", e)))
+        },
+        None => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timestamp_parsing() {
+        let parsed = parse_utc("2026-10-16T09:30:00Z").expect("Z is UTC");
+        assert_eq!(parsed, parse_utc("2026-10-16T09:30:00+00:00").expect("+00:00 is UTC"));
+        assert_eq!(
+            parse_utc("2026-10-16T09:30:00.123456Z").expect("Fractional seconds").timestamp(),
+            parsed.timestamp()
+        );
+
+        for rejected in [
+            "2026-10-16T11:30:00+02:00",
+            "2026-10-16T09:30:00",
+            "16/10/2026 09:30:00",
+            "Fri, 16 Oct 2026 09:30:00 GMT",
+            "1792143000",
+        ] {
+            assert!(parse_utc(rejected).is_err(), "{:?} should be rejected", rejected);
+        }
+
+        #[derive(serde::Deserialize)]
+        struct Stamped {
+            #[serde(deserialize_with = "utc")]
+            created_at: chrono::DateTime<Utc>,
+            #[serde(default, deserialize_with = "utc_option")]
+            deleted_at: Option<chrono::DateTime<Utc>>,
+        }
+        let stamped: Stamped = serde_json::from_str(r#"{"created_at": "2026-10-16T09:30:00Z"}"#)
+            .expect("Missing nullable timestamp is None");
+        assert_eq!((stamped.created_at, stamped.deleted_at), (parsed, None));
+        assert!(
+            serde_json::from_str::<Stamped>(r#"{"created_at": "2026-10-16T11:30:00+02:00"}"#)
+                .is_err(),
+            "A non-UTC offset fails the whole response"
+        );
+    }
+}
@@ -0,0 +1,429 @@
+// Tuple Import
+//
+// Loads relationship tuples from shared files into a vault, so a workload can be reproduced from
+// the same file by anyone, for correctness and perf runs alike. Two formats, picked by extension:
+//   .ndjson / .jsonl  One `{"resource": ..., "relation": ..., "subject": ...}` object per line
+//   .csv              `resource,relation,subject` per record, after an optional header row
+// Blank lines and lines starting with `#` are ignored in both. CSV fields are quoted as RFC 4180
+// has it: a field in double quotes may hold commas, line breaks, and `""` for a quote.
+//
+// `TupleImport` writes a file in batches and reports progress after each. With a checkpoint path
+// it records how far it got, keyed by the file's digest and the vault, so an interrupted import
+// (a failed batch, a stop requested from the progress callback, a killed process) resumes after
+// the last written batch instead of starting over. Writing a tuple that already exists is
+// harmless, so a batch that was in flight when the import stopped is simply written again.
+
+use std::{
+    ops::ControlFlow,
+    path::{Path, PathBuf},
+};
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use sha2::{Digest, Sha256};
+
+use super::*;
+
+/// Relationships per write request, unless `TupleImport::batch_size` overrides it
+const DEFAULT_BATCH: usize = 500;
+
+/// File formats tuples are read from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TupleFormat {
+    Ndjson,
+    Csv,
+}
+
+impl TupleFormat {
+    /// The format a file's extension names, if any
+    pub fn of(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_ascii_lowercase().as_str() {
+            "ndjson" | "jsonl" => Some(Self::Ndjson),
+            "csv" => Some(Self::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// The tuples of one file, with a digest identifying its contents
+#[derive(Debug, Clone)]
+pub struct TupleFile {
+    pub path: PathBuf,
+    pub relationships: Vec<Relationship>,
+    /// SHA-256 of the file's bytes, base64url-encoded
+    pub digest: String,
+}
+
+impl TupleFile {
+    pub fn read(path: &Path) -> Result<Self> {
+        let format = TupleFormat::of(path).with_context(|| {
+            format!("{}: expected a .ndjson, .jsonl, or .csv tuple file", path.display())
+        })?;
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read tuple file {}", path.display()))?;
+        let text = std::str::from_utf8(&bytes)
+            .with_context(|| format!("{} is not UTF-8", path.display()))?;
+        let relationships =
+            parse_tuples(text, format).with_context(|| format!("In {}", path.display()))?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            relationships,
+            digest: URL_SAFE_NO_PAD.encode(Sha256::digest(&bytes)),
+        })
+    }
+
+    /// Write `relationships` to `path` in the format its extension names
+    pub fn write(path: &Path, relationships: &[Relationship]) -> Result<()> {
+        let format = TupleFormat::of(path).with_context(|| {
+            format!("{}: expected a .ndjson, .jsonl, or .csv tuple file", path.display())
+        })?;
+        let mut text = String::new();
+        if format == TupleFormat::Csv {
+            text.push_str("resource,relation,subject\n");
+        }
+        for relationship in relationships {
+            match format {
+                TupleFormat::Ndjson => text.push_str(&serde_json::to_string(relationship)?),
+                TupleFormat::Csv => text.push_str(&csv_record(&[
+                    &relationship.resource,
+                    &relationship.relation,
+                    &relationship.subject,
+                ])),
+            }
+            text.push('\n');
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, text)
+            .with_context(|| format!("Failed to write tuple file {}", path.display()))
+    }
+}
+
+/// Parse tuples in `format`, naming the offending line on error
+pub fn parse_tuples(text: &str, format: TupleFormat) -> Result<Vec<Relationship>> {
+    let mut relationships = Vec::new();
+    match format {
+        TupleFormat::Ndjson => {
+            for (index, line) in text.lines().enumerate() {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    continue;
+                }
+                let relationship = serde_json::from_str(line)
+                    .with_context(|| format!("Line {}: {:?}", index + 1, line))?;
+                relationships.push(relationship);
+            }
+        },
+        TupleFormat::Csv => {
+            for (line, fields) in csv_records(text)? {
+                match parse_csv_fields(&fields) {
+                    Ok(relationship) => relationships.push(relationship),
+                    // A header row is only allowed first
+                    Err(_) if relationships.is_empty() && is_header(&fields) => {},
+                    Err(e) => return Err(e.context(format!("Line {}: {:?}", line, fields))),
+                }
+            }
+        },
+    }
+    Ok(relationships)
+}
+
+/// Split CSV text into records, each with the line it starts on, skipping blank and comment lines
+fn csv_records(text: &str) -> Result<Vec<(usize, Vec<String>)>> {
+    let mut records = Vec::new();
+    let mut rest = text;
+    let mut line = 1;
+    while !rest.is_empty() {
+        let first = rest.split('\n').next().unwrap_or_default().trim();
+        if first.is_empty() || first.starts_with('#') {
+            rest = rest.split_once('\n').map_or("", |(_, after)| after);
+            line += 1;
+            continue;
+        }
+        let (fields, after, lines) =
+            parse_csv_record(rest).with_context(|| format!("Line {}", line))?;
+        records.push((line, fields));
+        rest = after;
+        line += lines;
+    }
+    Ok(records)
+}
+
+/// Parse the record at the start of `text`: its fields, the text after it, and the lines it took
+///
+/// Whitespace around a field is dropped; inside quotes it is kept, like everything but `""`.
+fn parse_csv_record(text: &str) -> Result<(Vec<String>, &str, usize)> {
+    let mut fields = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    let mut lines = 0;
+    loop {
+        while chars.next_if(|(_, c)| *c == ' ' || *c == '\t').is_some() {}
+        let mut field = String::new();
+        if chars.next_if(|(_, c)| *c == '"').is_some() {
+            loop {
+                match chars.next() {
+                    Some((_, '"')) if chars.next_if(|(_, c)| *c == '"').is_some() => {
+                        field.push('"')
+                    },
+                    Some((_, '"')) => break,
+                    Some((_, c)) => {
+                        lines += usize::from(c == '\n');
+                        field.push(c);
+                    },
+                    None => anyhow::bail!("Unterminated quoted field"),
+                }
+            }
+            while chars.next_if(|(_, c)| matches!(c, ' ' | '\t' | '\r')).is_some() {}
+        } else {
+            while let Some((_, c)) = chars.next_if(|(_, c)| *c != ',' && *c != '\n') {
+                anyhow::ensure!(c != '"', "Quote inside an unquoted field");
+                field.push(c);
+            }
+            field.truncate(field.trim_end().len());
+        }
+        fields.push(field);
+
+        match chars.next() {
+            Some((_, ',')) => {},
+            Some((at, '\n')) => return Ok((fields, &text[at + 1..], lines + 1)),
+            None => return Ok((fields, "", lines + 1)),
+            Some((_, c)) => anyhow::bail!("Unexpected {:?} after a quoted field", c),
+        }
+    }
+}
+
+fn parse_csv_fields(fields: &[String]) -> Result<Relationship> {
+    let [resource, relation, subject] = fields else {
+        anyhow::bail!("Expected 3 fields, found {}", fields.len());
+    };
+    anyhow::ensure!(
+        resource.contains(':') && subject.contains(':') && !relation.is_empty(),
+        "Expected resource,relation,subject with typed resource and subject"
+    );
+    Ok(Relationship::new(resource, relation, subject))
+}
+
+fn is_header(fields: &[String]) -> bool {
+    fields.iter().map(String::as_str).eq(["resource", "relation", "subject"])
+}
+
+/// One CSV record, quoting the fields that need it to read back unchanged
+///
+/// A leading `#` is quoted too, since an unquoted one at the start of a line reads as a comment.
+fn csv_record(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            let plain = !field.contains([',', '"', '\n', '\r'])
+                && !field.starts_with('#')
+                && field.trim() == *field;
+            if plain { field.to_string() } else { format!("\"{}\"", field.replace('"', "\"\"")) }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// How far an import has got
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportProgress {
+    /// Tuples written so far, including any written before resuming
+    pub written: usize,
+    pub total: usize,
+    /// Tuples already written by an earlier, interrupted import
+    pub resumed_from: usize,
+    /// Time spent in this import, not counting earlier attempts
+    pub elapsed: std::time::Duration,
+}
+
+impl ImportProgress {
+    pub fn is_complete(&self) -> bool {
+        self.written >= self.total
+    }
+
+    /// Tuples written per second by this import
+    pub fn rate(&self) -> f64 {
+        (self.written - self.resumed_from) as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// What an interrupted import records to resume from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportCheckpoint {
+    pub digest: String,
+    pub vault_id: i64,
+    pub written: usize,
+}
+
+/// Batched, resumable import of a `TupleFile` into one vault
+#[derive(Debug, Clone)]
+pub struct TupleImport {
+    batch_size: usize,
+    checkpoint: Option<PathBuf>,
+}
+
+impl Default for TupleImport {
+    fn default() -> Self {
+        Self { batch_size: DEFAULT_BATCH, checkpoint: None }
+    }
+}
+
+impl TupleImport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Record progress at `path` and resume from it; removed once the import completes
+    pub fn checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint = Some(path.into());
+        self
+    }
+
+    /// Write `file` into `vault_id` through `engine`, which must hold `inferadb.write` for it
+    ///
+    /// `progress` is called after every batch; returning `ControlFlow::Break` stops the import
+    /// there, leaving the checkpoint to resume from. Returns the progress reached.
+    pub async fn run(
+        &self,
+        engine: &EngineClient,
+        vault_id: i64,
+        file: &TupleFile,
+        mut progress: impl FnMut(&ImportProgress) -> ControlFlow<()>,
+    ) -> Result<ImportProgress> {
+        let resumed_from = match self.read_checkpoint()? {
+            Some(checkpoint) => {
+                anyhow::ensure!(
+                    checkpoint.digest == file.digest && checkpoint.vault_id == vault_id,
+                    "Checkpoint is for another file or vault; delete it to start over"
+                );
+                checkpoint.written.min(file.relationships.len())
+            },
+            None => 0,
+        };
+
+        let started = std::time::Instant::now();
+        let mut reached = ImportProgress {
+            written: resumed_from,
+            total: file.relationships.len(),
+            resumed_from,
+            elapsed: std::time::Duration::ZERO,
+        };
+        for batch in file.relationships[resumed_from..].chunks(self.batch_size) {
+            engine.write_relationships(batch).await.with_context(|| {
+                format!(
+                    "Failed to write tuples {}..{}",
+                    reached.written,
+                    reached.written + batch.len()
+                )
+            })?;
+            reached.written += batch.len();
+            reached.elapsed = started.elapsed();
+            self.write_checkpoint(&ImportCheckpoint {
+                digest: file.digest.clone(),
+                vault_id,
+                written: reached.written,
+            })?;
+            if progress(&reached).is_break() {
+                return Ok(reached);
+            }
+        }
+
+        if let Some(path) = &self.checkpoint
+            && path.exists()
+        {
+            std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove checkpoint {}", path.display()))?;
+        }
+        Ok(reached)
+    }
+
+    fn read_checkpoint(&self) -> Result<Option<ImportCheckpoint>> {
+        let Some(path) = self.checkpoint.as_ref().filter(|path| path.exists()) else {
+            return Ok(None);
+        };
+        let bytes = std::fs::read(path)
+            .with_context(|| format!("Failed to read checkpoint {}", path.display()))?;
+        serde_json::from_slice(&bytes)
+            .map(Some)
+            .with_context(|| format!("Invalid checkpoint {}", path.display()))
+    }
+
+    /// Replace the checkpoint in one rename, so a killed import never leaves half a file
+    fn write_checkpoint(&self, checkpoint: &ImportCheckpoint) -> Result<()> {
+        let Some(path) = &self.checkpoint else { return Ok(()) };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let partial = path.with_extension("partial");
+        std::fs::write(&partial, serde_json::to_vec(checkpoint)?)?;
+        std::fs::rename(&partial, path)
+            .with_context(|| format!("Failed to write checkpoint {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tuple_file_parsing() {
+        let csv =
+            "resource,relation,subject\n# shared fixture\n\ndocument:1, viewer ,\"user:alice\"\n";
+        assert_eq!(
+            parse_tuples(csv, TupleFormat::Csv).expect("Valid CSV"),
+            [Relationship::new("document:1", "viewer", "user:alice")],
+            "Header, comments, blank lines, padding, and quotes are handled"
+        );
+        let error =
+            parse_tuples("document:1,viewer,user:a\nresource,relation,subject", TupleFormat::Csv)
+                .expect_err("A header after tuples is malformed");
+        assert!(format!("{:#}", error).contains("Line 2"), "Errors name the line: {:#}", error);
+        assert!(parse_tuples("document:1,viewer", TupleFormat::Csv).is_err());
+
+        let quoted =
+            "\"document:a,b\",viewer,\"user:\"\"q\"\"\"\r\n\"document:\nc\", viewer ,user:d\n";
+        assert_eq!(
+            parse_tuples(quoted, TupleFormat::Csv).expect("Valid RFC 4180 CSV"),
+            [
+                Relationship::new("document:a,b", "viewer", "user:\"q\""),
+                Relationship::new("document:\nc", "viewer", "user:d"),
+            ],
+            "Quoted commas, doubled quotes, line breaks, and CRLF are handled"
+        );
+        let error = parse_tuples(
+            "document:1,viewer,user:a\n\"document:2,viewer,user:b\n",
+            TupleFormat::Csv,
+        )
+        .expect_err("An unterminated quote is malformed");
+        assert!(format!("{:#}", error).contains("Line 2"), "Errors name the line: {:#}", error);
+        assert!(parse_tuples("document:1,vie\"wer,user:a", TupleFormat::Csv).is_err());
+        for fields in [
+            ["document:a,b", "viewer", "user:\"q\""],
+            ["document: c", "view\ner", "user:d"],
+            ["#document:e", "viewer", "user:f"],
+        ] {
+            let record = csv_record(&fields);
+            assert_eq!(
+                parse_tuples(&record, TupleFormat::Csv).expect("Written records read back"),
+                [Relationship::new(fields[0], fields[1], fields[2])],
+                "{:?} round-trips",
+                record
+            );
+        }
+
+        let ndjson = r#"{"resource":"folder:a","relation":"parent","subject":"folder:b"}
+    # comment
+    {"resource":"document:2","relation":"editor","subject":"group:eng#member"}"#;
+        assert_eq!(parse_tuples(ndjson, TupleFormat::Ndjson).expect("Valid NDJSON").len(), 2);
+        assert!(parse_tuples("{\"resource\":\"x\"}", TupleFormat::Ndjson).is_err());
+
+        use std::path::Path;
+        assert_eq!(TupleFormat::of(Path::new("a/b.JSONL")), Some(TupleFormat::Ndjson));
+        assert_eq!(TupleFormat::of(Path::new("tuples.csv")), Some(TupleFormat::Csv));
+        assert_eq!(TupleFormat::of(Path::new("tuples.txt")), None);
+    }
+}
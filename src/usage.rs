@@ -0,0 +1,198 @@
+// Per-test resource usage
+//
+// Attributes load on the shared environment to the tests that generated it: how many Control API
+// calls, Engine requests, and Ledger blocks each test's fixtures accounted for. Every fixture owns
+// a fresh organization and vault named for this run (see `naming`), so the counters labelled with
+// them measure that fixture's traffic alone, even with tests running in parallel. Those labels
+// can't exist before the fixture does, so a single scrape when it is cleaned up holds everything it
+// did, provisioning included; each counter over the fixture's tenant labels is added to the test it
+// was created under.
+//
+// Off by default; enabled by setting `INFERADB_USAGE_REPORT` to the JSON report's path. The report
+// is rewritten whenever a fixture is cleaned up, so it is complete when the run ends, and lists
+// tests heaviest first. Fixtures that are only dropped, never cleaned up, are not counted, and a
+// counter the server does not export for the tenant is reported as `null` rather than 0.
+
+use std::{collections::BTreeMap, sync::Mutex};
+
+use super::{metrics::Label, *};
+
+/// Control API requests, labelled with the organization
+pub const CONTROL_REQUESTS_METRIC: &str = "control_http_requests_total";
+
+/// Engine requests, labelled with the vault
+pub const ENGINE_REQUESTS_METRIC: &str = "engine_http_requests_total";
+
+/// Ledger blocks written, labelled with the vault
+pub const LEDGER_BLOCKS_METRIC: &str = "ledger_blocks_written_total";
+
+static FIXTURES: Mutex<BTreeMap<i64, PendingFixture>> = Mutex::new(BTreeMap::new());
+static TESTS: Mutex<BTreeMap<String, TestUsage>> = Mutex::new(BTreeMap::new());
+
+/// Counter deltas attributed to one fixture or test; `None` where the metric is not exported
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Usage {
+    pub control_calls: Option<u64>,
+    pub engine_requests: Option<u64>,
+    pub ledger_blocks: Option<u64>,
+}
+
+impl Usage {
+    /// Growth of each counter between two scrapes, over the tenant's labels
+    pub fn between(
+        before: &MetricsSnapshot,
+        after: &MetricsSnapshot,
+        labels: &TenantLabels,
+    ) -> Self {
+        let delta = |name: &str, label: Option<&Label>| {
+            let label = label?;
+            let exported = after
+                .samples
+                .iter()
+                .any(|s| s.name == name && s.labels.get(&label.name) == Some(&label.value));
+            let grown = after.sum(name, &[label]) - before.sum(name, &[label]);
+            exported.then(|| grown.max(0.0).round() as u64)
+        };
+        Self {
+            control_calls: delta(CONTROL_REQUESTS_METRIC, labels.org.as_ref()),
+            engine_requests: delta(ENGINE_REQUESTS_METRIC, labels.vault.as_ref()),
+            ledger_blocks: delta(LEDGER_BLOCKS_METRIC, labels.vault.as_ref()),
+        }
+    }
+
+    /// Both usages combined; a metric is missing only if it is missing from both
+    pub fn combine(self, other: Self) -> Self {
+        let add = |a: Option<u64>, b: Option<u64>| match (a, b) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0)),
+        };
+        Self {
+            control_calls: add(self.control_calls, other.control_calls),
+            engine_requests: add(self.engine_requests, other.engine_requests),
+            ledger_blocks: add(self.ledger_blocks, other.ledger_blocks),
+        }
+    }
+
+    /// Every counted operation, for ranking tests
+    pub fn total(&self) -> u64 {
+        [self.control_calls, self.engine_requests, self.ledger_blocks].into_iter().flatten().sum()
+    }
+}
+
+/// Usage of one test across all the fixtures it cleaned up
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TestUsage {
+    pub test: String,
+    pub fixtures: usize,
+    #[serde(flatten)]
+    pub usage: Usage,
+}
+
+/// The JSON report artifact
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UsageReport {
+    pub run_id: String,
+    pub api_base_url: String,
+    /// Heaviest first
+    pub tests: Vec<TestUsage>,
+}
+
+/// A provisioned fixture awaiting its scrape
+struct PendingFixture {
+    scope: String,
+}
+
+/// Whether usage accounting is on
+pub fn is_enabled() -> bool {
+    report_path().is_some()
+}
+
+fn report_path() -> Option<PathBuf> {
+    std::env::var("INFERADB_USAGE_REPORT")
+        .ok()
+        .filter(|path| !path.trim().is_empty())
+        .map(PathBuf::from)
+}
+
+/// Start accounting for the fixture owning `org_id`, under the current test, when accounting is on
+pub fn begin(org_id: i64) {
+    if !is_enabled() {
+        return;
+    }
+    let pending = PendingFixture { scope: recorder::current_scope() };
+    FIXTURES.lock().unwrap_or_else(|e| e.into_inner()).insert(org_id, pending);
+}
+
+/// Attribute a fixture's usage to the test that created it and rewrite the report
+pub async fn finish(fixture: &TestFixture) {
+    let Some(pending) = FIXTURES.lock().unwrap_or_else(|e| e.into_inner()).remove(&fixture.org_id)
+    else {
+        return;
+    };
+    let Some(after) = MetricsSnapshot::fetch(&fixture.ctx).await else { return };
+    let labels = TenantLabels::resolve(&after, fixture.org_id, fixture.client_id, fixture.vault_id);
+    let usage = Usage::between(&MetricsSnapshot::default(), &after, &labels);
+
+    // The report is written under the lock, so a slower writer can't overwrite a newer report
+    let mut tests = TESTS.lock().unwrap_or_else(|e| e.into_inner());
+    let entry = tests
+        .entry(pending.scope.clone())
+        .or_insert_with(|| TestUsage { test: pending.scope, ..Default::default() });
+    entry.fixtures += 1;
+    entry.usage = entry.usage.combine(usage);
+    if let Err(e) = write_report(&report(tests.values().cloned().collect())) {
+        eprintln!("Warning: {:#}", e);
+    }
+}
+
+/// Report of `tests`, heaviest first
+pub fn report(mut tests: Vec<TestUsage>) -> UsageReport {
+    tests.sort_by(|a, b| b.usage.total().cmp(&a.usage.total()).then_with(|| a.test.cmp(&b.test)));
+    UsageReport { run_id: naming::run_id().to_string(), api_base_url: api_base_url(), tests }
+}
+
+fn write_report(report: &UsageReport) -> Result<()> {
+    let Some(path) = report_path() else { return Ok(()) };
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&path, serde_json::to_vec_pretty(report)?)
+        .with_context(|| format!("Failed to write usage report {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_usage_deltas_scoped_to_tenant() {
+        let before = MetricsSnapshot::parse(
+            "control_http_requests_total{org_id=\"7\",route=\"/vaults\"} 4\n\
+             control_http_requests_total{org_id=\"8\",route=\"/vaults\"} 100\n\
+             engine_http_requests_total{vault_id=\"42\"} 10\n",
+        );
+        let after = MetricsSnapshot::parse(
+            "control_http_requests_total{org_id=\"7\",route=\"/vaults\"} 6\n\
+             control_http_requests_total{org_id=\"7\",route=\"/clients\"} 3\n\
+             control_http_requests_total{org_id=\"8\",route=\"/vaults\"} 900\n\
+             engine_http_requests_total{vault_id=\"42\"} 25\n\
+             engine_http_requests_total{vault_id=\"420\"} 70\n",
+        );
+        let labels = TenantLabels::resolve(&after, 7, 12, 42);
+        let usage = Usage::between(&before, &after, &labels);
+        assert_eq!(usage.control_calls, Some(5), "New series count from zero; other orgs ignored");
+        assert_eq!(usage.engine_requests, Some(15), "Only the fixture's vault counts");
+        assert_eq!(usage.ledger_blocks, None, "Unexported counters are not reported as zero");
+        assert_eq!(usage.total(), 20);
+
+        let combined = usage.combine(Usage { ledger_blocks: Some(2), ..Default::default() });
+        assert_eq!(combined.control_calls, Some(5));
+        assert_eq!(combined.ledger_blocks, Some(2));
+
+        let light = TestUsage { test: "light".into(), fixtures: 1, usage };
+        let heavy = TestUsage { test: "heavy".into(), fixtures: 2, usage: usage.combine(usage) };
+        let ordered: Vec<_> =
+            report(vec![light, heavy]).tests.into_iter().map(|t| t.test).collect();
+        assert_eq!(ordered, ["heavy", "light"], "Report lists the heaviest test first");
+    }
+}
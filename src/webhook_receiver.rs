@@ -0,0 +1,305 @@
+// Webhook Receiver
+//
+// An HTTP listener the suite runs itself, so Control has somewhere to deliver webhooks. It binds
+// INFERADB_WEBHOOK_BIND once per process, and Control reaches it at INFERADB_WEBHOOK_URL, the
+// listener's address as seen from the cluster. The default bind is loopback only, so the
+// listener is never exposed by accident; point INFERADB_WEBHOOK_BIND at the interface the cluster
+// reaches (the runner's Tailscale address, for instance). Tests that need deliveries skip when
+// INFERADB_WEBHOOK_URL is unset.
+//
+// Each test takes its own `WebhookEndpoint`, a unique path under the shared listener, and
+// registers that URL with Control. Requests to the path are recorded with their arrival time and
+// answered with the next scripted `WebhookResponse`, or the endpoint's fallback (200 unless
+// changed) once the script runs out. Besides plain statuses, a response can be slow (answered
+// after a delay) or a timeout (held open without an answer, then dropped), so tests can inject
+// the failures a real receiver has. The listener serves
+// from plain threads rather than a tokio task because every `#[tokio::test]` has its own runtime,
+// and a task spawned on one would stop when that test ends.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    io::{BufRead, BufReader, Read, Write},
+    net::{Shutdown, TcpListener, TcpStream},
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use reqwest::{
+    StatusCode,
+    header::{HeaderMap, HeaderName, HeaderValue},
+};
+
+use super::*;
+
+/// Where the listener binds unless INFERADB_WEBHOOK_BIND overrides it
+const DEFAULT_BIND: &str = "127.0.0.1:8787";
+
+/// Header Control sets to identify a delivery across retries
+pub const DELIVERY_ID_HEADER: &str = "x-inferadb-webhook-id";
+
+/// Largest request body the listener accepts
+const MAX_BODY: usize = 1 << 20;
+
+/// How long a connection may sit without sending before the listener drops it
+const READ_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+static RECEIVER: OnceLock<std::result::Result<WebhookReceiver, String>> = OnceLock::new();
+
+/// How the receiver answers one request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookResponse {
+    /// Answer at once with the status
+    Status(StatusCode),
+    /// Answer with the status after the delay
+    Slow(std::time::Duration, StatusCode),
+    /// Hold the connection open for the duration, then close it without answering
+    Timeout(std::time::Duration),
+}
+
+impl WebhookResponse {
+    /// Status the request is answered with, or `None` for a timeout
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            Self::Status(status) | Self::Slow(_, status) => Some(*status),
+            Self::Timeout(_) => None,
+        }
+    }
+}
+
+/// One request Control made to an endpoint
+#[derive(Debug, Clone)]
+pub struct Delivery {
+    pub received_at: Instant,
+    pub headers: HeaderMap,
+    pub body: Vec<u8>,
+    /// How the receiver answered
+    pub response: WebhookResponse,
+}
+
+impl Delivery {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).and_then(|value| value.to_str().ok())
+    }
+
+    /// Control's delivery ID, the same on every retry of one delivery
+    pub fn id(&self) -> Option<&str> {
+        self.header(DELIVERY_ID_HEADER)
+    }
+
+    pub fn json(&self) -> Option<serde_json::Value> {
+        serde_json::from_slice(&self.body).ok()
+    }
+
+    /// Whether the receiver accepted the delivery with a 2xx answer
+    pub fn accepted(&self) -> bool {
+        self.response.status().is_some_and(|status| status.is_success())
+    }
+}
+
+struct EndpointState {
+    deliveries: Vec<Delivery>,
+    script: VecDeque<WebhookResponse>,
+    fallback: WebhookResponse,
+}
+
+impl Default for EndpointState {
+    fn default() -> Self {
+        Self {
+            deliveries: Vec::new(),
+            script: VecDeque::new(),
+            fallback: WebhookResponse::Status(StatusCode::OK),
+        }
+    }
+}
+
+type Endpoints = Arc<Mutex<HashMap<String, EndpointState>>>;
+
+/// The process-wide listener
+struct WebhookReceiver {
+    public_url: String,
+    endpoints: Endpoints,
+}
+
+impl WebhookReceiver {
+    fn start(public_url: String) -> std::result::Result<Self, String> {
+        let bind = std::env::var("INFERADB_WEBHOOK_BIND").unwrap_or_else(|_| DEFAULT_BIND.into());
+        let listener = TcpListener::bind(&bind)
+            .map_err(|e| format!("Failed to bind webhook receiver to {}: {}", bind, e))?;
+        let endpoints = Endpoints::default();
+
+        let shared = Arc::clone(&endpoints);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let endpoints = Arc::clone(&shared);
+                std::thread::spawn(move || {
+                    if let Err(e) = serve(stream, &endpoints) {
+                        eprintln!("Webhook receiver: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(Self { public_url: public_url.trim_end_matches('/').to_string(), endpoints })
+    }
+}
+
+/// A path on the shared listener, owned by one test
+pub struct WebhookEndpoint {
+    path: String,
+    url: String,
+    endpoints: Endpoints,
+}
+
+impl WebhookEndpoint {
+    /// A fresh endpoint, or `None` (after logging) when INFERADB_WEBHOOK_URL is unset or the
+    /// listener cannot bind
+    pub fn create() -> Option<Self> {
+        let Ok(public_url) = std::env::var("INFERADB_WEBHOOK_URL") else {
+            outcome::skip("Skipping webhook test - INFERADB_WEBHOOK_URL not set");
+            return None;
+        };
+        let receiver = match RECEIVER.get_or_init(|| WebhookReceiver::start(public_url)) {
+            Ok(receiver) => receiver,
+            Err(e) => {
+                outcome::skip(format!("Skipping webhook test - {}", e));
+                return None;
+            },
+        };
+
+        let path = format!("/hooks/{}", Uuid::new_v4().simple());
+        receiver
+            .endpoints
+            .lock()
+            .expect("Receiver lock poisoned")
+            .insert(path.clone(), Default::default());
+        Some(Self {
+            url: format!("{}{}", receiver.public_url, path),
+            path,
+            endpoints: Arc::clone(&receiver.endpoints),
+        })
+    }
+
+    /// URL to register with Control
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Answer the next requests with `statuses`, in order, before returning to the fallback
+    pub fn respond_with(&self, statuses: impl IntoIterator<Item = StatusCode>) {
+        self.script(statuses.into_iter().map(WebhookResponse::Status));
+    }
+
+    /// Answer the next requests with `responses`, in order, before returning to the fallback
+    pub fn script(&self, responses: impl IntoIterator<Item = WebhookResponse>) {
+        self.with_state(|state| state.script.extend(responses));
+    }
+
+    /// Answer every request once the script runs out with `response` (200 by default)
+    pub fn respond_always(&self, response: WebhookResponse) {
+        self.with_state(|state| state.fallback = response);
+    }
+
+    /// Every request received so far, in arrival order
+    pub fn deliveries(&self) -> Vec<Delivery> {
+        self.with_state(|state| state.deliveries.clone())
+    }
+
+    /// Wait until `done` holds for the deliveries received, returning them either way
+    pub async fn wait_until(
+        &self,
+        timeout: std::time::Duration,
+        done: impl Fn(&[Delivery]) -> bool,
+    ) -> Vec<Delivery> {
+        let start = Instant::now();
+        loop {
+            let deliveries = self.deliveries();
+            if done(&deliveries) || start.elapsed() >= timeout {
+                return deliveries;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+        }
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut EndpointState) -> T) -> T {
+        let mut endpoints = self.endpoints.lock().expect("Receiver lock poisoned");
+        f(endpoints.entry(self.path.clone()).or_default())
+    }
+}
+
+impl Drop for WebhookEndpoint {
+    fn drop(&mut self) {
+        if let Ok(mut endpoints) = self.endpoints.lock() {
+            endpoints.remove(&self.path);
+        }
+    }
+}
+
+/// Read one HTTP/1.1 request from `stream`, record it, and answer it
+fn serve(stream: TcpStream, endpoints: &Endpoints) -> Result<()> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let path = request_line.split_whitespace().nth(1).context("Malformed request line")?;
+    let path = path.split('?').next().unwrap_or(path).to_string();
+
+    let mut headers = HeaderMap::new();
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim_end().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.trim_end().split_once(':')
+            && let (Ok(name), Ok(value)) =
+                (HeaderName::try_from(name.trim()), HeaderValue::try_from(value.trim()))
+        {
+            headers.append(name, value);
+        }
+    }
+
+    let length: usize = headers
+        .get("content-length")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(0);
+    anyhow::ensure!(length <= MAX_BODY, "Request body of {} bytes refused", length);
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    let received_at = Instant::now();
+
+    let response = {
+        let mut endpoints = endpoints.lock().map_err(|_| anyhow::anyhow!("Lock poisoned"))?;
+        match endpoints.get_mut(&path) {
+            Some(state) => {
+                let response = state.script.pop_front().unwrap_or(state.fallback);
+                state.deliveries.push(Delivery { received_at, headers, body, response });
+                response
+            },
+            None => WebhookResponse::Status(StatusCode::NOT_FOUND),
+        }
+    };
+
+    // Sleep outside the lock, so a held request never stalls the other endpoints
+    let status = match response {
+        WebhookResponse::Status(status) => status,
+        WebhookResponse::Slow(delay, status) => {
+            std::thread::sleep(delay);
+            status
+        },
+        WebhookResponse::Timeout(hold) => {
+            std::thread::sleep(hold);
+            let _ = stream.shutdown(Shutdown::Both);
+            return Ok(());
+        },
+    };
+
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status.as_u16(),
+        status.canonical_reason().unwrap_or("")
+    )?;
+    stream.flush()?;
+    Ok(())
+}
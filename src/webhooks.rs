@@ -0,0 +1,193 @@
+// Webhook Signatures
+//
+// Control signs the webhooks it delivers (cache invalidations, audit events) so a receiver can
+// tell them from forgeries. Each delivery carries two headers:
+//   X-InferaDB-Webhook-Timestamp   Unix seconds when the delivery was signed
+//   X-InferaDB-Webhook-Signature   Comma-separated signatures over "{timestamp}.{body}"
+//
+// A signature is `v1=<hex HMAC-SHA256>` under the endpoint's shared secret, or
+// `ed25519=<base64 signature>` under Control's webhook key. Several may be present while a secret
+// is rotated; a delivery is authentic if any signature of the receiver's scheme verifies. HMAC is
+// computed here over `sha2` (RFC 2104) rather than through another dependency.
+
+use base64::engine::general_purpose::STANDARD;
+use ed25519_dalek::{Signature, Signer, Verifier};
+use reqwest::header::HeaderMap;
+use sha2::{Digest, Sha256};
+
+use super::*;
+
+pub const TIMESTAMP_HEADER: &str = "x-inferadb-webhook-timestamp";
+pub const SIGNATURE_HEADER: &str = "x-inferadb-webhook-signature";
+
+/// Seconds a delivery's timestamp may differ from the receiver's clock
+pub const DEFAULT_TOLERANCE_SECONDS: i64 = 300;
+
+/// SHA-256 block size, which HMAC pads its key to
+const BLOCK_SIZE: usize = 64;
+
+/// What a receiver verifies deliveries with
+#[derive(Clone)]
+pub enum WebhookKey {
+    /// Shared secret of an HMAC-SHA256 endpoint
+    Hmac(Vec<u8>),
+    /// Control's public webhook key
+    Ed25519(VerifyingKey),
+}
+
+/// The bytes a delivery's signatures cover
+pub fn signed_payload(timestamp: i64, body: &[u8]) -> Vec<u8> {
+    let mut payload = format!("{}.", timestamp).into_bytes();
+    payload.extend_from_slice(body);
+    payload
+}
+
+/// HMAC-SHA256 of `message` under `key`
+pub fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let pad = |byte: u8| block.map(|k| k ^ byte);
+    let inner = Sha256::new().chain_update(pad(0x36)).chain_update(message).finalize();
+    Sha256::new().chain_update(pad(0x5c)).chain_update(inner).finalize().into()
+}
+
+/// `v1=` signature entry for a delivery signed with `secret`
+pub fn sign_hmac(secret: &[u8], timestamp: i64, body: &[u8]) -> String {
+    format!("v1={}", hex(&hmac_sha256(secret, &signed_payload(timestamp, body))))
+}
+
+/// `ed25519=` signature entry for a delivery signed with `key`
+pub fn sign_ed25519(key: &SigningKey, timestamp: i64, body: &[u8]) -> String {
+    format!("ed25519={}", STANDARD.encode(key.sign(&signed_payload(timestamp, body)).to_bytes()))
+}
+
+/// Verify a delivery's headers against `body`, returning its timestamp
+///
+/// Fails if either header is missing, the timestamp is more than `tolerance_seconds` from now, or
+/// no signature of `key`'s scheme matches.
+pub fn verify(
+    key: &WebhookKey,
+    headers: &HeaderMap,
+    body: &[u8],
+    tolerance_seconds: i64,
+) -> Result<i64> {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .with_context(|| format!("Missing {} header", name))
+    };
+    let timestamp: i64 =
+        header(TIMESTAMP_HEADER)?.trim().parse().context("Invalid webhook timestamp")?;
+    let skew = Utc::now()
+        .timestamp()
+        .checked_sub(timestamp)
+        .map(i64::unsigned_abs)
+        .context("Webhook timestamp out of range")?;
+    anyhow::ensure!(
+        skew <= tolerance_seconds.max(0) as u64,
+        "Webhook timestamp is {}s from now, beyond the {}s tolerance",
+        skew,
+        tolerance_seconds
+    );
+
+    let payload = signed_payload(timestamp, body);
+    let verified = header(SIGNATURE_HEADER)?
+        .split(',')
+        .filter_map(|entry| entry.trim().split_once('='))
+        .any(|(scheme, value)| match (key, scheme) {
+            (WebhookKey::Hmac(secret), "v1") => {
+                constant_time_eq(hex(&hmac_sha256(secret, &payload)).as_bytes(), value.as_bytes())
+            },
+            (WebhookKey::Ed25519(public), "ed25519") => STANDARD
+                .decode(value)
+                .ok()
+                .and_then(|bytes| Signature::from_slice(&bytes).ok())
+                .is_some_and(|signature| public.verify(&payload, &signature).is_ok()),
+            _ => false,
+        });
+    anyhow::ensure!(verified, "No webhook signature verifies against the receiver's key");
+    Ok(timestamp)
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
+
+/// Compare without returning early, so response timing does not reveal matching prefixes
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |diff, (x, y)| diff | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webhook_signatures_verify() {
+        let hex = |bytes: [u8; 32]| bytes.iter().map(|b| format!("{:02x}", b)).collect::<String>();
+        // RFC 4231 test cases 1, 2, and 6 (key longer than the block size)
+        assert_eq!(
+            hex(hmac_sha256(&[0x0b; 20], b"Hi There")),
+            "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+        );
+        assert_eq!(
+            hex(hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            hex(hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+
+        let body = br#"{"type":"cache.invalidation","vault_id":"1"}"#;
+        let now = Utc::now().timestamp();
+        let headers = |timestamp: i64, signature: String| {
+            let mut headers = reqwest::header::HeaderMap::new();
+            headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+            headers.insert(SIGNATURE_HEADER, signature.parse().unwrap());
+            headers
+        };
+        let verify = |key: &WebhookKey, headers: &reqwest::header::HeaderMap, body: &[u8]| {
+            verify(key, headers, body, DEFAULT_TOLERANCE_SECONDS)
+        };
+
+        let secret = WebhookKey::Hmac(b"whsec-test".to_vec());
+        let signed = headers(now, sign_hmac(b"whsec-test", now, body));
+        assert_eq!(verify(&secret, &signed, body).expect("HMAC signature verifies"), now);
+        assert!(verify(&secret, &signed, b"{}").is_err(), "Changed body is rejected");
+        assert!(
+            verify(&WebhookKey::Hmac(b"other".to_vec()), &signed, body).is_err(),
+            "Wrong secret is rejected"
+        );
+        let stale = now - DEFAULT_TOLERANCE_SECONDS - 1;
+        assert!(
+            verify(&secret, &headers(stale, sign_hmac(b"whsec-test", stale, body)), body).is_err(),
+            "Stale timestamp is rejected"
+        );
+        let signed = headers(i64::MIN, sign_hmac(b"whsec-test", i64::MIN, body));
+        assert!(
+            super::verify(&secret, &signed, body, i64::MAX).is_err(),
+            "A timestamp whose skew overflows is rejected, even with unlimited tolerance"
+        );
+
+        // Rotation: any matching entry verifies, whatever else is listed
+        let rotating = format!("v1=00, {}", sign_hmac(b"whsec-test", now, body));
+        verify(&secret, &headers(now, rotating), body).expect("Rotated signature list verifies");
+
+        let key = keys::from_seed("webhook");
+        let public = WebhookKey::Ed25519(key.verifying_key());
+        let signed = headers(now, sign_ed25519(&key, now, body));
+        verify(&public, &signed, body).expect("Ed25519 signature verifies");
+        assert!(verify(&public, &signed, b"{}").is_err(), "Changed body is rejected");
+        assert!(verify(&secret, &signed, body).is_err(), "Schemes do not cross-verify");
+    }
+}